@@ -0,0 +1,208 @@
+//! Compares the server CPU and round-trip time of the ways the MSSQL repositories have
+//! projected UNIQUEIDENTIFIER and DATETIME2 columns:
+//!
+//! - `format`: `CAST(... AS NVARCHAR(36))` and `FORMAT(...)`, parsed back on the client
+//! - `datediff`: the integer projections the sqlx backend uses
+//! - `native`: the raw columns the tiberius backend decodes
+//!
+//! Runs against the database configured by the `MSSQL_*` variables and is skipped when
+//! `MSSQL_HOST` is unset. `BENCH_ROWS` and `BENCH_ITERATIONS` size the run.
+//!
+//! ```bash
+//! cargo bench -p axum-api --bench mssql_decoding
+//! ```
+
+use std::{
+    env,
+    time::{Duration, Instant},
+};
+
+use axum_api::infra::{config::MssqlConfig, mssql_tiberius::init_mssql_tiberius};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use dotenvy::dotenv;
+use tiberius::Row;
+use tokio::net::TcpStream;
+use tokio_util::compat::Compat;
+use uuid::Uuid;
+
+type Client = tiberius::Client<Compat<TcpStream>>;
+
+/// What the repositories decode from each user row.
+type Decoded = (Uuid, DateTime<Utc>, DateTime<Utc>);
+
+struct Variant {
+    name: &'static str,
+    sql: &'static str,
+    decode: fn(&Row) -> Decoded,
+}
+
+const VARIANTS: &[Variant] = &[
+    Variant {
+        name: "format",
+        sql: "SELECT CAST(id AS NVARCHAR(36)) AS id, \
+              FORMAT(created_at, 'yyyy-MM-ddTHH:mm:ss.fffffff') AS created_at, \
+              FORMAT(updated_at, 'yyyy-MM-ddTHH:mm:ss.fffffff') AS updated_at \
+              FROM #users_bench",
+        decode: |row| {
+            let timestamp = |column| {
+                NaiveDateTime::parse_from_str(row.get(column).unwrap(), "%Y-%m-%dT%H:%M:%S%.f")
+                    .unwrap()
+                    .and_utc()
+            };
+
+            (
+                Uuid::parse_str(row.get("id").unwrap()).unwrap(),
+                timestamp("created_at"),
+                timestamp("updated_at"),
+            )
+        },
+    },
+    Variant {
+        name: "datediff",
+        sql: "SELECT CAST(id AS NVARCHAR(36)) AS id, \
+              DATEDIFF_BIG(SECOND, '1970-01-01', created_at) AS created_at_secs, \
+              DATEPART(NANOSECOND, created_at) AS created_at_nanos, \
+              DATEDIFF_BIG(SECOND, '1970-01-01', updated_at) AS updated_at_secs, \
+              DATEPART(NANOSECOND, updated_at) AS updated_at_nanos \
+              FROM #users_bench",
+        decode: |row| {
+            let timestamp = |column: &str| {
+                let secs: i64 = row.get(format!("{column}_secs").as_str()).unwrap();
+                let nanos: i32 = row.get(format!("{column}_nanos").as_str()).unwrap();
+                DateTime::from_timestamp(secs, nanos as u32).unwrap()
+            };
+
+            (
+                Uuid::parse_str(row.get("id").unwrap()).unwrap(),
+                timestamp("created_at"),
+                timestamp("updated_at"),
+            )
+        },
+    },
+    Variant {
+        name: "native",
+        sql: "SELECT id, created_at, updated_at FROM #users_bench",
+        decode: |row| {
+            let timestamp = |column| row.get::<NaiveDateTime, _>(column).unwrap().and_utc();
+
+            (
+                row.get("id").unwrap(),
+                timestamp("created_at"),
+                timestamp("updated_at"),
+            )
+        },
+    },
+];
+
+fn env_or(name: &str, default: u32) -> u32 {
+    env::var(name)
+        .map(|value| {
+            value
+                .parse()
+                .unwrap_or_else(|_| panic!("{name} must be a number"))
+        })
+        .unwrap_or(default)
+}
+
+/// CPU milliseconds the server has spent on this session so far.
+async fn session_cpu_ms(client: &mut Client) -> i32 {
+    client
+        .query(
+            "SELECT cpu_time FROM sys.dm_exec_sessions WHERE session_id = @@SPID",
+            &[],
+        )
+        .await
+        .unwrap()
+        .into_row()
+        .await
+        .unwrap()
+        .and_then(|row| row.get("cpu_time"))
+        .unwrap()
+}
+
+/// Runs the variant's query once and returns how long decoding its rows took.
+async fn run(client: &mut Client, variant: &Variant, rows: u32) -> Duration {
+    let result = client
+        .query(variant.sql, &[])
+        .await
+        .unwrap()
+        .into_first_result()
+        .await
+        .unwrap();
+
+    let started = Instant::now();
+    let decoded: Vec<_> = result.iter().map(variant.decode).collect();
+    let decode = started.elapsed();
+
+    assert_eq!(decoded.len(), rows as usize);
+
+    decode
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenv().ok();
+
+    if env::var("MSSQL_HOST").is_err() {
+        eprintln!("MSSQL_HOST is not set, skipping the MSSQL decoding benchmark");
+        return Ok(());
+    }
+
+    let rows = env_or("BENCH_ROWS", 50_000);
+    let iterations = env_or("BENCH_ITERATIONS", 10);
+
+    let pool = init_mssql_tiberius(&MssqlConfig::from_env()).await?;
+    // Temporary tables belong to a connection, so the whole run holds on to one.
+    let mut conn = pool.get().await?;
+    let client: &mut Client = &mut conn;
+
+    client
+        .simple_query(format!(
+            "CREATE TABLE #users_bench (
+                id UNIQUEIDENTIFIER NOT NULL DEFAULT NEWID(),
+                created_at DATETIME2 NOT NULL,
+                updated_at DATETIME2 NOT NULL
+            );
+            INSERT INTO #users_bench (created_at, updated_at)
+            SELECT TOP ({rows})
+                DATEADD(SECOND, -ROW_NUMBER() OVER (ORDER BY (SELECT NULL)), SYSUTCDATETIME()),
+                SYSUTCDATETIME()
+            FROM sys.all_objects a CROSS JOIN sys.all_objects b;"
+        ))
+        .await?
+        .into_results()
+        .await?;
+
+    println!("{rows} rows x {iterations} iterations, times per query");
+    println!(
+        "{:<10} {:>14} {:>14} {:>14}",
+        "variant", "server cpu ms", "wall ms", "decode ms"
+    );
+
+    for variant in VARIANTS {
+        run(client, variant, rows).await;
+
+        let cpu_before = session_cpu_ms(client).await;
+        let mut wall = Duration::ZERO;
+        let mut decode = Duration::ZERO;
+
+        for _ in 0..iterations {
+            let started = Instant::now();
+            decode += run(client, variant, rows).await;
+            wall += started.elapsed();
+        }
+
+        let cpu_ms = f64::from(session_cpu_ms(client).await - cpu_before);
+        let per_query = |total: Duration| total.as_secs_f64() * 1000.0 / f64::from(iterations);
+
+        println!(
+            "{:<10} {:>14.1} {:>14.1} {:>14.1}",
+            variant.name,
+            cpu_ms / f64::from(iterations),
+            per_query(wall),
+            per_query(decode),
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+IF NOT EXISTS (
+    SELECT * FROM sys.tables WHERE name = 'users'
+)
+BEGIN
+    CREATE TABLE users (
+        id UNIQUEIDENTIFIER NOT NULL
+            CONSTRAINT pk_users PRIMARY KEY
+            DEFAULT NEWID(),
+
+        email NVARCHAR(255) NOT NULL,
+        password NVARCHAR(255) NOT NULL,
+        name NVARCHAR(255) NOT NULL,
+        role NVARCHAR(32) NOT NULL DEFAULT 'user',
+        status NVARCHAR(32) NOT NULL DEFAULT 'active',
+        status_reason NVARCHAR(500) NULL,
+        status_until DATETIME2 NULL,
+        avatar_key NVARCHAR(255) NULL,
+
+        created_at DATETIME2 NOT NULL
+            CONSTRAINT df_users_created_at DEFAULT SYSUTCDATETIME(),
+        updated_at DATETIME2 NOT NULL
+            CONSTRAINT df_users_updated_at DEFAULT SYSUTCDATETIME()
+    );
+
+    CREATE UNIQUE INDEX idx_users_email_unique
+        ON users (email);
+END
+GO
+
+IF COL_LENGTH('users', 'role') IS NULL
+BEGIN
+    ALTER TABLE users
+        ADD role NVARCHAR(32) NOT NULL
+            CONSTRAINT df_users_role DEFAULT 'user';
+END
+GO
+
+IF COL_LENGTH('users', 'status') IS NULL
+BEGIN
+    ALTER TABLE users
+        ADD status NVARCHAR(32) NOT NULL
+            CONSTRAINT df_users_status DEFAULT 'active';
+END
+GO
+
+IF COL_LENGTH('users', 'status_reason') IS NULL
+BEGIN
+    ALTER TABLE users
+        ADD status_reason NVARCHAR(500) NULL,
+            status_until DATETIME2 NULL;
+END
+GO
+
+IF COL_LENGTH('users', 'avatar_key') IS NULL
+BEGIN
+    ALTER TABLE users
+        ADD avatar_key NVARCHAR(255) NULL;
+END
+GO
+
+-- Tables created before migrations defaulted to server-local time.
+DECLARE @drop_local_defaults NVARCHAR(MAX) = N'';
+
+SELECT @drop_local_defaults += N'ALTER TABLE users DROP CONSTRAINT ' + QUOTENAME(dc.name) + N';'
+FROM sys.default_constraints dc
+JOIN sys.columns c
+    ON c.object_id = dc.parent_object_id AND c.column_id = dc.parent_column_id
+WHERE dc.parent_object_id = OBJECT_ID('users')
+    AND c.name IN ('created_at', 'updated_at')
+    AND dc.definition <> '(sysutcdatetime())';
+
+EXEC sp_executesql @drop_local_defaults;
+GO
+
+IF NOT EXISTS (
+    SELECT * FROM sys.default_constraints
+    WHERE parent_object_id = OBJECT_ID('users')
+        AND parent_column_id = COLUMNPROPERTY(OBJECT_ID('users'), 'created_at', 'ColumnId')
+)
+BEGIN
+    ALTER TABLE users
+        ADD CONSTRAINT df_users_created_at DEFAULT SYSUTCDATETIME() FOR created_at;
+END
+GO
+
+IF NOT EXISTS (
+    SELECT * FROM sys.default_constraints
+    WHERE parent_object_id = OBJECT_ID('users')
+        AND parent_column_id = COLUMNPROPERTY(OBJECT_ID('users'), 'updated_at', 'ColumnId')
+)
+BEGIN
+    ALTER TABLE users
+        ADD CONSTRAINT df_users_updated_at DEFAULT SYSUTCDATETIME() FOR updated_at;
+END
+GO
+
+IF NOT EXISTS (
+    SELECT * FROM sys.indexes WHERE name = 'idx_users_status_until'
+)
+BEGIN
+    CREATE INDEX idx_users_status_until
+        ON users (status, status_until);
+END
+GO
+
+IF NOT EXISTS (
+    SELECT * FROM sys.indexes WHERE name = 'idx_users_created_at_id'
+)
+BEGIN
+    CREATE INDEX idx_users_created_at_id
+        ON users (created_at, id);
+END
+GO
@@ -0,0 +1,30 @@
+IF NOT EXISTS (
+    SELECT * FROM sys.tables WHERE name = 'passkey_credentials'
+)
+BEGIN
+    CREATE TABLE passkey_credentials (
+        id UNIQUEIDENTIFIER NOT NULL
+            CONSTRAINT pk_passkey_credentials PRIMARY KEY
+            DEFAULT NEWID(),
+
+        user_id UNIQUEIDENTIFIER NOT NULL
+            CONSTRAINT fk_passkey_credentials_user
+            FOREIGN KEY REFERENCES users (id) ON DELETE CASCADE,
+
+        credential_id VARCHAR(1400) NOT NULL,
+        public_key VARCHAR(255) NOT NULL,
+        sign_count BIGINT NOT NULL DEFAULT 0,
+        transports NVARCHAR(255) NOT NULL DEFAULT '',
+        name NVARCHAR(100) NOT NULL,
+
+        created_at DATETIME2 NOT NULL DEFAULT SYSUTCDATETIME(),
+        last_used_at DATETIME2 NULL
+    );
+
+    CREATE UNIQUE INDEX idx_passkey_credentials_credential_id
+        ON passkey_credentials (credential_id);
+
+    CREATE INDEX idx_passkey_credentials_user_id
+        ON passkey_credentials (user_id);
+END
+GO
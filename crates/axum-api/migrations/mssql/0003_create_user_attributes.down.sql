@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS user_attributes;
+GO
+
+DROP TABLE IF EXISTS attribute_schemas;
@@ -0,0 +1,38 @@
+IF NOT EXISTS (
+    SELECT * FROM sys.tables WHERE name = 'attribute_schemas'
+)
+BEGIN
+    CREATE TABLE attribute_schemas (
+        namespace NVARCHAR(64) NOT NULL
+            CONSTRAINT pk_attribute_schemas PRIMARY KEY,
+
+        json_schema NVARCHAR(MAX) NOT NULL,
+        user_writable BIT NOT NULL DEFAULT 0,
+
+        updated_at DATETIME2 NOT NULL DEFAULT SYSUTCDATETIME()
+    );
+END
+GO
+
+IF NOT EXISTS (
+    SELECT * FROM sys.tables WHERE name = 'user_attributes'
+)
+BEGIN
+    CREATE TABLE user_attributes (
+        user_id UNIQUEIDENTIFIER NOT NULL
+            CONSTRAINT fk_user_attributes_user
+            FOREIGN KEY REFERENCES users (id) ON DELETE CASCADE,
+
+        namespace NVARCHAR(64) NOT NULL,
+        data NVARCHAR(MAX) NOT NULL
+            CONSTRAINT ck_user_attributes_data CHECK (ISJSON(data) = 1),
+
+        updated_at DATETIME2 NOT NULL DEFAULT SYSUTCDATETIME(),
+
+        CONSTRAINT pk_user_attributes PRIMARY KEY (user_id, namespace)
+    );
+
+    CREATE INDEX idx_user_attributes_namespace
+        ON user_attributes (namespace);
+END
+GO
@@ -0,0 +1,15 @@
+IF NOT EXISTS (
+    SELECT * FROM sys.check_constraints WHERE name = 'ck_users_status'
+)
+BEGIN
+    ALTER TABLE users
+        ADD CONSTRAINT ck_users_status CHECK (status IN (
+            'active',
+            'deactivated',
+            'locked',
+            'suspended',
+            'pending_deletion',
+            'deleted'
+        ));
+END
+GO
@@ -0,0 +1,58 @@
+IF NOT EXISTS (
+    SELECT * FROM sys.tables WHERE name = 'outbox'
+)
+BEGIN
+    CREATE TABLE outbox (
+        id UNIQUEIDENTIFIER NOT NULL
+            CONSTRAINT pk_outbox PRIMARY KEY NONCLUSTERED,
+
+        -- Enqueue order, which the relay preserves per aggregate.
+        sequence BIGINT IDENTITY(1, 1) NOT NULL,
+
+        aggregate_id UNIQUEIDENTIFIER NOT NULL,
+        event_type NVARCHAR(100) NOT NULL,
+        payload NVARCHAR(MAX) NOT NULL,
+        attempts INT NOT NULL DEFAULT 0,
+        last_error NVARCHAR(MAX) NULL,
+
+        -- When a failed message may be published again.
+        next_attempt_at DATETIME2 NULL,
+
+        created_at DATETIME2 NOT NULL,
+        dispatched_at DATETIME2 NULL
+    );
+
+    CREATE UNIQUE CLUSTERED INDEX idx_outbox_sequence
+        ON outbox (sequence);
+
+    CREATE INDEX idx_outbox_pending
+        ON outbox (sequence)
+        WHERE dispatched_at IS NULL;
+
+    CREATE INDEX idx_outbox_pending_aggregate
+        ON outbox (aggregate_id, sequence)
+        WHERE dispatched_at IS NULL;
+
+    CREATE INDEX idx_outbox_dispatched
+        ON outbox (dispatched_at)
+        WHERE dispatched_at IS NOT NULL;
+END
+GO
+
+IF NOT EXISTS (
+    SELECT * FROM sys.tables WHERE name = 'outbox_relay_lease'
+)
+BEGIN
+    -- One row naming the relay allowed to publish, until its lease expires.
+    CREATE TABLE outbox_relay_lease (
+        id INT NOT NULL
+            CONSTRAINT pk_outbox_relay_lease PRIMARY KEY
+            CONSTRAINT ck_outbox_relay_lease_single_row CHECK (id = 1),
+
+        holder UNIQUEIDENTIFIER NULL,
+        expires_at DATETIME2 NULL
+    );
+
+    INSERT INTO outbox_relay_lease (id) VALUES (1);
+END
+GO
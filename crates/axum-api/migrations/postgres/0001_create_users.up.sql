@@ -0,0 +1,26 @@
+CREATE TABLE IF NOT EXISTS users (
+    id UUID NOT NULL
+        CONSTRAINT pk_users PRIMARY KEY
+        DEFAULT gen_random_uuid(),
+
+    email VARCHAR(255) NOT NULL,
+    password VARCHAR(255) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    role VARCHAR(32) NOT NULL DEFAULT 'user',
+    status VARCHAR(32) NOT NULL DEFAULT 'active',
+    status_reason VARCHAR(500) NULL,
+    status_until TIMESTAMPTZ NULL,
+    avatar_key VARCHAR(255) NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_email_unique
+    ON users (lower(email));
+
+CREATE INDEX IF NOT EXISTS idx_users_status_until
+    ON users (status, status_until);
+
+CREATE INDEX IF NOT EXISTS idx_users_created_at_id
+    ON users (created_at, id);
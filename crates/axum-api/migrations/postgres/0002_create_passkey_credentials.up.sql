@@ -0,0 +1,24 @@
+CREATE TABLE IF NOT EXISTS passkey_credentials (
+    id UUID NOT NULL
+        CONSTRAINT pk_passkey_credentials PRIMARY KEY
+        DEFAULT gen_random_uuid(),
+
+    user_id UUID NOT NULL
+        CONSTRAINT fk_passkey_credentials_user
+        REFERENCES users (id) ON DELETE CASCADE,
+
+    credential_id BYTEA NOT NULL,
+    public_key BYTEA NOT NULL,
+    sign_count BIGINT NOT NULL DEFAULT 0,
+    transports VARCHAR(255) NOT NULL DEFAULT '',
+    name VARCHAR(100) NOT NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_used_at TIMESTAMPTZ NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_passkey_credentials_credential_id
+    ON passkey_credentials (credential_id);
+
+CREATE INDEX IF NOT EXISTS idx_passkey_credentials_user_id
+    ON passkey_credentials (user_id);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS user_attributes;
+
+DROP TABLE IF EXISTS attribute_schemas;
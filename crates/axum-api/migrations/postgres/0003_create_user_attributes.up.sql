@@ -0,0 +1,25 @@
+CREATE TABLE IF NOT EXISTS attribute_schemas (
+    namespace VARCHAR(64) NOT NULL
+        CONSTRAINT pk_attribute_schemas PRIMARY KEY,
+
+    json_schema JSONB NOT NULL,
+    user_writable BOOLEAN NOT NULL DEFAULT FALSE,
+
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS user_attributes (
+    user_id UUID NOT NULL
+        CONSTRAINT fk_user_attributes_user
+        REFERENCES users (id) ON DELETE CASCADE,
+
+    namespace VARCHAR(64) NOT NULL,
+    data JSONB NOT NULL,
+
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+
+    CONSTRAINT pk_user_attributes PRIMARY KEY (user_id, namespace)
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_attributes_namespace
+    ON user_attributes (namespace);
@@ -0,0 +1,12 @@
+ALTER TABLE users
+    DROP CONSTRAINT IF EXISTS ck_users_status;
+
+ALTER TABLE users
+    ADD CONSTRAINT ck_users_status CHECK (status IN (
+        'active',
+        'deactivated',
+        'locked',
+        'suspended',
+        'pending_deletion',
+        'deleted'
+    ));
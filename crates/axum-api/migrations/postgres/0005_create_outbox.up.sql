@@ -0,0 +1,44 @@
+CREATE TABLE IF NOT EXISTS outbox (
+    id UUID NOT NULL
+        CONSTRAINT pk_outbox PRIMARY KEY,
+
+    -- Enqueue order, which the relay preserves per aggregate.
+    sequence BIGSERIAL NOT NULL,
+
+    aggregate_id UUID NOT NULL,
+    event_type VARCHAR(100) NOT NULL,
+    payload TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT NULL,
+
+    -- When a failed message may be published again.
+    next_attempt_at TIMESTAMPTZ NULL,
+
+    created_at TIMESTAMPTZ NOT NULL,
+    dispatched_at TIMESTAMPTZ NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_outbox_pending
+    ON outbox (sequence)
+    WHERE dispatched_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_outbox_pending_aggregate
+    ON outbox (aggregate_id, sequence)
+    WHERE dispatched_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_outbox_dispatched
+    ON outbox (dispatched_at)
+    WHERE dispatched_at IS NOT NULL;
+
+-- One row naming the relay allowed to publish, until its lease expires.
+CREATE TABLE IF NOT EXISTS outbox_relay_lease (
+    id INTEGER NOT NULL
+        CONSTRAINT pk_outbox_relay_lease PRIMARY KEY
+        CONSTRAINT ck_outbox_relay_lease_single_row CHECK (id = 1),
+
+    holder UUID NULL,
+    expires_at TIMESTAMPTZ NULL
+);
+
+INSERT INTO outbox_relay_lease (id) VALUES (1)
+ON CONFLICT (id) DO NOTHING;
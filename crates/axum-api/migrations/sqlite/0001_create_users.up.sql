@@ -0,0 +1,25 @@
+CREATE TABLE IF NOT EXISTS users (
+    id TEXT NOT NULL
+        CONSTRAINT pk_users PRIMARY KEY,
+
+    email TEXT NOT NULL COLLATE NOCASE,
+    password TEXT NOT NULL,
+    name TEXT NOT NULL,
+    role TEXT NOT NULL DEFAULT 'user',
+    status TEXT NOT NULL DEFAULT 'active',
+    status_reason TEXT NULL,
+    status_until TEXT NULL,
+    avatar_key TEXT NULL,
+
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_email_unique
+    ON users (email);
+
+CREATE INDEX IF NOT EXISTS idx_users_status_until
+    ON users (status, status_until);
+
+CREATE INDEX IF NOT EXISTS idx_users_created_at_id
+    ON users (created_at, id);
@@ -0,0 +1,23 @@
+CREATE TABLE IF NOT EXISTS passkey_credentials (
+    id TEXT NOT NULL
+        CONSTRAINT pk_passkey_credentials PRIMARY KEY,
+
+    user_id TEXT NOT NULL
+        CONSTRAINT fk_passkey_credentials_user
+        REFERENCES users (id) ON DELETE CASCADE,
+
+    credential_id BLOB NOT NULL,
+    public_key BLOB NOT NULL,
+    sign_count INTEGER NOT NULL DEFAULT 0,
+    transports TEXT NOT NULL DEFAULT '',
+    name TEXT NOT NULL,
+
+    created_at TEXT NOT NULL,
+    last_used_at TEXT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_passkey_credentials_credential_id
+    ON passkey_credentials (credential_id);
+
+CREATE INDEX IF NOT EXISTS idx_passkey_credentials_user_id
+    ON passkey_credentials (user_id);
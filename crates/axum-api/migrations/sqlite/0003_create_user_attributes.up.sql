@@ -0,0 +1,26 @@
+CREATE TABLE IF NOT EXISTS attribute_schemas (
+    namespace TEXT NOT NULL COLLATE NOCASE
+        CONSTRAINT pk_attribute_schemas PRIMARY KEY,
+
+    json_schema TEXT NOT NULL,
+    user_writable INTEGER NOT NULL DEFAULT 0,
+
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS user_attributes (
+    user_id TEXT NOT NULL
+        CONSTRAINT fk_user_attributes_user
+        REFERENCES users (id) ON DELETE CASCADE,
+
+    namespace TEXT NOT NULL COLLATE NOCASE,
+    data TEXT NOT NULL
+        CONSTRAINT ck_user_attributes_data CHECK (json_valid(data)),
+
+    updated_at TEXT NOT NULL,
+
+    CONSTRAINT pk_user_attributes PRIMARY KEY (user_id, namespace)
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_attributes_namespace
+    ON user_attributes (namespace);
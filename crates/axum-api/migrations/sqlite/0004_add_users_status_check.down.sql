@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS ck_users_status_insert;
+
+DROP TRIGGER IF EXISTS ck_users_status_update;
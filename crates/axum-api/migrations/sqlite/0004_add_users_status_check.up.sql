@@ -0,0 +1,28 @@
+-- SQLite cannot add a CHECK constraint to an existing table.
+CREATE TRIGGER IF NOT EXISTS ck_users_status_insert
+BEFORE INSERT ON users
+WHEN NEW.status NOT IN (
+    'active',
+    'deactivated',
+    'locked',
+    'suspended',
+    'pending_deletion',
+    'deleted'
+)
+BEGIN
+    SELECT RAISE(ABORT, 'CHECK constraint failed: ck_users_status');
+END;
+
+CREATE TRIGGER IF NOT EXISTS ck_users_status_update
+BEFORE UPDATE OF status ON users
+WHEN NEW.status NOT IN (
+    'active',
+    'deactivated',
+    'locked',
+    'suspended',
+    'pending_deletion',
+    'deleted'
+)
+BEGIN
+    SELECT RAISE(ABORT, 'CHECK constraint failed: ck_users_status');
+END;
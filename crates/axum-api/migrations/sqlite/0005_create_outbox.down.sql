@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS outbox_relay_lease;
+
+DROP TABLE IF EXISTS outbox;
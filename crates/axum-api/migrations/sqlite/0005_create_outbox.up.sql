@@ -0,0 +1,43 @@
+CREATE TABLE IF NOT EXISTS outbox (
+    -- Enqueue order, which the relay preserves per aggregate.
+    sequence INTEGER PRIMARY KEY AUTOINCREMENT,
+
+    id TEXT NOT NULL
+        CONSTRAINT uq_outbox_id UNIQUE,
+
+    aggregate_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT NULL,
+
+    -- When a failed message may be published again.
+    next_attempt_at TEXT NULL,
+
+    created_at TEXT NOT NULL,
+    dispatched_at TEXT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_outbox_pending
+    ON outbox (sequence)
+    WHERE dispatched_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_outbox_pending_aggregate
+    ON outbox (aggregate_id, sequence)
+    WHERE dispatched_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_outbox_dispatched
+    ON outbox (dispatched_at)
+    WHERE dispatched_at IS NOT NULL;
+
+-- One row naming the relay allowed to publish, until its lease expires.
+CREATE TABLE IF NOT EXISTS outbox_relay_lease (
+    id INTEGER NOT NULL
+        CONSTRAINT pk_outbox_relay_lease PRIMARY KEY
+        CONSTRAINT ck_outbox_relay_lease_single_row CHECK (id = 1),
+
+    holder TEXT NULL,
+    expires_at TEXT NULL
+);
+
+INSERT OR IGNORE INTO outbox_relay_lease (id) VALUES (1);
@@ -1,8 +1,18 @@
 use std::sync::Arc;
 
 use tonic::{Request, Response, Status};
+use uuid::Uuid;
 
-use crate::application::use_cases::user::UserUseCase;
+use crate::{
+    application::{
+        app_error::AppError,
+        use_cases::{
+            account_status::check_account_status, attribute::AttributeUseCase,
+            avatar::AvatarUseCase, user::UserUseCase,
+        },
+    },
+    domain::entities::{attribute::UserAttributes, user::Role},
+};
 
 pub mod user_grpc {
     tonic::include_proto!("user");
@@ -10,11 +20,41 @@ pub mod user_grpc {
 
 pub struct UserService {
     user_use_case: Arc<UserUseCase>,
+    avatar_use_case: Arc<AvatarUseCase>,
+    attribute_use_case: Arc<AttributeUseCase>,
 }
 
 impl UserService {
-    pub fn new(user_use_case: Arc<UserUseCase>) -> Self {
-        Self { user_use_case }
+    pub fn new(
+        user_use_case: Arc<UserUseCase>,
+        avatar_use_case: Arc<AvatarUseCase>,
+        attribute_use_case: Arc<AttributeUseCase>,
+    ) -> Self {
+        Self {
+            user_use_case,
+            avatar_use_case,
+            attribute_use_case,
+        }
+    }
+}
+
+fn account_status_to_grpc(error: AppError) -> Status {
+    match error {
+        AppError::AccountDeleted => Status::not_found("User not found"),
+        AppError::AccountLocked | AppError::AccountSuspended | AppError::Unauthorized => {
+            Status::permission_denied(error.to_string())
+        }
+        other => Status::internal(other.to_string()),
+    }
+}
+
+impl From<UserAttributes> for user_grpc::UserAttribute {
+    fn from(attributes: UserAttributes) -> Self {
+        Self {
+            namespace: attributes.namespace,
+            data: attributes.data.to_string(),
+            updated_at: attributes.updated_at.to_rfc3339(),
+        }
     }
 }
 
@@ -34,14 +74,67 @@ impl user_grpc::user_service_server::UserService for UserService {
 
         match user {
             Some(u) => {
+                check_account_status(&u).map_err(account_status_to_grpc)?;
+
+                let avatar_url = self
+                    .avatar_use_case
+                    .avatar_url(&u)
+                    .await
+                    .map_err(|e| Status::internal(e.to_string()))?;
+
                 let response = user_grpc::UserProfileResponse {
                     id: u.id().to_string(),
                     email: u.email().to_string(),
                     name: u.name().to_string(),
+                    avatar_url,
                 };
                 Ok(Response::new(response))
             }
             None => Err(Status::not_found("User not found")),
         }
     }
+
+    async fn get_user_attributes(
+        &self,
+        request: Request<user_grpc::GetUserAttributesRequest>,
+    ) -> Result<Response<user_grpc::UserAttributesResponse>, Status> {
+        let req = request.into_inner();
+
+        let user_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid user id"))?;
+
+        let user = self
+            .user_use_case
+            .get_user_by_id(&req.id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| Status::not_found("User not found"))?;
+
+        check_account_status(&user).map_err(account_status_to_grpc)?;
+
+        // Internal services read every namespace, including those users cannot see.
+        let attributes = match req.namespace {
+            Some(namespace) => match self
+                .attribute_use_case
+                .get_attributes(user_id, &namespace, Role::Admin)
+                .await
+            {
+                Ok(attributes) => vec![attributes],
+                Err(AppError::AttributesNotFound) => Vec::new(),
+                Err(AppError::ValidationError(_)) => {
+                    return Err(Status::invalid_argument("Invalid attribute namespace"));
+                }
+                Err(e) => return Err(Status::internal(e.to_string())),
+            },
+            None => self
+                .attribute_use_case
+                .list_attributes(user_id, Role::Admin)
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?,
+        };
+
+        Ok(Response::new(user_grpc::UserAttributesResponse {
+            attributes: attributes.into_iter().map(Into::into).collect(),
+        }))
+    }
 }
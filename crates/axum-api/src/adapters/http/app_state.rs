@@ -1,14 +1,35 @@
 use std::sync::Arc;
 
 use crate::{
-    application::use_cases::{auth::AuthUseCase, user::UserUseCase},
-    infra::{config::AppConfig, security::jwt::TokenProvider},
+    application::use_cases::{
+        account::AccountUseCase, attribute::AttributeUseCase, auth::AuthUseCase,
+        avatar::AvatarUseCase, data_export::DataExportUseCase, email_change::EmailChangeUseCase,
+        outbox::OutboxUseCase, passkey::PasskeyUseCase, trusted_device::TrustedDeviceUseCase,
+        user::UserUseCase, user_admin::UserAdminUseCase, user_import::UserImportUseCase,
+    },
+    domain::repositories::object_storage::ObjectStorage,
+    infra::{
+        config::AppConfig,
+        security::{jwt::TokenProvider, url_signer::UrlSigner},
+    },
 };
 
 #[derive(Clone)]
 pub struct AppState {
     pub config: Arc<AppConfig>,
     pub user_use_case: Arc<UserUseCase>,
+    pub user_admin_use_case: Arc<UserAdminUseCase>,
+    pub user_import_use_case: Arc<UserImportUseCase>,
+    pub account_use_case: Arc<AccountUseCase>,
+    pub attribute_use_case: Arc<AttributeUseCase>,
     pub auth_use_case: Arc<AuthUseCase>,
+    pub passkey_use_case: Arc<PasskeyUseCase>,
+    pub trusted_device_use_case: Arc<TrustedDeviceUseCase>,
+    pub avatar_use_case: Arc<AvatarUseCase>,
+    pub data_export_use_case: Arc<DataExportUseCase>,
+    pub email_change_use_case: Arc<EmailChangeUseCase>,
+    pub outbox_use_case: Arc<OutboxUseCase>,
     pub token_provider: Arc<dyn TokenProvider>,
+    pub object_storage: Arc<dyn ObjectStorage>,
+    pub file_url_signer: Option<Arc<dyn UrlSigner>>,
 }
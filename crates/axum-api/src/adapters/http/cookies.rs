@@ -0,0 +1,20 @@
+use axum::http::{HeaderMap, header};
+
+pub const TRUSTED_DEVICE_COOKIE: &str = "trusted_device";
+
+pub fn read_cookie(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+}
+
+pub fn trusted_device_cookie(value: &str, max_age_secs: i64) -> String {
+    format!(
+        "{TRUSTED_DEVICE_COOKIE}={value}; Max-Age={max_age_secs}; Path=/auth; HttpOnly; Secure; SameSite=Strict"
+    )
+}
@@ -0,0 +1,9 @@
+use serde::Deserialize;
+use serde_json::Value;
+use validator::Validate;
+
+/// Body of attribute writes, shared by the user and admin routes.
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct AttributesRequest {
+    pub data: Value,
+}
@@ -0,0 +1,44 @@
+use axum::http::{HeaderMap, header};
+use chrono::{DateTime, Utc};
+
+use crate::application::app_error::AppError;
+
+/// Seconds and nanoseconds, so the tag survives storage with sub-microsecond precision.
+pub fn etag(updated_at: DateTime<Utc>) -> String {
+    format!(
+        "\"{}.{:09}\"",
+        updated_at.timestamp(),
+        updated_at.timestamp_subsec_nanos()
+    )
+}
+
+/// Reads the version a write is conditional on. The header is mandatory so a client
+/// cannot overwrite changes it never saw; `*` is the explicit opt-out and yields `None`.
+/// Weak tags never match, since `If-Match` uses the strong comparison.
+pub fn if_match(headers: &HeaderMap) -> Result<Option<DateTime<Utc>>, AppError> {
+    let value = headers
+        .get(header::IF_MATCH)
+        .ok_or(AppError::PreconditionRequired)?
+        .to_str()
+        .map_err(|_| AppError::PreconditionFailed)?
+        .trim();
+    if value == "*" {
+        return Ok(None);
+    }
+
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .and_then(parse_etag)
+        .map(Some)
+        .ok_or(AppError::PreconditionFailed)
+}
+
+fn parse_etag(tag: &str) -> Option<DateTime<Utc>> {
+    let (secs, nanos) = tag.split_once('.')?;
+    if nanos.len() != 9 || !nanos.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    DateTime::from_timestamp(secs.parse().ok()?, nanos.parse().ok()?)
+}
@@ -0,0 +1,31 @@
+use axum::{extract::FromRequestParts, http::request::Parts};
+
+use crate::{application::app_error::AppError, domain::entities::session::ClientInfo};
+
+pub const CLIENT_ID_HEADER: &str = "x-client-id";
+pub const DEVICE_FINGERPRINT_HEADER: &str = "x-device-fingerprint";
+
+pub struct ExtractClientInfo(pub ClientInfo);
+
+impl<S> FromRequestParts<S> for ExtractClientInfo
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let header_value = |name: &str| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_string()
+        };
+
+        Ok(ExtractClientInfo(ClientInfo {
+            client_id: header_value(CLIENT_ID_HEADER),
+            device_fingerprint: header_value(DEVICE_FINGERPRINT_HEADER),
+        }))
+    }
+}
@@ -1 +1,3 @@
+pub mod client_info;
 pub mod validate_json;
+pub mod validate_query;
@@ -0,0 +1,44 @@
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::request::Parts,
+};
+use serde::de::DeserializeOwned;
+use validator::Validate;
+
+use crate::application::app_error::AppError;
+
+pub struct ValidateQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ValidateQuery<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(payload) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection| AppError::ValidationError(vec![rejection.body_text()]))?;
+
+        if let Err(e) = payload.validate() {
+            let error_messages: Vec<String> = e
+                .field_errors()
+                .into_iter()
+                .flat_map(|(field, field_errors)| {
+                    field_errors.iter().map(move |error| {
+                        if let Some(message) = &error.message {
+                            message.to_string()
+                        } else {
+                            format!("{} is invalid", field)
+                        }
+                    })
+                })
+                .collect();
+
+            return Err(AppError::ValidationError(error_messages));
+        }
+
+        Ok(ValidateQuery(payload))
+    }
+}
@@ -0,0 +1,23 @@
+use axum::{
+    body::Body,
+    http::{Request, Response},
+    middleware::Next,
+};
+
+use crate::{
+    application::app_error::AppError,
+    domain::entities::user::{Role, User},
+};
+
+pub async fn admin_middleware(req: Request<Body>, next: Next) -> Result<Response<Body>, AppError> {
+    let user = req
+        .extensions()
+        .get::<User>()
+        .ok_or(AppError::Unauthorized)?;
+
+    if user.role() != Role::Admin {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(next.run(req).await)
+}
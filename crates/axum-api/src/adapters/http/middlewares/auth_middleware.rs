@@ -5,7 +5,10 @@ use axum::{
     middleware::Next,
 };
 
-use crate::{adapters::http::app_state::AppState, application::app_error::AppError};
+use crate::{
+    adapters::http::app_state::AppState,
+    application::{app_error::AppError, use_cases::account_status::check_account_status},
+};
 
 pub async fn auth_middleware(
     State(state): State<AppState>,
@@ -36,6 +39,9 @@ pub async fn auth_middleware(
         return Err(AppError::Unauthorized);
     }
 
+    // Runs on every request, so it reads from a replica. Status changes revoke the user's
+    // sessions, and closing one's own account also blacklists the token used for it, so
+    // replication lag cannot keep a revoked session alive.
     let current_user = state
         .user_use_case
         .get_user_by_id(&claims.sub)
@@ -43,6 +49,8 @@ pub async fn auth_middleware(
         .map_err(|_| AppError::Unauthorized)?
         .ok_or(AppError::Unauthorized)?;
 
+    check_account_status(&current_user)?;
+
     req.extensions_mut().insert(claims.clone());
     req.extensions_mut().insert(current_user);
 
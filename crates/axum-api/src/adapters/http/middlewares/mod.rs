@@ -1 +1,3 @@
+pub mod admin_middleware;
 pub mod auth_middleware;
+pub mod read_consistency_middleware;
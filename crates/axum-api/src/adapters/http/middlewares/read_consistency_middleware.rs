@@ -0,0 +1,18 @@
+use axum::{
+    body::Body,
+    http::{Request, Response},
+    middleware::Next,
+};
+
+use crate::adapters::persistence::replica::{consistent_reads, primary_reads};
+
+/// Lets a request read from replicas until it writes.
+pub async fn read_consistency_middleware(req: Request<Body>, next: Next) -> Response<Body> {
+    consistent_reads(next.run(req)).await
+}
+
+/// Keeps every read of the request on the primary, for sign-in and token flows that
+/// must see the latest credentials and account status.
+pub async fn primary_reads_middleware(req: Request<Body>, next: Next) -> Response<Body> {
+    primary_reads(next.run(req)).await
+}
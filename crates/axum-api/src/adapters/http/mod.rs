@@ -1,6 +1,9 @@
 pub mod app_state;
+pub mod cookies;
 pub mod dto;
+pub mod etag;
 pub mod extractors;
 pub mod middlewares;
+pub mod pagination;
 pub mod response;
 pub mod routes;
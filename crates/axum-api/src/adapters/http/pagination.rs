@@ -0,0 +1,54 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use validator::Validate;
+
+use crate::application::app_error::AppError;
+
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct PaginationParams {
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    pub limit: Option<u32>,
+
+    #[validate(length(min = 1, max = 512, message = "Invalid cursor"))]
+    pub cursor: Option<String>,
+}
+
+impl PaginationParams {
+    pub fn limit(&self) -> u32 {
+        self.limit.unwrap_or(DEFAULT_PAGE_SIZE)
+    }
+
+    pub fn decode_cursor<T: DeserializeOwned>(&self) -> Result<Option<T>, AppError> {
+        self.cursor
+            .as_deref()
+            .map(|cursor| {
+                URL_SAFE_NO_PAD
+                    .decode(cursor)
+                    .ok()
+                    .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+                    .ok_or_else(|| AppError::ValidationError(vec!["Invalid cursor".to_string()]))
+            })
+            .transpose()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PaginatedResponse<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> PaginatedResponse<T> {
+    pub fn new<C: Serialize>(items: Vec<T>, next_cursor: Option<C>) -> Self {
+        Self {
+            items,
+            next_cursor: next_cursor.and_then(|cursor| {
+                serde_json::to_vec(&cursor)
+                    .ok()
+                    .map(|bytes| URL_SAFE_NO_PAD.encode(bytes))
+            }),
+        }
+    }
+}
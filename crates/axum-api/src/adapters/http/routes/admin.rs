@@ -0,0 +1,430 @@
+use axum::{
+    Extension, Json, RequestExt, Router,
+    extract::{DefaultBodyLimit, Path, Request, State},
+    routing::{delete, get, post},
+};
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio_util::io::StreamReader;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    adapters::http::{
+        app_state::AppState,
+        dto::attribute::AttributesRequest,
+        extractors::validate_json::ValidateJson,
+        extractors::validate_query::ValidateQuery,
+        pagination::{PaginatedResponse, PaginationParams},
+        response::ApiSuccessResponse,
+    },
+    application::{
+        app_error::AppError,
+        use_cases::user_import::{ImportFormat, ImportOptions, ImportReport},
+    },
+    domain::{
+        entities::{
+            attribute::{
+                AttributeSchema, UserAttributes, is_valid_attribute_key, is_valid_namespace,
+            },
+            outbox::OutboxBacklog,
+            user::{Role, User, UserStatus},
+        },
+        repositories::user::{AttributeFilter, SortDirection, UserFilter, UserListQuery},
+    },
+};
+
+const MAX_ATTRIBUTE_FILTERS: usize = 5;
+
+/// Imports are streamed, so this only bounds how long one upload can keep a worker busy.
+const MAX_IMPORT_BODY_BYTES: usize = 256 * 1024 * 1024;
+
+pub fn admin_routes() -> Router<AppState> {
+    Router::new()
+        .route("/attribute-schemas", get(list_attribute_schemas))
+        .route(
+            "/attribute-schemas/{namespace}",
+            get(get_attribute_schema)
+                .put(put_attribute_schema)
+                .delete(delete_attribute_schema),
+        )
+        .route("/outbox", get(outbox_status))
+        .route("/users", get(list_users))
+        .route(
+            "/users/import",
+            post(import_users).layer(DefaultBodyLimit::max(MAX_IMPORT_BODY_BYTES)),
+        )
+        .route("/users/{id}", delete(erase_user))
+        .route("/users/{id}/suspend", post(suspend_user))
+        .route("/users/{id}/reactivate", post(reactivate_user))
+        .route("/users/{id}/lock", post(lock_user))
+        .route("/users/{id}/unlock", post(unlock_user))
+        .route("/users/{id}/attributes", get(list_user_attributes))
+        .route(
+            "/users/{id}/attributes/{namespace}",
+            get(get_user_attributes)
+                .put(put_user_attributes)
+                .delete(delete_user_attributes),
+        )
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ImportUsersQuery {
+    #[serde(default)]
+    format: ImportFormat,
+
+    #[serde(default)]
+    dry_run: bool,
+
+    #[serde(default)]
+    send_welcome: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ListUsersQuery {
+    #[validate(length(min = 1, max = 255, message = "Email prefix must be 1-255 characters"))]
+    email: Option<String>,
+
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    name: Option<String>,
+
+    status: Option<UserStatus>,
+
+    role: Option<Role>,
+
+    created_from: Option<DateTime<Utc>>,
+
+    created_to: Option<DateTime<Utc>>,
+
+    #[validate(length(max = 1000, message = "Attribute filter is too long"))]
+    attribute: Option<String>,
+
+    #[serde(default)]
+    order: SortDirection,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct AttributeSchemaRequest {
+    schema: Value,
+
+    #[serde(default)]
+    user_writable: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminUserResponse {
+    id: Uuid,
+    email: String,
+    name: String,
+    role: Role,
+    status: UserStatus,
+    status_reason: Option<String>,
+    status_until: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct StatusChangeRequest {
+    #[validate(length(min = 1, max = 500, message = "Reason must be 1-500 characters"))]
+    reason: String,
+
+    until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OutboxStatusResponse {
+    #[serde(flatten)]
+    backlog: OutboxBacklog,
+    dispatched_total: u64,
+    publish_failures_total: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErasureScheduledResponse {
+    erase_at: DateTime<Utc>,
+}
+
+impl From<User> for AdminUserResponse {
+    fn from(user: User) -> Self {
+        Self {
+            id: *user.id(),
+            email: user.email().to_string(),
+            name: user.name().to_string(),
+            role: user.role(),
+            status: user.status(),
+            status_reason: user.status_reason().map(str::to_string),
+            status_until: user.status_until(),
+            created_at: user.created_at(),
+            updated_at: user.updated_at(),
+        }
+    }
+}
+
+async fn list_users(
+    State(state): State<AppState>,
+    ValidateQuery(pagination): ValidateQuery<PaginationParams>,
+    ValidateQuery(params): ValidateQuery<ListUsersQuery>,
+) -> Result<Json<ApiSuccessResponse<PaginatedResponse<AdminUserResponse>>>, AppError> {
+    let query = UserListQuery {
+        filter: UserFilter {
+            email_prefix: params.email,
+            name: params.name,
+            status: params.status,
+            role: params.role,
+            created_from: params.created_from,
+            created_to: params.created_to,
+            attributes: parse_attribute_filters(params.attribute.as_deref())?,
+        },
+        direction: params.order,
+        after: pagination.decode_cursor()?,
+        limit: pagination.limit(),
+    };
+
+    let (users, next_cursor) = state.user_use_case.list_users(query).await?;
+
+    Ok(Json(ApiSuccessResponse::new(PaginatedResponse::new(
+        users.into_iter().map(Into::into).collect(),
+        next_cursor,
+    ))))
+}
+
+async fn outbox_status(
+    State(state): State<AppState>,
+) -> Result<Json<ApiSuccessResponse<OutboxStatusResponse>>, AppError> {
+    let backlog = state.outbox_use_case.backlog().await?;
+    let metrics = state.outbox_use_case.metrics();
+
+    Ok(Json(ApiSuccessResponse::new(OutboxStatusResponse {
+        backlog,
+        dispatched_total: metrics.dispatched_total(),
+        publish_failures_total: metrics.publish_failures_total(),
+    })))
+}
+
+async fn suspend_user(
+    State(state): State<AppState>,
+    Extension(admin): Extension<User>,
+    Path(user_id): Path<Uuid>,
+    ValidateJson(payload): ValidateJson<StatusChangeRequest>,
+) -> Result<Json<ApiSuccessResponse<AdminUserResponse>>, AppError> {
+    if payload.until.is_some_and(|until| until <= Utc::now()) {
+        return Err(AppError::ValidationError(vec![
+            "Suspension end must be in the future".to_string(),
+        ]));
+    }
+
+    let user = state
+        .account_use_case
+        .suspend(*admin.id(), user_id, payload.reason, payload.until)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(user.into())))
+}
+
+async fn lock_user(
+    State(state): State<AppState>,
+    Extension(admin): Extension<User>,
+    Path(user_id): Path<Uuid>,
+    ValidateJson(payload): ValidateJson<StatusChangeRequest>,
+) -> Result<Json<ApiSuccessResponse<AdminUserResponse>>, AppError> {
+    if payload.until.is_some_and(|until| until <= Utc::now()) {
+        return Err(AppError::ValidationError(vec![
+            "Lock end must be in the future".to_string(),
+        ]));
+    }
+
+    let user = state
+        .account_use_case
+        .lock(*admin.id(), user_id, payload.reason, payload.until)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(user.into())))
+}
+
+async fn unlock_user(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<ApiSuccessResponse<AdminUserResponse>>, AppError> {
+    let user = state.account_use_case.unlock(user_id).await?;
+
+    Ok(Json(ApiSuccessResponse::new(user.into())))
+}
+
+async fn reactivate_user(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<ApiSuccessResponse<AdminUserResponse>>, AppError> {
+    let user = state.account_use_case.reactivate(user_id).await?;
+
+    Ok(Json(ApiSuccessResponse::new(user.into())))
+}
+
+async fn erase_user(
+    State(state): State<AppState>,
+    Extension(admin): Extension<User>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<ApiSuccessResponse<ErasureScheduledResponse>>, AppError> {
+    let erase_at = state.account_use_case.erase(*admin.id(), user_id).await?;
+
+    Ok(Json(ApiSuccessResponse::new(ErasureScheduledResponse {
+        erase_at,
+    })))
+}
+
+async fn import_users(
+    State(state): State<AppState>,
+    ValidateQuery(query): ValidateQuery<ImportUsersQuery>,
+    request: Request,
+) -> Result<Json<ApiSuccessResponse<ImportReport>>, AppError> {
+    // A raw body skips the default limit unless it is applied explicitly.
+    let body = request.into_limited_body();
+    let reader = StreamReader::new(body.into_data_stream().map_err(std::io::Error::other));
+
+    let report = state
+        .user_import_use_case
+        .import(
+            reader,
+            query.format,
+            ImportOptions {
+                dry_run: query.dry_run,
+                send_welcome_events: query.send_welcome,
+            },
+        )
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(report)))
+}
+
+fn parse_attribute_filters(raw: Option<&str>) -> Result<Vec<AttributeFilter>, AppError> {
+    let Some(raw) = raw else {
+        return Ok(Vec::new());
+    };
+
+    let filters = raw
+        .split(',')
+        .map(|expression| {
+            let invalid = || {
+                AppError::ValidationError(vec![format!(
+                    "Invalid attribute filter '{expression}', expected namespace.key:value"
+                )])
+            };
+
+            let (path, value) = expression.split_once(':').ok_or_else(invalid)?;
+            let mut segments = path.split('.');
+            let namespace = segments.next().filter(|ns| is_valid_namespace(ns));
+            let keys: Vec<String> = segments.map(str::to_string).collect();
+
+            match namespace {
+                Some(namespace)
+                    if !keys.is_empty() && keys.iter().all(|key| is_valid_attribute_key(key)) =>
+                {
+                    Ok(AttributeFilter {
+                        namespace: namespace.to_string(),
+                        path: keys,
+                        value: value.to_string(),
+                    })
+                }
+                _ => Err(invalid()),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if filters.len() > MAX_ATTRIBUTE_FILTERS {
+        return Err(AppError::ValidationError(vec![format!(
+            "At most {MAX_ATTRIBUTE_FILTERS} attribute filters are allowed"
+        )]));
+    }
+
+    Ok(filters)
+}
+
+async fn list_attribute_schemas(
+    State(state): State<AppState>,
+) -> Result<Json<ApiSuccessResponse<Vec<AttributeSchema>>>, AppError> {
+    let schemas = state.attribute_use_case.list_schemas().await?;
+
+    Ok(Json(ApiSuccessResponse::new(schemas)))
+}
+
+async fn get_attribute_schema(
+    State(state): State<AppState>,
+    Path(namespace): Path<String>,
+) -> Result<Json<ApiSuccessResponse<AttributeSchema>>, AppError> {
+    let schema = state.attribute_use_case.get_schema(&namespace).await?;
+
+    Ok(Json(ApiSuccessResponse::new(schema)))
+}
+
+async fn put_attribute_schema(
+    State(state): State<AppState>,
+    Path(namespace): Path<String>,
+    ValidateJson(payload): ValidateJson<AttributeSchemaRequest>,
+) -> Result<Json<ApiSuccessResponse<AttributeSchema>>, AppError> {
+    let schema = state
+        .attribute_use_case
+        .put_schema(&namespace, payload.schema, payload.user_writable)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(schema)))
+}
+
+async fn delete_attribute_schema(
+    State(state): State<AppState>,
+    Path(namespace): Path<String>,
+) -> Result<Json<ApiSuccessResponse<()>>, AppError> {
+    state.attribute_use_case.delete_schema(&namespace).await?;
+
+    Ok(Json(ApiSuccessResponse::new(())))
+}
+
+async fn list_user_attributes(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<ApiSuccessResponse<Vec<UserAttributes>>>, AppError> {
+    let attributes = state
+        .attribute_use_case
+        .list_attributes(user_id, Role::Admin)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(attributes)))
+}
+
+async fn get_user_attributes(
+    State(state): State<AppState>,
+    Path((user_id, namespace)): Path<(Uuid, String)>,
+) -> Result<Json<ApiSuccessResponse<UserAttributes>>, AppError> {
+    let attributes = state
+        .attribute_use_case
+        .get_attributes(user_id, &namespace, Role::Admin)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(attributes)))
+}
+
+async fn put_user_attributes(
+    State(state): State<AppState>,
+    Path((user_id, namespace)): Path<(Uuid, String)>,
+    ValidateJson(payload): ValidateJson<AttributesRequest>,
+) -> Result<Json<ApiSuccessResponse<UserAttributes>>, AppError> {
+    let attributes = state
+        .attribute_use_case
+        .set_attributes(user_id, &namespace, payload.data, Role::Admin)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(attributes)))
+}
+
+async fn delete_user_attributes(
+    State(state): State<AppState>,
+    Path((user_id, namespace)): Path<(Uuid, String)>,
+) -> Result<Json<ApiSuccessResponse<()>>, AppError> {
+    state
+        .attribute_use_case
+        .delete_attributes(user_id, &namespace, Role::Admin)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(())))
+}
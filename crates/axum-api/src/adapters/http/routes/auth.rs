@@ -1,17 +1,28 @@
-use crate::adapters::http::{
-    dto::validators::validate_uuid::validate_uuid, extractors::validate_json::ValidateJson,
+use crate::adapters::http::extractors::{
+    client_info::ExtractClientInfo, validate_json::ValidateJson,
 };
-use axum::{Extension, Json, Router, extract::State, middleware, routing::post};
-
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderName, header},
+    middleware,
+    routing::{delete, get, post},
+};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
     adapters::http::{
-        app_state::AppState, middlewares::auth_middleware::auth_middleware,
+        app_state::AppState,
+        cookies::{TRUSTED_DEVICE_COOKIE, read_cookie, trusted_device_cookie},
+        middlewares::auth_middleware::auth_middleware,
         response::ApiSuccessResponse,
+        routes::passkey::passkey_routes,
     },
-    application::app_error::AppError,
+    application::{app_error::AppError, use_cases::auth::LoginOutcome},
+    domain::entities::{trusted_device::TrustedDevice, user::User},
     infra::security::jwt::Claims,
 };
 
@@ -21,15 +32,29 @@ pub fn auth_routes(state: AppState) -> Router<AppState> {
         .route("/register", post(register))
         .route("/refresh", post(refresh));
 
-    let protected_routes =
-        Router::new()
-            .route("/logout", post(logout))
-            .route_layer(middleware::from_fn_with_state(
-                state.clone(),
-                auth_middleware,
-            ));
+    let protected_routes = Router::new()
+        .route("/logout", post(logout))
+        .route("/password", post(change_password))
+        .route("/devices", get(list_devices).post(trust_device))
+        .route("/devices/{id}", delete(revoke_device))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth_middleware,
+        ));
+
+    public_routes
+        .merge(protected_routes)
+        .nest("/passkeys", passkey_routes(state))
+}
 
-    public_routes.merge(protected_routes)
+pub(crate) fn device_label(headers: &HeaderMap) -> String {
+    headers
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("Unknown device")
+        .chars()
+        .take(100)
+        .collect()
 }
 
 #[derive(Debug, Clone, Deserialize, Validate)]
@@ -46,8 +71,21 @@ pub struct RegisterRequest {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct CredentialsResponse {
-    access_token: String,
-    refresh_token: String,
+    pub(crate) access_token: String,
+    pub(crate) refresh_token: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MfaRequiredResponse {
+    mfa_required: bool,
+    mfa_token: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum LoginResponse {
+    Credentials(CredentialsResponse),
+    MfaRequired(MfaRequiredResponse),
 }
 
 #[derive(Debug, Clone, Deserialize, Validate)]
@@ -61,17 +99,54 @@ pub struct LoginRequest {
 
 #[derive(Debug, Clone, Deserialize, Validate)]
 pub struct RefreshRequest {
-    #[validate(custom(function = "validate_uuid"))]
+    #[validate(length(min = 1, max = 256, message = "Invalid refresh token"))]
     refresh_token: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ChangePasswordRequest {
+    #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
+    current_password: String,
+
+    #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
+    new_password: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct TrustDeviceRequest {
+    #[validate(length(min = 1, max = 100, message = "Label must be 1-100 characters"))]
+    label: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TrustedDeviceResponse {
+    id: Uuid,
+    label: String,
+    created_at: DateTime<Utc>,
+    last_used_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+impl From<TrustedDevice> for TrustedDeviceResponse {
+    fn from(device: TrustedDevice) -> Self {
+        Self {
+            id: device.id,
+            label: device.label,
+            created_at: device.created_at,
+            last_used_at: device.last_used_at,
+            expires_at: device.expires_at,
+        }
+    }
+}
+
 async fn register(
     State(state): State<AppState>,
+    ExtractClientInfo(client): ExtractClientInfo,
     ValidateJson(payload): ValidateJson<RegisterRequest>,
 ) -> Result<Json<ApiSuccessResponse<CredentialsResponse>>, AppError> {
     let (access_token, refresh_token) = state
         .auth_use_case
-        .register(payload.email, payload.password, payload.name)
+        .register(payload.email, payload.password, payload.name, client)
         .await?;
 
     Ok(Json(ApiSuccessResponse::new(CredentialsResponse {
@@ -82,26 +157,44 @@ async fn register(
 
 async fn login(
     State(state): State<AppState>,
+    ExtractClientInfo(client): ExtractClientInfo,
+    headers: HeaderMap,
     ValidateJson(payload): ValidateJson<LoginRequest>,
-) -> Result<Json<ApiSuccessResponse<CredentialsResponse>>, AppError> {
-    let (access_token, refresh_token) = state
+) -> Result<Json<ApiSuccessResponse<LoginResponse>>, AppError> {
+    let trusted_device = read_cookie(&headers, TRUSTED_DEVICE_COOKIE);
+
+    let outcome = state
         .auth_use_case
-        .login(payload.email, payload.password)
+        .login(payload.email, payload.password, client, trusted_device)
         .await?;
 
-    Ok(Json(ApiSuccessResponse::new(CredentialsResponse {
-        access_token,
-        refresh_token,
-    })))
+    let response = match outcome {
+        LoginOutcome::Authenticated {
+            access_token,
+            refresh_token,
+        } => LoginResponse::Credentials(CredentialsResponse {
+            access_token,
+            refresh_token,
+        }),
+        LoginOutcome::MfaRequired { mfa_token } => {
+            LoginResponse::MfaRequired(MfaRequiredResponse {
+                mfa_required: true,
+                mfa_token,
+            })
+        }
+    };
+
+    Ok(Json(ApiSuccessResponse::new(response)))
 }
 
 async fn refresh(
     State(state): State<AppState>,
+    ExtractClientInfo(client): ExtractClientInfo,
     ValidateJson(payload): ValidateJson<RefreshRequest>,
 ) -> Result<Json<ApiSuccessResponse<CredentialsResponse>>, AppError> {
     let (access_token, refresh_token) = state
         .auth_use_case
-        .refresh_token(&payload.refresh_token)
+        .refresh_token(&payload.refresh_token, client)
         .await?;
 
     Ok(Json(ApiSuccessResponse::new(CredentialsResponse {
@@ -121,3 +214,70 @@ async fn logout(
 
     Ok(Json(ApiSuccessResponse::new(())))
 }
+
+async fn change_password(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    ValidateJson(payload): ValidateJson<ChangePasswordRequest>,
+) -> Result<Json<ApiSuccessResponse<()>>, AppError> {
+    state
+        .auth_use_case
+        .change_password(*user.id(), payload.current_password, payload.new_password)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(())))
+}
+
+async fn trust_device(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    headers: HeaderMap,
+    ValidateJson(payload): ValidateJson<TrustDeviceRequest>,
+) -> Result<
+    (
+        [(HeaderName, String); 1],
+        Json<ApiSuccessResponse<TrustedDeviceResponse>>,
+    ),
+    AppError,
+> {
+    let label = payload.label.unwrap_or_else(|| device_label(&headers));
+
+    let (device, cookie) = state
+        .trusted_device_use_case
+        .trust_device(*user.id(), label)
+        .await?;
+
+    let max_age = state.trusted_device_use_case.trust_period().num_seconds();
+
+    Ok((
+        [(header::SET_COOKIE, trusted_device_cookie(&cookie, max_age))],
+        Json(ApiSuccessResponse::new(device.into())),
+    ))
+}
+
+async fn list_devices(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+) -> Result<Json<ApiSuccessResponse<Vec<TrustedDeviceResponse>>>, AppError> {
+    let devices = state
+        .trusted_device_use_case
+        .list_devices(*user.id())
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(
+        devices.into_iter().map(Into::into).collect(),
+    )))
+}
+
+async fn revoke_device(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(device_id): Path<Uuid>,
+) -> Result<Json<ApiSuccessResponse<()>>, AppError> {
+    state
+        .trusted_device_use_case
+        .revoke_device(*user.id(), device_id)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(())))
+}
@@ -0,0 +1,54 @@
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    http::{HeaderName, header},
+    routing::get,
+};
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::{adapters::http::app_state::AppState, application::app_error::AppError};
+
+pub fn file_routes() -> Router<AppState> {
+    Router::new().route("/{*key}", get(download_file))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SignedFileQuery {
+    expires: i64,
+    signature: String,
+}
+
+fn content_type_for(key: &str) -> &'static str {
+    match key.rsplit_once('.').map(|(_, extension)| extension) {
+        Some("json") => "application/json",
+        Some("zip") => "application/zip",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        _ => "application/octet-stream",
+    }
+}
+
+async fn download_file(
+    State(state): State<AppState>,
+    Path(key): Path<String>,
+    Query(query): Query<SignedFileQuery>,
+) -> Result<([(HeaderName, &'static str); 1], Vec<u8>), AppError> {
+    let signer = state
+        .file_url_signer
+        .as_ref()
+        .ok_or(AppError::FileNotFound)?;
+
+    if query.expires < Utc::now().timestamp()
+        || !signer.verify(&key, query.expires, &query.signature)
+    {
+        return Err(AppError::Forbidden);
+    }
+
+    let body = state
+        .object_storage
+        .get(&key)
+        .await?
+        .ok_or(AppError::FileNotFound)?;
+
+    Ok(([(header::CONTENT_TYPE, content_type_for(&key))], body))
+}
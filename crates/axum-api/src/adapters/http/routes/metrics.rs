@@ -0,0 +1,72 @@
+use std::fmt::Write;
+
+use axum::{
+    Router,
+    extract::State,
+    http::{HeaderName, header},
+    routing::get,
+};
+use chrono::Utc;
+
+use crate::adapters::http::app_state::AppState;
+
+pub fn metrics_routes() -> Router<AppState> {
+    Router::new().route("/metrics", get(metrics))
+}
+
+/// Prometheus text exposition. The backlog gauges reflect the last relay pass, so
+/// scraping never touches the database.
+async fn metrics(State(state): State<AppState>) -> ([(HeaderName, &'static str); 1], String) {
+    let metrics = state.outbox_use_case.metrics();
+    let backlog = metrics.backlog();
+    let oldest_pending_age = backlog
+        .oldest_pending_at
+        .map(|oldest| (Utc::now() - oldest).num_milliseconds().max(0) as f64 / 1000.0)
+        .unwrap_or_default();
+
+    let mut body = String::new();
+    let mut sample = |name: &str, kind: &str, help: &str, value: String| {
+        let _ = writeln!(body, "# HELP {name} {help}");
+        let _ = writeln!(body, "# TYPE {name} {kind}");
+        let _ = writeln!(body, "{name} {value}");
+    };
+
+    sample(
+        "outbox_pending_messages",
+        "gauge",
+        "Outbox messages waiting to be published.",
+        backlog.pending.to_string(),
+    );
+    sample(
+        "outbox_failing_messages",
+        "gauge",
+        "Pending outbox messages with at least one failed publish.",
+        backlog.failing.to_string(),
+    );
+    sample(
+        "outbox_dead_lettered_messages",
+        "gauge",
+        "Outbox messages no longer retried after too many failed publishes.",
+        backlog.dead_lettered.to_string(),
+    );
+    sample(
+        "outbox_oldest_pending_age_seconds",
+        "gauge",
+        "Age of the oldest pending outbox message.",
+        oldest_pending_age.to_string(),
+    );
+    sample(
+        "outbox_dispatched_total",
+        "counter",
+        "Outbox messages published and marked dispatched.",
+        metrics.dispatched_total().to_string(),
+    );
+    sample(
+        "outbox_publish_failures_total",
+        "counter",
+        "Failed outbox publish attempts, including retried ones.",
+        metrics.publish_failures_total().to_string(),
+    );
+
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body)
+}
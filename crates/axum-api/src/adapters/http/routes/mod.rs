@@ -1,2 +1,6 @@
+pub mod admin;
 pub mod auth;
+pub mod files;
+pub mod metrics;
+pub mod passkey;
 pub mod user;
@@ -0,0 +1,426 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderName, header},
+    middleware,
+    response::AppendHeaders,
+    routing::{delete, get, post},
+};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    adapters::http::{
+        app_state::AppState,
+        cookies::trusted_device_cookie,
+        extractors::{client_info::ExtractClientInfo, validate_json::ValidateJson},
+        middlewares::auth_middleware::auth_middleware,
+        response::ApiSuccessResponse,
+        routes::auth::{CredentialsResponse, device_label},
+    },
+    application::{app_error::AppError, use_cases::passkey::PasskeyChallenge},
+    domain::entities::{passkey::Passkey, user::User},
+    infra::security::webauthn::{AuthenticatorAssertion, AuthenticatorAttestation, COSE_ALG_ES256},
+};
+
+const CEREMONY_TIMEOUT_MS: u64 = 300_000;
+
+pub fn passkey_routes(state: AppState) -> Router<AppState> {
+    let public_routes = Router::new()
+        .route("/login/start", post(start_login))
+        .route("/login/finish", post(finish_login))
+        .route("/mfa/start", post(start_second_factor))
+        .route("/mfa/finish", post(finish_second_factor));
+
+    let protected_routes = Router::new()
+        .route("/", get(list_passkeys))
+        .route("/{id}", delete(delete_passkey))
+        .route("/register/start", post(start_registration))
+        .route("/register/finish", post(finish_registration))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth_middleware,
+        ));
+
+    public_routes.merge(protected_routes)
+}
+
+fn decode_field(field: &str, value: &str) -> Result<Vec<u8>, AppError> {
+    URL_SAFE_NO_PAD
+        .decode(value)
+        .map_err(|_| AppError::ValidationError(vec![format!("{field} must be base64url encoded")]))
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PasskeyOptionsResponse<T> {
+    ceremony_id: String,
+    public_key: T,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RelyingParty {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserEntity {
+    id: String,
+    name: String,
+    display_name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialParameter {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    alg: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialDescriptor {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    id: String,
+    transports: Vec<String>,
+}
+
+impl From<&Passkey> for CredentialDescriptor {
+    fn from(passkey: &Passkey) -> Self {
+        Self {
+            kind: "public-key",
+            id: URL_SAFE_NO_PAD.encode(&passkey.credential_id),
+            transports: passkey.transports.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorSelection {
+    resident_key: &'static str,
+    user_verification: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreationOptions {
+    challenge: String,
+    rp: RelyingParty,
+    user: UserEntity,
+    pub_key_cred_params: Vec<CredentialParameter>,
+    timeout: u64,
+    attestation: &'static str,
+    exclude_credentials: Vec<CredentialDescriptor>,
+    authenticator_selection: AuthenticatorSelection,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestOptions {
+    challenge: String,
+    rp_id: String,
+    timeout: u64,
+    allow_credentials: Vec<CredentialDescriptor>,
+    user_verification: &'static str,
+}
+
+fn request_options(
+    state: &AppState,
+    challenge: PasskeyChallenge,
+    user_verification: &'static str,
+) -> PasskeyOptionsResponse<RequestOptions> {
+    PasskeyOptionsResponse {
+        ceremony_id: challenge.ceremony_id,
+        public_key: RequestOptions {
+            challenge: challenge.challenge,
+            rp_id: state.config.webauthn.rp_id.clone(),
+            timeout: CEREMONY_TIMEOUT_MS,
+            allow_credentials: challenge.credentials.iter().map(Into::into).collect(),
+            user_verification,
+        },
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationResponse {
+    #[serde(rename = "clientDataJSON")]
+    client_data_json: String,
+    attestation_object: String,
+    #[serde(default)]
+    transports: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistrationCredential {
+    response: AttestationResponse,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionResponse {
+    #[serde(rename = "clientDataJSON")]
+    client_data_json: String,
+    authenticator_data: String,
+    signature: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionCredential {
+    raw_id: String,
+    response: AssertionResponse,
+}
+
+impl AssertionCredential {
+    fn decode(&self) -> Result<(Vec<u8>, AuthenticatorAssertion), AppError> {
+        Ok((
+            decode_field("rawId", &self.raw_id)?,
+            AuthenticatorAssertion {
+                client_data_json: decode_field("clientDataJSON", &self.response.client_data_json)?,
+                authenticator_data: decode_field(
+                    "authenticatorData",
+                    &self.response.authenticator_data,
+                )?,
+                signature: decode_field("signature", &self.response.signature)?,
+            },
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct FinishRegistrationRequest {
+    #[validate(length(min = 1, max = 64, message = "Invalid ceremony id"))]
+    ceremony_id: String,
+
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    name: Option<String>,
+
+    credential: RegistrationCredential,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct FinishLoginRequest {
+    #[validate(length(min = 1, max = 64, message = "Invalid ceremony id"))]
+    ceremony_id: String,
+
+    credential: AssertionCredential,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct StartSecondFactorRequest {
+    #[validate(length(min = 1, max = 256, message = "Invalid MFA token"))]
+    mfa_token: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct FinishSecondFactorRequest {
+    #[validate(length(min = 1, max = 64, message = "Invalid ceremony id"))]
+    ceremony_id: String,
+
+    credential: AssertionCredential,
+
+    #[serde(default)]
+    remember_device: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PasskeyResponse {
+    id: Uuid,
+    name: String,
+    transports: Vec<String>,
+    created_at: DateTime<Utc>,
+    last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<Passkey> for PasskeyResponse {
+    fn from(passkey: Passkey) -> Self {
+        Self {
+            id: passkey.id,
+            name: passkey.name,
+            transports: passkey.transports,
+            created_at: passkey.created_at,
+            last_used_at: passkey.last_used_at,
+        }
+    }
+}
+
+async fn start_registration(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+) -> Result<Json<ApiSuccessResponse<PasskeyOptionsResponse<CreationOptions>>>, AppError> {
+    let challenge = state
+        .passkey_use_case
+        .start_registration(*user.id())
+        .await?;
+    let webauthn = &state.config.webauthn;
+
+    Ok(Json(ApiSuccessResponse::new(PasskeyOptionsResponse {
+        ceremony_id: challenge.ceremony_id,
+        public_key: CreationOptions {
+            challenge: challenge.challenge,
+            rp: RelyingParty {
+                id: webauthn.rp_id.clone(),
+                name: webauthn.rp_name.clone(),
+            },
+            user: UserEntity {
+                id: URL_SAFE_NO_PAD.encode(user.id().as_bytes()),
+                name: user.email().to_string(),
+                display_name: user.name().to_string(),
+            },
+            pub_key_cred_params: vec![CredentialParameter {
+                kind: "public-key",
+                alg: COSE_ALG_ES256,
+            }],
+            timeout: CEREMONY_TIMEOUT_MS,
+            attestation: "none",
+            exclude_credentials: challenge.credentials.iter().map(Into::into).collect(),
+            authenticator_selection: AuthenticatorSelection {
+                resident_key: "preferred",
+                user_verification: "preferred",
+            },
+        },
+    })))
+}
+
+async fn finish_registration(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    headers: HeaderMap,
+    ValidateJson(payload): ValidateJson<FinishRegistrationRequest>,
+) -> Result<Json<ApiSuccessResponse<PasskeyResponse>>, AppError> {
+    let response = payload.credential.response;
+    let attestation = AuthenticatorAttestation {
+        client_data_json: decode_field("clientDataJSON", &response.client_data_json)?,
+        attestation_object: decode_field("attestationObject", &response.attestation_object)?,
+    };
+    let name = payload.name.unwrap_or_else(|| device_label(&headers));
+
+    let passkey = state
+        .passkey_use_case
+        .finish_registration(
+            *user.id(),
+            &payload.ceremony_id,
+            attestation,
+            response.transports,
+            name,
+        )
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(passkey.into())))
+}
+
+async fn list_passkeys(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+) -> Result<Json<ApiSuccessResponse<Vec<PasskeyResponse>>>, AppError> {
+    let passkeys = state.passkey_use_case.list_passkeys(*user.id()).await?;
+
+    Ok(Json(ApiSuccessResponse::new(
+        passkeys.into_iter().map(Into::into).collect(),
+    )))
+}
+
+async fn delete_passkey(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(passkey_id): Path<Uuid>,
+) -> Result<Json<ApiSuccessResponse<()>>, AppError> {
+    state
+        .passkey_use_case
+        .delete_passkey(*user.id(), passkey_id)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(())))
+}
+
+async fn start_login(
+    State(state): State<AppState>,
+) -> Result<Json<ApiSuccessResponse<PasskeyOptionsResponse<RequestOptions>>>, AppError> {
+    let challenge = state.passkey_use_case.start_login().await?;
+
+    Ok(Json(ApiSuccessResponse::new(request_options(
+        &state, challenge, "required",
+    ))))
+}
+
+async fn finish_login(
+    State(state): State<AppState>,
+    ExtractClientInfo(client): ExtractClientInfo,
+    ValidateJson(payload): ValidateJson<FinishLoginRequest>,
+) -> Result<Json<ApiSuccessResponse<CredentialsResponse>>, AppError> {
+    let (credential_id, assertion) = payload.credential.decode()?;
+
+    let (access_token, refresh_token) = state
+        .passkey_use_case
+        .finish_login(&payload.ceremony_id, &credential_id, assertion, client)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(CredentialsResponse {
+        access_token,
+        refresh_token,
+    })))
+}
+
+async fn start_second_factor(
+    State(state): State<AppState>,
+    ExtractClientInfo(client): ExtractClientInfo,
+    ValidateJson(payload): ValidateJson<StartSecondFactorRequest>,
+) -> Result<Json<ApiSuccessResponse<PasskeyOptionsResponse<RequestOptions>>>, AppError> {
+    let challenge = state
+        .passkey_use_case
+        .start_second_factor(&payload.mfa_token, &client)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(request_options(
+        &state,
+        challenge,
+        "preferred",
+    ))))
+}
+
+async fn finish_second_factor(
+    State(state): State<AppState>,
+    ExtractClientInfo(client): ExtractClientInfo,
+    headers: HeaderMap,
+    ValidateJson(payload): ValidateJson<FinishSecondFactorRequest>,
+) -> Result<
+    (
+        AppendHeaders<Option<(HeaderName, String)>>,
+        Json<ApiSuccessResponse<CredentialsResponse>>,
+    ),
+    AppError,
+> {
+    let (credential_id, assertion) = payload.credential.decode()?;
+    let remember_device = payload.remember_device.then(|| device_label(&headers));
+
+    let login = state
+        .passkey_use_case
+        .finish_second_factor(
+            &payload.ceremony_id,
+            &credential_id,
+            assertion,
+            client,
+            remember_device,
+        )
+        .await?;
+
+    let max_age = state.trusted_device_use_case.trust_period().num_seconds();
+    let cookie = login
+        .trusted_device_cookie
+        .map(|cookie| (header::SET_COOKIE, trusted_device_cookie(&cookie, max_age)));
+
+    Ok((
+        AppendHeaders(cookie),
+        Json(ApiSuccessResponse::new(CredentialsResponse {
+            access_token: login.access_token,
+            refresh_token: login.refresh_token,
+        })),
+    ))
+}
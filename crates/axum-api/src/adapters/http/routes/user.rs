@@ -1,15 +1,65 @@
-use axum::{Extension, Json, Router, routing::get};
-use serde::Serialize;
+use std::collections::BTreeMap;
+
+use axum::{
+    Extension, Json, Router,
+    extract::{DefaultBodyLimit, Multipart, Path, State},
+    http::{HeaderMap, HeaderName, StatusCode, header},
+    middleware,
+    routing::{delete, get, post, put},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::{
-    adapters::http::{app_state::AppState, response::ApiSuccessResponse},
-    application::app_error::AppError,
-    domain::entities::user::User,
+    adapters::http::{
+        app_state::AppState,
+        dto::attribute::AttributesRequest,
+        etag::{etag, if_match},
+        extractors::validate_json::ValidateJson,
+        middlewares::auth_middleware::auth_middleware,
+        response::ApiSuccessResponse,
+    },
+    application::{
+        app_error::{AppError, AppResult},
+        use_cases::avatar::MAX_AVATAR_BYTES,
+    },
+    domain::entities::{
+        attribute::UserAttributes,
+        data_export::{DataExport, DataExportFormat, DataExportStatus},
+        user::{ProfileChanges, Role, User},
+    },
+    infra::security::jwt::Claims,
 };
 
-pub fn user_routes() -> Router<AppState> {
-    Router::new().route("/profile", get(get_profile))
+const MULTIPART_OVERHEAD: usize = 16 * 1024;
+const AVATAR_FIELD: &str = "avatar";
+
+pub fn user_routes(state: AppState) -> Router<AppState> {
+    let public_routes = Router::new().route("/email-change/confirm", post(confirm_email_change));
+
+    let protected_routes = Router::new()
+        .route("/profile", get(get_profile).patch(update_profile))
+        .route(
+            "/profile/avatar",
+            put(upload_avatar).layer(DefaultBodyLimit::max(MAX_AVATAR_BYTES + MULTIPART_OVERHEAD)),
+        )
+        .route("/profile/attributes", get(list_attributes))
+        .route(
+            "/profile/attributes/{namespace}",
+            get(get_attributes)
+                .put(put_attributes)
+                .delete(delete_attributes),
+        )
+        .route("/email-change", post(request_email_change))
+        .route("/me", delete(request_erasure))
+        .route("/me/deactivate", post(deactivate))
+        .route("/me/export", post(request_export))
+        .route("/me/export/{id}", get(get_export))
+        .route_layer(middleware::from_fn_with_state(state, auth_middleware));
+
+    public_routes.merge(protected_routes)
 }
 
 #[derive(Debug, Serialize)]
@@ -17,13 +67,341 @@ pub struct UserProfileResponse {
     pub id: Uuid,
     pub email: String,
     pub name: String,
+    pub avatar_url: Option<String>,
+}
+
+impl UserProfileResponse {
+    async fn build(state: &AppState, user: &User) -> AppResult<Self> {
+        Ok(Self {
+            id: *user.id(),
+            email: user.email().to_string(),
+            name: user.name().to_string(),
+            avatar_url: state.avatar_use_case.avatar_url(user).await?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AvatarResponse {
+    avatar_url: Option<String>,
+    variants: BTreeMap<&'static str, String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct UpdateProfileRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    name: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ConfirmPasswordRequest {
+    #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
+    password: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct EmailChangeRequest {
+    #[validate(email(message = "Invalid email format"))]
+    new_email: String,
+
+    #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
+    password: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ConfirmEmailChangeRequest {
+    #[validate(length(min = 1, message = "Token is required"))]
+    token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmailChangeRequestedResponse {
+    expires_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Serialize)]
+pub struct ErasureScheduledResponse {
+    erase_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+pub struct DataExportRequest {
+    #[serde(default)]
+    format: DataExportFormat,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DataExportResponse {
+    id: Uuid,
+    format: DataExportFormat,
+    status: DataExportStatus,
+    requested_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+    download_url: Option<String>,
+}
+
+impl DataExportResponse {
+    fn new(export: DataExport, download_url: Option<String>) -> Self {
+        Self {
+            id: export.id,
+            format: export.format,
+            status: export.status,
+            requested_at: export.requested_at,
+            completed_at: export.completed_at,
+            download_url,
+        }
+    }
+}
+
 async fn get_profile(
+    State(state): State<AppState>,
     Extension(user): Extension<User>,
-) -> Result<Json<ApiSuccessResponse<UserProfileResponse>>, AppError> {
-    Ok(Json(ApiSuccessResponse::new(UserProfileResponse {
-        id: *user.id(),
-        email: user.email().to_string(),
-        name: user.name().to_string(),
+) -> Result<
+    (
+        [(HeaderName, String); 1],
+        Json<ApiSuccessResponse<UserProfileResponse>>,
+    ),
+    AppError,
+> {
+    Ok((
+        [(header::ETAG, etag(user.updated_at()))],
+        Json(ApiSuccessResponse::new(
+            UserProfileResponse::build(&state, &user).await?,
+        )),
+    ))
+}
+
+async fn update_profile(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    headers: HeaderMap,
+    ValidateJson(payload): ValidateJson<UpdateProfileRequest>,
+) -> Result<
+    (
+        [(HeaderName, String); 1],
+        Json<ApiSuccessResponse<UserProfileResponse>>,
+    ),
+    AppError,
+> {
+    let expected_updated_at = if_match(&headers)?;
+
+    let user = state
+        .user_use_case
+        .update_profile(
+            *user.id(),
+            expected_updated_at,
+            ProfileChanges { name: payload.name },
+        )
+        .await?;
+
+    Ok((
+        [(header::ETAG, etag(user.updated_at()))],
+        Json(ApiSuccessResponse::new(
+            UserProfileResponse::build(&state, &user).await?,
+        )),
+    ))
+}
+
+async fn deactivate(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Extension(claims): Extension<Claims>,
+    ValidateJson(payload): ValidateJson<ConfirmPasswordRequest>,
+) -> Result<Json<ApiSuccessResponse<()>>, AppError> {
+    state
+        .account_use_case
+        .deactivate(*user.id(), &payload.password)
+        .await?;
+    state
+        .auth_use_case
+        .revoke_token(&claims.jti, claims.exp)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(())))
+}
+
+async fn request_erasure(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Extension(claims): Extension<Claims>,
+    ValidateJson(payload): ValidateJson<ConfirmPasswordRequest>,
+) -> Result<Json<ApiSuccessResponse<ErasureScheduledResponse>>, AppError> {
+    let erase_at = state
+        .account_use_case
+        .request_erasure(*user.id(), &payload.password)
+        .await?;
+    state
+        .auth_use_case
+        .revoke_token(&claims.jti, claims.exp)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(ErasureScheduledResponse {
+        erase_at,
     })))
 }
+
+async fn request_export(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    ValidateJson(payload): ValidateJson<DataExportRequest>,
+) -> Result<(StatusCode, Json<ApiSuccessResponse<DataExportResponse>>), AppError> {
+    let export = state
+        .data_export_use_case
+        .request_export(&user, payload.format)
+        .await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ApiSuccessResponse::new(DataExportResponse::new(
+            export, None,
+        ))),
+    ))
+}
+
+async fn get_export(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(export_id): Path<Uuid>,
+) -> Result<Json<ApiSuccessResponse<DataExportResponse>>, AppError> {
+    let (export, download_url) = state
+        .data_export_use_case
+        .get_export(*user.id(), export_id)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(DataExportResponse::new(
+        export,
+        download_url,
+    ))))
+}
+
+async fn request_email_change(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    ValidateJson(payload): ValidateJson<EmailChangeRequest>,
+) -> Result<
+    (
+        StatusCode,
+        Json<ApiSuccessResponse<EmailChangeRequestedResponse>>,
+    ),
+    AppError,
+> {
+    let expires_at = state
+        .email_change_use_case
+        .request_change(*user.id(), payload.new_email, &payload.password)
+        .await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ApiSuccessResponse::new(EmailChangeRequestedResponse {
+            expires_at,
+        })),
+    ))
+}
+
+async fn confirm_email_change(
+    State(state): State<AppState>,
+    ValidateJson(payload): ValidateJson<ConfirmEmailChangeRequest>,
+) -> Result<Json<ApiSuccessResponse<UserProfileResponse>>, AppError> {
+    let user = state
+        .email_change_use_case
+        .confirm_change(&payload.token)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(
+        UserProfileResponse::build(&state, &user).await?,
+    )))
+}
+
+async fn upload_avatar(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    mut multipart: Multipart,
+) -> Result<Json<ApiSuccessResponse<AvatarResponse>>, AppError> {
+    let multipart_error = |e: axum::extract::multipart::MultipartError| {
+        if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            AppError::PayloadTooLarge
+        } else {
+            AppError::ValidationError(vec![e.body_text()])
+        }
+    };
+
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        if field.name() != Some(AVATAR_FIELD) {
+            continue;
+        }
+
+        let content_type = field
+            .content_type()
+            .map(str::to_string)
+            .ok_or(AppError::UnsupportedMediaType)?;
+        let data = field.bytes().await.map_err(multipart_error)?;
+
+        let user = state
+            .avatar_use_case
+            .upload(*user.id(), data.to_vec(), &content_type)
+            .await?;
+
+        let variants = state.avatar_use_case.avatar_urls(&user).await?;
+
+        return Ok(Json(ApiSuccessResponse::new(AvatarResponse {
+            avatar_url: state.avatar_use_case.avatar_url(&user).await?,
+            variants,
+        })));
+    }
+
+    Err(AppError::ValidationError(vec![format!(
+        "Multipart field '{AVATAR_FIELD}' is required"
+    )]))
+}
+
+async fn list_attributes(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+) -> Result<Json<ApiSuccessResponse<Vec<UserAttributes>>>, AppError> {
+    let attributes = state
+        .attribute_use_case
+        .list_attributes(*user.id(), Role::User)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(attributes)))
+}
+
+async fn get_attributes(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(namespace): Path<String>,
+) -> Result<Json<ApiSuccessResponse<UserAttributes>>, AppError> {
+    let attributes = state
+        .attribute_use_case
+        .get_attributes(*user.id(), &namespace, Role::User)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(attributes)))
+}
+
+async fn put_attributes(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(namespace): Path<String>,
+    ValidateJson(payload): ValidateJson<AttributesRequest>,
+) -> Result<Json<ApiSuccessResponse<UserAttributes>>, AppError> {
+    let attributes = state
+        .attribute_use_case
+        .set_attributes(*user.id(), &namespace, payload.data, Role::User)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(attributes)))
+}
+
+async fn delete_attributes(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(namespace): Path<String>,
+) -> Result<Json<ApiSuccessResponse<()>>, AppError> {
+    state
+        .attribute_use_case
+        .delete_attributes(*user.id(), &namespace, Role::User)
+        .await?;
+
+    Ok(Json(ApiSuccessResponse::new(())))
+}
@@ -0,0 +1,141 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+};
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::attribute::{AttributeSchema, UserAttributes},
+    repositories::{attribute::AttributeRepository, error::RepositoryResult},
+};
+
+#[derive(Default)]
+struct State {
+    schemas: BTreeMap<String, AttributeSchema>,
+    attributes: HashMap<Uuid, BTreeMap<String, UserAttributes>>,
+}
+
+/// Schemas and documents are listed by namespace, as the SQL adapters order them.
+#[derive(Default)]
+pub struct InMemoryAttributeRepository {
+    state: Mutex<State>,
+}
+
+impl InMemoryAttributeRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl AttributeRepository for InMemoryAttributeRepository {
+    async fn upsert_schema(
+        &self,
+        namespace: &str,
+        schema: &str,
+        user_writable: bool,
+    ) -> RepositoryResult<AttributeSchema> {
+        let schema = AttributeSchema {
+            namespace: namespace.to_string(),
+            schema: serde_json::from_str(schema)?,
+            user_writable,
+            updated_at: Utc::now(),
+        };
+
+        self.state
+            .lock()
+            .unwrap()
+            .schemas
+            .insert(namespace.to_string(), schema.clone());
+
+        Ok(schema)
+    }
+
+    async fn find_schema(&self, namespace: &str) -> RepositoryResult<Option<AttributeSchema>> {
+        Ok(self.state.lock().unwrap().schemas.get(namespace).cloned())
+    }
+
+    async fn list_schemas(&self) -> RepositoryResult<Vec<AttributeSchema>> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .schemas
+            .values()
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_schema(&self, namespace: &str) -> RepositoryResult<bool> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .schemas
+            .remove(namespace)
+            .is_some())
+    }
+
+    async fn upsert(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+        data: &str,
+    ) -> RepositoryResult<UserAttributes> {
+        let attributes = UserAttributes {
+            user_id,
+            namespace: namespace.to_string(),
+            data: serde_json::from_str(data)?,
+            updated_at: Utc::now(),
+        };
+
+        self.state
+            .lock()
+            .unwrap()
+            .attributes
+            .entry(user_id)
+            .or_default()
+            .insert(namespace.to_string(), attributes.clone());
+
+        Ok(attributes)
+    }
+
+    async fn find(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+    ) -> RepositoryResult<Option<UserAttributes>> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .attributes
+            .get(&user_id)
+            .and_then(|attributes| attributes.get(namespace))
+            .cloned())
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<UserAttributes>> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .attributes
+            .get(&user_id)
+            .map(|attributes| attributes.values().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn delete(&self, user_id: Uuid, namespace: &str) -> RepositoryResult<bool> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .attributes
+            .get_mut(&user_id)
+            .and_then(|attributes| attributes.remove(namespace))
+            .is_some())
+    }
+}
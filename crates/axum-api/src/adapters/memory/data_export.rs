@@ -0,0 +1,136 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::data_export::{DataExport, DataExportStatus},
+    repositories::{data_export::DataExportRepository, error::RepositoryResult},
+};
+
+struct StoredExport {
+    export: DataExport,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct State {
+    exports: HashMap<Uuid, StoredExport>,
+    objects: HashMap<String, DateTime<Utc>>,
+}
+
+/// Exports and their tracked objects expire on the system clock, so a TTL of zero
+/// expires them at once.
+#[derive(Default)]
+pub struct InMemoryDataExportRepository {
+    state: Mutex<State>,
+}
+
+impl InMemoryDataExportRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn expiry(ttl_secs: u64) -> DateTime<Utc> {
+    Utc::now() + chrono::Duration::seconds(ttl_secs as i64)
+}
+
+#[async_trait::async_trait]
+impl DataExportRepository for InMemoryDataExportRepository {
+    async fn save(&self, export: &DataExport, ttl_secs: u64) -> RepositoryResult<()> {
+        let mut state = self.state.lock().unwrap();
+
+        if let (DataExportStatus::Completed, Some(object_key)) =
+            (&export.status, &export.object_key)
+        {
+            state.objects.insert(object_key.clone(), expiry(ttl_secs));
+        }
+
+        state.exports.insert(
+            export.id,
+            StoredExport {
+                export: export.clone(),
+                expires_at: expiry(ttl_secs),
+            },
+        );
+
+        Ok(())
+    }
+
+    async fn find(&self, user_id: Uuid, export_id: Uuid) -> RepositoryResult<Option<DataExport>> {
+        let state = self.state.lock().unwrap();
+
+        Ok(state
+            .exports
+            .get(&export_id)
+            .filter(|stored| stored.export.user_id == user_id && stored.expires_at > Utc::now())
+            .map(|stored| stored.export.clone()))
+    }
+
+    async fn list_stalled(
+        &self,
+        requested_before: DateTime<Utc>,
+        limit: usize,
+    ) -> RepositoryResult<Vec<DataExport>> {
+        let state = self.state.lock().unwrap();
+        let now = Utc::now();
+
+        let mut stalled: Vec<DataExport> = state
+            .exports
+            .values()
+            .filter(|stored| {
+                stored.expires_at > now
+                    && stored.export.status == DataExportStatus::Pending
+                    && stored.export.requested_at < requested_before
+            })
+            .map(|stored| stored.export.clone())
+            .collect();
+
+        stalled.sort_by_key(|export| export.requested_at);
+        stalled.truncate(limit);
+
+        Ok(stalled)
+    }
+
+    async fn list_expired_objects(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> RepositoryResult<Vec<String>> {
+        let state = self.state.lock().unwrap();
+
+        let mut expired: Vec<(DateTime<Utc>, String)> = state
+            .objects
+            .iter()
+            .filter(|(_, expires_at)| **expires_at <= now)
+            .map(|(key, expires_at)| (*expires_at, key.clone()))
+            .collect();
+
+        expired.sort();
+
+        Ok(expired
+            .into_iter()
+            .take(limit)
+            .map(|(_, key)| key)
+            .collect())
+    }
+
+    async fn list_objects(&self, user_id: Uuid) -> RepositoryResult<Vec<String>> {
+        let state = self.state.lock().unwrap();
+        let prefix = DataExport::object_prefix(user_id);
+
+        Ok(state
+            .objects
+            .keys()
+            .filter(|key| key.starts_with(&prefix))
+            .cloned()
+            .collect())
+    }
+
+    async fn forget_object(&self, object_key: &str) -> RepositoryResult<()> {
+        self.state.lock().unwrap().objects.remove(object_key);
+
+        Ok(())
+    }
+}
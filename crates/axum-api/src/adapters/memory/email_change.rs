@@ -0,0 +1,49 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use tokio::time::Instant;
+
+use crate::domain::{
+    entities::email_change::PendingEmailChange,
+    repositories::{email_change::EmailChangeRepository, error::RepositoryResult},
+};
+
+/// Pending changes expire on the Tokio clock, like the token cache entries.
+#[derive(Default)]
+pub struct InMemoryEmailChangeRepository {
+    changes: Mutex<HashMap<String, (PendingEmailChange, Instant)>>,
+}
+
+impl InMemoryEmailChangeRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailChangeRepository for InMemoryEmailChangeRepository {
+    async fn store(
+        &self,
+        token_hash: &str,
+        change: &PendingEmailChange,
+        ttl_secs: u64,
+    ) -> RepositoryResult<()> {
+        let expires_at = Instant::now() + Duration::from_secs(ttl_secs);
+
+        self.changes
+            .lock()
+            .unwrap()
+            .insert(token_hash.to_string(), (change.clone(), expires_at));
+
+        Ok(())
+    }
+
+    async fn take(&self, token_hash: &str) -> RepositoryResult<Option<PendingEmailChange>> {
+        Ok(self
+            .changes
+            .lock()
+            .unwrap()
+            .remove(token_hash)
+            .filter(|(_, expires_at)| *expires_at > Instant::now())
+            .map(|(change, _)| change))
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::{
+    Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use async_trait::async_trait;
+
+use crate::domain::events::{
+    error::{KafkaError, KafkaResult},
+    user::{
+        EmailChangeRequested, UserDataExportReady, UserEmailChanged, UserEventPublisher,
+        UserStatusChanged, UserUpdated,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub enum RecordedEvent {
+    UserUpdated(UserUpdated),
+    DataExportReady(UserDataExportReady),
+    EmailChangeRequested(EmailChangeRequested),
+    EmailChanged(UserEmailChanged),
+    StatusChanged(UserStatusChanged),
+}
+
+/// Keeps every published event in order. While failing, publishes are rejected and not
+/// recorded.
+#[derive(Default)]
+pub struct RecordingEventPublisher {
+    events: Mutex<Vec<RecordedEvent>>,
+    failing: AtomicBool,
+}
+
+impl RecordingEventPublisher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn events(&self) -> Vec<RecordedEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    pub fn take(&self) -> Vec<RecordedEvent> {
+        std::mem::take(&mut *self.events.lock().unwrap())
+    }
+
+    pub fn set_failing(&self, failing: bool) {
+        self.failing.store(failing, Ordering::SeqCst);
+    }
+
+    fn record(&self, event: RecordedEvent) -> KafkaResult<()> {
+        if self.failing.load(Ordering::SeqCst) {
+            return Err(KafkaError::MessageSend(
+                "recording publisher is set to fail".to_string(),
+            ));
+        }
+
+        self.events.lock().unwrap().push(event);
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UserEventPublisher for RecordingEventPublisher {
+    async fn publish_user_updated(&self, event: UserUpdated) -> KafkaResult<()> {
+        self.record(RecordedEvent::UserUpdated(event))
+    }
+
+    async fn publish_data_export_ready(&self, event: UserDataExportReady) -> KafkaResult<()> {
+        self.record(RecordedEvent::DataExportReady(event))
+    }
+
+    async fn publish_email_change_requested(&self, event: EmailChangeRequested) -> KafkaResult<()> {
+        self.record(RecordedEvent::EmailChangeRequested(event))
+    }
+
+    async fn publish_email_changed(&self, event: UserEmailChanged) -> KafkaResult<()> {
+        self.record(RecordedEvent::EmailChanged(event))
+    }
+
+    async fn publish_status_changed(&self, event: UserStatusChanged) -> KafkaResult<()> {
+        self.record(RecordedEvent::StatusChanged(event))
+    }
+}
@@ -0,0 +1,19 @@
+//! In-memory implementations of the application ports, enabled by the `testing` feature.
+//!
+//! They keep the observable behavior of the real adapters (case-insensitive emails,
+//! compare-and-set updates, expiring cache entries) so use cases can be exercised
+//! without external services.
+
+pub mod attribute;
+pub mod data_export;
+pub mod email_change;
+pub mod events;
+pub mod object_storage;
+pub mod outbox;
+pub mod passkey;
+pub mod second_factor;
+pub mod security;
+pub mod token_cache;
+pub mod trusted_device;
+pub mod unit_of_work;
+pub mod user;
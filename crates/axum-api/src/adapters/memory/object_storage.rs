@@ -0,0 +1,47 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use crate::domain::repositories::{error::RepositoryResult, object_storage::ObjectStorage};
+
+/// Signed URLs are not verifiable; they only name the key and lifetime.
+#[derive(Default)]
+pub struct InMemoryObjectStorage {
+    objects: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl InMemoryObjectStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.objects.lock().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+}
+
+#[async_trait::async_trait]
+impl ObjectStorage for InMemoryObjectStorage {
+    async fn put(&self, key: &str, body: Vec<u8>, _content_type: &str) -> RepositoryResult<()> {
+        self.objects.lock().unwrap().insert(key.to_string(), body);
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> RepositoryResult<Option<Vec<u8>>> {
+        Ok(self.objects.lock().unwrap().get(key).cloned())
+    }
+
+    async fn delete(&self, key: &str) -> RepositoryResult<()> {
+        self.objects.lock().unwrap().remove(key);
+
+        Ok(())
+    }
+
+    async fn signed_url(&self, key: &str, expires_in: Duration) -> RepositoryResult<String> {
+        Ok(format!(
+            "memory://{key}?expires_in={}",
+            expires_in.as_secs()
+        ))
+    }
+}
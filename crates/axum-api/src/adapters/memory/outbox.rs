@@ -0,0 +1,257 @@
+use std::{
+    collections::HashSet,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::outbox::{OutboxBacklog, OutboxMessage},
+    events::{
+        error::{KafkaError, KafkaResult},
+        outbox::OutboxPublisher,
+    },
+    repositories::{
+        error::{RepositoryError, RepositoryResult},
+        outbox::OutboxRepository,
+    },
+};
+
+#[derive(Clone)]
+struct StoredMessage {
+    message: OutboxMessage,
+    dispatched_at: Option<DateTime<Utc>>,
+}
+
+impl StoredMessage {
+    fn is_live(&self, max_attempts: u32) -> bool {
+        self.dispatched_at.is_none() && self.message.attempts < max_attempts
+    }
+
+    fn waits_past(&self, now: DateTime<Utc>) -> bool {
+        self.message
+            .next_attempt_at
+            .is_some_and(|next_attempt_at| next_attempt_at > now)
+    }
+}
+
+/// Keeps messages in enqueue order, including dispatched ones.
+#[derive(Default)]
+pub struct InMemoryOutboxRepository {
+    messages: Mutex<Vec<StoredMessage>>,
+    relay_lease: Mutex<Option<(Uuid, Instant)>>,
+}
+
+impl InMemoryOutboxRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn messages(&self) -> Vec<OutboxMessage> {
+        self.messages
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|stored| stored.message.clone())
+            .collect()
+    }
+
+    pub(super) fn snapshot(&self) -> Self {
+        Self {
+            messages: Mutex::new(self.messages.lock().unwrap().clone()),
+            relay_lease: Mutex::new(*self.relay_lease.lock().unwrap()),
+        }
+    }
+
+    pub(super) fn restore(&self, snapshot: Self) {
+        *self.messages.lock().unwrap() = snapshot.messages.into_inner().unwrap();
+    }
+
+    fn update(&self, id: &Uuid, change: impl FnOnce(&mut StoredMessage)) -> RepositoryResult<()> {
+        let mut messages = self.messages.lock().unwrap();
+        let stored = messages
+            .iter_mut()
+            .find(|stored| stored.message.id == *id)
+            .ok_or(RepositoryError::NoRowFound)?;
+
+        change(stored);
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for InMemoryOutboxRepository {
+    async fn enqueue(&self, message: &OutboxMessage) -> RepositoryResult<()> {
+        let mut messages = self.messages.lock().unwrap();
+
+        if messages
+            .iter()
+            .any(|stored| stored.message.id == message.id)
+        {
+            return Err(RepositoryError::UniqueViolation {
+                constraint: "pk_outbox".to_string(),
+            });
+        }
+
+        messages.push(StoredMessage {
+            message: message.clone(),
+            dispatched_at: None,
+        });
+
+        Ok(())
+    }
+
+    async fn list_pending(
+        &self,
+        now: DateTime<Utc>,
+        max_attempts: u32,
+        limit: u32,
+    ) -> RepositoryResult<Vec<OutboxMessage>> {
+        let messages = self.messages.lock().unwrap();
+        let mut waiting = HashSet::new();
+        let mut pending = Vec::new();
+
+        for stored in messages
+            .iter()
+            .filter(|stored| stored.is_live(max_attempts))
+        {
+            if stored.waits_past(now) {
+                waiting.insert(stored.message.aggregate_id);
+            } else if !waiting.contains(&stored.message.aggregate_id) {
+                pending.push(stored.message.clone());
+            }
+        }
+
+        pending.truncate(limit as usize);
+
+        Ok(pending)
+    }
+
+    async fn mark_dispatched(&self, id: &Uuid) -> RepositoryResult<()> {
+        self.update(id, |stored| stored.dispatched_at = Some(Utc::now()))
+    }
+
+    async fn record_failure(
+        &self,
+        id: &Uuid,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> RepositoryResult<()> {
+        self.update(id, |stored| {
+            stored.message.attempts += 1;
+            stored.message.last_error = Some(error.to_string());
+            stored.message.next_attempt_at = Some(next_attempt_at);
+        })
+    }
+
+    async fn backlog(&self, max_attempts: u32) -> RepositoryResult<OutboxBacklog> {
+        let messages = self.messages.lock().unwrap();
+        let pending = messages
+            .iter()
+            .filter(|stored| stored.is_live(max_attempts));
+
+        Ok(OutboxBacklog {
+            pending: pending.clone().count() as u64,
+            failing: pending
+                .clone()
+                .filter(|stored| stored.message.attempts > 0)
+                .count() as u64,
+            dead_lettered: messages
+                .iter()
+                .filter(|stored| stored.dispatched_at.is_none() && !stored.is_live(max_attempts))
+                .count() as u64,
+            oldest_pending_at: pending.map(|stored| stored.message.created_at).min(),
+        })
+    }
+
+    async fn delete_dispatched(&self, before: DateTime<Utc>, limit: u32) -> RepositoryResult<u64> {
+        let mut messages = self.messages.lock().unwrap();
+        let mut deleted = 0;
+
+        messages.retain(|stored| {
+            let expired = deleted < limit
+                && stored
+                    .dispatched_at
+                    .is_some_and(|dispatched_at| dispatched_at < before);
+            deleted += u32::from(expired);
+
+            !expired
+        });
+
+        Ok(u64::from(deleted))
+    }
+
+    async fn acquire_relay_lease(&self, holder: &Uuid, ttl: Duration) -> RepositoryResult<bool> {
+        let mut relay_lease = self.relay_lease.lock().unwrap();
+        let now = Instant::now();
+
+        if relay_lease.is_some_and(|(current, expires_at)| current != *holder && expires_at > now) {
+            return Ok(false);
+        }
+
+        *relay_lease = Some((*holder, now + ttl));
+
+        Ok(true)
+    }
+}
+
+/// Keeps every published message in order. Publishes for failing aggregates, or all
+/// publishes while set to fail, are rejected and not recorded.
+#[derive(Default)]
+pub struct RecordingOutboxPublisher {
+    published: Mutex<Vec<OutboxMessage>>,
+    failing_aggregates: Mutex<HashSet<Uuid>>,
+    failing: AtomicBool,
+}
+
+impl RecordingOutboxPublisher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn published(&self) -> Vec<OutboxMessage> {
+        self.published.lock().unwrap().clone()
+    }
+
+    pub fn set_failing(&self, failing: bool) {
+        self.failing.store(failing, Ordering::SeqCst);
+    }
+
+    pub fn set_failing_aggregate(&self, aggregate_id: Uuid, failing: bool) {
+        let mut failing_aggregates = self.failing_aggregates.lock().unwrap();
+
+        if failing {
+            failing_aggregates.insert(aggregate_id);
+        } else {
+            failing_aggregates.remove(&aggregate_id);
+        }
+    }
+}
+
+#[async_trait]
+impl OutboxPublisher for RecordingOutboxPublisher {
+    async fn publish(&self, message: &OutboxMessage) -> KafkaResult<()> {
+        if self.failing.load(Ordering::SeqCst)
+            || self
+                .failing_aggregates
+                .lock()
+                .unwrap()
+                .contains(&message.aggregate_id)
+        {
+            return Err(KafkaError::MessageSend(
+                "recording publisher is set to fail".to_string(),
+            ));
+        }
+
+        self.published.lock().unwrap().push(message.clone());
+
+        Ok(())
+    }
+}
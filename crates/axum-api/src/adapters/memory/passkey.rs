@@ -0,0 +1,139 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use chrono::Utc;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::passkey::{Passkey, PasskeyCeremony},
+    repositories::{
+        error::{RepositoryError, RepositoryResult},
+        passkey::{PasskeyCeremonyRepository, PasskeyRepository},
+    },
+};
+
+const CREDENTIAL_CONSTRAINT: &str = "passkey_credentials.credential_id";
+
+#[derive(Default)]
+pub struct InMemoryPasskeyRepository {
+    passkeys: Mutex<HashMap<Uuid, Passkey>>,
+}
+
+impl InMemoryPasskeyRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl PasskeyRepository for InMemoryPasskeyRepository {
+    async fn create(&self, passkey: &Passkey) -> RepositoryResult<()> {
+        let mut passkeys = self.passkeys.lock().unwrap();
+
+        if passkeys
+            .values()
+            .any(|existing| existing.credential_id == passkey.credential_id)
+        {
+            return Err(RepositoryError::UniqueViolation {
+                constraint: CREDENTIAL_CONSTRAINT.to_string(),
+            });
+        }
+
+        passkeys.insert(passkey.id, passkey.clone());
+
+        Ok(())
+    }
+
+    async fn find_by_credential_id(
+        &self,
+        credential_id: &[u8],
+    ) -> RepositoryResult<Option<Passkey>> {
+        Ok(self
+            .passkeys
+            .lock()
+            .unwrap()
+            .values()
+            .find(|passkey| passkey.credential_id == credential_id)
+            .cloned())
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<Passkey>> {
+        let mut passkeys: Vec<Passkey> = self
+            .passkeys
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|passkey| passkey.user_id == user_id)
+            .cloned()
+            .collect();
+
+        passkeys.sort_by_key(|passkey| passkey.created_at);
+
+        Ok(passkeys)
+    }
+
+    async fn update_sign_count(&self, id: Uuid, sign_count: u32) -> RepositoryResult<()> {
+        let mut passkeys = self.passkeys.lock().unwrap();
+        let passkey = passkeys.get_mut(&id).ok_or(RepositoryError::NoRowFound)?;
+
+        passkey.sign_count = sign_count;
+        passkey.last_used_at = Some(Utc::now());
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> RepositoryResult<bool> {
+        let mut passkeys = self.passkeys.lock().unwrap();
+
+        let owned = passkeys
+            .get(&id)
+            .is_some_and(|passkey| passkey.user_id == user_id);
+
+        if owned {
+            passkeys.remove(&id);
+        }
+
+        Ok(owned)
+    }
+}
+
+/// Ceremonies expire on the Tokio clock, like the token cache entries.
+#[derive(Default)]
+pub struct InMemoryPasskeyCeremonyRepository {
+    ceremonies: Mutex<HashMap<String, (PasskeyCeremony, Instant)>>,
+}
+
+impl InMemoryPasskeyCeremonyRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl PasskeyCeremonyRepository for InMemoryPasskeyCeremonyRepository {
+    async fn store(
+        &self,
+        ceremony_id: &str,
+        ceremony: &PasskeyCeremony,
+        ttl_secs: u64,
+    ) -> RepositoryResult<()> {
+        let expires_at = Instant::now() + Duration::from_secs(ttl_secs);
+
+        self.ceremonies
+            .lock()
+            .unwrap()
+            .insert(ceremony_id.to_string(), (ceremony.clone(), expires_at));
+
+        Ok(())
+    }
+
+    async fn take(&self, ceremony_id: &str) -> RepositoryResult<Option<PasskeyCeremony>> {
+        Ok(self
+            .ceremonies
+            .lock()
+            .unwrap()
+            .remove(ceremony_id)
+            .filter(|(_, expires_at)| *expires_at > Instant::now())
+            .map(|(ceremony, _)| ceremony))
+    }
+}
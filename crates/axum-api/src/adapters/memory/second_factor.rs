@@ -0,0 +1,31 @@
+use std::{collections::HashSet, sync::Mutex};
+
+use uuid::Uuid;
+
+use crate::domain::repositories::{error::RepositoryResult, second_factor::SecondFactorRepository};
+
+#[derive(Default)]
+pub struct InMemorySecondFactorRepository {
+    enrolled: Mutex<HashSet<Uuid>>,
+}
+
+impl InMemorySecondFactorRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn enroll(&self, user_id: Uuid) {
+        self.enrolled.lock().unwrap().insert(user_id);
+    }
+
+    pub fn unenroll(&self, user_id: Uuid) {
+        self.enrolled.lock().unwrap().remove(&user_id);
+    }
+}
+
+#[async_trait::async_trait]
+impl SecondFactorRepository for InMemorySecondFactorRepository {
+    async fn has_second_factor(&self, user_id: Uuid) -> RepositoryResult<bool> {
+        Ok(self.enrolled.lock().unwrap().contains(&user_id))
+    }
+}
@@ -0,0 +1,86 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use chrono::{Duration, Utc};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    application::app_error::AppError,
+    infra::security::{argon2::PasswordHasherTrait, jwt::Claims, jwt::TokenProvider},
+};
+
+const HASH_PREFIX: &str = "sha256$";
+
+/// Unsalted SHA-256, which keeps tests fast where Argon2 would dominate their runtime.
+#[derive(Default)]
+pub struct InsecurePasswordHasher;
+
+impl InsecurePasswordHasher {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl PasswordHasherTrait for InsecurePasswordHasher {
+    fn hash_password(&self, password: &str) -> Result<String, AppError> {
+        Ok(format!(
+            "{HASH_PREFIX}{}",
+            hex::encode(Sha256::digest(password.as_bytes()))
+        ))
+    }
+
+    fn verify_password(&self, password: &str, hash: &str) -> Result<bool, AppError> {
+        if !self.is_supported_hash(hash) {
+            return Err(AppError::PasswordHashingFailed(
+                "unsupported password hash".to_string(),
+            ));
+        }
+
+        Ok(self.hash_password(password)? == hash)
+    }
+
+    fn is_supported_hash(&self, hash: &str) -> bool {
+        hash.starts_with(HASH_PREFIX)
+    }
+}
+
+/// Issues opaque tokens and remembers their claims until they expire.
+#[derive(Default)]
+pub struct InMemoryTokenProvider {
+    issued: Mutex<HashMap<String, Claims>>,
+}
+
+impl InMemoryTokenProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TokenProvider for InMemoryTokenProvider {
+    fn generate_token(&self, user_id: &str, expiration: Duration) -> Result<String, AppError> {
+        let now = Utc::now().timestamp();
+
+        let claims = Claims {
+            sub: user_id.to_owned(),
+            jti: Uuid::new_v4().to_string(),
+            iat: now,
+            exp: now + expiration.num_seconds(),
+        };
+
+        let token = Uuid::new_v4().simple().to_string();
+        self.issued.lock().unwrap().insert(token.clone(), claims);
+
+        Ok(token)
+    }
+
+    fn decode_token(&self, token: &str) -> Result<Claims, AppError> {
+        let mut issued = self.issued.lock().unwrap();
+        let now = Utc::now().timestamp();
+        issued.retain(|_, claims| claims.exp > now);
+
+        issued
+            .get(token)
+            .cloned()
+            .ok_or_else(|| AppError::TokenParsingFailed("unknown or expired token".to_string()))
+    }
+}
@@ -0,0 +1,227 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::session::{MfaChallenge, RefreshSession},
+    repositories::{
+        error::RepositoryResult,
+        token_cache::{SessionLimit, TokenCacheRepository},
+    },
+};
+
+struct Entry<T> {
+    value: T,
+    expires_at: Instant,
+}
+
+impl<T> Entry<T> {
+    fn new(value: T, ttl_secs: u64) -> Self {
+        Self {
+            value,
+            expires_at: Instant::now() + Duration::from_secs(ttl_secs),
+        }
+    }
+
+    fn is_live(&self, now: Instant) -> bool {
+        self.expires_at > now
+    }
+}
+
+#[derive(Default)]
+struct State {
+    refresh_tokens: HashMap<String, Entry<(u64, RefreshSession)>>,
+    next_sequence: u64,
+    legacy_refresh_tokens: HashMap<String, Entry<Uuid>>,
+    mfa_challenges: HashMap<String, Entry<MfaChallenge>>,
+    blacklist: HashMap<String, Entry<()>>,
+}
+
+impl State {
+    fn purge_expired(&mut self) {
+        let now = Instant::now();
+
+        self.refresh_tokens.retain(|_, entry| entry.is_live(now));
+        self.legacy_refresh_tokens
+            .retain(|_, entry| entry.is_live(now));
+        self.mfa_challenges.retain(|_, entry| entry.is_live(now));
+        self.blacklist.retain(|_, entry| entry.is_live(now));
+    }
+
+    /// The user's sessions, oldest first.
+    fn sessions_of(&self, user_id: Uuid) -> Vec<(String, RefreshSession)> {
+        let mut sessions: Vec<(u64, String, RefreshSession)> = self
+            .refresh_tokens
+            .iter()
+            .filter(|(_, entry)| entry.value.1.user_id == user_id)
+            .map(|(token_hash, entry)| {
+                let (sequence, session) = &entry.value;
+                (*sequence, token_hash.clone(), session.clone())
+            })
+            .collect();
+
+        sessions.sort_by_key(|(sequence, _, session)| (session.created_at, *sequence));
+
+        sessions
+            .into_iter()
+            .map(|(_, token_hash, session)| (token_hash, session))
+            .collect()
+    }
+}
+
+/// Entries expire on the Tokio clock, so paused-time tests can advance past a TTL.
+/// Sessions created at the same instant are listed in the order they were stored.
+#[derive(Default)]
+pub struct InMemoryTokenCache {
+    state: Mutex<State>,
+}
+
+impl InMemoryTokenCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seeds a refresh token issued before token hashing was introduced.
+    pub fn store_legacy_refresh_token(&self, token_id: &str, user_id: Uuid, ttl_secs: u64) {
+        self.state
+            .lock()
+            .unwrap()
+            .legacy_refresh_tokens
+            .insert(token_id.to_string(), Entry::new(user_id, ttl_secs));
+    }
+
+    fn with_state<T>(&self, f: impl FnOnce(&mut State) -> T) -> T {
+        let mut state = self.state.lock().unwrap();
+        state.purge_expired();
+
+        f(&mut state)
+    }
+}
+
+#[async_trait::async_trait]
+impl TokenCacheRepository for InMemoryTokenCache {
+    async fn store_refresh_token(
+        &self,
+        token_hash: &str,
+        session: &RefreshSession,
+        ttl_secs: u64,
+        limit: SessionLimit,
+    ) -> RepositoryResult<bool> {
+        Ok(self.with_state(|state| {
+            let sessions = state.sessions_of(session.user_id);
+
+            match limit {
+                SessionLimit::Reject(max) if sessions.len() >= max => return false,
+                SessionLimit::EvictOldest(max) if sessions.len() >= max => {
+                    for (token_hash, _) in &sessions[..=sessions.len() - max] {
+                        state.refresh_tokens.remove(token_hash);
+                    }
+                }
+                _ => {}
+            }
+
+            state.next_sequence += 1;
+            let value = (state.next_sequence, session.clone());
+            state
+                .refresh_tokens
+                .insert(token_hash.to_string(), Entry::new(value, ttl_secs));
+
+            true
+        }))
+    }
+
+    async fn take_refresh_token(
+        &self,
+        token_hash: &str,
+    ) -> RepositoryResult<Option<RefreshSession>> {
+        Ok(self.with_state(|state| {
+            state
+                .refresh_tokens
+                .remove(token_hash)
+                .map(|entry| entry.value.1)
+        }))
+    }
+
+    async fn list_refresh_tokens(
+        &self,
+        user_id: Uuid,
+    ) -> RepositoryResult<Vec<(String, RefreshSession)>> {
+        Ok(self.with_state(|state| state.sessions_of(user_id)))
+    }
+
+    async fn revoke_refresh_token(&self, user_id: Uuid, token_hash: &str) -> RepositoryResult<()> {
+        self.with_state(|state| {
+            if state
+                .refresh_tokens
+                .get(token_hash)
+                .is_some_and(|entry| entry.value.1.user_id == user_id)
+            {
+                state.refresh_tokens.remove(token_hash);
+            }
+        });
+
+        Ok(())
+    }
+
+    async fn revoke_all_refresh_tokens(&self, user_id: Uuid) -> RepositoryResult<()> {
+        self.with_state(|state| {
+            state
+                .refresh_tokens
+                .retain(|_, entry| entry.value.1.user_id != user_id);
+        });
+
+        Ok(())
+    }
+
+    async fn take_legacy_refresh_token(&self, token_id: &str) -> RepositoryResult<Option<Uuid>> {
+        Ok(self.with_state(|state| {
+            state
+                .legacy_refresh_tokens
+                .remove(token_id)
+                .map(|entry| entry.value)
+        }))
+    }
+
+    async fn store_mfa_challenge(
+        &self,
+        challenge_hash: &str,
+        challenge: &MfaChallenge,
+        ttl_secs: u64,
+    ) -> RepositoryResult<()> {
+        self.with_state(|state| {
+            state.mfa_challenges.insert(
+                challenge_hash.to_string(),
+                Entry::new(challenge.clone(), ttl_secs),
+            );
+        });
+
+        Ok(())
+    }
+
+    async fn take_mfa_challenge(
+        &self,
+        challenge_hash: &str,
+    ) -> RepositoryResult<Option<MfaChallenge>> {
+        Ok(self.with_state(|state| {
+            state
+                .mfa_challenges
+                .remove(challenge_hash)
+                .map(|entry| entry.value)
+        }))
+    }
+
+    async fn blacklist_access_token(&self, jti: &str, ttl_secs: u64) -> RepositoryResult<()> {
+        self.with_state(|state| {
+            state
+                .blacklist
+                .insert(jti.to_string(), Entry::new((), ttl_secs));
+        });
+
+        Ok(())
+    }
+
+    async fn is_access_token_blacklisted(&self, jti: &str) -> RepositoryResult<bool> {
+        Ok(self.with_state(|state| state.blacklist.contains_key(jti)))
+    }
+}
@@ -0,0 +1,77 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::trusted_device::TrustedDevice,
+    repositories::{error::RepositoryResult, trusted_device::TrustedDeviceRepository},
+};
+
+/// Expired devices are dropped when listed, as the Redis adapter does.
+#[derive(Default)]
+pub struct InMemoryTrustedDeviceRepository {
+    devices: Mutex<HashMap<(Uuid, Uuid), TrustedDevice>>,
+}
+
+impl InMemoryTrustedDeviceRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl TrustedDeviceRepository for InMemoryTrustedDeviceRepository {
+    async fn save(&self, device: &TrustedDevice) -> RepositoryResult<()> {
+        self.devices
+            .lock()
+            .unwrap()
+            .insert((device.user_id, device.id), device.clone());
+
+        Ok(())
+    }
+
+    async fn find(
+        &self,
+        user_id: Uuid,
+        device_id: Uuid,
+    ) -> RepositoryResult<Option<TrustedDevice>> {
+        Ok(self
+            .devices
+            .lock()
+            .unwrap()
+            .get(&(user_id, device_id))
+            .cloned())
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<TrustedDevice>> {
+        let mut devices = self.devices.lock().unwrap();
+        devices.retain(|_, device| !device.is_expired());
+
+        let mut listed: Vec<TrustedDevice> = devices
+            .values()
+            .filter(|device| device.user_id == user_id)
+            .cloned()
+            .collect();
+        listed.sort_by_key(|device| device.created_at);
+
+        Ok(listed)
+    }
+
+    async fn revoke(&self, user_id: Uuid, device_id: Uuid) -> RepositoryResult<bool> {
+        Ok(self
+            .devices
+            .lock()
+            .unwrap()
+            .remove(&(user_id, device_id))
+            .is_some())
+    }
+
+    async fn revoke_all(&self, user_id: Uuid) -> RepositoryResult<()> {
+        self.devices
+            .lock()
+            .unwrap()
+            .retain(|(owner, _), _| *owner != user_id);
+
+        Ok(())
+    }
+}
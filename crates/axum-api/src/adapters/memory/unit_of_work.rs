@@ -0,0 +1,88 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    adapters::memory::{outbox::InMemoryOutboxRepository, user::InMemoryUserRepository},
+    domain::repositories::{
+        error::{RepositoryError, RepositoryResult},
+        outbox::OutboxRepository,
+        unit_of_work::{UnitOfWork, UnitOfWorkFactory},
+        user::UserRepository,
+    },
+};
+
+/// Units of work change copies of the repositories that replace their contents on commit,
+/// so they are not isolated from each other and are meant to run one at a time. While
+/// failing, commits are rejected and the copy is discarded.
+pub struct InMemoryUnitOfWorkFactory {
+    users: Arc<InMemoryUserRepository>,
+    outbox: Arc<InMemoryOutboxRepository>,
+    failing: Arc<AtomicBool>,
+}
+
+impl InMemoryUnitOfWorkFactory {
+    pub fn new(users: Arc<InMemoryUserRepository>, outbox: Arc<InMemoryOutboxRepository>) -> Self {
+        Self {
+            users,
+            outbox,
+            failing: Arc::default(),
+        }
+    }
+
+    pub fn set_failing(&self, failing: bool) {
+        self.failing.store(failing, Ordering::SeqCst);
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkFactory for InMemoryUnitOfWorkFactory {
+    async fn begin(&self) -> RepositoryResult<Box<dyn UnitOfWork>> {
+        Ok(Box::new(InMemoryUnitOfWork {
+            users_target: self.users.clone(),
+            users: self.users.snapshot(),
+            outbox_target: self.outbox.clone(),
+            outbox: self.outbox.snapshot(),
+            failing: self.failing.clone(),
+        }))
+    }
+}
+
+pub struct InMemoryUnitOfWork {
+    users_target: Arc<InMemoryUserRepository>,
+    users: InMemoryUserRepository,
+    outbox_target: Arc<InMemoryOutboxRepository>,
+    outbox: InMemoryOutboxRepository,
+    failing: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl UnitOfWork for InMemoryUnitOfWork {
+    fn users(&self) -> &dyn UserRepository {
+        &self.users
+    }
+
+    fn outbox(&self) -> &dyn OutboxRepository {
+        &self.outbox
+    }
+
+    async fn commit(self: Box<Self>) -> RepositoryResult<()> {
+        if self.failing.load(Ordering::SeqCst) {
+            return Err(RepositoryError::StorageFailure(
+                "unit of work is set to fail".to_string(),
+            ));
+        }
+
+        self.users_target.restore(self.users);
+        self.outbox_target.restore(self.outbox);
+
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> RepositoryResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,408 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::user::{User, UserStatus},
+    repositories::{
+        error::{RepositoryError, RepositoryResult},
+        user::{SortDirection, UserFilter, UserListQuery, UserRepository},
+    },
+};
+
+const EMAIL_CONSTRAINT: &str = "users.email";
+const ID_CONSTRAINT: &str = "users.id";
+
+/// Attribute documents live outside this repository, so attribute filters match no users.
+#[derive(Default)]
+pub struct InMemoryUserRepository {
+    users: Mutex<HashMap<Uuid, User>>,
+    unnamed_violations: Arc<AtomicBool>,
+}
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reports unique violations without naming the index, like SQL Server does in
+    /// languages other than English.
+    pub fn set_unnamed_violations(&self, unnamed: bool) {
+        self.unnamed_violations.store(unnamed, Ordering::SeqCst);
+    }
+
+    fn unique_violation(&self, constraint: &str) -> RepositoryError {
+        let constraint = if self.unnamed_violations.load(Ordering::SeqCst) {
+            ""
+        } else {
+            constraint
+        };
+
+        RepositoryError::UniqueViolation {
+            constraint: constraint.to_string(),
+        }
+    }
+
+    /// Stores the user as-is, keeping its timestamps, role and status.
+    pub fn insert(&self, user: User) -> RepositoryResult<()> {
+        let mut users = self.users.lock().unwrap();
+
+        if email_taken(&users, user.email(), None) {
+            return Err(self.unique_violation(EMAIL_CONSTRAINT));
+        }
+
+        users.insert(*user.id(), user);
+
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(super) fn snapshot(&self) -> Self {
+        Self {
+            users: Mutex::new(self.users.lock().unwrap().clone()),
+            unnamed_violations: self.unnamed_violations.clone(),
+        }
+    }
+
+    pub(super) fn restore(&self, snapshot: Self) {
+        *self.users.lock().unwrap() = snapshot.users.into_inner().unwrap();
+    }
+}
+
+fn email_taken(users: &HashMap<Uuid, User>, email: &str, except: Option<&Uuid>) -> bool {
+    users
+        .values()
+        .any(|user| Some(user.id()) != except && user.email().eq_ignore_ascii_case(email))
+}
+
+fn stored(user: &User, updated_at: DateTime<Utc>) -> User {
+    User::from_db(
+        *user.id(),
+        user.email().to_string(),
+        user.password().to_string(),
+        user.name().to_string(),
+        user.role(),
+        user.status(),
+        user.status_reason().map(str::to_string),
+        user.status_until(),
+        user.avatar_key().map(str::to_string),
+        user.created_at(),
+        updated_at,
+    )
+}
+
+fn created(user: &User, now: DateTime<Utc>) -> User {
+    User::from_db(
+        *user.id(),
+        user.email().to_string(),
+        user.password().to_string(),
+        user.name().to_string(),
+        user.role(),
+        user.status(),
+        None,
+        None,
+        None,
+        now,
+        now,
+    )
+}
+
+fn matches(user: &User, filter: &UserFilter) -> bool {
+    filter.email_prefix.as_ref().is_none_or(|prefix| {
+        user.email()
+            .to_lowercase()
+            .starts_with(&prefix.to_lowercase())
+    }) && filter
+        .name
+        .as_ref()
+        .is_none_or(|name| user.name().to_lowercase().contains(&name.to_lowercase()))
+        && filter.status.is_none_or(|status| user.status() == status)
+        && filter.role.is_none_or(|role| user.role() == role)
+        && filter
+            .created_from
+            .is_none_or(|from| user.created_at() >= from)
+        && filter.created_to.is_none_or(|to| user.created_at() < to)
+        && filter.attributes.is_empty()
+}
+
+fn parse_id(id: &str) -> Option<Uuid> {
+    Uuid::parse_str(id).ok()
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn create(&self, user: &User) -> RepositoryResult<User> {
+        let mut users = self.users.lock().unwrap();
+
+        if users.contains_key(user.id()) {
+            return Err(self.unique_violation(ID_CONSTRAINT));
+        }
+
+        if email_taken(&users, user.email(), None) {
+            return Err(self.unique_violation(EMAIL_CONSTRAINT));
+        }
+
+        let created = created(user, Utc::now());
+        users.insert(*created.id(), created.clone());
+
+        Ok(created)
+    }
+
+    async fn create_many(&self, new_users: &[User]) -> RepositoryResult<Vec<User>> {
+        let mut users = self.users.lock().unwrap();
+        let now = Utc::now();
+        let mut inserted = Vec::new();
+
+        for user in new_users {
+            if users.contains_key(user.id()) || email_taken(&users, user.email(), None) {
+                continue;
+            }
+
+            let created = created(user, now);
+            users.insert(*created.id(), created.clone());
+            inserted.push(created);
+        }
+
+        Ok(inserted)
+    }
+
+    async fn find_existing_emails(&self, emails: &[String]) -> RepositoryResult<Vec<String>> {
+        let users = self.users.lock().unwrap();
+
+        Ok(users
+            .values()
+            .filter(|user| {
+                emails
+                    .iter()
+                    .any(|email| user.email().eq_ignore_ascii_case(email))
+            })
+            .map(|user| user.email().to_string())
+            .collect())
+    }
+
+    async fn find_by_email(&self, email: &str) -> RepositoryResult<Option<User>> {
+        let users = self.users.lock().unwrap();
+
+        Ok(users
+            .values()
+            .find(|user| user.email().eq_ignore_ascii_case(email))
+            .cloned())
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<User>> {
+        let users = self.users.lock().unwrap();
+
+        Ok(parse_id(id).and_then(|id| users.get(&id).cloned()))
+    }
+
+    async fn list(&self, query: &UserListQuery) -> RepositoryResult<Vec<User>> {
+        let users = self.users.lock().unwrap();
+
+        let mut listed: Vec<User> = users
+            .values()
+            .filter(|user| matches(user, &query.filter))
+            .filter(|user| {
+                query.after.is_none_or(|cursor| {
+                    let key = (user.created_at(), *user.id());
+                    let after = (cursor.created_at, cursor.id);
+
+                    match query.direction {
+                        SortDirection::Asc => key > after,
+                        SortDirection::Desc => key < after,
+                    }
+                })
+            })
+            .cloned()
+            .collect();
+
+        listed.sort_by_key(|user| (user.created_at(), *user.id()));
+        if query.direction == SortDirection::Desc {
+            listed.reverse();
+        }
+        listed.truncate(query.limit as usize);
+
+        Ok(listed)
+    }
+
+    async fn update_password(&self, id: &str, password: &str) -> RepositoryResult<()> {
+        let mut users = self.users.lock().unwrap();
+
+        let user = parse_id(id)
+            .and_then(|id| users.get_mut(&id))
+            .ok_or(RepositoryError::NoRowFound)?;
+
+        *user = User::from_db(
+            *user.id(),
+            user.email().to_string(),
+            password.to_string(),
+            user.name().to_string(),
+            user.role(),
+            user.status(),
+            user.status_reason().map(str::to_string),
+            user.status_until(),
+            user.avatar_key().map(str::to_string),
+            user.created_at(),
+            Utc::now(),
+        );
+
+        Ok(())
+    }
+
+    async fn update_profile(
+        &self,
+        user: &User,
+        expected_updated_at: DateTime<Utc>,
+    ) -> RepositoryResult<Option<User>> {
+        let mut users = self.users.lock().unwrap();
+
+        let Some(current) = users.get_mut(user.id()) else {
+            return Ok(None);
+        };
+
+        if current.updated_at() != expected_updated_at {
+            return Ok(None);
+        }
+
+        *current = User::from_db(
+            *current.id(),
+            current.email().to_string(),
+            current.password().to_string(),
+            user.name().to_string(),
+            current.role(),
+            current.status(),
+            current.status_reason().map(str::to_string),
+            current.status_until(),
+            current.avatar_key().map(str::to_string),
+            current.created_at(),
+            Utc::now(),
+        );
+
+        Ok(Some(current.clone()))
+    }
+
+    async fn update_email(&self, user: &User, previous_email: &str) -> RepositoryResult<bool> {
+        let mut users = self.users.lock().unwrap();
+
+        if email_taken(&users, user.email(), None) {
+            return Ok(false);
+        }
+
+        let Some(current) = users.get_mut(user.id()) else {
+            return Ok(false);
+        };
+
+        if !current.email().eq_ignore_ascii_case(previous_email) {
+            return Ok(false);
+        }
+
+        current.change_email(user.email().to_string());
+        *current = stored(current, Utc::now());
+
+        Ok(true)
+    }
+
+    async fn update_avatar(&self, user: &User) -> RepositoryResult<()> {
+        let mut users = self.users.lock().unwrap();
+
+        let current = users
+            .get_mut(user.id())
+            .ok_or(RepositoryError::NoRowFound)?;
+
+        current.set_avatar(user.avatar_key().map(str::to_string));
+        *current = stored(current, Utc::now());
+
+        Ok(())
+    }
+
+    async fn update_status(&self, user: &User, previous: UserStatus) -> RepositoryResult<bool> {
+        let mut users = self.users.lock().unwrap();
+
+        let Some(current) = users.get_mut(user.id()) else {
+            return Ok(false);
+        };
+
+        if current.status() != previous {
+            return Ok(false);
+        }
+
+        *current = User::from_db(
+            *current.id(),
+            current.email().to_string(),
+            current.password().to_string(),
+            current.name().to_string(),
+            current.role(),
+            user.status(),
+            user.status_reason().map(str::to_string),
+            user.status_until(),
+            current.avatar_key().map(str::to_string),
+            current.created_at(),
+            Utc::now(),
+        );
+
+        Ok(true)
+    }
+
+    async fn list_due_erasures(
+        &self,
+        due_before: DateTime<Utc>,
+        limit: u32,
+    ) -> RepositoryResult<Vec<User>> {
+        let users = self.users.lock().unwrap();
+
+        let mut due: Vec<User> = users
+            .values()
+            .filter(|user| {
+                user.status() == UserStatus::PendingDeletion
+                    && user.status_until().is_some_and(|until| until <= due_before)
+            })
+            .cloned()
+            .collect();
+
+        due.sort_by_key(|user| user.status_until());
+        due.truncate(limit as usize);
+
+        Ok(due)
+    }
+
+    async fn erase(&self, user: &User) -> RepositoryResult<bool> {
+        let mut users = self.users.lock().unwrap();
+
+        let Some(current) = users
+            .get_mut(user.id())
+            .filter(|current| current.status() == UserStatus::PendingDeletion)
+        else {
+            return Ok(false);
+        };
+
+        *current = User::from_db(
+            *user.id(),
+            user.email().to_string(),
+            user.password().to_string(),
+            user.name().to_string(),
+            current.role(),
+            user.status(),
+            None,
+            None,
+            None,
+            current.created_at(),
+            Utc::now(),
+        );
+
+        Ok(true)
+    }
+}
@@ -2,9 +2,16 @@ use rdkafka::producer::{FutureProducer, FutureRecord};
 
 use crate::{
     adapters::messaging::kafka::topics,
-    domain::events::{
-        error::{KafkaError, KafkaResult},
-        user::{UserCreated, UserEventPublisher},
+    domain::{
+        entities::outbox::OutboxMessage,
+        events::{
+            error::{KafkaError, KafkaResult},
+            outbox::OutboxPublisher,
+            user::{
+                EmailChangeRequested, UserDataExportReady, UserEmailChanged, UserEventPublisher,
+                UserStatusChanged, UserUpdated,
+            },
+        },
     },
 };
 use async_trait::async_trait;
@@ -32,10 +39,51 @@ impl KafkaProducer {
 
 #[async_trait]
 impl UserEventPublisher for KafkaProducer {
-    async fn publish_user_created(&self, event: UserCreated) -> KafkaResult<()> {
+    async fn publish_user_updated(&self, event: UserUpdated) -> KafkaResult<()> {
         let key = event.user_id.to_string();
         let payload = serde_json::to_string(&event)?;
 
-        self.send(topics::USER_CREATED, &key, &payload).await
+        self.send(topics::USER_UPDATED, &key, &payload).await
+    }
+
+    async fn publish_data_export_ready(&self, event: UserDataExportReady) -> KafkaResult<()> {
+        let key = event.user_id.to_string();
+        let payload = serde_json::to_string(&event)?;
+
+        self.send(topics::USER_DATA_EXPORT_READY, &key, &payload)
+            .await
+    }
+
+    async fn publish_email_change_requested(&self, event: EmailChangeRequested) -> KafkaResult<()> {
+        let key = event.user_id.to_string();
+        let payload = serde_json::to_string(&event)?;
+
+        self.send(topics::USER_EMAIL_CHANGE_REQUESTED, &key, &payload)
+            .await
+    }
+
+    async fn publish_email_changed(&self, event: UserEmailChanged) -> KafkaResult<()> {
+        let key = event.user_id.to_string();
+        let payload = serde_json::to_string(&event)?;
+
+        self.send(topics::USER_EMAIL_CHANGED, &key, &payload).await
+    }
+
+    async fn publish_status_changed(&self, event: UserStatusChanged) -> KafkaResult<()> {
+        let key = event.user_id.to_string();
+        let payload = serde_json::to_string(&event)?;
+        self.send(topics::USER_STATUS_CHANGED, &key, &payload).await
+    }
+}
+
+#[async_trait]
+impl OutboxPublisher for KafkaProducer {
+    async fn publish(&self, message: &OutboxMessage) -> KafkaResult<()> {
+        let topic = topics::for_event_type(&message.event_type).ok_or_else(|| {
+            KafkaError::MessageSend(format!("No topic for event type {}", message.event_type))
+        })?;
+
+        self.send(topic, &message.aggregate_id.to_string(), &message.payload)
+            .await
     }
 }
@@ -1 +1,21 @@
+use crate::domain::events::{
+    outbox::OutboxEvent,
+    user::{UserCreated, UserDeleted},
+};
+
 pub const USER_CREATED: &str = "user.created";
+pub const USER_UPDATED: &str = "user.updated";
+pub const USER_DELETED: &str = "user.deleted";
+pub const USER_DATA_EXPORT_READY: &str = "user.data_export.ready";
+pub const USER_EMAIL_CHANGE_REQUESTED: &str = "user.email_change.requested";
+pub const USER_EMAIL_CHANGED: &str = "user.email_changed";
+pub const USER_STATUS_CHANGED: &str = "user.status_changed";
+
+/// Topic for messages relayed from the outbox, by event type.
+pub fn for_event_type(event_type: &str) -> Option<&'static str> {
+    match event_type {
+        <UserCreated as OutboxEvent>::EVENT_TYPE => Some(USER_CREATED),
+        <UserDeleted as OutboxEvent>::EVENT_TYPE => Some(USER_DELETED),
+        _ => None,
+    }
+}
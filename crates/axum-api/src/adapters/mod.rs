@@ -1,4 +1,7 @@
 pub mod grpc;
 pub mod http;
+#[cfg(feature = "testing")]
+pub mod memory;
 pub mod messaging;
 pub mod persistence;
+pub mod storage;
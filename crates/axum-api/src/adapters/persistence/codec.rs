@@ -0,0 +1,39 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use crate::domain::repositories::error::{RepositoryError, RepositoryResult};
+
+pub fn encode_bytes(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+pub fn decode_bytes(column: &str, value: &str) -> RepositoryResult<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(value)
+        .map_err(|e| RepositoryError::ConversionError(format!("Invalid {}: {}", column, e)))
+}
+
+pub fn join_transports(transports: &[String]) -> String {
+    transports.join(",")
+}
+
+pub fn split_transports(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .filter(|transport| !transport.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Fixed-width UTC text, so timestamps stored as strings sort chronologically.
+pub fn encode_timestamp(value: DateTime<Utc>) -> String {
+    value.format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string()
+}
+
+pub fn decode_timestamp(column: &str, value: &str) -> RepositoryResult<DateTime<Utc>> {
+    Ok(
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.fZ")
+            .map_err(|e| RepositoryError::ConversionError(format!("Invalid {}: {}", column, e)))?
+            .and_utc(),
+    )
+}
@@ -1,3 +1,13 @@
+pub mod codec;
+#[cfg(feature = "postgres")]
+pub mod postgres;
 pub mod redis;
+pub mod replica;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "mssql-sqlx")]
 pub mod sqlx;
+#[cfg(feature = "mssql-tiberius")]
 pub mod tiberius;
+pub mod transaction;
+pub mod user_query;
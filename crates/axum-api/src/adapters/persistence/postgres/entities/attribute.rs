@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::attribute::{AttributeSchema, UserAttributes},
+    repositories::error::RepositoryResult,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct AttributeSchemaEntity {
+    pub namespace: String,
+    pub json_schema: String,
+    pub user_writable: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AttributeSchemaEntity {
+    pub fn to_domain(&self) -> RepositoryResult<AttributeSchema> {
+        Ok(AttributeSchema {
+            namespace: self.namespace.clone(),
+            schema: serde_json::from_str(&self.json_schema)?,
+            user_writable: self.user_writable,
+            updated_at: self.updated_at,
+        })
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct UserAttributesEntity {
+    pub user_id: Uuid,
+    pub namespace: String,
+    pub data: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl UserAttributesEntity {
+    pub fn to_domain(&self) -> RepositoryResult<UserAttributes> {
+        Ok(UserAttributes {
+            user_id: self.user_id,
+            namespace: self.namespace.clone(),
+            data: serde_json::from_str(&self.data)?,
+            updated_at: self.updated_at,
+        })
+    }
+}
@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::outbox::OutboxMessage,
+    repositories::error::{RepositoryError, RepositoryResult},
+};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct OutboxEntity {
+    pub id: Uuid,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    pub payload: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OutboxEntity {
+    pub fn to_domain(&self) -> RepositoryResult<OutboxMessage> {
+        Ok(OutboxMessage {
+            id: self.id,
+            aggregate_id: self.aggregate_id,
+            event_type: self.event_type.clone(),
+            payload: self.payload.clone(),
+            attempts: u32::try_from(self.attempts).map_err(|e| {
+                RepositoryError::ConversionError(format!("Invalid attempts: {}", e))
+            })?,
+            last_error: self.last_error.clone(),
+            next_attempt_at: self.next_attempt_at,
+            created_at: self.created_at,
+        })
+    }
+}
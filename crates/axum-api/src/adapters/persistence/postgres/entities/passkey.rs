@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::codec::split_transports,
+    domain::{
+        entities::passkey::Passkey,
+        repositories::error::{RepositoryError, RepositoryResult},
+    },
+};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct PasskeyEntity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub credential_id: Vec<u8>,
+    pub public_key: Vec<u8>,
+    pub sign_count: i64,
+    pub transports: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl PasskeyEntity {
+    pub fn to_domain(&self) -> RepositoryResult<Passkey> {
+        Ok(Passkey {
+            id: self.id,
+            user_id: self.user_id,
+            credential_id: self.credential_id.clone(),
+            public_key: self.public_key.clone(),
+            sign_count: u32::try_from(self.sign_count).map_err(|e| {
+                RepositoryError::ConversionError(format!("Invalid sign_count: {}", e))
+            })?,
+            transports: split_transports(&self.transports),
+            name: self.name.clone(),
+            created_at: self.created_at,
+            last_used_at: self.last_used_at,
+        })
+    }
+}
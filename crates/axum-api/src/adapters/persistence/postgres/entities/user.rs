@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::user::User,
+    repositories::error::{RepositoryError, RepositoryResult},
+};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct UserEntity {
+    pub id: Uuid,
+    pub email: String,
+    pub password: String,
+    pub name: String,
+    pub role: String,
+    pub status: String,
+    pub status_reason: Option<String>,
+    pub status_until: Option<DateTime<Utc>>,
+    pub avatar_key: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl UserEntity {
+    pub fn to_domain(&self) -> RepositoryResult<User> {
+        Ok(User::from_db(
+            self.id,
+            self.email.clone(),
+            self.password.clone(),
+            self.name.clone(),
+            self.role
+                .parse()
+                .map_err(RepositoryError::ConversionError)?,
+            self.status
+                .parse()
+                .map_err(RepositoryError::ConversionError)?,
+            self.status_reason.clone(),
+            self.status_until,
+            self.avatar_key.clone(),
+            self.created_at,
+            self.updated_at,
+        ))
+    }
+}
@@ -0,0 +1,3 @@
+pub mod entities;
+pub mod repositories;
+pub mod user_query;
@@ -0,0 +1,195 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::postgres::entities::attribute::{
+        AttributeSchemaEntity, UserAttributesEntity,
+    },
+    domain::{
+        entities::attribute::{AttributeSchema, UserAttributes},
+        repositories::{attribute::AttributeRepository, error::RepositoryResult},
+    },
+    infra::postgres::PgPool,
+};
+
+#[derive(Clone)]
+pub struct PgAttributeRepository {
+    pool: PgPool,
+}
+
+impl PgAttributeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AttributeRepository for PgAttributeRepository {
+    async fn upsert_schema(
+        &self,
+        namespace: &str,
+        schema: &str,
+        user_writable: bool,
+    ) -> RepositoryResult<AttributeSchema> {
+        let row = sqlx::query_as::<_, AttributeSchemaEntity>(
+            r#"
+            INSERT INTO attribute_schemas (namespace, json_schema, user_writable)
+            VALUES ($1, $2::jsonb, $3)
+            ON CONFLICT (namespace) DO UPDATE
+                SET json_schema = EXCLUDED.json_schema,
+                    user_writable = EXCLUDED.user_writable,
+                    updated_at = now()
+            RETURNING
+                namespace,
+                json_schema::text as json_schema,
+                user_writable,
+                updated_at
+            "#,
+        )
+        .bind(namespace)
+        .bind(schema)
+        .bind(user_writable)
+        .fetch_one(&self.pool)
+        .await?;
+
+        row.to_domain()
+    }
+
+    async fn find_schema(&self, namespace: &str) -> RepositoryResult<Option<AttributeSchema>> {
+        let row = sqlx::query_as::<_, AttributeSchemaEntity>(
+            r#"
+            SELECT
+                namespace,
+                json_schema::text as json_schema,
+                user_writable,
+                updated_at
+            FROM attribute_schemas
+            WHERE namespace = $1
+            "#,
+        )
+        .bind(namespace)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn list_schemas(&self) -> RepositoryResult<Vec<AttributeSchema>> {
+        let rows = sqlx::query_as::<_, AttributeSchemaEntity>(
+            r#"
+            SELECT
+                namespace,
+                json_schema::text as json_schema,
+                user_writable,
+                updated_at
+            FROM attribute_schemas
+            ORDER BY namespace
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(AttributeSchemaEntity::to_domain).collect()
+    }
+
+    async fn delete_schema(&self, namespace: &str) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM attribute_schemas
+            WHERE namespace = $1
+            "#,
+        )
+        .bind(namespace)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn upsert(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+        data: &str,
+    ) -> RepositoryResult<UserAttributes> {
+        let row = sqlx::query_as::<_, UserAttributesEntity>(
+            r#"
+            INSERT INTO user_attributes (user_id, namespace, data)
+            VALUES ($1, $2, $3::jsonb)
+            ON CONFLICT (user_id, namespace) DO UPDATE
+                SET data = EXCLUDED.data, updated_at = now()
+            RETURNING
+                user_id,
+                namespace,
+                data::text as data,
+                updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(namespace)
+        .bind(data)
+        .fetch_one(&self.pool)
+        .await?;
+
+        row.to_domain()
+    }
+
+    async fn find(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+    ) -> RepositoryResult<Option<UserAttributes>> {
+        let row = sqlx::query_as::<_, UserAttributesEntity>(
+            r#"
+            SELECT
+                user_id,
+                namespace,
+                data::text as data,
+                updated_at
+            FROM user_attributes
+            WHERE user_id = $1 AND namespace = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(namespace)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<UserAttributes>> {
+        let rows = sqlx::query_as::<_, UserAttributesEntity>(
+            r#"
+            SELECT
+                user_id,
+                namespace,
+                data::text as data,
+                updated_at
+            FROM user_attributes
+            WHERE user_id = $1
+            ORDER BY namespace
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(UserAttributesEntity::to_domain).collect()
+    }
+
+    async fn delete(&self, user_id: Uuid, namespace: &str) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM user_attributes
+            WHERE user_id = $1 AND namespace = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(namespace)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,203 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Postgres;
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::{
+        postgres::entities::outbox::OutboxEntity, transaction::SqlxConnectionSource,
+    },
+    domain::{
+        entities::outbox::{OutboxBacklog, OutboxMessage},
+        repositories::{
+            error::{RepositoryError, RepositoryResult},
+            outbox::OutboxRepository,
+        },
+    },
+    infra::postgres::PgPool,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct BacklogRow {
+    pending: i64,
+    failing: i64,
+    dead_lettered: i64,
+    oldest_pending_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+pub struct PgOutboxRepository {
+    source: SqlxConnectionSource<Postgres>,
+}
+
+impl PgOutboxRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self::from_source(SqlxConnectionSource::Pool(pool))
+    }
+
+    pub fn from_source(source: SqlxConnectionSource<Postgres>) -> Self {
+        Self { source }
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for PgOutboxRepository {
+    async fn enqueue(&self, message: &OutboxMessage) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO outbox (id, aggregate_id, event_type, payload, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(message.id)
+        .bind(message.aggregate_id)
+        .bind(message.event_type.as_str())
+        .bind(message.payload.as_str())
+        .bind(message.created_at)
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_pending(
+        &self,
+        now: DateTime<Utc>,
+        max_attempts: u32,
+        limit: u32,
+    ) -> RepositoryResult<Vec<OutboxMessage>> {
+        let rows = sqlx::query_as::<_, OutboxEntity>(
+            r#"
+            SELECT
+                id, aggregate_id, event_type, payload, attempts, last_error, next_attempt_at,
+                created_at
+            FROM outbox AS message
+            WHERE dispatched_at IS NULL
+              AND attempts < $2
+              AND (next_attempt_at IS NULL OR next_attempt_at <= $1)
+              AND NOT EXISTS (
+                  SELECT 1
+                  FROM outbox AS earlier
+                  WHERE earlier.aggregate_id = message.aggregate_id
+                    AND earlier.sequence < message.sequence
+                    AND earlier.dispatched_at IS NULL
+                    AND earlier.attempts < $2
+                    AND earlier.next_attempt_at > $1
+              )
+            ORDER BY sequence
+            LIMIT $3
+            "#,
+        )
+        .bind(now)
+        .bind(i32::try_from(max_attempts).unwrap_or(i32::MAX))
+        .bind(i64::from(limit))
+        .fetch_all(&mut *self.source.acquire().await?)
+        .await?;
+
+        rows.iter().map(OutboxEntity::to_domain).collect()
+    }
+
+    async fn mark_dispatched(&self, id: &Uuid) -> RepositoryResult<()> {
+        let result = sqlx::query("UPDATE outbox SET dispatched_at = now() WHERE id = $1")
+            .bind(*id)
+            .execute(&mut *self.source.acquire().await?)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        id: &Uuid,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE outbox
+            SET attempts = attempts + 1, last_error = $1, next_attempt_at = $2
+            WHERE id = $3
+            "#,
+        )
+        .bind(error)
+        .bind(next_attempt_at)
+        .bind(*id)
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn backlog(&self, max_attempts: u32) -> RepositoryResult<OutboxBacklog> {
+        let row = sqlx::query_as::<_, BacklogRow>(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE attempts < $1) AS pending,
+                COUNT(*) FILTER (WHERE attempts > 0 AND attempts < $1) AS failing,
+                COUNT(*) FILTER (WHERE attempts >= $1) AS dead_lettered,
+                MIN(created_at) FILTER (WHERE attempts < $1) AS oldest_pending_at
+            FROM outbox
+            WHERE dispatched_at IS NULL
+            "#,
+        )
+        .bind(i32::try_from(max_attempts).unwrap_or(i32::MAX))
+        .fetch_one(&mut *self.source.acquire().await?)
+        .await?;
+
+        Ok(OutboxBacklog {
+            pending: row.pending as u64,
+            failing: row.failing as u64,
+            dead_lettered: row.dead_lettered as u64,
+            oldest_pending_at: row.oldest_pending_at,
+        })
+    }
+
+    async fn delete_dispatched(&self, before: DateTime<Utc>, limit: u32) -> RepositoryResult<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM outbox
+            WHERE sequence IN (
+                SELECT sequence
+                FROM outbox
+                WHERE dispatched_at < $1
+                ORDER BY sequence
+                LIMIT $2
+            )
+            "#,
+        )
+        .bind(before)
+        .bind(i64::from(limit))
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn acquire_relay_lease(&self, holder: &Uuid, ttl: Duration) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE outbox_relay_lease
+            SET holder = $1,
+                expires_at = clock_timestamp() + make_interval(secs => $2)
+            WHERE id = 1
+              AND (holder = $1 OR expires_at IS NULL OR expires_at <= clock_timestamp())
+            "#,
+        )
+        .bind(*holder)
+        .bind(ttl.as_secs_f64())
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
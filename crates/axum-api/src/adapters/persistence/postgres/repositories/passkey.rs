@@ -0,0 +1,157 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::{codec::join_transports, postgres::entities::passkey::PasskeyEntity},
+    domain::{
+        entities::passkey::Passkey,
+        repositories::{
+            error::{RepositoryError, RepositoryResult},
+            passkey::PasskeyRepository,
+            second_factor::SecondFactorRepository,
+        },
+    },
+    infra::postgres::PgPool,
+};
+
+#[derive(Clone)]
+pub struct PgPasskeyRepository {
+    pool: PgPool,
+}
+
+impl PgPasskeyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PasskeyRepository for PgPasskeyRepository {
+    async fn create(&self, passkey: &Passkey) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO passkey_credentials
+                (id, user_id, credential_id, public_key, sign_count, transports, name)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(passkey.id)
+        .bind(passkey.user_id)
+        .bind(passkey.credential_id.as_slice())
+        .bind(passkey.public_key.as_slice())
+        .bind(i64::from(passkey.sign_count))
+        .bind(join_transports(&passkey.transports))
+        .bind(passkey.name.as_str())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_credential_id(
+        &self,
+        credential_id: &[u8],
+    ) -> RepositoryResult<Option<Passkey>> {
+        let row = sqlx::query_as::<_, PasskeyEntity>(
+            r#"
+            SELECT
+                id,
+                user_id,
+                credential_id,
+                public_key,
+                sign_count,
+                transports,
+                name,
+                created_at,
+                last_used_at
+            FROM passkey_credentials
+            WHERE credential_id = $1
+            "#,
+        )
+        .bind(credential_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<Passkey>> {
+        let rows = sqlx::query_as::<_, PasskeyEntity>(
+            r#"
+            SELECT
+                id,
+                user_id,
+                credential_id,
+                public_key,
+                sign_count,
+                transports,
+                name,
+                created_at,
+                last_used_at
+            FROM passkey_credentials
+            WHERE user_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(PasskeyEntity::to_domain).collect()
+    }
+
+    async fn update_sign_count(&self, id: Uuid, sign_count: u32) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE passkey_credentials
+            SET sign_count = $1, last_used_at = now()
+            WHERE id = $2
+            "#,
+        )
+        .bind(i64::from(sign_count))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM passkey_credentials
+            WHERE id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl SecondFactorRepository for PgPasskeyRepository {
+    async fn has_second_factor(&self, user_id: Uuid) -> RepositoryResult<bool> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM passkey_credentials
+                WHERE user_id = $1
+            )
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+}
@@ -0,0 +1,326 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Connection, Postgres};
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::{
+        postgres::{
+            entities::user::UserEntity,
+            user_query::{USER_COLUMNS, list_users_sql},
+        },
+        transaction::SqlxConnectionSource,
+    },
+    domain::{
+        entities::user::{User, UserStatus},
+        repositories::{
+            error::{RepositoryError, RepositoryResult},
+            user::{UserListQuery, UserRepository},
+        },
+    },
+    infra::postgres::PgPool,
+};
+
+#[derive(Clone)]
+pub struct PgUserRepository {
+    source: SqlxConnectionSource<Postgres>,
+}
+
+impl PgUserRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self::from_source(SqlxConnectionSource::Pool(pool))
+    }
+
+    pub fn from_source(source: SqlxConnectionSource<Postgres>) -> Self {
+        Self { source }
+    }
+}
+
+fn parse_id(id: &str) -> RepositoryResult<Uuid> {
+    Uuid::parse_str(id).map_err(|_| RepositoryError::InvalidUuidFormat)
+}
+
+#[async_trait]
+impl UserRepository for PgUserRepository {
+    async fn create(&self, user: &User) -> RepositoryResult<User> {
+        let row = sqlx::query_as::<_, UserEntity>(&format!(
+            r#"
+            INSERT INTO users (id, email, password, name, role, status)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {USER_COLUMNS}
+            "#
+        ))
+        .bind(user.id())
+        .bind(user.email())
+        .bind(user.password())
+        .bind(user.name())
+        .bind(user.role().as_str())
+        .bind(user.status().as_str())
+        .fetch_one(&mut *self.source.acquire().await?)
+        .await?;
+
+        row.to_domain()
+    }
+
+    async fn create_many(&self, users: &[User]) -> RepositoryResult<Vec<User>> {
+        if users.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let column = |value: fn(&User) -> &str| -> Vec<String> {
+            users.iter().map(|user| value(user).to_string()).collect()
+        };
+
+        let rows = sqlx::query_as::<_, UserEntity>(&format!(
+            r#"
+            INSERT INTO users (id, email, password, name, role, status)
+            SELECT * FROM UNNEST(
+                $1::uuid[], $2::varchar[], $3::varchar[], $4::varchar[], $5::varchar[],
+                $6::varchar[]
+            )
+            ON CONFLICT DO NOTHING
+            RETURNING {USER_COLUMNS}
+            "#
+        ))
+        .bind(users.iter().map(|user| *user.id()).collect::<Vec<Uuid>>())
+        .bind(column(|user| user.email()))
+        .bind(column(|user| user.password()))
+        .bind(column(|user| user.name()))
+        .bind(column(|user| user.role().as_str()))
+        .bind(column(|user| user.status().as_str()))
+        .fetch_all(&mut *self.source.acquire().await?)
+        .await?;
+
+        rows.iter().map(UserEntity::to_domain).collect()
+    }
+
+    async fn find_existing_emails(&self, emails: &[String]) -> RepositoryResult<Vec<String>> {
+        if emails.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let emails: Vec<String> = emails.iter().map(|email| email.to_lowercase()).collect();
+
+        Ok(
+            sqlx::query_scalar::<_, String>("SELECT email FROM users WHERE lower(email) = ANY($1)")
+                .bind(emails)
+                .fetch_all(&mut *self.source.acquire().await?)
+                .await?,
+        )
+    }
+
+    async fn find_by_email(&self, email: &str) -> RepositoryResult<Option<User>> {
+        let row = sqlx::query_as::<_, UserEntity>(&format!(
+            "SELECT {USER_COLUMNS} FROM users WHERE lower(email) = lower($1)"
+        ))
+        .bind(email)
+        .fetch_optional(&mut *self.source.acquire().await?)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<User>> {
+        let row = sqlx::query_as::<_, UserEntity>(&format!(
+            "SELECT {USER_COLUMNS} FROM users WHERE id = $1"
+        ))
+        .bind(parse_id(id)?)
+        .fetch_optional(&mut *self.source.acquire().await?)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn list(&self, query: &UserListQuery) -> RepositoryResult<Vec<User>> {
+        let list_sql = list_users_sql(query);
+
+        let mut statement = sqlx::query_as::<_, UserEntity>(&list_sql.sql);
+        for param in &list_sql.params {
+            statement = statement.bind(param);
+        }
+
+        let rows = statement
+            .fetch_all(&mut *self.source.acquire().await?)
+            .await?;
+
+        rows.iter().map(UserEntity::to_domain).collect()
+    }
+
+    async fn update_password(&self, id: &str, password: &str) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET password = $1, updated_at = now()
+            WHERE id = $2
+            "#,
+        )
+        .bind(password)
+        .bind(parse_id(id)?)
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn update_profile(
+        &self,
+        user: &User,
+        expected_updated_at: DateTime<Utc>,
+    ) -> RepositoryResult<Option<User>> {
+        let row = sqlx::query_as::<_, UserEntity>(&format!(
+            r#"
+            UPDATE users
+            SET name = $1, updated_at = now()
+            WHERE id = $2 AND updated_at = $3
+            RETURNING {USER_COLUMNS}
+            "#
+        ))
+        .bind(user.name())
+        .bind(*user.id())
+        .bind(expected_updated_at)
+        .fetch_optional(&mut *self.source.acquire().await?)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn update_email(&self, user: &User, previous_email: &str) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET email = $1, updated_at = now()
+            WHERE id = $2
+                AND lower(email) = lower($3)
+                AND NOT EXISTS (
+                    SELECT 1 FROM users WHERE lower(email) = lower($1)
+                )
+            "#,
+        )
+        .bind(user.email())
+        .bind(*user.id())
+        .bind(previous_email)
+        .execute(&mut *self.source.acquire().await?)
+        .await;
+
+        // A concurrent change can claim the address between the check and the write.
+        match result.map_err(RepositoryError::from) {
+            Ok(result) => Ok(result.rows_affected() > 0),
+            Err(e) if e.is_duplicate_email() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn update_avatar(&self, user: &User) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET avatar_key = $1, updated_at = now()
+            WHERE id = $2
+            "#,
+        )
+        .bind(user.avatar_key())
+        .bind(*user.id())
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn update_status(&self, user: &User, previous: UserStatus) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET status = $1,
+                status_reason = $2,
+                status_until = $3,
+                updated_at = now()
+            WHERE id = $4 AND status = $5
+            "#,
+        )
+        .bind(user.status().as_str())
+        .bind(user.status_reason())
+        .bind(user.status_until())
+        .bind(*user.id())
+        .bind(previous.as_str())
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_due_erasures(
+        &self,
+        due_before: DateTime<Utc>,
+        limit: u32,
+    ) -> RepositoryResult<Vec<User>> {
+        let rows = sqlx::query_as::<_, UserEntity>(&format!(
+            r#"
+            SELECT {USER_COLUMNS}
+            FROM users
+            WHERE status = 'pending_deletion'
+                AND status_until <= $1
+            ORDER BY status_until
+            LIMIT $2
+            "#
+        ))
+        .bind(due_before)
+        .bind(i64::from(limit))
+        .fetch_all(&mut *self.source.acquire().await?)
+        .await?;
+
+        rows.iter().map(UserEntity::to_domain).collect()
+    }
+
+    async fn erase(&self, user: &User) -> RepositoryResult<bool> {
+        let mut conn = self.source.acquire().await?;
+        let mut tx = conn.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET email = $1,
+                password = $2,
+                name = $3,
+                status = $4,
+                status_reason = NULL,
+                status_until = NULL,
+                avatar_key = NULL,
+                updated_at = now()
+            WHERE id = $5 AND status = 'pending_deletion'
+            "#,
+        )
+        .bind(user.email())
+        .bind(user.password())
+        .bind(user.name())
+        .bind(user.status().as_str())
+        .bind(*user.id())
+        .execute(&mut tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query("DELETE FROM passkey_credentials WHERE user_id = $1")
+            .bind(*user.id())
+            .execute(&mut tx)
+            .await?;
+
+        sqlx::query("DELETE FROM user_attributes WHERE user_id = $1")
+            .bind(*user.id())
+            .execute(&mut tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
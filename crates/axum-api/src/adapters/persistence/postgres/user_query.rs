@@ -0,0 +1,111 @@
+use chrono::SecondsFormat;
+
+use crate::{
+    adapters::persistence::user_query::{UserSql, escape_like},
+    domain::repositories::user::{SortDirection, UserListQuery},
+};
+
+pub const USER_COLUMNS: &str = "id, email, password, name, role, status, status_reason, \
+                                status_until, avatar_key, created_at, updated_at";
+
+pub fn list_users_sql(query: &UserListQuery) -> UserSql {
+    let mut clauses = Vec::new();
+    let mut params = Vec::new();
+
+    let bind = |params: &mut Vec<String>, value: String| {
+        params.push(value);
+        format!("${}", params.len())
+    };
+
+    let filter = &query.filter;
+
+    if let Some(email_prefix) = &filter.email_prefix {
+        let param = bind(&mut params, format!("{}%", escape_like(email_prefix)));
+        clauses.push(format!("email ILIKE {param} ESCAPE '\\'"));
+    }
+
+    if let Some(name) = &filter.name {
+        let param = bind(&mut params, format!("%{}%", escape_like(name)));
+        clauses.push(format!("name ILIKE {param} ESCAPE '\\'"));
+    }
+
+    if let Some(status) = filter.status {
+        let param = bind(&mut params, status.as_str().to_string());
+        clauses.push(format!("status = {param}"));
+    }
+
+    if let Some(role) = filter.role {
+        let param = bind(&mut params, role.as_str().to_string());
+        clauses.push(format!("role = {param}"));
+    }
+
+    if let Some(created_from) = filter.created_from {
+        let param = bind(
+            &mut params,
+            created_from.to_rfc3339_opts(SecondsFormat::Micros, true),
+        );
+        clauses.push(format!("created_at >= {param}::timestamptz"));
+    }
+
+    if let Some(created_to) = filter.created_to {
+        let param = bind(
+            &mut params,
+            created_to.to_rfc3339_opts(SecondsFormat::Micros, true),
+        );
+        clauses.push(format!("created_at < {param}::timestamptz"));
+    }
+
+    for attribute in &filter.attributes {
+        let namespace = bind(&mut params, attribute.namespace.clone());
+        let path: Vec<String> = attribute
+            .path
+            .iter()
+            .map(|key| bind(&mut params, key.clone()))
+            .collect();
+        let value = bind(&mut params, attribute.value.clone());
+        clauses.push(format!(
+            "EXISTS (SELECT 1 FROM user_attributes \
+             WHERE user_attributes.user_id = users.id \
+             AND user_attributes.namespace = {namespace} \
+             AND jsonb_extract_path_text(user_attributes.data, {path}) = {value})",
+            path = path.join(", "),
+        ));
+    }
+
+    let (comparison, order) = match query.direction {
+        SortDirection::Asc => (">", "ASC"),
+        SortDirection::Desc => ("<", "DESC"),
+    };
+
+    if let Some(cursor) = &query.after {
+        let created_at = bind(
+            &mut params,
+            cursor
+                .created_at
+                .to_rfc3339_opts(SecondsFormat::Micros, true),
+        );
+        let id = bind(&mut params, cursor.id.to_string());
+        clauses.push(format!(
+            "(created_at, id) {comparison} ({created_at}::timestamptz, {id}::uuid)"
+        ));
+    }
+
+    let where_clause = if clauses.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", clauses.join(" AND "))
+    };
+
+    let sql = format!(
+        r#"
+        SELECT {USER_COLUMNS}
+        FROM users
+        {where_clause}
+        ORDER BY created_at {order}, id {order}
+        LIMIT {limit}
+        "#,
+        limit = query.limit,
+    );
+
+    UserSql { sql, params }
+}
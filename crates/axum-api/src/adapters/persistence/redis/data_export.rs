@@ -0,0 +1,168 @@
+use chrono::{DateTime, Utc};
+use redis::{AsyncCommands, aio::ConnectionManager};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::data_export::{DataExport, DataExportStatus},
+    repositories::{data_export::DataExportRepository, error::RepositoryResult},
+};
+
+/// Sorted set of pending exports as `user_id:export_id`, scored by request time.
+const PENDING_KEY: &str = "users:data_export:pending";
+/// Sorted set of completed export object keys, scored by when they expire.
+const OBJECTS_KEY: &str = "users:data_export:objects";
+
+pub struct RedisDataExportRepository {
+    conn: ConnectionManager,
+}
+
+impl RedisDataExportRepository {
+    pub fn new(conn: ConnectionManager) -> Self {
+        Self { conn }
+    }
+
+    fn export_key(user_id: Uuid, export_id: Uuid) -> String {
+        Self::member_key(&Self::pending_member(user_id, export_id))
+    }
+
+    fn pending_member(user_id: Uuid, export_id: Uuid) -> String {
+        format!("{user_id}:{export_id}")
+    }
+
+    fn member_key(member: &str) -> String {
+        format!("users:data_export:{member}")
+    }
+}
+
+#[async_trait::async_trait]
+impl DataExportRepository for RedisDataExportRepository {
+    async fn save(&self, export: &DataExport, ttl_secs: u64) -> RepositoryResult<()> {
+        let mut conn = self.conn.clone();
+
+        let key = Self::export_key(export.user_id, export.id);
+        let member = Self::pending_member(export.user_id, export.id);
+        let value = serde_json::to_string(export)?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic().set_ex(&key, value, ttl_secs).ignore();
+
+        match (&export.status, &export.object_key) {
+            (DataExportStatus::Pending, _) => {
+                pipe.zadd(PENDING_KEY, &member, export.requested_at.timestamp_millis())
+                    .ignore();
+            }
+            (DataExportStatus::Completed, Some(object_key)) => {
+                let expires_at = Utc::now().timestamp_millis() + ttl_secs as i64 * 1000;
+                pipe.zrem(PENDING_KEY, &member)
+                    .ignore()
+                    .zadd(OBJECTS_KEY, object_key, expires_at)
+                    .ignore();
+            }
+            _ => {
+                pipe.zrem(PENDING_KEY, &member).ignore();
+            }
+        }
+
+        let _: () = pipe.query_async(&mut conn).await?;
+
+        Ok(())
+    }
+
+    async fn find(&self, user_id: Uuid, export_id: Uuid) -> RepositoryResult<Option<DataExport>> {
+        let mut conn = self.conn.clone();
+
+        let key = Self::export_key(user_id, export_id);
+        let value: Option<String> = conn.get(key).await?;
+
+        match value {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn list_stalled(
+        &self,
+        requested_before: DateTime<Utc>,
+        limit: usize,
+    ) -> RepositoryResult<Vec<DataExport>> {
+        let mut conn = self.conn.clone();
+
+        let members: Vec<String> = conn
+            .zrangebyscore_limit(
+                PENDING_KEY,
+                "-inf",
+                requested_before.timestamp_millis(),
+                0,
+                limit as isize,
+            )
+            .await?;
+
+        if members.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let keys: Vec<String> = members
+            .iter()
+            .map(|member| Self::member_key(member))
+            .collect();
+        let values: Vec<Option<String>> =
+            redis::cmd("MGET").arg(&keys).query_async(&mut conn).await?;
+
+        let mut stalled = Vec::with_capacity(members.len());
+        let mut expired = Vec::new();
+
+        for (member, value) in members.into_iter().zip(values) {
+            match value {
+                Some(value) => stalled.push(serde_json::from_str(&value)?),
+                None => expired.push(member),
+            }
+        }
+
+        if !expired.is_empty() {
+            let _: () = conn.zrem(PENDING_KEY, expired).await?;
+        }
+
+        Ok(stalled)
+    }
+
+    async fn list_expired_objects(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> RepositoryResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+
+        let keys: Vec<String> = conn
+            .zrangebyscore_limit(
+                OBJECTS_KEY,
+                "-inf",
+                now.timestamp_millis(),
+                0,
+                limit as isize,
+            )
+            .await?;
+
+        Ok(keys)
+    }
+
+    async fn list_objects(&self, user_id: Uuid) -> RepositoryResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+
+        // The set only holds objects within the retention period, so it stays small.
+        let keys: Vec<String> = conn.zrange(OBJECTS_KEY, 0, -1).await?;
+        let prefix = DataExport::object_prefix(user_id);
+
+        Ok(keys
+            .into_iter()
+            .filter(|key| key.starts_with(&prefix))
+            .collect())
+    }
+
+    async fn forget_object(&self, object_key: &str) -> RepositoryResult<()> {
+        let mut conn = self.conn.clone();
+
+        let _: () = conn.zrem(OBJECTS_KEY, object_key).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use redis::{AsyncCommands, aio::ConnectionManager};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::email_change::PendingEmailChange,
+    repositories::{email_change::EmailChangeRepository, error::RepositoryResult},
+};
+
+pub struct RedisEmailChangeRepository {
+    conn: ConnectionManager,
+}
+
+impl RedisEmailChangeRepository {
+    pub fn new(conn: ConnectionManager) -> Self {
+        Self { conn }
+    }
+
+    fn change_key(token_hash: &str) -> String {
+        format!("auth:email_change:{token_hash}")
+    }
+
+    fn user_key(user_id: Uuid) -> String {
+        format!("auth:email_change:user:{user_id}")
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailChangeRepository for RedisEmailChangeRepository {
+    async fn store(
+        &self,
+        token_hash: &str,
+        change: &PendingEmailChange,
+        ttl_secs: u64,
+    ) -> RepositoryResult<()> {
+        let mut conn = self.conn.clone();
+
+        let user_key = Self::user_key(change.user_id);
+        let value = serde_json::to_string(change)?;
+        let previous: Option<String> = conn.get(&user_key).await?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+
+        if let Some(previous) = previous {
+            pipe.del(Self::change_key(&previous)).ignore();
+        }
+
+        let _: () = pipe
+            .set_ex(Self::change_key(token_hash), value, ttl_secs)
+            .ignore()
+            .set_ex(&user_key, token_hash, ttl_secs)
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn take(&self, token_hash: &str) -> RepositoryResult<Option<PendingEmailChange>> {
+        let mut conn = self.conn.clone();
+
+        let value: Option<String> = conn.get_del(Self::change_key(token_hash)).await?;
+
+        match value {
+            Some(value) => {
+                let change: PendingEmailChange = serde_json::from_str(&value)?;
+                let _: () = conn.del(Self::user_key(change.user_id)).await?;
+
+                Ok(Some(change))
+            }
+            None => Ok(None),
+        }
+    }
+}
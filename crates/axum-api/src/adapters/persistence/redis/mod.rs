@@ -1 +1,5 @@
+pub mod data_export;
+pub mod email_change;
+pub mod passkey_ceremony;
 pub mod token;
+pub mod trusted_device;
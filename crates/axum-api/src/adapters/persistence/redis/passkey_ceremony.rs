@@ -0,0 +1,50 @@
+use redis::{AsyncCommands, aio::ConnectionManager};
+
+use crate::domain::{
+    entities::passkey::PasskeyCeremony,
+    repositories::{error::RepositoryResult, passkey::PasskeyCeremonyRepository},
+};
+
+pub struct RedisPasskeyCeremonyRepository {
+    conn: ConnectionManager,
+}
+
+impl RedisPasskeyCeremonyRepository {
+    pub fn new(conn: ConnectionManager) -> Self {
+        Self { conn }
+    }
+
+    fn ceremony_key(ceremony_id: &str) -> String {
+        format!("auth:passkey_ceremony:{ceremony_id}")
+    }
+}
+
+#[async_trait::async_trait]
+impl PasskeyCeremonyRepository for RedisPasskeyCeremonyRepository {
+    async fn store(
+        &self,
+        ceremony_id: &str,
+        ceremony: &PasskeyCeremony,
+        ttl_secs: u64,
+    ) -> RepositoryResult<()> {
+        let mut conn = self.conn.clone();
+
+        let key = Self::ceremony_key(ceremony_id);
+        let value = serde_json::to_string(ceremony)?;
+        let _: () = conn.set_ex(key, value, ttl_secs).await?;
+
+        Ok(())
+    }
+
+    async fn take(&self, ceremony_id: &str) -> RepositoryResult<Option<PasskeyCeremony>> {
+        let mut conn = self.conn.clone();
+
+        let key = Self::ceremony_key(ceremony_id);
+        let value: Option<String> = conn.get_del(key).await?;
+
+        match value {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+}
@@ -1,7 +1,50 @@
-use crate::domain::repositories::{error::RepositoryResult, token_cache::TokenCacheRepository};
-use redis::{AsyncCommands, aio::ConnectionManager};
+use crate::domain::{
+    entities::session::{MfaChallenge, RefreshSession},
+    repositories::{
+        error::RepositoryResult,
+        token_cache::{SessionLimit, TokenCacheRepository},
+    },
+};
+use redis::{AsyncCommands, Script, aio::ConnectionManager};
 use uuid::Uuid;
 
+/// Applies the session limit and stores the new session in one step, so concurrent
+/// sign-ins cannot both fit into the last free slot. The other sessions' keys are derived
+/// from the index, which a single Redis instance allows but Redis Cluster does not.
+///
+/// KEYS: the user's session index, the new session's key.
+/// ARGV: token hash, session JSON, TTL, created_at in milliseconds, refresh key prefix,
+/// limit mode (`unlimited`, `reject` or `evict_oldest`), limit.
+const STORE_SESSION_SCRIPT: &str = r#"
+local live = {}
+for _, hash in ipairs(redis.call('ZRANGE', KEYS[1], 0, -1)) do
+    if redis.call('EXISTS', ARGV[5] .. hash) == 1 then
+        table.insert(live, hash)
+    else
+        redis.call('ZREM', KEYS[1], hash)
+    end
+end
+
+local max = tonumber(ARGV[7])
+if ARGV[6] ~= 'unlimited' and #live >= max then
+    if ARGV[6] == 'reject' then
+        return 0
+    end
+    for i = 1, #live - max + 1 do
+        redis.call('DEL', ARGV[5] .. live[i])
+        redis.call('ZREM', KEYS[1], live[i])
+    end
+end
+
+redis.call('SET', KEYS[2], ARGV[2], 'EX', ARGV[3])
+redis.call('ZADD', KEYS[1], ARGV[4], ARGV[1])
+redis.call('EXPIRE', KEYS[1], ARGV[3], 'NX')
+redis.call('EXPIRE', KEYS[1], ARGV[3], 'GT')
+return 1
+"#;
+
+const REFRESH_KEY_PREFIX: &str = "auth:refresh_token:";
+
 pub struct AuthTokenCacheRepository {
     conn: ConnectionManager,
 }
@@ -11,10 +54,22 @@ impl AuthTokenCacheRepository {
         Self { conn }
     }
 
-    fn refresh_key(token_id: &str) -> String {
+    fn refresh_key(token_hash: &str) -> String {
+        format!("{REFRESH_KEY_PREFIX}{token_hash}")
+    }
+
+    fn sessions_key(user_id: Uuid) -> String {
+        format!("auth:sessions:{user_id}")
+    }
+
+    fn legacy_refresh_key(token_id: &str) -> String {
         format!("auth:refresh:{token_id}")
     }
 
+    fn mfa_challenge_key(challenge_hash: &str) -> String {
+        format!("auth:mfa_challenge:{challenge_hash}")
+    }
+
     fn blacklist_key(jti: &str) -> String {
         format!("auth:blacklist:{jti}")
     }
@@ -23,25 +78,169 @@ impl AuthTokenCacheRepository {
 #[async_trait::async_trait]
 impl TokenCacheRepository for AuthTokenCacheRepository {
     async fn store_refresh_token(
+        &self,
+        token_hash: &str,
+        session: &RefreshSession,
+        ttl_secs: u64,
+        limit: SessionLimit,
+    ) -> RepositoryResult<bool> {
+        let value = serde_json::to_string(session)?;
+        let (mode, max) = match limit {
+            SessionLimit::Unlimited => ("unlimited", 0),
+            SessionLimit::Reject(max) => ("reject", max),
+            SessionLimit::EvictOldest(max) => ("evict_oldest", max),
+        };
+        let mut conn = self.conn.clone();
+
+        let stored: bool = Script::new(STORE_SESSION_SCRIPT)
+            .key(Self::sessions_key(session.user_id))
+            .key(Self::refresh_key(token_hash))
+            .arg(token_hash)
+            .arg(value)
+            .arg(ttl_secs)
+            .arg(session.created_at.timestamp_millis())
+            .arg(REFRESH_KEY_PREFIX)
+            .arg(mode)
+            .arg(max)
+            .invoke_async(&mut conn)
+            .await?;
+
+        Ok(stored)
+    }
+
+    async fn take_refresh_token(
+        &self,
+        token_hash: &str,
+    ) -> RepositoryResult<Option<RefreshSession>> {
+        let mut conn = self.conn.clone();
+
+        let key = Self::refresh_key(token_hash);
+        let value: Option<String> = conn.get_del(key).await?;
+
+        let Some(value) = value else {
+            return Ok(None);
+        };
+        let session: RefreshSession = serde_json::from_str(&value)?;
+
+        // GETDEL already decided the winner; this only tidies the session index.
+        let _: () = conn
+            .zrem(Self::sessions_key(session.user_id), token_hash)
+            .await?;
+
+        Ok(Some(session))
+    }
+
+    async fn list_refresh_tokens(
         &self,
         user_id: Uuid,
-        token_id: &str,
+    ) -> RepositoryResult<Vec<(String, RefreshSession)>> {
+        let mut conn = self.conn.clone();
+
+        let sessions_key = Self::sessions_key(user_id);
+        let token_hashes: Vec<String> = conn.zrange(&sessions_key, 0, -1).await?;
+
+        if token_hashes.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let keys: Vec<String> = token_hashes
+            .iter()
+            .map(|token_hash| Self::refresh_key(token_hash))
+            .collect();
+        let values: Vec<Option<String>> =
+            redis::cmd("MGET").arg(&keys).query_async(&mut conn).await?;
+
+        let mut sessions = Vec::with_capacity(token_hashes.len());
+        let mut expired = Vec::new();
+
+        for (token_hash, value) in token_hashes.into_iter().zip(values) {
+            match value {
+                Some(value) => sessions.push((token_hash, serde_json::from_str(&value)?)),
+                None => expired.push(token_hash),
+            }
+        }
+
+        if !expired.is_empty() {
+            let _: () = conn.zrem(&sessions_key, expired).await?;
+        }
+
+        Ok(sessions)
+    }
+
+    async fn revoke_refresh_token(&self, user_id: Uuid, token_hash: &str) -> RepositoryResult<()> {
+        let mut conn = self.conn.clone();
+
+        let _: () = redis::pipe()
+            .atomic()
+            .del(Self::refresh_key(token_hash))
+            .ignore()
+            .zrem(Self::sessions_key(user_id), token_hash)
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn revoke_all_refresh_tokens(&self, user_id: Uuid) -> RepositoryResult<()> {
+        let mut conn = self.conn.clone();
+
+        let sessions_key = Self::sessions_key(user_id);
+        let token_hashes: Vec<String> = conn.zrange(&sessions_key, 0, -1).await?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+
+        for token_hash in &token_hashes {
+            pipe.del(Self::refresh_key(token_hash)).ignore();
+        }
+
+        let _: () = pipe
+            .del(&sessions_key)
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn take_legacy_refresh_token(&self, token_id: &str) -> RepositoryResult<Option<Uuid>> {
+        let mut conn = self.conn.clone();
+
+        let key = Self::legacy_refresh_key(token_id);
+        let value: Option<String> = conn.get_del(key).await?;
+
+        Ok(value.and_then(|user_id| Uuid::parse_str(&user_id).ok()))
+    }
+
+    async fn store_mfa_challenge(
+        &self,
+        challenge_hash: &str,
+        challenge: &MfaChallenge,
         ttl_secs: u64,
     ) -> RepositoryResult<()> {
-        let key = Self::refresh_key(token_id);
         let mut conn = self.conn.clone();
-        let _: () = conn.set_ex(key, user_id.to_string(), ttl_secs).await?;
+
+        let key = Self::mfa_challenge_key(challenge_hash);
+        let value = serde_json::to_string(challenge)?;
+        let _: () = conn.set_ex(key, value, ttl_secs).await?;
 
         Ok(())
     }
 
-    async fn get_refresh_token(&self, token_id: &str) -> RepositoryResult<Option<String>> {
+    async fn take_mfa_challenge(
+        &self,
+        challenge_hash: &str,
+    ) -> RepositoryResult<Option<MfaChallenge>> {
         let mut conn = self.conn.clone();
 
-        let key = Self::refresh_key(token_id);
-        let value = conn.get(key).await?;
+        let key = Self::mfa_challenge_key(challenge_hash);
+        let value: Option<String> = conn.get_del(key).await?;
 
-        Ok(value)
+        match value {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
     }
 
     async fn blacklist_access_token(&self, jti: &str, ttl_secs: u64) -> RepositoryResult<()> {
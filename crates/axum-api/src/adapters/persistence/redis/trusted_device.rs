@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use redis::{AsyncCommands, aio::ConnectionManager};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::trusted_device::TrustedDevice,
+    repositories::{error::RepositoryResult, trusted_device::TrustedDeviceRepository},
+};
+
+pub struct RedisTrustedDeviceRepository {
+    conn: ConnectionManager,
+}
+
+impl RedisTrustedDeviceRepository {
+    pub fn new(conn: ConnectionManager) -> Self {
+        Self { conn }
+    }
+
+    fn devices_key(user_id: Uuid) -> String {
+        format!("auth:trusted_devices:{user_id}")
+    }
+}
+
+#[async_trait::async_trait]
+impl TrustedDeviceRepository for RedisTrustedDeviceRepository {
+    async fn save(&self, device: &TrustedDevice) -> RepositoryResult<()> {
+        let mut conn = self.conn.clone();
+
+        let key = Self::devices_key(device.user_id);
+        let value = serde_json::to_string(device)?;
+        let ttl_secs = (device.expires_at - device.created_at).num_seconds().max(1);
+
+        let _: () = redis::pipe()
+            .atomic()
+            .hset(&key, device.id.to_string(), value)
+            .ignore()
+            .cmd("EXPIRE")
+            .arg(&key)
+            .arg(ttl_secs)
+            .arg("NX")
+            .ignore()
+            .cmd("EXPIRE")
+            .arg(&key)
+            .arg(ttl_secs)
+            .arg("GT")
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find(
+        &self,
+        user_id: Uuid,
+        device_id: Uuid,
+    ) -> RepositoryResult<Option<TrustedDevice>> {
+        let mut conn = self.conn.clone();
+
+        let key = Self::devices_key(user_id);
+        let value: Option<String> = conn.hget(key, device_id.to_string()).await?;
+
+        match value {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<TrustedDevice>> {
+        let mut conn = self.conn.clone();
+
+        let key = Self::devices_key(user_id);
+        let values: HashMap<String, String> = conn.hgetall(&key).await?;
+
+        let mut devices = Vec::with_capacity(values.len());
+        let mut expired = Vec::new();
+
+        for (device_id, value) in values {
+            let device: TrustedDevice = serde_json::from_str(&value)?;
+
+            if device.is_expired() {
+                expired.push(device_id);
+            } else {
+                devices.push(device);
+            }
+        }
+
+        if !expired.is_empty() {
+            let _: () = conn.hdel(&key, expired).await?;
+        }
+
+        devices.sort_by_key(|device| device.created_at);
+
+        Ok(devices)
+    }
+
+    async fn revoke(&self, user_id: Uuid, device_id: Uuid) -> RepositoryResult<bool> {
+        let mut conn = self.conn.clone();
+
+        let key = Self::devices_key(user_id);
+        let removed: u64 = conn.hdel(key, device_id.to_string()).await?;
+
+        Ok(removed > 0)
+    }
+
+    async fn revoke_all(&self, user_id: Uuid) -> RepositoryResult<()> {
+        let mut conn = self.conn.clone();
+
+        let key = Self::devices_key(user_id);
+        let _: () = conn.del(key).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use std::{
+    cell::Cell,
+    future::Future,
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use tracing::warn;
+
+use crate::domain::repositories::error::RepositoryResult;
+
+tokio::task_local! {
+    static WROTE: Cell<bool>;
+    static PRIMARY_ONLY: ();
+}
+
+/// Runs `request` with read-your-writes tracking: its reads may go to replicas until it
+/// writes, and to the primary from then on. Reads outside such a scope, e.g. from
+/// background jobs, always go to the primary.
+pub async fn consistent_reads<F: Future>(request: F) -> F::Output {
+    WROTE.scope(Cell::new(false), request).await
+}
+
+/// Keeps the rest of the current request reading from the primary.
+pub fn record_write() {
+    let _ = WROTE.try_with(|wrote| wrote.set(true));
+}
+
+/// Runs `reads` on the primary even inside a `consistent_reads` scope, for lookups that
+/// must not act on replication lag, such as signing in with a password that was just
+/// changed. Writes inside still pin the rest of the request to the primary.
+pub async fn primary_reads<F: Future>(reads: F) -> F::Output {
+    PRIMARY_ONLY.scope((), reads).await
+}
+
+fn replica_reads_allowed() -> bool {
+    PRIMARY_ONLY.try_with(|_| ()).is_err() && WROTE.try_with(|wrote| !wrote.get()).unwrap_or(false)
+}
+
+struct Replica<S> {
+    source: S,
+    unhealthy_until: Mutex<Option<Instant>>,
+}
+
+impl<S> Replica<S> {
+    fn is_healthy(&self) -> bool {
+        self.unhealthy_until
+            .lock()
+            .unwrap()
+            .is_none_or(|until| Instant::now() >= until)
+    }
+}
+
+/// Read replicas of one primary, used round-robin. A replica that cannot be reached is
+/// skipped for `retry_after`, and reads fall back to the primary while none is healthy.
+/// Other errors, such as a row that fails to decode, are returned as they are.
+pub struct ReplicaSet<S> {
+    replicas: Vec<Replica<S>>,
+    next: AtomicUsize,
+    retry_after: Duration,
+}
+
+impl<S> Default for ReplicaSet<S> {
+    fn default() -> Self {
+        Self::new(Vec::new(), Duration::ZERO)
+    }
+}
+
+impl<S> ReplicaSet<S> {
+    pub fn new(sources: Vec<S>, retry_after: Duration) -> Self {
+        Self {
+            replicas: sources
+                .into_iter()
+                .map(|source| Replica {
+                    source,
+                    unhealthy_until: Mutex::new(None),
+                })
+                .collect(),
+            next: AtomicUsize::new(0),
+            retry_after,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.replicas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.replicas.is_empty()
+    }
+
+    fn pick(&self) -> Option<&Replica<S>> {
+        if self.replicas.is_empty() || !replica_reads_allowed() {
+            return None;
+        }
+
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+
+        (0..self.replicas.len())
+            .map(|offset| &self.replicas[(start + offset) % self.replicas.len()])
+            .find(|replica| replica.is_healthy())
+    }
+}
+
+impl<S: Clone> ReplicaSet<S> {
+    /// Runs `query` on a replica when the current request allows it, and on `primary`
+    /// otherwise or when the replica cannot be reached. A replica that finds nothing is
+    /// trusted like any other answer; requests that must see their own writes get them
+    /// through `record_write`.
+    pub async fn read<T, F, Fut>(&self, primary: &S, query: F) -> RepositoryResult<T>
+    where
+        F: Fn(S) -> Fut,
+        Fut: Future<Output = RepositoryResult<T>>,
+    {
+        match self.read_replica(&query).await {
+            Some(result) => result,
+            None => query(primary.clone()).await,
+        }
+    }
+
+    /// Returns `None` when the read should go to the primary instead.
+    async fn read_replica<T, F, Fut>(&self, query: &F) -> Option<RepositoryResult<T>>
+    where
+        F: Fn(S) -> Fut,
+        Fut: Future<Output = RepositoryResult<T>>,
+    {
+        let replica = self.pick()?;
+
+        match query(replica.source.clone()).await {
+            Err(e) if e.is_connection_failure() => {
+                warn!("Read replica unreachable, using the primary: {}", e);
+                *replica.unhealthy_until.lock().unwrap() = Some(Instant::now() + self.retry_after);
+                None
+            }
+            result => Some(result),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::codec::decode_timestamp,
+    domain::{
+        entities::attribute::{AttributeSchema, UserAttributes},
+        repositories::error::{RepositoryError, RepositoryResult},
+    },
+};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct AttributeSchemaEntity {
+    pub namespace: String,
+    pub json_schema: String,
+    pub user_writable: bool,
+    pub updated_at: String,
+}
+
+impl AttributeSchemaEntity {
+    pub fn to_domain(&self) -> RepositoryResult<AttributeSchema> {
+        Ok(AttributeSchema {
+            namespace: self.namespace.clone(),
+            schema: serde_json::from_str(&self.json_schema)?,
+            user_writable: self.user_writable,
+            updated_at: decode_timestamp("updated_at", &self.updated_at)?,
+        })
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct UserAttributesEntity {
+    pub user_id: String,
+    pub namespace: String,
+    pub data: String,
+    pub updated_at: String,
+}
+
+impl UserAttributesEntity {
+    pub fn to_domain(&self) -> RepositoryResult<UserAttributes> {
+        Ok(UserAttributes {
+            user_id: Uuid::parse_str(&self.user_id)
+                .map_err(|_| RepositoryError::InvalidUuidFormat)?,
+            namespace: self.namespace.clone(),
+            data: serde_json::from_str(&self.data)?,
+            updated_at: decode_timestamp("updated_at", &self.updated_at)?,
+        })
+    }
+}
@@ -0,0 +1,43 @@
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::codec::decode_timestamp,
+    domain::{
+        entities::outbox::OutboxMessage,
+        repositories::error::{RepositoryError, RepositoryResult},
+    },
+};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct OutboxEntity {
+    pub id: String,
+    pub aggregate_id: String,
+    pub event_type: String,
+    pub payload: String,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<String>,
+    pub created_at: String,
+}
+
+impl OutboxEntity {
+    pub fn to_domain(&self) -> RepositoryResult<OutboxMessage> {
+        Ok(OutboxMessage {
+            id: Uuid::parse_str(&self.id).map_err(|_| RepositoryError::InvalidUuidFormat)?,
+            aggregate_id: Uuid::parse_str(&self.aggregate_id)
+                .map_err(|_| RepositoryError::InvalidUuidFormat)?,
+            event_type: self.event_type.clone(),
+            payload: self.payload.clone(),
+            attempts: u32::try_from(self.attempts).map_err(|e| {
+                RepositoryError::ConversionError(format!("Invalid attempts: {}", e))
+            })?,
+            last_error: self.last_error.clone(),
+            next_attempt_at: self
+                .next_attempt_at
+                .as_deref()
+                .map(|value| decode_timestamp("next_attempt_at", value))
+                .transpose()?,
+            created_at: decode_timestamp("created_at", &self.created_at)?,
+        })
+    }
+}
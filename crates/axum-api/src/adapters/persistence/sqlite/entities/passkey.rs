@@ -0,0 +1,45 @@
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::codec::{decode_timestamp, split_transports},
+    domain::{
+        entities::passkey::Passkey,
+        repositories::error::{RepositoryError, RepositoryResult},
+    },
+};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct PasskeyEntity {
+    pub id: String,
+    pub user_id: String,
+    pub credential_id: Vec<u8>,
+    pub public_key: Vec<u8>,
+    pub sign_count: i64,
+    pub transports: String,
+    pub name: String,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+}
+
+impl PasskeyEntity {
+    pub fn to_domain(&self) -> RepositoryResult<Passkey> {
+        Ok(Passkey {
+            id: Uuid::parse_str(&self.id).map_err(|_| RepositoryError::InvalidUuidFormat)?,
+            user_id: Uuid::parse_str(&self.user_id)
+                .map_err(|_| RepositoryError::InvalidUuidFormat)?,
+            credential_id: self.credential_id.clone(),
+            public_key: self.public_key.clone(),
+            sign_count: u32::try_from(self.sign_count).map_err(|e| {
+                RepositoryError::ConversionError(format!("Invalid sign_count: {}", e))
+            })?,
+            transports: split_transports(&self.transports),
+            name: self.name.clone(),
+            created_at: decode_timestamp("created_at", &self.created_at)?,
+            last_used_at: self
+                .last_used_at
+                .as_deref()
+                .map(|value| decode_timestamp("last_used_at", value))
+                .transpose()?,
+        })
+    }
+}
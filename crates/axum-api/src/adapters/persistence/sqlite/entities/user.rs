@@ -0,0 +1,49 @@
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::codec::decode_timestamp,
+    domain::{
+        entities::user::User,
+        repositories::error::{RepositoryError, RepositoryResult},
+    },
+};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct UserEntity {
+    pub id: String,
+    pub email: String,
+    pub password: String,
+    pub name: String,
+    pub role: String,
+    pub status: String,
+    pub status_reason: Option<String>,
+    pub status_until: Option<String>,
+    pub avatar_key: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl UserEntity {
+    pub fn to_domain(&self) -> RepositoryResult<User> {
+        Ok(User::from_db(
+            Uuid::parse_str(&self.id).map_err(|_| RepositoryError::InvalidUuidFormat)?,
+            self.email.clone(),
+            self.password.clone(),
+            self.name.clone(),
+            self.role
+                .parse()
+                .map_err(RepositoryError::ConversionError)?,
+            self.status
+                .parse()
+                .map_err(RepositoryError::ConversionError)?,
+            self.status_reason.clone(),
+            self.status_until
+                .as_deref()
+                .map(|value| decode_timestamp("status_until", value))
+                .transpose()?,
+            self.avatar_key.clone(),
+            decode_timestamp("created_at", &self.created_at)?,
+            decode_timestamp("updated_at", &self.updated_at)?,
+        ))
+    }
+}
@@ -0,0 +1,175 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::{
+        codec::encode_timestamp,
+        sqlite::entities::attribute::{AttributeSchemaEntity, UserAttributesEntity},
+    },
+    domain::{
+        entities::attribute::{AttributeSchema, UserAttributes},
+        repositories::{attribute::AttributeRepository, error::RepositoryResult},
+    },
+    infra::sqlite::SqlitePool,
+};
+
+#[derive(Clone)]
+pub struct SqliteAttributeRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteAttributeRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AttributeRepository for SqliteAttributeRepository {
+    async fn upsert_schema(
+        &self,
+        namespace: &str,
+        schema: &str,
+        user_writable: bool,
+    ) -> RepositoryResult<AttributeSchema> {
+        let row = sqlx::query_as::<_, AttributeSchemaEntity>(
+            r#"
+            INSERT INTO attribute_schemas (namespace, json_schema, user_writable, updated_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (namespace) DO UPDATE
+                SET json_schema = excluded.json_schema,
+                    user_writable = excluded.user_writable,
+                    updated_at = excluded.updated_at
+            RETURNING namespace, json_schema, user_writable, updated_at
+            "#,
+        )
+        .bind(namespace)
+        .bind(schema)
+        .bind(user_writable)
+        .bind(encode_timestamp(Utc::now()))
+        .fetch_one(&self.pool)
+        .await?;
+
+        row.to_domain()
+    }
+
+    async fn find_schema(&self, namespace: &str) -> RepositoryResult<Option<AttributeSchema>> {
+        let row = sqlx::query_as::<_, AttributeSchemaEntity>(
+            r#"
+            SELECT namespace, json_schema, user_writable, updated_at
+            FROM attribute_schemas
+            WHERE namespace = ?1
+            "#,
+        )
+        .bind(namespace)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn list_schemas(&self) -> RepositoryResult<Vec<AttributeSchema>> {
+        let rows = sqlx::query_as::<_, AttributeSchemaEntity>(
+            r#"
+            SELECT namespace, json_schema, user_writable, updated_at
+            FROM attribute_schemas
+            ORDER BY namespace
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(AttributeSchemaEntity::to_domain).collect()
+    }
+
+    async fn delete_schema(&self, namespace: &str) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM attribute_schemas
+            WHERE namespace = ?1
+            "#,
+        )
+        .bind(namespace)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn upsert(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+        data: &str,
+    ) -> RepositoryResult<UserAttributes> {
+        let row = sqlx::query_as::<_, UserAttributesEntity>(
+            r#"
+            INSERT INTO user_attributes (user_id, namespace, data, updated_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (user_id, namespace) DO UPDATE
+                SET data = excluded.data, updated_at = excluded.updated_at
+            RETURNING user_id, namespace, data, updated_at
+            "#,
+        )
+        .bind(user_id.to_string())
+        .bind(namespace)
+        .bind(data)
+        .bind(encode_timestamp(Utc::now()))
+        .fetch_one(&self.pool)
+        .await?;
+
+        row.to_domain()
+    }
+
+    async fn find(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+    ) -> RepositoryResult<Option<UserAttributes>> {
+        let row = sqlx::query_as::<_, UserAttributesEntity>(
+            r#"
+            SELECT user_id, namespace, data, updated_at
+            FROM user_attributes
+            WHERE user_id = ?1 AND namespace = ?2
+            "#,
+        )
+        .bind(user_id.to_string())
+        .bind(namespace)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<UserAttributes>> {
+        let rows = sqlx::query_as::<_, UserAttributesEntity>(
+            r#"
+            SELECT user_id, namespace, data, updated_at
+            FROM user_attributes
+            WHERE user_id = ?1
+            ORDER BY namespace
+            "#,
+        )
+        .bind(user_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(UserAttributesEntity::to_domain).collect()
+    }
+
+    async fn delete(&self, user_id: Uuid, namespace: &str) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM user_attributes
+            WHERE user_id = ?1 AND namespace = ?2
+            "#,
+        )
+        .bind(user_id.to_string())
+        .bind(namespace)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,213 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Sqlite;
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::{
+        codec::{decode_timestamp, encode_timestamp},
+        sqlite::entities::outbox::OutboxEntity,
+        transaction::SqlxConnectionSource,
+    },
+    domain::{
+        entities::outbox::{OutboxBacklog, OutboxMessage},
+        repositories::{
+            error::{RepositoryError, RepositoryResult},
+            outbox::OutboxRepository,
+        },
+    },
+    infra::sqlite::SqlitePool,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct BacklogRow {
+    pending: i64,
+    failing: i64,
+    dead_lettered: i64,
+    oldest_pending_at: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct SqliteOutboxRepository {
+    source: SqlxConnectionSource<Sqlite>,
+}
+
+impl SqliteOutboxRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self::from_source(SqlxConnectionSource::Pool(pool))
+    }
+
+    pub fn from_source(source: SqlxConnectionSource<Sqlite>) -> Self {
+        Self { source }
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for SqliteOutboxRepository {
+    async fn enqueue(&self, message: &OutboxMessage) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO outbox (id, aggregate_id, event_type, payload, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )
+        .bind(message.id.to_string())
+        .bind(message.aggregate_id.to_string())
+        .bind(message.event_type.as_str())
+        .bind(message.payload.as_str())
+        .bind(encode_timestamp(message.created_at))
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_pending(
+        &self,
+        now: DateTime<Utc>,
+        max_attempts: u32,
+        limit: u32,
+    ) -> RepositoryResult<Vec<OutboxMessage>> {
+        let rows = sqlx::query_as::<_, OutboxEntity>(
+            r#"
+            SELECT
+                id, aggregate_id, event_type, payload, attempts, last_error, next_attempt_at,
+                created_at
+            FROM outbox AS message
+            WHERE dispatched_at IS NULL
+              AND attempts < ?2
+              AND (next_attempt_at IS NULL OR next_attempt_at <= ?1)
+              AND NOT EXISTS (
+                  SELECT 1
+                  FROM outbox AS earlier
+                  WHERE earlier.aggregate_id = message.aggregate_id
+                    AND earlier.sequence < message.sequence
+                    AND earlier.dispatched_at IS NULL
+                    AND earlier.attempts < ?2
+                    AND earlier.next_attempt_at > ?1
+              )
+            ORDER BY sequence
+            LIMIT ?3
+            "#,
+        )
+        .bind(encode_timestamp(now))
+        .bind(i64::from(max_attempts))
+        .bind(i64::from(limit))
+        .fetch_all(&mut *self.source.acquire().await?)
+        .await?;
+
+        rows.iter().map(OutboxEntity::to_domain).collect()
+    }
+
+    async fn mark_dispatched(&self, id: &Uuid) -> RepositoryResult<()> {
+        let result = sqlx::query("UPDATE outbox SET dispatched_at = ?1 WHERE id = ?2")
+            .bind(encode_timestamp(Utc::now()))
+            .bind(id.to_string())
+            .execute(&mut *self.source.acquire().await?)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        id: &Uuid,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE outbox
+            SET attempts = attempts + 1, last_error = ?1, next_attempt_at = ?2
+            WHERE id = ?3
+            "#,
+        )
+        .bind(error)
+        .bind(encode_timestamp(next_attempt_at))
+        .bind(id.to_string())
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn backlog(&self, max_attempts: u32) -> RepositoryResult<OutboxBacklog> {
+        let row = sqlx::query_as::<_, BacklogRow>(
+            r#"
+            SELECT
+                COUNT(CASE WHEN attempts < ?1 THEN 1 END) AS pending,
+                COUNT(CASE WHEN attempts > 0 AND attempts < ?1 THEN 1 END) AS failing,
+                COUNT(CASE WHEN attempts >= ?1 THEN 1 END) AS dead_lettered,
+                MIN(CASE WHEN attempts < ?1 THEN created_at END) AS oldest_pending_at
+            FROM outbox
+            WHERE dispatched_at IS NULL
+            "#,
+        )
+        .bind(i64::from(max_attempts))
+        .fetch_one(&mut *self.source.acquire().await?)
+        .await?;
+
+        Ok(OutboxBacklog {
+            pending: row.pending as u64,
+            failing: row.failing as u64,
+            dead_lettered: row.dead_lettered as u64,
+            oldest_pending_at: row
+                .oldest_pending_at
+                .as_deref()
+                .map(|value| decode_timestamp("oldest_pending_at", value))
+                .transpose()?,
+        })
+    }
+
+    async fn delete_dispatched(&self, before: DateTime<Utc>, limit: u32) -> RepositoryResult<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM outbox
+            WHERE sequence IN (
+                SELECT sequence
+                FROM outbox
+                WHERE dispatched_at < ?1
+                ORDER BY sequence
+                LIMIT ?2
+            )
+            "#,
+        )
+        .bind(encode_timestamp(before))
+        .bind(i64::from(limit))
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn acquire_relay_lease(&self, holder: &Uuid, ttl: Duration) -> RepositoryResult<bool> {
+        let now = Utc::now();
+        let expires_at = now + chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::MAX);
+
+        let result = sqlx::query(
+            r#"
+            UPDATE outbox_relay_lease
+            SET holder = ?1, expires_at = ?2
+            WHERE id = 1
+              AND (holder = ?1 OR expires_at IS NULL OR expires_at <= ?3)
+            "#,
+        )
+        .bind(holder.to_string())
+        .bind(encode_timestamp(expires_at))
+        .bind(encode_timestamp(now))
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
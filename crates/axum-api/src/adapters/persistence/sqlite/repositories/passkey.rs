@@ -0,0 +1,161 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::{
+        codec::{encode_timestamp, join_transports},
+        sqlite::entities::passkey::PasskeyEntity,
+    },
+    domain::{
+        entities::passkey::Passkey,
+        repositories::{
+            error::{RepositoryError, RepositoryResult},
+            passkey::PasskeyRepository,
+            second_factor::SecondFactorRepository,
+        },
+    },
+    infra::sqlite::SqlitePool,
+};
+
+#[derive(Clone)]
+pub struct SqlitePasskeyRepository {
+    pool: SqlitePool,
+}
+
+impl SqlitePasskeyRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PasskeyRepository for SqlitePasskeyRepository {
+    async fn create(&self, passkey: &Passkey) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO passkey_credentials
+                (id, user_id, credential_id, public_key, sign_count, transports, name, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "#,
+        )
+        .bind(passkey.id.to_string())
+        .bind(passkey.user_id.to_string())
+        .bind(passkey.credential_id.as_slice())
+        .bind(passkey.public_key.as_slice())
+        .bind(i64::from(passkey.sign_count))
+        .bind(join_transports(&passkey.transports))
+        .bind(passkey.name.as_str())
+        .bind(encode_timestamp(Utc::now()))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_credential_id(
+        &self,
+        credential_id: &[u8],
+    ) -> RepositoryResult<Option<Passkey>> {
+        let row = sqlx::query_as::<_, PasskeyEntity>(
+            r#"
+            SELECT
+                id,
+                user_id,
+                credential_id,
+                public_key,
+                sign_count,
+                transports,
+                name,
+                created_at,
+                last_used_at
+            FROM passkey_credentials
+            WHERE credential_id = ?1
+            "#,
+        )
+        .bind(credential_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<Passkey>> {
+        let rows = sqlx::query_as::<_, PasskeyEntity>(
+            r#"
+            SELECT
+                id,
+                user_id,
+                credential_id,
+                public_key,
+                sign_count,
+                transports,
+                name,
+                created_at,
+                last_used_at
+            FROM passkey_credentials
+            WHERE user_id = ?1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(user_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(PasskeyEntity::to_domain).collect()
+    }
+
+    async fn update_sign_count(&self, id: Uuid, sign_count: u32) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE passkey_credentials
+            SET sign_count = ?1, last_used_at = ?2
+            WHERE id = ?3
+            "#,
+        )
+        .bind(i64::from(sign_count))
+        .bind(encode_timestamp(Utc::now()))
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM passkey_credentials
+            WHERE id = ?1 AND user_id = ?2
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(user_id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl SecondFactorRepository for SqlitePasskeyRepository {
+    async fn has_second_factor(&self, user_id: Uuid) -> RepositoryResult<bool> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(1)
+            FROM passkey_credentials
+            WHERE user_id = ?1
+            "#,
+        )
+        .bind(user_id.to_string())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count > 0)
+    }
+}
@@ -0,0 +1,358 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Connection, Sqlite};
+
+use crate::{
+    adapters::persistence::{
+        codec::encode_timestamp,
+        sqlite::{
+            entities::user::UserEntity,
+            user_query::{USER_COLUMNS, list_users_sql},
+        },
+        transaction::SqlxConnectionSource,
+    },
+    domain::{
+        entities::user::{User, UserStatus},
+        repositories::{
+            error::{RepositoryError, RepositoryResult},
+            user::{UserListQuery, UserRepository},
+        },
+    },
+    infra::sqlite::SqlitePool,
+};
+
+/// Timestamps are taken from the application clock, since SQLite's own `now` stops at
+/// milliseconds.
+#[derive(Clone)]
+pub struct SqliteUserRepository {
+    source: SqlxConnectionSource<Sqlite>,
+}
+
+impl SqliteUserRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self::from_source(SqlxConnectionSource::Pool(pool))
+    }
+
+    pub fn from_source(source: SqlxConnectionSource<Sqlite>) -> Self {
+        Self { source }
+    }
+}
+
+#[async_trait]
+impl UserRepository for SqliteUserRepository {
+    async fn create(&self, user: &User) -> RepositoryResult<User> {
+        let now = encode_timestamp(Utc::now());
+
+        let row = sqlx::query_as::<_, UserEntity>(&format!(
+            r#"
+            INSERT INTO users (id, email, password, name, role, status, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+            RETURNING {USER_COLUMNS}
+            "#
+        ))
+        .bind(user.id().to_string())
+        .bind(user.email())
+        .bind(user.password())
+        .bind(user.name())
+        .bind(user.role().as_str())
+        .bind(user.status().as_str())
+        .bind(now)
+        .fetch_one(&mut *self.source.acquire().await?)
+        .await?;
+
+        row.to_domain()
+    }
+
+    async fn create_many(&self, users: &[User]) -> RepositoryResult<Vec<User>> {
+        if users.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows: Vec<String> = (0..users.len())
+            .map(|index| {
+                let offset = index * 6 + 1;
+                format!(
+                    "(?{}, ?{}, ?{}, ?{}, ?{}, ?{}, ?1, ?1)",
+                    offset + 1,
+                    offset + 2,
+                    offset + 3,
+                    offset + 4,
+                    offset + 5,
+                    offset + 6,
+                )
+            })
+            .collect();
+
+        let sql = format!(
+            r#"
+            INSERT OR IGNORE INTO users
+                (id, email, password, name, role, status, created_at, updated_at)
+            VALUES {}
+            RETURNING {USER_COLUMNS}
+            "#,
+            rows.join(", "),
+        );
+
+        let mut statement =
+            sqlx::query_as::<_, UserEntity>(&sql).bind(encode_timestamp(Utc::now()));
+        for user in users {
+            statement = statement
+                .bind(user.id().to_string())
+                .bind(user.email())
+                .bind(user.password())
+                .bind(user.name())
+                .bind(user.role().as_str())
+                .bind(user.status().as_str());
+        }
+
+        let rows = statement
+            .fetch_all(&mut *self.source.acquire().await?)
+            .await?;
+
+        rows.iter().map(UserEntity::to_domain).collect()
+    }
+
+    async fn find_existing_emails(&self, emails: &[String]) -> RepositoryResult<Vec<String>> {
+        if emails.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let placeholders: Vec<String> = (1..=emails.len())
+            .map(|index| format!("?{index}"))
+            .collect();
+
+        let sql = format!(
+            "SELECT email FROM users WHERE email IN ({})",
+            placeholders.join(", ")
+        );
+
+        let mut statement = sqlx::query_scalar::<_, String>(&sql);
+        for email in emails {
+            statement = statement.bind(email);
+        }
+
+        Ok(statement
+            .fetch_all(&mut *self.source.acquire().await?)
+            .await?)
+    }
+
+    async fn find_by_email(&self, email: &str) -> RepositoryResult<Option<User>> {
+        let row = sqlx::query_as::<_, UserEntity>(&format!(
+            "SELECT {USER_COLUMNS} FROM users WHERE email = ?1"
+        ))
+        .bind(email)
+        .fetch_optional(&mut *self.source.acquire().await?)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<User>> {
+        let row = sqlx::query_as::<_, UserEntity>(&format!(
+            "SELECT {USER_COLUMNS} FROM users WHERE id = ?1"
+        ))
+        .bind(id.to_lowercase())
+        .fetch_optional(&mut *self.source.acquire().await?)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn list(&self, query: &UserListQuery) -> RepositoryResult<Vec<User>> {
+        let list_sql = list_users_sql(query);
+
+        let mut statement = sqlx::query_as::<_, UserEntity>(&list_sql.sql);
+        for param in &list_sql.params {
+            statement = statement.bind(param);
+        }
+
+        let rows = statement
+            .fetch_all(&mut *self.source.acquire().await?)
+            .await?;
+
+        rows.iter().map(UserEntity::to_domain).collect()
+    }
+
+    async fn update_password(&self, id: &str, password: &str) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET password = ?1, updated_at = ?2
+            WHERE id = ?3
+            "#,
+        )
+        .bind(password)
+        .bind(encode_timestamp(Utc::now()))
+        .bind(id.to_lowercase())
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn update_profile(
+        &self,
+        user: &User,
+        expected_updated_at: DateTime<Utc>,
+    ) -> RepositoryResult<Option<User>> {
+        let row = sqlx::query_as::<_, UserEntity>(&format!(
+            r#"
+            UPDATE users
+            SET name = ?1, updated_at = ?2
+            WHERE id = ?3 AND updated_at = ?4
+            RETURNING {USER_COLUMNS}
+            "#
+        ))
+        .bind(user.name())
+        .bind(encode_timestamp(Utc::now()))
+        .bind(user.id().to_string())
+        .bind(encode_timestamp(expected_updated_at))
+        .fetch_optional(&mut *self.source.acquire().await?)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn update_email(&self, user: &User, previous_email: &str) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET email = ?1, updated_at = ?2
+            WHERE id = ?3
+                AND email = ?4
+                AND NOT EXISTS (
+                    SELECT 1 FROM users WHERE email = ?1
+                )
+            "#,
+        )
+        .bind(user.email())
+        .bind(encode_timestamp(Utc::now()))
+        .bind(user.id().to_string())
+        .bind(previous_email)
+        .execute(&mut *self.source.acquire().await?)
+        .await;
+
+        match result.map_err(RepositoryError::from) {
+            Ok(result) => Ok(result.rows_affected() > 0),
+            Err(e) if e.is_duplicate_email() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn update_avatar(&self, user: &User) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET avatar_key = ?1, updated_at = ?2
+            WHERE id = ?3
+            "#,
+        )
+        .bind(user.avatar_key())
+        .bind(encode_timestamp(Utc::now()))
+        .bind(user.id().to_string())
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn update_status(&self, user: &User, previous: UserStatus) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET status = ?1,
+                status_reason = ?2,
+                status_until = ?3,
+                updated_at = ?4
+            WHERE id = ?5 AND status = ?6
+            "#,
+        )
+        .bind(user.status().as_str())
+        .bind(user.status_reason())
+        .bind(user.status_until().map(encode_timestamp))
+        .bind(encode_timestamp(Utc::now()))
+        .bind(user.id().to_string())
+        .bind(previous.as_str())
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_due_erasures(
+        &self,
+        due_before: DateTime<Utc>,
+        limit: u32,
+    ) -> RepositoryResult<Vec<User>> {
+        let rows = sqlx::query_as::<_, UserEntity>(&format!(
+            r#"
+            SELECT {USER_COLUMNS}
+            FROM users
+            WHERE status = 'pending_deletion'
+                AND status_until <= ?1
+            ORDER BY status_until
+            LIMIT ?2
+            "#
+        ))
+        .bind(encode_timestamp(due_before))
+        .bind(i64::from(limit))
+        .fetch_all(&mut *self.source.acquire().await?)
+        .await?;
+
+        rows.iter().map(UserEntity::to_domain).collect()
+    }
+
+    async fn erase(&self, user: &User) -> RepositoryResult<bool> {
+        let mut conn = self.source.acquire().await?;
+        let mut tx = conn.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET email = ?1,
+                password = ?2,
+                name = ?3,
+                status = ?4,
+                status_reason = NULL,
+                status_until = NULL,
+                avatar_key = NULL,
+                updated_at = ?5
+            WHERE id = ?6 AND status = 'pending_deletion'
+            "#,
+        )
+        .bind(user.email())
+        .bind(user.password())
+        .bind(user.name())
+        .bind(user.status().as_str())
+        .bind(encode_timestamp(Utc::now()))
+        .bind(user.id().to_string())
+        .execute(&mut tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query("DELETE FROM passkey_credentials WHERE user_id = ?1")
+            .bind(user.id().to_string())
+            .execute(&mut tx)
+            .await?;
+
+        sqlx::query("DELETE FROM user_attributes WHERE user_id = ?1")
+            .bind(user.id().to_string())
+            .execute(&mut tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
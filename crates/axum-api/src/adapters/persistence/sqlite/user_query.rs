@@ -0,0 +1,101 @@
+use crate::{
+    adapters::persistence::{
+        codec::encode_timestamp,
+        user_query::{UserSql, escape_like},
+    },
+    domain::repositories::user::{SortDirection, UserListQuery},
+};
+
+pub const USER_COLUMNS: &str = "id, email, password, name, role, status, status_reason, \
+                                status_until, avatar_key, created_at, updated_at";
+
+pub fn list_users_sql(query: &UserListQuery) -> UserSql {
+    let mut clauses = Vec::new();
+    let mut params = Vec::new();
+
+    let bind = |params: &mut Vec<String>, value: String| {
+        params.push(value);
+        format!("?{}", params.len())
+    };
+
+    let filter = &query.filter;
+
+    if let Some(email_prefix) = &filter.email_prefix {
+        let param = bind(&mut params, format!("{}%", escape_like(email_prefix)));
+        clauses.push(format!("email LIKE {param} ESCAPE '\\'"));
+    }
+
+    if let Some(name) = &filter.name {
+        let param = bind(&mut params, format!("%{}%", escape_like(name)));
+        clauses.push(format!("name LIKE {param} ESCAPE '\\'"));
+    }
+
+    if let Some(status) = filter.status {
+        let param = bind(&mut params, status.as_str().to_string());
+        clauses.push(format!("status = {param}"));
+    }
+
+    if let Some(role) = filter.role {
+        let param = bind(&mut params, role.as_str().to_string());
+        clauses.push(format!("role = {param}"));
+    }
+
+    if let Some(created_from) = filter.created_from {
+        let param = bind(&mut params, encode_timestamp(created_from));
+        clauses.push(format!("created_at >= {param}"));
+    }
+
+    if let Some(created_to) = filter.created_to {
+        let param = bind(&mut params, encode_timestamp(created_to));
+        clauses.push(format!("created_at < {param}"));
+    }
+
+    // JSON booleans come back from json_extract as integers, so compare them by JSON type.
+    for attribute in &filter.attributes {
+        let namespace = bind(&mut params, attribute.namespace.clone());
+        let path = bind(&mut params, attribute.json_path());
+        let value = bind(&mut params, attribute.value.clone());
+        clauses.push(format!(
+            "EXISTS (SELECT 1 FROM user_attributes \
+             WHERE user_attributes.user_id = users.id \
+             AND user_attributes.namespace = {namespace} \
+             AND CASE json_type(user_attributes.data, {path}) \
+                 WHEN 'true' THEN 'true' \
+                 WHEN 'false' THEN 'false' \
+                 ELSE CAST(json_extract(user_attributes.data, {path}) AS TEXT) \
+             END = {value})"
+        ));
+    }
+
+    let (comparison, order) = match query.direction {
+        SortDirection::Asc => (">", "ASC"),
+        SortDirection::Desc => ("<", "DESC"),
+    };
+
+    if let Some(cursor) = &query.after {
+        let created_at = bind(&mut params, encode_timestamp(cursor.created_at));
+        let id = bind(&mut params, cursor.id.to_string());
+        clauses.push(format!(
+            "(created_at, id) {comparison} ({created_at}, {id})"
+        ));
+    }
+
+    let where_clause = if clauses.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", clauses.join(" AND "))
+    };
+
+    let sql = format!(
+        r#"
+        SELECT {USER_COLUMNS}
+        FROM users
+        {where_clause}
+        ORDER BY created_at {order}, id {order}
+        LIMIT {limit}
+        "#,
+        limit = query.limit,
+    );
+
+    UserSql { sql, params }
+}
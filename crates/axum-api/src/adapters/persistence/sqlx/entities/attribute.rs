@@ -0,0 +1,71 @@
+use crate::{
+    adapters::persistence::sqlx::entities::{datetime2_columns, decode_datetime2, decode_uuid},
+    domain::{
+        entities::attribute::{AttributeSchema, UserAttributes},
+        repositories::error::RepositoryResult,
+    },
+};
+
+/// Columns read into [`AttributeSchemaEntity`], qualified by `source`.
+pub fn attribute_schema_columns(source: &str) -> String {
+    format!(
+        "{source}.namespace, {source}.json_schema, {source}.user_writable, {}",
+        datetime2_columns(&format!("{source}.updated_at"), "updated_at"),
+    )
+}
+
+/// Columns read into [`UserAttributesEntity`], qualified by `source`.
+pub fn user_attributes_columns(source: &str) -> String {
+    format!(
+        "CAST({source}.user_id AS NVARCHAR(36)) AS user_id, {source}.namespace, {source}.data, {}",
+        datetime2_columns(&format!("{source}.updated_at"), "updated_at"),
+    )
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct AttributeSchemaEntity {
+    pub namespace: String,
+    pub json_schema: String,
+    pub user_writable: bool,
+    pub updated_at_secs: i64,
+    pub updated_at_nanos: i32,
+}
+
+impl AttributeSchemaEntity {
+    pub fn to_domain(&self) -> RepositoryResult<AttributeSchema> {
+        Ok(AttributeSchema {
+            namespace: self.namespace.clone(),
+            schema: serde_json::from_str(&self.json_schema)?,
+            user_writable: self.user_writable,
+            updated_at: decode_datetime2(
+                "updated_at",
+                self.updated_at_secs,
+                self.updated_at_nanos,
+            )?,
+        })
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct UserAttributesEntity {
+    pub user_id: String,
+    pub namespace: String,
+    pub data: String,
+    pub updated_at_secs: i64,
+    pub updated_at_nanos: i32,
+}
+
+impl UserAttributesEntity {
+    pub fn to_domain(&self) -> RepositoryResult<UserAttributes> {
+        Ok(UserAttributes {
+            user_id: decode_uuid(&self.user_id)?,
+            namespace: self.namespace.clone(),
+            data: serde_json::from_str(&self.data)?,
+            updated_at: decode_datetime2(
+                "updated_at",
+                self.updated_at_secs,
+                self.updated_at_nanos,
+            )?,
+        })
+    }
+}
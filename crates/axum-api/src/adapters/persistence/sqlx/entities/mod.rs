@@ -1 +1,47 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::repositories::error::{RepositoryError, RepositoryResult};
+
+pub mod attribute;
+pub mod outbox;
+pub mod passkey;
 pub mod user;
+
+/// sqlx 0.6 cannot decode DATETIME2, so timestamps are projected as whole seconds since
+/// the Unix epoch plus the nanosecond part, which keeps the column's 100ns precision.
+pub fn datetime2_columns(column: &str, alias: &str) -> String {
+    format!(
+        "DATEDIFF_BIG(SECOND, '1970-01-01', {column}) AS {alias}_secs, \
+         DATEPART(NANOSECOND, {column}) AS {alias}_nanos"
+    )
+}
+
+pub fn decode_datetime2(column: &str, secs: i64, nanos: i32) -> RepositoryResult<DateTime<Utc>> {
+    u32::try_from(nanos)
+        .ok()
+        .and_then(|nanos| DateTime::from_timestamp(secs, nanos))
+        .ok_or_else(|| {
+            RepositoryError::ConversionError(format!("Invalid {}: {}s {}ns", column, secs, nanos))
+        })
+}
+
+pub fn decode_optional_datetime2(
+    column: &str,
+    secs: Option<i64>,
+    nanos: Option<i32>,
+) -> RepositoryResult<Option<DateTime<Utc>>> {
+    match (secs, nanos) {
+        (Some(secs), Some(nanos)) => decode_datetime2(column, secs, nanos).map(Some),
+        (None, None) => Ok(None),
+        _ => Err(RepositoryError::ConversionError(format!(
+            "Partial {} value",
+            column
+        ))),
+    }
+}
+
+/// UNIQUEIDENTIFIER is read as its canonical text form for the same reason.
+pub fn decode_uuid(value: &str) -> RepositoryResult<Uuid> {
+    Uuid::parse_str(value).map_err(|_| RepositoryError::InvalidUuidFormat)
+}
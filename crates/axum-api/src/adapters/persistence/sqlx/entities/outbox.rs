@@ -0,0 +1,58 @@
+use crate::{
+    adapters::persistence::sqlx::entities::{
+        datetime2_columns, decode_datetime2, decode_optional_datetime2, decode_uuid,
+    },
+    domain::{
+        entities::outbox::OutboxMessage,
+        repositories::error::{RepositoryError, RepositoryResult},
+    },
+};
+
+/// Columns read into [`OutboxEntity`].
+pub fn outbox_columns() -> String {
+    format!(
+        "CAST(id AS NVARCHAR(36)) AS id, CAST(aggregate_id AS NVARCHAR(36)) AS aggregate_id, \
+         event_type, payload, attempts, last_error, {}, {}",
+        datetime2_columns("next_attempt_at", "next_attempt_at"),
+        datetime2_columns("created_at", "created_at"),
+    )
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct OutboxEntity {
+    pub id: String,
+    pub aggregate_id: String,
+    pub event_type: String,
+    pub payload: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at_secs: Option<i64>,
+    pub next_attempt_at_nanos: Option<i32>,
+    pub created_at_secs: i64,
+    pub created_at_nanos: i32,
+}
+
+impl OutboxEntity {
+    pub fn to_domain(&self) -> RepositoryResult<OutboxMessage> {
+        Ok(OutboxMessage {
+            id: decode_uuid(&self.id)?,
+            aggregate_id: decode_uuid(&self.aggregate_id)?,
+            event_type: self.event_type.clone(),
+            payload: self.payload.clone(),
+            attempts: u32::try_from(self.attempts).map_err(|e| {
+                RepositoryError::ConversionError(format!("Invalid attempts: {}", e))
+            })?,
+            last_error: self.last_error.clone(),
+            next_attempt_at: decode_optional_datetime2(
+                "next_attempt_at",
+                self.next_attempt_at_secs,
+                self.next_attempt_at_nanos,
+            )?,
+            created_at: decode_datetime2(
+                "created_at",
+                self.created_at_secs,
+                self.created_at_nanos,
+            )?,
+        })
+    }
+}
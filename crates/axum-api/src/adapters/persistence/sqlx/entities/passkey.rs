@@ -0,0 +1,65 @@
+use crate::{
+    adapters::persistence::{
+        codec::{decode_bytes, split_transports},
+        sqlx::entities::{
+            datetime2_columns, decode_datetime2, decode_optional_datetime2, decode_uuid,
+        },
+    },
+    domain::{
+        entities::passkey::Passkey,
+        repositories::error::{RepositoryError, RepositoryResult},
+    },
+};
+
+/// Columns read into [`PasskeyEntity`], qualified by `source`.
+pub fn passkey_columns(source: &str) -> String {
+    format!(
+        "CAST({source}.id AS NVARCHAR(36)) AS id, \
+         CAST({source}.user_id AS NVARCHAR(36)) AS user_id, \
+         {source}.credential_id, {source}.public_key, {source}.sign_count, \
+         {source}.transports, {source}.name, {}, {}",
+        datetime2_columns(&format!("{source}.created_at"), "created_at"),
+        datetime2_columns(&format!("{source}.last_used_at"), "last_used_at"),
+    )
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct PasskeyEntity {
+    pub id: String,
+    pub user_id: String,
+    pub credential_id: String,
+    pub public_key: String,
+    pub sign_count: i64,
+    pub transports: String,
+    pub name: String,
+    pub created_at_secs: i64,
+    pub created_at_nanos: i32,
+    pub last_used_at_secs: Option<i64>,
+    pub last_used_at_nanos: Option<i32>,
+}
+
+impl PasskeyEntity {
+    pub fn to_domain(&self) -> RepositoryResult<Passkey> {
+        Ok(Passkey {
+            id: decode_uuid(&self.id)?,
+            user_id: decode_uuid(&self.user_id)?,
+            credential_id: decode_bytes("credential_id", &self.credential_id)?,
+            public_key: decode_bytes("public_key", &self.public_key)?,
+            sign_count: u32::try_from(self.sign_count).map_err(|e| {
+                RepositoryError::ConversionError(format!("Invalid sign_count: {}", e))
+            })?,
+            transports: split_transports(&self.transports),
+            name: self.name.clone(),
+            created_at: decode_datetime2(
+                "created_at",
+                self.created_at_secs,
+                self.created_at_nanos,
+            )?,
+            last_used_at: decode_optional_datetime2(
+                "last_used_at",
+                self.last_used_at_secs,
+                self.last_used_at_nanos,
+            )?,
+        })
+    }
+}
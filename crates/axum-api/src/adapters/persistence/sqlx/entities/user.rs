@@ -1,6 +1,24 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
+use crate::{
+    adapters::persistence::sqlx::entities::{
+        datetime2_columns, decode_datetime2, decode_optional_datetime2, decode_uuid,
+    },
+    domain::{
+        entities::user::User,
+        repositories::error::{RepositoryError, RepositoryResult},
+    },
+};
 
-use crate::domain::entities::user::User;
+/// Columns read into [`UserEntity`], qualified by `source` (`users` or `inserted`).
+pub fn user_columns(source: &str) -> String {
+    format!(
+        "CAST({source}.id AS NVARCHAR(36)) AS id, {source}.email, {source}.password, \
+         {source}.name, {source}.role, {source}.status, {source}.status_reason, {}, \
+         {source}.avatar_key, {}, {}",
+        datetime2_columns(&format!("{source}.status_until"), "status_until"),
+        datetime2_columns(&format!("{source}.created_at"), "created_at"),
+        datetime2_columns(&format!("{source}.updated_at"), "updated_at"),
+    )
+}
 
 #[derive(Debug, sqlx::FromRow)]
 pub struct UserEntity {
@@ -8,31 +26,40 @@ pub struct UserEntity {
     pub email: String,
     pub password: String,
     pub name: String,
-    pub created_at: String,
-    pub updated_at: String,
+    pub role: String,
+    pub status: String,
+    pub status_reason: Option<String>,
+    pub status_until_secs: Option<i64>,
+    pub status_until_nanos: Option<i32>,
+    pub avatar_key: Option<String>,
+    pub created_at_secs: i64,
+    pub created_at_nanos: i32,
+    pub updated_at_secs: i64,
+    pub updated_at_nanos: i32,
 }
 
 impl UserEntity {
-    pub fn to_domain(&self) -> User {
-        let created_at_naive =
-            NaiveDateTime::parse_from_str(&self.created_at, "%Y-%m-%dT%H:%M:%S.%f")
-                .unwrap_or_default();
-        let created_at: DateTime<Utc> =
-            DateTime::<Utc>::from_naive_utc_and_offset(created_at_naive, Utc);
-
-        let updated_at_naive =
-            NaiveDateTime::parse_from_str(&self.updated_at, "%Y-%m-%dT%H:%M:%S.%f")
-                .unwrap_or_default();
-        let updated_at: DateTime<Utc> =
-            DateTime::<Utc>::from_naive_utc_and_offset(updated_at_naive, Utc);
-
-        User::from_db(
-            uuid::Uuid::parse_str(&self.id).unwrap_or_default(),
+    pub fn to_domain(&self) -> RepositoryResult<User> {
+        Ok(User::from_db(
+            decode_uuid(&self.id)?,
             self.email.clone(),
             self.password.clone(),
             self.name.clone(),
-            created_at,
-            updated_at,
-        )
+            self.role
+                .parse()
+                .map_err(RepositoryError::ConversionError)?,
+            self.status
+                .parse()
+                .map_err(RepositoryError::ConversionError)?,
+            self.status_reason.clone(),
+            decode_optional_datetime2(
+                "status_until",
+                self.status_until_secs,
+                self.status_until_nanos,
+            )?,
+            self.avatar_key.clone(),
+            decode_datetime2("created_at", self.created_at_secs, self.created_at_nanos)?,
+            decode_datetime2("updated_at", self.updated_at_secs, self.updated_at_nanos)?,
+        ))
     }
 }
@@ -0,0 +1,182 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::sqlx::entities::attribute::{
+        AttributeSchemaEntity, UserAttributesEntity, attribute_schema_columns,
+        user_attributes_columns,
+    },
+    domain::{
+        entities::attribute::{AttributeSchema, UserAttributes},
+        repositories::{attribute::AttributeRepository, error::RepositoryResult},
+    },
+    infra::mssql_sqlx::MssqlPool,
+};
+
+#[derive(Clone)]
+pub struct SqlXAttributeRepository {
+    pool: MssqlPool,
+}
+
+impl SqlXAttributeRepository {
+    pub fn new(pool: MssqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AttributeRepository for SqlXAttributeRepository {
+    async fn upsert_schema(
+        &self,
+        namespace: &str,
+        schema: &str,
+        user_writable: bool,
+    ) -> RepositoryResult<AttributeSchema> {
+        let row = sqlx::query_as::<_, AttributeSchemaEntity>(&format!(
+            r#"
+            MERGE attribute_schemas WITH (HOLDLOCK) AS target
+            USING (SELECT @p1 AS namespace) AS source
+                ON target.namespace = source.namespace
+            WHEN MATCHED THEN
+                UPDATE SET json_schema = @p2, user_writable = @p3, updated_at = SYSUTCDATETIME()
+            WHEN NOT MATCHED THEN
+                INSERT (namespace, json_schema, user_writable) VALUES (@p1, @p2, @p3)
+            OUTPUT {columns};
+            "#,
+            columns = attribute_schema_columns("inserted"),
+        ))
+        .bind(namespace)
+        .bind(schema)
+        .bind(user_writable)
+        .fetch_one(&self.pool)
+        .await?;
+
+        row.to_domain()
+    }
+
+    async fn find_schema(&self, namespace: &str) -> RepositoryResult<Option<AttributeSchema>> {
+        let row = sqlx::query_as::<_, AttributeSchemaEntity>(&format!(
+            r#"
+            SELECT {columns}
+            FROM attribute_schemas
+            WHERE namespace = @p1
+            "#,
+            columns = attribute_schema_columns("attribute_schemas"),
+        ))
+        .bind(namespace)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn list_schemas(&self) -> RepositoryResult<Vec<AttributeSchema>> {
+        let rows = sqlx::query_as::<_, AttributeSchemaEntity>(&format!(
+            r#"
+            SELECT {columns}
+            FROM attribute_schemas
+            ORDER BY namespace
+            "#,
+            columns = attribute_schema_columns("attribute_schemas"),
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(AttributeSchemaEntity::to_domain).collect()
+    }
+
+    async fn delete_schema(&self, namespace: &str) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM attribute_schemas
+            WHERE namespace = @p1
+            "#,
+        )
+        .bind(namespace)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn upsert(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+        data: &str,
+    ) -> RepositoryResult<UserAttributes> {
+        let row = sqlx::query_as::<_, UserAttributesEntity>(&format!(
+            r#"
+            MERGE user_attributes WITH (HOLDLOCK) AS target
+            USING (SELECT CAST(@p1 AS UNIQUEIDENTIFIER) AS user_id, @p2 AS namespace) AS source
+                ON target.user_id = source.user_id AND target.namespace = source.namespace
+            WHEN MATCHED THEN
+                UPDATE SET data = @p3, updated_at = SYSUTCDATETIME()
+            WHEN NOT MATCHED THEN
+                INSERT (user_id, namespace, data) VALUES (source.user_id, @p2, @p3)
+            OUTPUT {columns};
+            "#,
+            columns = user_attributes_columns("inserted"),
+        ))
+        .bind(user_id.to_string())
+        .bind(namespace)
+        .bind(data)
+        .fetch_one(&self.pool)
+        .await?;
+
+        row.to_domain()
+    }
+
+    async fn find(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+    ) -> RepositoryResult<Option<UserAttributes>> {
+        let row = sqlx::query_as::<_, UserAttributesEntity>(&format!(
+            r#"
+            SELECT {columns}
+            FROM user_attributes
+            WHERE user_id = @p1 AND namespace = @p2
+            "#,
+            columns = user_attributes_columns("user_attributes"),
+        ))
+        .bind(user_id.to_string())
+        .bind(namespace)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<UserAttributes>> {
+        let rows = sqlx::query_as::<_, UserAttributesEntity>(&format!(
+            r#"
+            SELECT {columns}
+            FROM user_attributes
+            WHERE user_id = @p1
+            ORDER BY namespace
+            "#,
+            columns = user_attributes_columns("user_attributes"),
+        ))
+        .bind(user_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(UserAttributesEntity::to_domain).collect()
+    }
+
+    async fn delete(&self, user_id: Uuid, namespace: &str) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM user_attributes
+            WHERE user_id = @p1 AND namespace = @p2
+            "#,
+        )
+        .bind(user_id.to_string())
+        .bind(namespace)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -1 +1,4 @@
+pub mod attribute;
+pub mod outbox;
+pub mod passkey;
 pub mod user;
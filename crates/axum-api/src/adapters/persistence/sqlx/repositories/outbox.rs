@@ -0,0 +1,218 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Mssql;
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::{
+        sqlx::entities::{
+            datetime2_columns, decode_optional_datetime2,
+            outbox::{OutboxEntity, outbox_columns},
+        },
+        transaction::SqlxConnectionSource,
+        user_query::format_datetime2,
+    },
+    domain::{
+        entities::outbox::{OutboxBacklog, OutboxMessage},
+        repositories::{
+            error::{RepositoryError, RepositoryResult},
+            outbox::OutboxRepository,
+        },
+    },
+    infra::mssql_sqlx::MssqlPool,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct BacklogRow {
+    pending: i64,
+    failing: i64,
+    dead_lettered: i64,
+    oldest_pending_at_secs: Option<i64>,
+    oldest_pending_at_nanos: Option<i32>,
+}
+
+#[derive(Clone)]
+pub struct SqlXOutboxRepository {
+    source: SqlxConnectionSource<Mssql>,
+}
+
+impl SqlXOutboxRepository {
+    pub fn new(pool: MssqlPool) -> Self {
+        Self::from_source(SqlxConnectionSource::Pool(pool))
+    }
+
+    pub fn from_source(source: SqlxConnectionSource<Mssql>) -> Self {
+        Self { source }
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for SqlXOutboxRepository {
+    async fn enqueue(&self, message: &OutboxMessage) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO outbox (id, aggregate_id, event_type, payload, created_at)
+            VALUES (@p1, @p2, @p3, @p4, CAST(@p5 AS DATETIME2));
+            "#,
+        )
+        .bind(message.id.to_string())
+        .bind(message.aggregate_id.to_string())
+        .bind(message.event_type.as_str())
+        .bind(message.payload.as_str())
+        .bind(format_datetime2(message.created_at))
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_pending(
+        &self,
+        now: DateTime<Utc>,
+        max_attempts: u32,
+        limit: u32,
+    ) -> RepositoryResult<Vec<OutboxMessage>> {
+        let rows = sqlx::query_as::<_, OutboxEntity>(&format!(
+            r#"
+            SELECT TOP ({limit}) {columns}
+            FROM outbox AS message
+            WHERE dispatched_at IS NULL
+              AND attempts < @p2
+              AND (next_attempt_at IS NULL OR next_attempt_at <= CAST(@p1 AS DATETIME2))
+              AND NOT EXISTS (
+                  SELECT 1
+                  FROM outbox AS earlier
+                  WHERE earlier.aggregate_id = message.aggregate_id
+                    AND earlier.sequence < message.sequence
+                    AND earlier.dispatched_at IS NULL
+                    AND earlier.attempts < @p2
+                    AND earlier.next_attempt_at > CAST(@p1 AS DATETIME2)
+              )
+            ORDER BY sequence
+            "#,
+            columns = outbox_columns(),
+        ))
+        .bind(format_datetime2(now))
+        .bind(i32::try_from(max_attempts).unwrap_or(i32::MAX))
+        .fetch_all(&mut *self.source.acquire().await?)
+        .await?;
+
+        rows.iter().map(OutboxEntity::to_domain).collect()
+    }
+
+    async fn mark_dispatched(&self, id: &Uuid) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE outbox
+            SET dispatched_at = SYSUTCDATETIME()
+            WHERE id = @p1
+            "#,
+        )
+        .bind(id.to_string())
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        id: &Uuid,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE outbox
+            SET attempts = attempts + 1,
+                last_error = @p1,
+                next_attempt_at = CAST(@p2 AS DATETIME2)
+            WHERE id = @p3
+            "#,
+        )
+        .bind(error)
+        .bind(format_datetime2(next_attempt_at))
+        .bind(id.to_string())
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn backlog(&self, max_attempts: u32) -> RepositoryResult<OutboxBacklog> {
+        let row = sqlx::query_as::<_, BacklogRow>(&format!(
+            r#"
+            SELECT
+                COUNT_BIG(CASE WHEN attempts < @p1 THEN 1 END) AS pending,
+                COUNT_BIG(CASE WHEN attempts > 0 AND attempts < @p1 THEN 1 END) AS failing,
+                COUNT_BIG(CASE WHEN attempts >= @p1 THEN 1 END) AS dead_lettered,
+                {oldest_pending_at}
+            FROM outbox
+            WHERE dispatched_at IS NULL
+            "#,
+            oldest_pending_at = datetime2_columns(
+                "MIN(CASE WHEN attempts < @p1 THEN created_at END)",
+                "oldest_pending_at"
+            ),
+        ))
+        .bind(i32::try_from(max_attempts).unwrap_or(i32::MAX))
+        .fetch_one(&mut *self.source.acquire().await?)
+        .await?;
+
+        Ok(OutboxBacklog {
+            pending: row.pending as u64,
+            failing: row.failing as u64,
+            dead_lettered: row.dead_lettered as u64,
+            oldest_pending_at: decode_optional_datetime2(
+                "oldest_pending_at",
+                row.oldest_pending_at_secs,
+                row.oldest_pending_at_nanos,
+            )?,
+        })
+    }
+
+    async fn delete_dispatched(&self, before: DateTime<Utc>, limit: u32) -> RepositoryResult<u64> {
+        let result = sqlx::query(&format!(
+            r#"
+            DELETE TOP ({limit}) FROM outbox
+            WHERE dispatched_at < CAST(@p1 AS DATETIME2)
+            "#
+        ))
+        .bind(format_datetime2(before))
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn acquire_relay_lease(&self, holder: &Uuid, ttl: Duration) -> RepositoryResult<bool> {
+        // `DATEADD` takes an `INT` count.
+        let ttl_millis = i32::try_from(ttl.as_millis()).unwrap_or(i32::MAX);
+
+        let result = sqlx::query(
+            r#"
+            UPDATE outbox_relay_lease
+            SET holder = @p1,
+                expires_at = DATEADD(MILLISECOND, @p2, SYSUTCDATETIME())
+            WHERE id = 1
+              AND (holder = @p1 OR expires_at IS NULL OR expires_at <= SYSUTCDATETIME())
+            "#,
+        )
+        .bind(holder.to_string())
+        .bind(ttl_millis)
+        .execute(&mut *self.source.acquire().await?)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
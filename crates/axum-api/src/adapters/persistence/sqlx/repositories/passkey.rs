@@ -0,0 +1,142 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::{
+        codec::{encode_bytes, join_transports},
+        sqlx::entities::passkey::{PasskeyEntity, passkey_columns},
+    },
+    domain::{
+        entities::passkey::Passkey,
+        repositories::{
+            error::{RepositoryError, RepositoryResult},
+            passkey::PasskeyRepository,
+            second_factor::SecondFactorRepository,
+        },
+    },
+    infra::mssql_sqlx::MssqlPool,
+};
+
+#[derive(Clone)]
+pub struct SqlXPasskeyRepository {
+    pool: MssqlPool,
+}
+
+impl SqlXPasskeyRepository {
+    pub fn new(pool: MssqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PasskeyRepository for SqlXPasskeyRepository {
+    async fn create(&self, passkey: &Passkey) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO passkey_credentials
+                (id, user_id, credential_id, public_key, sign_count, transports, name)
+            VALUES (@p1, @p2, @p3, @p4, @p5, @p6, @p7);
+            "#,
+        )
+        .bind(passkey.id.to_string())
+        .bind(passkey.user_id.to_string())
+        .bind(encode_bytes(&passkey.credential_id))
+        .bind(encode_bytes(&passkey.public_key))
+        .bind(i64::from(passkey.sign_count))
+        .bind(join_transports(&passkey.transports))
+        .bind(passkey.name.as_str())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_credential_id(
+        &self,
+        credential_id: &[u8],
+    ) -> RepositoryResult<Option<Passkey>> {
+        let row = sqlx::query_as::<_, PasskeyEntity>(&format!(
+            r#"
+            SELECT {columns}
+            FROM passkey_credentials
+            WHERE credential_id = CAST(@p1 AS VARCHAR(1400))
+            "#,
+            columns = passkey_columns("passkey_credentials"),
+        ))
+        .bind(encode_bytes(credential_id))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<Passkey>> {
+        let rows = sqlx::query_as::<_, PasskeyEntity>(&format!(
+            r#"
+            SELECT {columns}
+            FROM passkey_credentials
+            WHERE user_id = @p1
+            ORDER BY created_at
+            "#,
+            columns = passkey_columns("passkey_credentials"),
+        ))
+        .bind(user_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(PasskeyEntity::to_domain).collect()
+    }
+
+    async fn update_sign_count(&self, id: Uuid, sign_count: u32) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE passkey_credentials
+            SET sign_count = @p1, last_used_at = SYSUTCDATETIME()
+            WHERE id = @p2
+            "#,
+        )
+        .bind(i64::from(sign_count))
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM passkey_credentials
+            WHERE id = @p1 AND user_id = @p2
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(user_id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl SecondFactorRepository for SqlXPasskeyRepository {
+    async fn has_second_factor(&self, user_id: Uuid) -> RepositoryResult<bool> {
+        let count = sqlx::query_scalar::<_, i32>(
+            r#"
+            SELECT COUNT(1)
+            FROM passkey_credentials
+            WHERE user_id = @p1
+            "#,
+        )
+        .bind(user_id.to_string())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count > 0)
+    }
+}
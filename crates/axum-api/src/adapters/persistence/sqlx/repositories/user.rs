@@ -1,97 +1,351 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Connection, Mssql};
 
 use crate::{
-    adapters::persistence::sqlx::entities::user::UserEntity,
+    adapters::persistence::{
+        replica::{ReplicaSet, record_write},
+        sqlx::entities::user::{UserEntity, user_columns},
+        transaction::SqlxConnectionSource,
+        user_query::{create_users_sql, existing_emails_sql, format_datetime2, list_users_sql},
+    },
     domain::{
-        entities::user::User,
-        repositories::{error::RepositoryResult, user::UserRepository},
+        entities::user::{User, UserStatus},
+        repositories::{
+            error::{RepositoryError, RepositoryResult},
+            user::{UserListQuery, UserRepository},
+        },
     },
     infra::mssql_sqlx::MssqlPool,
 };
 
 #[derive(Clone)]
 pub struct SqlXUserRepository {
-    pool: MssqlPool,
+    source: SqlxConnectionSource<Mssql>,
+    replicas: Arc<ReplicaSet<SqlxConnectionSource<Mssql>>>,
 }
 
 impl SqlXUserRepository {
     pub fn new(pool: MssqlPool) -> Self {
-        Self { pool }
+        Self::from_source(SqlxConnectionSource::Pool(pool))
+    }
+
+    pub fn from_source(source: SqlxConnectionSource<Mssql>) -> Self {
+        Self {
+            source,
+            replicas: Arc::default(),
+        }
+    }
+
+    /// Sends lookups and listings to `replicas` where the request allows it.
+    pub fn with_read_replicas(
+        mut self,
+        replicas: Arc<ReplicaSet<SqlxConnectionSource<Mssql>>>,
+    ) -> Self {
+        self.replicas = replicas;
+        self
+    }
+
+    fn primary(&self) -> &SqlxConnectionSource<Mssql> {
+        record_write();
+        &self.source
     }
 }
 
 #[async_trait]
 impl UserRepository for SqlXUserRepository {
     async fn create(&self, user: &User) -> RepositoryResult<User> {
-        let row = sqlx::query_as::<_, UserEntity>(
+        let row = sqlx::query_as::<_, UserEntity>(&format!(
             r#"
-            INSERT INTO users (email, password, name)
-            OUTPUT
-                CAST(inserted.id AS NVARCHAR(36)) as id,
-                inserted.email,
-                inserted.password,
-                inserted.name,
-                FORMAT(inserted.created_at, 'yyyy-MM-ddTHH:mm:ss.ffffff') as created_at,
-                FORMAT(inserted.updated_at, 'yyyy-MM-ddTHH:mm:ss.ffffff') as updated_at
-            VALUES (@p1, @p2, @p3);
+            INSERT INTO users (id, email, password, name, role, status)
+            OUTPUT {columns}
+            VALUES (@p1, @p2, @p3, @p4, @p5, @p6);
             "#,
-        )
+            columns = user_columns("inserted"),
+        ))
+        .bind(user.id().to_string())
         .bind(user.email())
         .bind(user.password())
         .bind(user.name())
-        .fetch_one(&self.pool)
+        .bind(user.role().as_str())
+        .bind(user.status().as_str())
+        .fetch_one(&mut *self.primary().acquire().await?)
         .await?;
 
-        Ok(row.to_domain())
+        row.to_domain()
+    }
+
+    async fn create_many(&self, users: &[User]) -> RepositoryResult<Vec<User>> {
+        if users.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let create_sql = create_users_sql(users, "@p", &user_columns("inserted"));
+
+        let mut statement = sqlx::query_as::<_, UserEntity>(&create_sql.sql);
+        for param in &create_sql.params {
+            statement = statement.bind(param);
+        }
+
+        let rows = statement
+            .fetch_all(&mut *self.primary().acquire().await?)
+            .await?;
+
+        rows.iter().map(UserEntity::to_domain).collect()
+    }
+
+    async fn find_existing_emails(&self, emails: &[String]) -> RepositoryResult<Vec<String>> {
+        if emails.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let existing_sql = &existing_emails_sql(emails, "@p");
+
+        self.replicas
+            .read(&self.source, |source| async move {
+                let mut statement = sqlx::query_scalar::<_, String>(&existing_sql.sql);
+                for param in &existing_sql.params {
+                    statement = statement.bind(param);
+                }
+
+                Ok(statement.fetch_all(&mut *source.acquire().await?).await?)
+            })
+            .await
     }
 
     async fn find_by_email(&self, email: &str) -> RepositoryResult<Option<User>> {
-        let row = sqlx::query_as::<_, UserEntity>(
-            r#"
-            SELECT
-                CAST(id AS NVARCHAR(36)) as id,
-                email,
-                password,
-                name,
-                FORMAT(created_at, 'yyyy-MM-ddTHH:mm:ss.ffffff') as created_at,
-                FORMAT(updated_at, 'yyyy-MM-ddTHH:mm:ss.ffffff') as updated_at
+        self.replicas
+            .read(&self.source, |source| async move {
+                let row = sqlx::query_as::<_, UserEntity>(&format!(
+                    r#"
+            SELECT {columns}
             FROM users
             WHERE email = @p1
+            "#,
+                    columns = user_columns("users"),
+                ))
+                .bind(email)
+                .fetch_optional(&mut *source.acquire().await?)
+                .await?;
+
+                row.map(|entity| entity.to_domain()).transpose()
+            })
+            .await
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<User>> {
+        self.replicas
+            .read(&self.source, |source| async move {
+                let row = sqlx::query_as::<_, UserEntity>(&format!(
+                    r#"
+            SELECT {columns}
+            FROM users
+            WHERE id = @p1
+            "#,
+                    columns = user_columns("users"),
+                ))
+                .bind(id)
+                .fetch_optional(&mut *source.acquire().await?)
+                .await?;
+
+                row.map(|entity| entity.to_domain()).transpose()
+            })
+            .await
+    }
+
+    async fn list(&self, query: &UserListQuery) -> RepositoryResult<Vec<User>> {
+        let list_sql = &list_users_sql(query, "@p", &user_columns("users"));
+
+        self.replicas
+            .read(&self.source, |source| async move {
+                let mut statement = sqlx::query_as::<_, UserEntity>(&list_sql.sql);
+                for param in &list_sql.params {
+                    statement = statement.bind(param);
+                }
+
+                let rows = statement.fetch_all(&mut *source.acquire().await?).await?;
+
+                rows.iter().map(UserEntity::to_domain).collect()
+            })
+            .await
+    }
+
+    async fn update_password(&self, id: &str, password: &str) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET password = @p1, updated_at = SYSUTCDATETIME()
+            WHERE id = @p2
             "#,
         )
-        .bind(email)
-        .fetch_optional(&self.pool)
+        .bind(password)
+        .bind(id)
+        .execute(&mut *self.primary().acquire().await?)
         .await?;
 
-        println!("row: {:?}", row);
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
 
-        match row {
-            Some(entity) => Ok(Some(entity.to_domain())),
-            None => Ok(None),
+    async fn update_profile(
+        &self,
+        user: &User,
+        expected_updated_at: DateTime<Utc>,
+    ) -> RepositoryResult<Option<User>> {
+        let row = sqlx::query_as::<_, UserEntity>(&format!(
+            r#"
+            UPDATE users
+            SET name = @p1, updated_at = SYSUTCDATETIME()
+            OUTPUT {columns}
+            WHERE id = @p2
+                AND updated_at = CAST(@p3 AS DATETIME2)
+            "#,
+            columns = user_columns("inserted"),
+        ))
+        .bind(user.name())
+        .bind(user.id().to_string())
+        .bind(format_datetime2(expected_updated_at))
+        .fetch_optional(&mut *self.primary().acquire().await?)
+        .await?;
+
+        row.map(|entity| entity.to_domain()).transpose()
+    }
+
+    async fn update_email(&self, user: &User, previous_email: &str) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET email = @p1, updated_at = SYSUTCDATETIME()
+            WHERE id = @p2
+                AND email = @p3
+                AND NOT EXISTS (
+                    SELECT 1 FROM users WITH (UPDLOCK, HOLDLOCK)
+                    WHERE email = @p1
+                )
+            "#,
+        )
+        .bind(user.email())
+        .bind(user.id().to_string())
+        .bind(previous_email)
+        .execute(&mut *self.primary().acquire().await?)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn update_avatar(&self, user: &User) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET avatar_key = @p1, updated_at = SYSUTCDATETIME()
+            WHERE id = @p2
+            "#,
+        )
+        .bind(user.avatar_key())
+        .bind(user.id().to_string())
+        .execute(&mut *self.primary().acquire().await?)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NoRowFound);
         }
+
+        Ok(())
     }
 
-    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<User>> {
-        let row = sqlx::query_as::<_, UserEntity>(
+    async fn update_status(&self, user: &User, previous: UserStatus) -> RepositoryResult<bool> {
+        let result = sqlx::query(
             r#"
-            SELECT
-                CAST(id AS NVARCHAR(36)) as id,
-                email,
-                password,
-                name,
-                FORMAT(created_at, 'yyyy-MM-ddTHH:mm:ss.ffffff') as created_at,
-                FORMAT(updated_at, 'yyyy-MM-ddTHH:mm:ss.ffffff') as updated_at
+            UPDATE users
+            SET status = @p1,
+                status_reason = @p2,
+                status_until = CAST(@p3 AS DATETIME2),
+                updated_at = SYSUTCDATETIME()
+            WHERE id = @p4 AND status = @p5
+            "#,
+        )
+        .bind(user.status().as_str())
+        .bind(user.status_reason())
+        .bind(user.status_until().map(format_datetime2))
+        .bind(user.id().to_string())
+        .bind(previous.as_str())
+        .execute(&mut *self.primary().acquire().await?)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_due_erasures(
+        &self,
+        due_before: DateTime<Utc>,
+        limit: u32,
+    ) -> RepositoryResult<Vec<User>> {
+        let query = format!(
+            r#"
+            SELECT TOP ({limit}) {columns}
             FROM users
-            WHERE id = @p1
+            WHERE status = 'pending_deletion'
+                AND status_until <= CAST(@p1 AS DATETIME2)
+            ORDER BY status_until
+            "#,
+            columns = user_columns("users"),
+        );
+
+        let rows = sqlx::query_as::<_, UserEntity>(&query)
+            .bind(format_datetime2(due_before))
+            .fetch_all(&mut *self.source.acquire().await?)
+            .await?;
+
+        rows.iter().map(UserEntity::to_domain).collect()
+    }
+
+    async fn erase(&self, user: &User) -> RepositoryResult<bool> {
+        let mut conn = self.primary().acquire().await?;
+        let mut tx = conn.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET email = @p1,
+                password = @p2,
+                name = @p3,
+                status = @p4,
+                status_reason = NULL,
+                status_until = NULL,
+                avatar_key = NULL,
+                updated_at = SYSUTCDATETIME()
+            WHERE id = @p5 AND status = 'pending_deletion'
             "#,
         )
-        .bind(id)
-        .fetch_optional(&self.pool)
+        .bind(user.email())
+        .bind(user.password())
+        .bind(user.name())
+        .bind(user.status().as_str())
+        .bind(user.id().to_string())
+        .execute(&mut tx)
         .await?;
 
-        match row {
-            Some(entity) => Ok(Some(entity.to_domain())),
-            None => Ok(None),
+        if result.rows_affected() == 0 {
+            return Ok(false);
         }
+
+        sqlx::query("DELETE FROM passkey_credentials WHERE user_id = @p1")
+            .bind(user.id().to_string())
+            .execute(&mut tx)
+            .await?;
+
+        sqlx::query("DELETE FROM user_attributes WHERE user_id = @p1")
+            .bind(user.id().to_string())
+            .execute(&mut tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
     }
 }
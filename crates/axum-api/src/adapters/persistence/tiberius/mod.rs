@@ -1 +1,3 @@
 pub mod repositories;
+pub mod row;
+pub mod transaction;
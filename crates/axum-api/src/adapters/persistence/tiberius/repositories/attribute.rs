@@ -0,0 +1,212 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::tiberius::row::{required, timestamp},
+    domain::{
+        entities::attribute::{AttributeSchema, UserAttributes},
+        repositories::{
+            attribute::AttributeRepository,
+            error::{RepositoryError, RepositoryResult},
+        },
+    },
+    infra::mssql_tiberius::TiberiusPool,
+};
+
+const SELECT_SCHEMA: &str = r#"
+    SELECT
+        namespace,
+        json_schema,
+        user_writable,
+        updated_at
+    FROM attribute_schemas
+"#;
+
+const SELECT_ATTRIBUTES: &str = r#"
+    SELECT
+        user_id,
+        namespace,
+        data,
+        updated_at
+    FROM user_attributes
+"#;
+
+#[derive(Clone)]
+pub struct TiberiusAttributeRepository {
+    pool: TiberiusPool,
+}
+
+impl TiberiusAttributeRepository {
+    pub fn new(pool: TiberiusPool) -> Self {
+        Self { pool }
+    }
+
+    fn map_schema(row: tiberius::Row) -> RepositoryResult<AttributeSchema> {
+        Ok(AttributeSchema {
+            namespace: required::<&str>(&row, "namespace")?.to_string(),
+            schema: serde_json::from_str(required(&row, "json_schema")?)?,
+            user_writable: required(&row, "user_writable")?,
+            updated_at: timestamp(&row, "updated_at")?,
+        })
+    }
+
+    fn map_attributes(row: tiberius::Row) -> RepositoryResult<UserAttributes> {
+        Ok(UserAttributes {
+            user_id: required(&row, "user_id")?,
+            namespace: required::<&str>(&row, "namespace")?.to_string(),
+            data: serde_json::from_str(required(&row, "data")?)?,
+            updated_at: timestamp(&row, "updated_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl AttributeRepository for TiberiusAttributeRepository {
+    async fn upsert_schema(
+        &self,
+        namespace: &str,
+        schema: &str,
+        user_writable: bool,
+    ) -> RepositoryResult<AttributeSchema> {
+        let mut conn = self.pool.get().await?;
+
+        let row = conn
+            .query(
+                r#"
+                MERGE attribute_schemas WITH (HOLDLOCK) AS target
+                USING (SELECT @P1 AS namespace) AS source
+                    ON target.namespace = source.namespace
+                WHEN MATCHED THEN
+                    UPDATE SET json_schema = @P2, user_writable = @P3, updated_at = SYSUTCDATETIME()
+                WHEN NOT MATCHED THEN
+                    INSERT (namespace, json_schema, user_writable) VALUES (@P1, @P2, @P3)
+                OUTPUT
+                    inserted.namespace,
+                    inserted.json_schema,
+                    inserted.user_writable,
+                    inserted.updated_at;
+                "#,
+                &[&namespace, &schema, &user_writable],
+            )
+            .await?
+            .into_row()
+            .await?
+            .ok_or(RepositoryError::NoRowFound)?;
+
+        Self::map_schema(row)
+    }
+
+    async fn find_schema(&self, namespace: &str) -> RepositoryResult<Option<AttributeSchema>> {
+        let mut conn = self.pool.get().await?;
+
+        let query = format!("{} WHERE namespace = @P1", SELECT_SCHEMA);
+        let row = conn.query(query, &[&namespace]).await?.into_row().await?;
+
+        row.map(Self::map_schema).transpose()
+    }
+
+    async fn list_schemas(&self) -> RepositoryResult<Vec<AttributeSchema>> {
+        let mut conn = self.pool.get().await?;
+
+        let query = format!("{} ORDER BY namespace", SELECT_SCHEMA);
+        let rows = conn.query(query, &[]).await?.into_first_result().await?;
+
+        rows.into_iter().map(Self::map_schema).collect()
+    }
+
+    async fn delete_schema(&self, namespace: &str) -> RepositoryResult<bool> {
+        let mut conn = self.pool.get().await?;
+
+        let result = conn
+            .execute(
+                "DELETE FROM attribute_schemas WHERE namespace = @P1",
+                &[&namespace],
+            )
+            .await?;
+
+        Ok(result.total() > 0)
+    }
+
+    async fn upsert(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+        data: &str,
+    ) -> RepositoryResult<UserAttributes> {
+        let mut conn = self.pool.get().await?;
+
+        let row = conn
+            .query(
+                r#"
+                MERGE user_attributes WITH (HOLDLOCK) AS target
+                USING (SELECT @P1 AS user_id, @P2 AS namespace) AS source
+                    ON target.user_id = source.user_id AND target.namespace = source.namespace
+                WHEN MATCHED THEN
+                    UPDATE SET data = @P3, updated_at = SYSUTCDATETIME()
+                WHEN NOT MATCHED THEN
+                    INSERT (user_id, namespace, data) VALUES (source.user_id, @P2, @P3)
+                OUTPUT
+                    inserted.user_id,
+                    inserted.namespace,
+                    inserted.data,
+                    inserted.updated_at;
+                "#,
+                &[&user_id, &namespace, &data],
+            )
+            .await?
+            .into_row()
+            .await?
+            .ok_or(RepositoryError::NoRowFound)?;
+
+        Self::map_attributes(row)
+    }
+
+    async fn find(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+    ) -> RepositoryResult<Option<UserAttributes>> {
+        let mut conn = self.pool.get().await?;
+
+        let query = format!(
+            "{} WHERE user_id = @P1 AND namespace = @P2",
+            SELECT_ATTRIBUTES
+        );
+        let row = conn
+            .query(query, &[&user_id, &namespace])
+            .await?
+            .into_row()
+            .await?;
+
+        row.map(Self::map_attributes).transpose()
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<UserAttributes>> {
+        let mut conn = self.pool.get().await?;
+
+        let query = format!(
+            "{} WHERE user_id = @P1 ORDER BY namespace",
+            SELECT_ATTRIBUTES
+        );
+        let rows = conn
+            .query(query, &[&user_id])
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.into_iter().map(Self::map_attributes).collect()
+    }
+
+    async fn delete(&self, user_id: Uuid, namespace: &str) -> RepositoryResult<bool> {
+        let mut conn = self.pool.get().await?;
+
+        let result = conn
+            .execute(
+                "DELETE FROM user_attributes WHERE user_id = @P1 AND namespace = @P2",
+                &[&user_id, &namespace],
+            )
+            .await?;
+
+        Ok(result.total() > 0)
+    }
+}
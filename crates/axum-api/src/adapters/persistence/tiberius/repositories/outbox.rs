@@ -0,0 +1,232 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::tiberius::{
+        row::{optional, optional_timestamp, required, timestamp},
+        transaction::TiberiusConnectionSource,
+    },
+    domain::{
+        entities::outbox::{OutboxBacklog, OutboxMessage},
+        repositories::{
+            error::{RepositoryError, RepositoryResult},
+            outbox::OutboxRepository,
+        },
+    },
+    infra::mssql_tiberius::TiberiusPool,
+};
+
+#[derive(Clone)]
+pub struct TiberiusOutboxRepository {
+    source: TiberiusConnectionSource,
+}
+
+impl TiberiusOutboxRepository {
+    pub fn new(pool: TiberiusPool) -> Self {
+        Self::from_source(TiberiusConnectionSource::Pool(pool))
+    }
+
+    pub fn from_source(source: TiberiusConnectionSource) -> Self {
+        Self { source }
+    }
+
+    fn map_row(row: tiberius::Row) -> RepositoryResult<OutboxMessage> {
+        Ok(OutboxMessage {
+            id: required(&row, "id")?,
+            aggregate_id: required(&row, "aggregate_id")?,
+            event_type: required::<&str>(&row, "event_type")?.to_string(),
+            payload: required::<&str>(&row, "payload")?.to_string(),
+            attempts: u32::try_from(required::<i32>(&row, "attempts")?).map_err(|e| {
+                RepositoryError::ConversionError(format!("Invalid attempts: {}", e))
+            })?,
+            last_error: optional::<&str>(&row, "last_error")?.map(str::to_string),
+            next_attempt_at: optional_timestamp(&row, "next_attempt_at")?,
+            created_at: timestamp(&row, "created_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for TiberiusOutboxRepository {
+    async fn enqueue(&self, message: &OutboxMessage) -> RepositoryResult<()> {
+        let mut conn = self.source.acquire().await?;
+
+        conn.execute(
+            r#"
+            INSERT INTO outbox (id, aggregate_id, event_type, payload, created_at)
+            VALUES (@P1, @P2, @P3, @P4, @P5);
+            "#,
+            &[
+                &message.id,
+                &message.aggregate_id,
+                &message.event_type.as_str(),
+                &message.payload.as_str(),
+                &message.created_at.naive_utc(),
+            ],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_pending(
+        &self,
+        now: DateTime<Utc>,
+        max_attempts: u32,
+        limit: u32,
+    ) -> RepositoryResult<Vec<OutboxMessage>> {
+        let mut conn = self.source.acquire().await?;
+        let max_attempts = i32::try_from(max_attempts).unwrap_or(i32::MAX);
+
+        let rows = conn
+            .query(
+                format!(
+                    r#"
+            SELECT TOP ({limit})
+                id, aggregate_id, event_type, payload, attempts, last_error, next_attempt_at,
+                created_at
+            FROM outbox AS message
+            WHERE dispatched_at IS NULL
+              AND attempts < @P2
+              AND (next_attempt_at IS NULL OR next_attempt_at <= @P1)
+              AND NOT EXISTS (
+                  SELECT 1
+                  FROM outbox AS earlier
+                  WHERE earlier.aggregate_id = message.aggregate_id
+                    AND earlier.sequence < message.sequence
+                    AND earlier.dispatched_at IS NULL
+                    AND earlier.attempts < @P2
+                    AND earlier.next_attempt_at > @P1
+              )
+            ORDER BY sequence
+            "#
+                ),
+                &[&now.naive_utc(), &max_attempts],
+            )
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.into_iter().map(Self::map_row).collect()
+    }
+
+    async fn mark_dispatched(&self, id: &Uuid) -> RepositoryResult<()> {
+        let mut conn = self.source.acquire().await?;
+
+        let result = conn
+            .execute(
+                r#"
+            UPDATE outbox
+            SET dispatched_at = SYSUTCDATETIME()
+            WHERE id = @P1
+            "#,
+                &[id],
+            )
+            .await?;
+
+        if result.total() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        id: &Uuid,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> RepositoryResult<()> {
+        let mut conn = self.source.acquire().await?;
+
+        let result = conn
+            .execute(
+                r#"
+            UPDATE outbox
+            SET attempts = attempts + 1, last_error = @P1, next_attempt_at = @P2
+            WHERE id = @P3
+            "#,
+                &[&error, &next_attempt_at.naive_utc(), id],
+            )
+            .await?;
+
+        if result.total() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn backlog(&self, max_attempts: u32) -> RepositoryResult<OutboxBacklog> {
+        let mut conn = self.source.acquire().await?;
+        let max_attempts = i32::try_from(max_attempts).unwrap_or(i32::MAX);
+
+        let row = conn
+            .query(
+                r#"
+            SELECT
+                COUNT_BIG(CASE WHEN attempts < @P1 THEN 1 END) AS pending,
+                COUNT_BIG(CASE WHEN attempts > 0 AND attempts < @P1 THEN 1 END) AS failing,
+                COUNT_BIG(CASE WHEN attempts >= @P1 THEN 1 END) AS dead_lettered,
+                MIN(CASE WHEN attempts < @P1 THEN created_at END) AS oldest_pending_at
+            FROM outbox
+            WHERE dispatched_at IS NULL
+            "#,
+                &[&max_attempts],
+            )
+            .await?
+            .into_row()
+            .await?
+            .ok_or(RepositoryError::NoRowFound)?;
+
+        Ok(OutboxBacklog {
+            pending: required::<i64>(&row, "pending")? as u64,
+            failing: required::<i64>(&row, "failing")? as u64,
+            dead_lettered: required::<i64>(&row, "dead_lettered")? as u64,
+            oldest_pending_at: optional_timestamp(&row, "oldest_pending_at")?,
+        })
+    }
+
+    async fn delete_dispatched(&self, before: DateTime<Utc>, limit: u32) -> RepositoryResult<u64> {
+        let mut conn = self.source.acquire().await?;
+
+        let result = conn
+            .execute(
+                format!(
+                    r#"
+            DELETE TOP ({limit}) FROM outbox
+            WHERE dispatched_at < @P1
+            "#
+                ),
+                &[&before.naive_utc()],
+            )
+            .await?;
+
+        Ok(result.total())
+    }
+
+    async fn acquire_relay_lease(&self, holder: &Uuid, ttl: Duration) -> RepositoryResult<bool> {
+        let mut conn = self.source.acquire().await?;
+
+        // `DATEADD` takes an `INT` count.
+        let ttl_millis = i32::try_from(ttl.as_millis()).unwrap_or(i32::MAX);
+
+        let result = conn
+            .execute(
+                r#"
+            UPDATE outbox_relay_lease
+            SET holder = @P1,
+                expires_at = DATEADD(MILLISECOND, @P2, SYSUTCDATETIME())
+            WHERE id = 1
+              AND (holder = @P1 OR expires_at IS NULL OR expires_at <= SYSUTCDATETIME())
+            "#,
+                &[holder, &ttl_millis],
+            )
+            .await?;
+
+        Ok(result.total() > 0)
+    }
+}
@@ -0,0 +1,182 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    adapters::persistence::{
+        codec::{decode_bytes, encode_bytes, join_transports, split_transports},
+        tiberius::row::{optional_timestamp, required, timestamp},
+    },
+    domain::{
+        entities::passkey::Passkey,
+        repositories::{
+            error::{RepositoryError, RepositoryResult},
+            passkey::PasskeyRepository,
+            second_factor::SecondFactorRepository,
+        },
+    },
+    infra::mssql_tiberius::TiberiusPool,
+};
+
+const SELECT_PASSKEY: &str = r#"
+    SELECT
+        id,
+        user_id,
+        credential_id,
+        public_key,
+        sign_count,
+        transports,
+        name,
+        created_at,
+        last_used_at
+    FROM passkey_credentials
+"#;
+
+#[derive(Clone)]
+pub struct TiberiusPasskeyRepository {
+    pool: TiberiusPool,
+}
+
+impl TiberiusPasskeyRepository {
+    pub fn new(pool: TiberiusPool) -> Self {
+        Self { pool }
+    }
+
+    fn map_row(row: tiberius::Row) -> RepositoryResult<Passkey> {
+        let sign_count: i64 = required(&row, "sign_count")?;
+
+        Ok(Passkey {
+            id: required(&row, "id")?,
+            user_id: required(&row, "user_id")?,
+            credential_id: decode_bytes("credential_id", required(&row, "credential_id")?)?,
+            public_key: decode_bytes("public_key", required(&row, "public_key")?)?,
+            sign_count: u32::try_from(sign_count).map_err(|e| {
+                RepositoryError::ConversionError(format!("Invalid sign_count: {}", e))
+            })?,
+            transports: split_transports(required(&row, "transports")?),
+            name: required::<&str>(&row, "name")?.to_string(),
+            created_at: timestamp(&row, "created_at")?,
+            last_used_at: optional_timestamp(&row, "last_used_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl PasskeyRepository for TiberiusPasskeyRepository {
+    async fn create(&self, passkey: &Passkey) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().await?;
+
+        conn.execute(
+            r#"
+            INSERT INTO passkey_credentials
+                (id, user_id, credential_id, public_key, sign_count, transports, name)
+            VALUES (@P1, @P2, @P3, @P4, @P5, @P6, @P7);
+            "#,
+            &[
+                &passkey.id,
+                &passkey.user_id,
+                &encode_bytes(&passkey.credential_id),
+                &encode_bytes(&passkey.public_key),
+                &i64::from(passkey.sign_count),
+                &join_transports(&passkey.transports),
+                &passkey.name.as_str(),
+            ],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_credential_id(
+        &self,
+        credential_id: &[u8],
+    ) -> RepositoryResult<Option<Passkey>> {
+        let mut conn = self.pool.get().await?;
+
+        let query = format!(
+            "{} WHERE credential_id = CAST(@P1 AS VARCHAR(1400))",
+            SELECT_PASSKEY
+        );
+        let row = conn
+            .query(query, &[&encode_bytes(credential_id)])
+            .await?
+            .into_row()
+            .await?;
+
+        row.map(Self::map_row).transpose()
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<Passkey>> {
+        let mut conn = self.pool.get().await?;
+
+        let query = format!("{} WHERE user_id = @P1 ORDER BY created_at", SELECT_PASSKEY);
+        let rows = conn
+            .query(query, &[&user_id])
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.into_iter().map(Self::map_row).collect()
+    }
+
+    async fn update_sign_count(&self, id: Uuid, sign_count: u32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().await?;
+
+        let result = conn
+            .execute(
+                r#"
+            UPDATE passkey_credentials
+            SET sign_count = @P1, last_used_at = SYSUTCDATETIME()
+            WHERE id = @P2
+            "#,
+                &[&i64::from(sign_count), &id],
+            )
+            .await?;
+
+        if result.total() == 0 {
+            return Err(RepositoryError::NoRowFound);
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> RepositoryResult<bool> {
+        let mut conn = self.pool.get().await?;
+
+        let result = conn
+            .execute(
+                r#"
+            DELETE FROM passkey_credentials
+            WHERE id = @P1 AND user_id = @P2
+            "#,
+                &[&id, &user_id],
+            )
+            .await?;
+
+        Ok(result.total() > 0)
+    }
+}
+
+#[async_trait]
+impl SecondFactorRepository for TiberiusPasskeyRepository {
+    async fn has_second_factor(&self, user_id: Uuid) -> RepositoryResult<bool> {
+        let mut conn = self.pool.get().await?;
+
+        let row = conn
+            .query(
+                r#"
+            SELECT COUNT(1) as total
+            FROM passkey_credentials
+            WHERE user_id = @P1
+            "#,
+                &[&user_id],
+            )
+            .await?
+            .into_row()
+            .await?
+            .ok_or(RepositoryError::NoRowFound)?;
+
+        let total: i32 = row.get("total").unwrap_or_default();
+
+        Ok(total > 0)
+    }
+}
@@ -1,13 +1,22 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
-use chrono::NaiveDateTime;
-use uuid::Uuid;
+use chrono::{DateTime, Utc};
 
 use crate::{
+    adapters::persistence::{
+        replica::{ReplicaSet, record_write},
+        tiberius::{
+            row::{optional, optional_timestamp, required, timestamp},
+            transaction::TiberiusConnectionSource,
+        },
+        user_query::{create_users_sql, existing_emails_sql, list_users_sql},
+    },
     domain::{
-        entities::user::User,
+        entities::user::{User, UserStatus},
         repositories::{
             error::{RepositoryError, RepositoryResult},
-            user::UserRepository,
+            user::{UserListQuery, UserRepository},
         },
     },
     infra::mssql_tiberius::TiberiusPool,
@@ -15,79 +24,88 @@ use crate::{
 
 #[derive(Clone)]
 pub struct TiberiusUserRepository {
-    pool: TiberiusPool,
+    source: TiberiusConnectionSource,
+    replicas: Arc<ReplicaSet<TiberiusConnectionSource>>,
 }
 
 impl TiberiusUserRepository {
     pub fn new(pool: TiberiusPool) -> Self {
-        Self { pool }
+        Self::from_source(TiberiusConnectionSource::Pool(pool))
     }
 
-    fn map_row(row: tiberius::Row) -> RepositoryResult<User> {
-        let id_str: &str = row
-            .get("id")
-            .ok_or_else(|| RepositoryError::ConversionError("Missing id column".to_string()))?;
-
-        let email: &str = row
-            .get("email")
-            .ok_or_else(|| RepositoryError::ConversionError("Missing email column".to_string()))?;
-
-        let password: &str = row.get("password").ok_or_else(|| {
-            RepositoryError::ConversionError("Missing password column".to_string())
-        })?;
-
-        let name: &str = row
-            .get("name")
-            .ok_or_else(|| RepositoryError::ConversionError("Missing name column".to_string()))?;
-
-        let created_at_str: &str = row.get("created_at").ok_or_else(|| {
-            RepositoryError::ConversionError("Missing created_at column".to_string())
-        })?;
-
-        let updated_at_str: &str = row.get("updated_at").ok_or_else(|| {
-            RepositoryError::ConversionError("Missing updated_at column".to_string())
-        })?;
+    pub fn from_source(source: TiberiusConnectionSource) -> Self {
+        Self {
+            source,
+            replicas: Arc::default(),
+        }
+    }
 
-        let id = Uuid::parse_str(id_str).map_err(|_| RepositoryError::InvalidUuidFormat)?;
+    /// Sends lookups and listings to `replicas` where the request allows it.
+    pub fn with_read_replicas(
+        mut self,
+        replicas: Arc<ReplicaSet<TiberiusConnectionSource>>,
+    ) -> Self {
+        self.replicas = replicas;
+        self
+    }
 
-        let created_at = NaiveDateTime::parse_from_str(created_at_str, "%Y-%m-%dT%H:%M:%S.%f")
-            .map_err(|e| RepositoryError::ConversionError(format!("Invalid created_at: {}", e)))?
-            .and_utc();
+    fn primary(&self) -> &TiberiusConnectionSource {
+        record_write();
+        &self.source
+    }
 
-        let updated_at = NaiveDateTime::parse_from_str(updated_at_str, "%Y-%m-%dT%H:%M:%S.%f")
-            .map_err(|e| RepositoryError::ConversionError(format!("Invalid updated_at: {}", e)))?
-            .and_utc();
+    fn map_row(row: tiberius::Row) -> RepositoryResult<User> {
+        let role: &str = required(&row, "role")?;
+        let status: &str = required(&row, "status")?;
 
         Ok(User::from_db(
-            id,
-            email.to_string(),
-            password.to_string(),
-            name.to_string(),
-            created_at,
-            updated_at,
+            required(&row, "id")?,
+            required::<&str>(&row, "email")?.to_string(),
+            required::<&str>(&row, "password")?.to_string(),
+            required::<&str>(&row, "name")?.to_string(),
+            role.parse().map_err(RepositoryError::ConversionError)?,
+            status.parse().map_err(RepositoryError::ConversionError)?,
+            optional::<&str>(&row, "status_reason")?.map(str::to_string),
+            optional_timestamp(&row, "status_until")?,
+            optional::<&str>(&row, "avatar_key")?.map(str::to_string),
+            timestamp(&row, "created_at")?,
+            timestamp(&row, "updated_at")?,
         ))
     }
 }
 
+/// Columns read by `map_row`, qualified by `source` (`users` or `inserted`).
+fn user_columns(source: &str) -> String {
+    format!(
+        "{source}.id, {source}.email, {source}.password, {source}.name, {source}.role, \
+         {source}.status, {source}.status_reason, {source}.status_until, {source}.avatar_key, \
+         {source}.created_at, {source}.updated_at"
+    )
+}
+
 #[async_trait]
 impl UserRepository for TiberiusUserRepository {
     async fn create(&self, user: &User) -> RepositoryResult<User> {
-        let mut conn = self.pool.get().await?;
+        let mut conn = self.primary().acquire().await?;
 
         let row = conn
             .query(
-                r#"
-            INSERT INTO users (email, password, name)
-            OUTPUT
-                CAST(inserted.id AS NVARCHAR(36)) as id,
-                inserted.email,
-                inserted.password,
-                inserted.name,
-                FORMAT(inserted.created_at, 'yyyy-MM-ddTHH:mm:ss.ffffff') as created_at,
-                FORMAT(inserted.updated_at, 'yyyy-MM-ddTHH:mm:ss.ffffff') as updated_at
-            VALUES (@P1, @P2, @P3);
+                format!(
+                    r#"
+            INSERT INTO users (id, email, password, name, role, status)
+            OUTPUT {columns}
+            VALUES (@P1, @P2, @P3, @P4, @P5, @P6);
             "#,
-                &[&user.email(), &user.password(), &user.name()],
+                    columns = user_columns("inserted"),
+                ),
+                &[
+                    user.id(),
+                    &user.email(),
+                    &user.password(),
+                    &user.name(),
+                    &user.role().as_str(),
+                    &user.status().as_str(),
+                ],
             )
             .await?;
         let result = row.into_row().await?.ok_or(RepositoryError::NoRowFound)?;
@@ -95,59 +113,320 @@ impl UserRepository for TiberiusUserRepository {
         Self::map_row(result)
     }
 
+    async fn create_many(&self, users: &[User]) -> RepositoryResult<Vec<User>> {
+        if users.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.primary().acquire().await?;
+
+        let create_sql = create_users_sql(users, "@P", &user_columns("inserted"));
+        let params: Vec<&dyn tiberius::ToSql> = create_sql
+            .params
+            .iter()
+            .map(|param| param as &dyn tiberius::ToSql)
+            .collect();
+
+        let rows = conn
+            .query(create_sql.sql, &params)
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.into_iter().map(Self::map_row).collect()
+    }
+
+    async fn find_existing_emails(&self, emails: &[String]) -> RepositoryResult<Vec<String>> {
+        if emails.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let existing_sql = &existing_emails_sql(emails, "@P");
+
+        self.replicas
+            .read(&self.source, |source| async move {
+                let mut conn = source.acquire().await?;
+
+                let params: Vec<&dyn tiberius::ToSql> = existing_sql
+                    .params
+                    .iter()
+                    .map(|param| param as &dyn tiberius::ToSql)
+                    .collect();
+
+                let rows = conn
+                    .query(existing_sql.sql.as_str(), &params)
+                    .await?
+                    .into_first_result()
+                    .await?;
+
+                Ok(rows
+                    .iter()
+                    .filter_map(|row| row.get::<&str, _>("email").map(str::to_string))
+                    .collect())
+            })
+            .await
+    }
+
     async fn find_by_email(&self, email: &str) -> RepositoryResult<Option<User>> {
-        let mut conn = self.pool.get().await?;
+        self.replicas
+            .read(&self.source, |source| async move {
+                let mut conn = source.acquire().await?;
 
-        let row = conn
-            .query(
-                r#"
-            SELECT
-                CAST(id AS NVARCHAR(36)) as id,
-                email,
-                password,
-                name,
-                FORMAT(created_at, 'yyyy-MM-ddTHH:mm:ss.ffffff') as created_at,
-                FORMAT(updated_at, 'yyyy-MM-ddTHH:mm:ss.ffffff') as updated_at
+                let row = conn
+                    .query(
+                        format!(
+                            r#"
+            SELECT {columns}
             FROM users
             WHERE email = @P1
             "#,
-                &[&email],
+                            columns = user_columns("users"),
+                        ),
+                        &[&email],
+                    )
+                    .await?
+                    .into_row()
+                    .await?;
+
+                row.map(Self::map_row).transpose()
+            })
+            .await
+    }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<User>> {
+        self.replicas
+            .read(&self.source, |source| async move {
+                let mut conn = source.acquire().await?;
+
+                let row = conn
+                    .query(
+                        format!(
+                            r#"
+            SELECT {columns}
+            FROM users
+            WHERE id = @P1
+            "#,
+                            columns = user_columns("users"),
+                        ),
+                        &[&id],
+                    )
+                    .await?
+                    .into_row()
+                    .await?;
+
+                row.map(Self::map_row).transpose()
+            })
+            .await
+    }
+
+    async fn list(&self, query: &UserListQuery) -> RepositoryResult<Vec<User>> {
+        let list_sql = &list_users_sql(query, "@P", &user_columns("users"));
+
+        self.replicas
+            .read(&self.source, |source| async move {
+                let mut conn = source.acquire().await?;
+
+                let params: Vec<&dyn tiberius::ToSql> = list_sql
+                    .params
+                    .iter()
+                    .map(|param| param as &dyn tiberius::ToSql)
+                    .collect();
+
+                let rows = conn
+                    .query(list_sql.sql.as_str(), &params)
+                    .await?
+                    .into_first_result()
+                    .await?;
+
+                rows.into_iter().map(Self::map_row).collect()
+            })
+            .await
+    }
+
+    async fn update_password(&self, id: &str, password: &str) -> RepositoryResult<()> {
+        let mut conn = self.primary().acquire().await?;
+
+        let result = conn
+            .execute(
+                r#"
+            UPDATE users
+            SET password = @P1, updated_at = SYSUTCDATETIME()
+            WHERE id = @P2
+            "#,
+                &[&password, &id],
             )
             .await?;
 
-        let row = row.into_row().await?;
-
-        match row {
-            Some(row) => Ok(Some(Self::map_row(row)?)),
-            None => Ok(None),
+        if result.total() == 0 {
+            return Err(RepositoryError::NoRowFound);
         }
+
+        Ok(())
     }
 
-    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<User>> {
-        let mut conn = self.pool.get().await?;
+    async fn update_profile(
+        &self,
+        user: &User,
+        expected_updated_at: DateTime<Utc>,
+    ) -> RepositoryResult<Option<User>> {
+        let mut conn = self.primary().acquire().await?;
 
         let row = conn
             .query(
+                format!(
+                    r#"
+            UPDATE users
+            SET name = @P1, updated_at = SYSUTCDATETIME()
+            OUTPUT {columns}
+            WHERE id = @P2 AND updated_at = @P3
+            "#,
+                    columns = user_columns("inserted"),
+                ),
+                &[&user.name(), user.id(), &expected_updated_at.naive_utc()],
+            )
+            .await?
+            .into_row()
+            .await?;
+
+        row.map(Self::map_row).transpose()
+    }
+
+    async fn update_email(&self, user: &User, previous_email: &str) -> RepositoryResult<bool> {
+        let mut conn = self.primary().acquire().await?;
+
+        let result = conn
+            .execute(
                 r#"
-            SELECT
-                CAST(id AS NVARCHAR(36)) as id,
-                email,
-                password,
-                name,
-                FORMAT(created_at, 'yyyy-MM-ddTHH:mm:ss.ffffff') as created_at,
-                FORMAT(updated_at, 'yyyy-MM-ddTHH:mm:ss.ffffff') as updated_at
-            FROM users
-            WHERE id = @P1
+            UPDATE users
+            SET email = @P1, updated_at = SYSUTCDATETIME()
+            WHERE id = @P2
+                AND email = @P3
+                AND NOT EXISTS (
+                    SELECT 1 FROM users WITH (UPDLOCK, HOLDLOCK)
+                    WHERE email = @P1
+                )
             "#,
-                &[&id],
+                &[&user.email(), user.id(), &previous_email],
             )
             .await?;
 
-        let row = row.into_row().await?;
+        Ok(result.total() > 0)
+    }
 
-        match row {
-            Some(row) => Ok(Some(Self::map_row(row)?)),
-            None => Ok(None),
+    async fn update_avatar(&self, user: &User) -> RepositoryResult<()> {
+        let mut conn = self.primary().acquire().await?;
+
+        let result = conn
+            .execute(
+                r#"
+            UPDATE users
+            SET avatar_key = @P1, updated_at = SYSUTCDATETIME()
+            WHERE id = @P2
+            "#,
+                &[&user.avatar_key(), user.id()],
+            )
+            .await?;
+
+        if result.total() == 0 {
+            return Err(RepositoryError::NoRowFound);
         }
+
+        Ok(())
+    }
+
+    async fn update_status(&self, user: &User, previous: UserStatus) -> RepositoryResult<bool> {
+        let mut conn = self.primary().acquire().await?;
+
+        let status_until = user.status_until().map(|until| until.naive_utc());
+
+        let result = conn
+            .execute(
+                r#"
+            UPDATE users
+            SET status = @P1,
+                status_reason = @P2,
+                status_until = @P3,
+                updated_at = SYSUTCDATETIME()
+            WHERE id = @P4 AND status = @P5
+            "#,
+                &[
+                    &user.status().as_str(),
+                    &user.status_reason(),
+                    &status_until,
+                    user.id(),
+                    &previous.as_str(),
+                ],
+            )
+            .await?;
+
+        Ok(result.total() > 0)
+    }
+
+    async fn list_due_erasures(
+        &self,
+        due_before: DateTime<Utc>,
+        limit: u32,
+    ) -> RepositoryResult<Vec<User>> {
+        let mut conn = self.source.acquire().await?;
+
+        let query = format!(
+            r#"
+            SELECT TOP ({limit}) {columns}
+            FROM users
+            WHERE status = 'pending_deletion'
+                AND status_until <= @P1
+            ORDER BY status_until
+            "#,
+            columns = user_columns("users"),
+        );
+
+        let rows = conn
+            .query(query, &[&due_before.naive_utc()])
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.into_iter().map(Self::map_row).collect()
+    }
+
+    async fn erase(&self, user: &User) -> RepositoryResult<bool> {
+        let mut conn = self.primary().acquire().await?;
+
+        let result = conn
+            .execute(
+                r#"
+            SET XACT_ABORT ON;
+            BEGIN TRANSACTION;
+
+            UPDATE users
+            SET email = @P1,
+                password = @P2,
+                name = @P3,
+                status = @P4,
+                status_reason = NULL,
+                status_until = NULL,
+                avatar_key = NULL,
+                updated_at = SYSUTCDATETIME()
+            WHERE id = @P5 AND status = 'pending_deletion';
+
+            IF @@ROWCOUNT > 0
+            BEGIN
+                DELETE FROM passkey_credentials WHERE user_id = @P5;
+                DELETE FROM user_attributes WHERE user_id = @P5;
+            END
+
+            COMMIT TRANSACTION;
+            "#,
+                &[
+                    &user.email(),
+                    &user.password(),
+                    &user.name(),
+                    &user.status().as_str(),
+                    user.id(),
+                ],
+            )
+            .await?;
+
+        Ok(result.rows_affected().first().copied().unwrap_or_default() > 0)
     }
 }
@@ -0,0 +1,23 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use tiberius::{FromSql, Row};
+
+use crate::domain::repositories::error::{RepositoryError, RepositoryResult};
+
+/// Reads a nullable column, reporting type mismatches instead of panicking like `Row::get`.
+pub fn optional<'a, R: FromSql<'a>>(row: &'a Row, column: &str) -> RepositoryResult<Option<R>> {
+    row.try_get(column)
+        .map_err(|e| RepositoryError::ConversionError(format!("Invalid {}: {}", column, e)))
+}
+
+pub fn required<'a, R: FromSql<'a>>(row: &'a Row, column: &str) -> RepositoryResult<R> {
+    optional(row, column)?
+        .ok_or_else(|| RepositoryError::ConversionError(format!("Missing {} column", column)))
+}
+
+pub fn timestamp(row: &Row, column: &str) -> RepositoryResult<DateTime<Utc>> {
+    required::<NaiveDateTime>(row, column).map(|value| value.and_utc())
+}
+
+pub fn optional_timestamp(row: &Row, column: &str) -> RepositoryResult<Option<DateTime<Utc>>> {
+    optional::<NaiveDateTime>(row, column).map(|value| value.map(|value| value.and_utc()))
+}
@@ -0,0 +1,167 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use bb8::PooledConnection;
+use tokio::sync::{MappedMutexGuard, Mutex, MutexGuard};
+
+use crate::{
+    adapters::persistence::tiberius::repositories::{
+        outbox::TiberiusOutboxRepository, user::TiberiusUserRepository,
+    },
+    domain::repositories::{
+        error::{RepositoryError, RepositoryResult},
+        outbox::OutboxRepository,
+        unit_of_work::{UnitOfWork, UnitOfWorkFactory},
+        user::UserRepository,
+    },
+    infra::mssql_tiberius::{TiberiusClient, TiberiusConnectionManager, TiberiusPool},
+};
+
+type OwnedConnection = PooledConnection<'static, TiberiusConnectionManager>;
+
+type SharedConnection = Arc<Mutex<Option<OwnedConnection>>>;
+
+/// Where a tiberius repository runs its statements: a fresh pooled connection each time,
+/// or the connection holding the transaction of the unit of work it was handed out by.
+#[derive(Clone)]
+pub enum TiberiusConnectionSource {
+    Pool(TiberiusPool),
+    Transaction(SharedConnection),
+}
+
+impl TiberiusConnectionSource {
+    pub async fn acquire(&self) -> RepositoryResult<TiberiusConnection<'_>> {
+        match self {
+            Self::Pool(pool) => Ok(TiberiusConnection::Pooled(Box::new(pool.get().await?))),
+            Self::Transaction(connection) => {
+                MutexGuard::try_map(connection.lock().await, Option::as_mut)
+                    .map(TiberiusConnection::Transaction)
+                    .map_err(|_| finished())
+            }
+        }
+    }
+}
+
+pub enum TiberiusConnection<'a> {
+    Pooled(Box<PooledConnection<'a, TiberiusConnectionManager>>),
+    Transaction(MappedMutexGuard<'a, OwnedConnection>),
+}
+
+impl Deref for TiberiusConnection<'_> {
+    type Target = TiberiusClient;
+
+    fn deref(&self) -> &TiberiusClient {
+        match self {
+            Self::Pooled(connection) => connection,
+            Self::Transaction(connection) => connection,
+        }
+    }
+}
+
+impl DerefMut for TiberiusConnection<'_> {
+    fn deref_mut(&mut self) -> &mut TiberiusClient {
+        match self {
+            Self::Pooled(connection) => connection,
+            Self::Transaction(connection) => connection,
+        }
+    }
+}
+
+fn finished() -> RepositoryError {
+    RepositoryError::StorageFailure("Transaction already finished".to_string())
+}
+
+async fn run(client: &mut TiberiusClient, statement: &str) -> RepositoryResult<()> {
+    client.simple_query(statement).await?.into_results().await?;
+
+    Ok(())
+}
+
+/// `@@TRANCOUNT` guards against transactions the server already aborted, e.g. under
+/// `XACT_ABORT`.
+async fn rollback(client: &mut TiberiusClient) -> RepositoryResult<()> {
+    run(client, "IF @@TRANCOUNT > 0 ROLLBACK TRANSACTION").await
+}
+
+#[derive(Clone)]
+pub struct TiberiusUnitOfWorkFactory {
+    pool: TiberiusPool,
+}
+
+impl TiberiusUnitOfWorkFactory {
+    pub fn new(pool: TiberiusPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkFactory for TiberiusUnitOfWorkFactory {
+    async fn begin(&self) -> RepositoryResult<Box<dyn UnitOfWork>> {
+        let mut connection = self.pool.get_owned().await?;
+        run(&mut connection, "BEGIN TRANSACTION").await?;
+        connection.set_broken(true);
+
+        let connection = Arc::new(Mutex::new(Some(connection)));
+        let source = TiberiusConnectionSource::Transaction(connection.clone());
+
+        Ok(Box::new(TiberiusUnitOfWork {
+            users: TiberiusUserRepository::from_source(source.clone()),
+            outbox: TiberiusOutboxRepository::from_source(source),
+            connection,
+        }))
+    }
+}
+
+/// Holds one pooled connection for the whole transaction. Tiberius has no transaction
+/// guard, so the connection stays marked broken until the transaction ends cleanly: one
+/// dropped uncommitted, or whose rollback failed, is closed by the pool, and the server
+/// rolls back when the session ends, instead of being handed out with `@@TRANCOUNT > 0`.
+pub struct TiberiusUnitOfWork {
+    connection: SharedConnection,
+    users: TiberiusUserRepository,
+    outbox: TiberiusOutboxRepository,
+}
+
+impl TiberiusUnitOfWork {
+    async fn take(&self) -> RepositoryResult<OwnedConnection> {
+        self.connection.lock().await.take().ok_or_else(finished)
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for TiberiusUnitOfWork {
+    fn users(&self) -> &dyn UserRepository {
+        &self.users
+    }
+
+    fn outbox(&self) -> &dyn OutboxRepository {
+        &self.outbox
+    }
+
+    async fn commit(self: Box<Self>) -> RepositoryResult<()> {
+        let mut connection = self.take().await?;
+
+        if let Err(e) = run(&mut connection, "COMMIT TRANSACTION").await {
+            if rollback(&mut connection).await.is_ok() {
+                connection.set_broken(false);
+            }
+            return Err(e);
+        }
+
+        connection.set_broken(false);
+
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> RepositoryResult<()> {
+        let mut connection = self.take().await?;
+
+        rollback(&mut connection).await?;
+        connection.set_broken(false);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use sqlx::{Database, Pool, Transaction, pool::PoolConnection};
+use tokio::sync::{MappedMutexGuard, Mutex, MutexGuard};
+
+use crate::domain::repositories::{
+    error::{RepositoryError, RepositoryResult},
+    outbox::OutboxRepository,
+    unit_of_work::{UnitOfWork, UnitOfWorkFactory},
+    user::UserRepository,
+};
+
+type SharedTransaction<DB> = Arc<Mutex<Option<Transaction<'static, DB>>>>;
+
+/// Where a sqlx repository runs its statements: a fresh pooled connection each time, or
+/// the transaction of the unit of work it was handed out by.
+pub enum SqlxConnectionSource<DB: Database> {
+    Pool(Pool<DB>),
+    Transaction(SharedTransaction<DB>),
+}
+
+impl<DB: Database> Clone for SqlxConnectionSource<DB> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Pool(pool) => Self::Pool(pool.clone()),
+            Self::Transaction(transaction) => Self::Transaction(transaction.clone()),
+        }
+    }
+}
+
+impl<DB: Database> SqlxConnectionSource<DB> {
+    pub async fn acquire(&self) -> RepositoryResult<SqlxConnection<'_, DB>> {
+        match self {
+            Self::Pool(pool) => Ok(SqlxConnection::Pooled(pool.acquire().await?)),
+            Self::Transaction(transaction) => {
+                MutexGuard::try_map(transaction.lock().await, Option::as_mut)
+                    .map(SqlxConnection::Transaction)
+                    .map_err(|_| finished())
+            }
+        }
+    }
+}
+
+pub enum SqlxConnection<'a, DB: Database> {
+    Pooled(PoolConnection<DB>),
+    Transaction(MappedMutexGuard<'a, Transaction<'static, DB>>),
+}
+
+impl<DB: Database> Deref for SqlxConnection<'_, DB> {
+    type Target = DB::Connection;
+
+    fn deref(&self) -> &DB::Connection {
+        match self {
+            Self::Pooled(connection) => connection,
+            Self::Transaction(transaction) => transaction,
+        }
+    }
+}
+
+impl<DB: Database> DerefMut for SqlxConnection<'_, DB> {
+    fn deref_mut(&mut self) -> &mut DB::Connection {
+        match self {
+            Self::Pooled(connection) => connection,
+            Self::Transaction(transaction) => transaction,
+        }
+    }
+}
+
+fn finished() -> RepositoryError {
+    RepositoryError::StorageFailure("Transaction already finished".to_string())
+}
+
+pub type UserRepositoryFactory<DB> = fn(SqlxConnectionSource<DB>) -> Box<dyn UserRepository>;
+
+pub type OutboxRepositoryFactory<DB> = fn(SqlxConnectionSource<DB>) -> Box<dyn OutboxRepository>;
+
+pub struct SqlxUnitOfWorkFactory<DB: Database> {
+    pool: Pool<DB>,
+    users: UserRepositoryFactory<DB>,
+    outbox: OutboxRepositoryFactory<DB>,
+}
+
+impl<DB: Database> SqlxUnitOfWorkFactory<DB> {
+    pub fn new(
+        pool: Pool<DB>,
+        users: UserRepositoryFactory<DB>,
+        outbox: OutboxRepositoryFactory<DB>,
+    ) -> Self {
+        Self {
+            pool,
+            users,
+            outbox,
+        }
+    }
+}
+
+#[async_trait]
+impl<DB: Database> UnitOfWorkFactory for SqlxUnitOfWorkFactory<DB> {
+    async fn begin(&self) -> RepositoryResult<Box<dyn UnitOfWork>> {
+        let transaction = Arc::new(Mutex::new(Some(self.pool.begin().await?)));
+
+        Ok(Box::new(SqlxUnitOfWork {
+            users: (self.users)(SqlxConnectionSource::Transaction(transaction.clone())),
+            outbox: (self.outbox)(SqlxConnectionSource::Transaction(transaction.clone())),
+            transaction,
+        }))
+    }
+}
+
+/// Relies on `sqlx::Transaction` rolling back when it is dropped uncommitted.
+pub struct SqlxUnitOfWork<DB: Database> {
+    transaction: SharedTransaction<DB>,
+    users: Box<dyn UserRepository>,
+    outbox: Box<dyn OutboxRepository>,
+}
+
+impl<DB: Database> SqlxUnitOfWork<DB> {
+    async fn take(&self) -> RepositoryResult<Transaction<'static, DB>> {
+        self.transaction.lock().await.take().ok_or_else(finished)
+    }
+}
+
+#[async_trait]
+impl<DB: Database> UnitOfWork for SqlxUnitOfWork<DB> {
+    fn users(&self) -> &dyn UserRepository {
+        self.users.as_ref()
+    }
+
+    fn outbox(&self) -> &dyn OutboxRepository {
+        self.outbox.as_ref()
+    }
+
+    async fn commit(self: Box<Self>) -> RepositoryResult<()> {
+        Ok(self.take().await?.commit().await?)
+    }
+
+    async fn rollback(self: Box<Self>) -> RepositoryResult<()> {
+        Ok(self.take().await?.rollback().await?)
+    }
+}
@@ -0,0 +1,169 @@
+use chrono::{DateTime, Timelike, Utc};
+
+use crate::domain::{
+    entities::user::User,
+    repositories::user::{SortDirection, UserListQuery},
+};
+
+pub struct UserSql {
+    pub sql: String,
+    pub params: Vec<String>,
+}
+
+pub fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+        .replace('[', "\\[")
+}
+
+/// DATETIME2 literal at the column's full 100ns precision.
+pub fn format_datetime2(value: DateTime<Utc>) -> String {
+    format!(
+        "{}.{:07}",
+        value.format("%Y-%m-%dT%H:%M:%S"),
+        value.nanosecond() / 100
+    )
+}
+
+pub fn list_users_sql(query: &UserListQuery, placeholder: &str, columns: &str) -> UserSql {
+    let mut clauses = Vec::new();
+    let mut params = Vec::new();
+
+    let bind = |params: &mut Vec<String>, value: String| {
+        params.push(value);
+        format!("{placeholder}{}", params.len())
+    };
+
+    let filter = &query.filter;
+
+    if let Some(email_prefix) = &filter.email_prefix {
+        let param = bind(&mut params, format!("{}%", escape_like(email_prefix)));
+        clauses.push(format!("email LIKE {param} ESCAPE '\\'"));
+    }
+
+    if let Some(name) = &filter.name {
+        let param = bind(&mut params, format!("%{}%", escape_like(name)));
+        clauses.push(format!("name LIKE {param} ESCAPE '\\'"));
+    }
+
+    if let Some(status) = filter.status {
+        let param = bind(&mut params, status.as_str().to_string());
+        clauses.push(format!("status = {param}"));
+    }
+
+    if let Some(role) = filter.role {
+        let param = bind(&mut params, role.as_str().to_string());
+        clauses.push(format!("role = {param}"));
+    }
+
+    if let Some(created_from) = filter.created_from {
+        let param = bind(&mut params, format_datetime2(created_from));
+        clauses.push(format!("created_at >= CAST({param} AS DATETIME2)"));
+    }
+
+    if let Some(created_to) = filter.created_to {
+        let param = bind(&mut params, format_datetime2(created_to));
+        clauses.push(format!("created_at < CAST({param} AS DATETIME2)"));
+    }
+
+    for attribute in &filter.attributes {
+        let namespace = bind(&mut params, attribute.namespace.clone());
+        let path = bind(&mut params, attribute.json_path());
+        let value = bind(&mut params, attribute.value.clone());
+        clauses.push(format!(
+            "EXISTS (SELECT 1 FROM user_attributes \
+             WHERE user_attributes.user_id = users.id \
+             AND user_attributes.namespace = {namespace} \
+             AND JSON_VALUE(user_attributes.data, {path}) = {value})"
+        ));
+    }
+
+    let (comparison, order) = match query.direction {
+        SortDirection::Asc => (">", "ASC"),
+        SortDirection::Desc => ("<", "DESC"),
+    };
+
+    if let Some(cursor) = &query.after {
+        let created_at = bind(&mut params, format_datetime2(cursor.created_at));
+        let id = bind(&mut params, cursor.id.to_string());
+        clauses.push(format!(
+            "(created_at {comparison} CAST({created_at} AS DATETIME2) \
+             OR (created_at = CAST({created_at} AS DATETIME2) \
+             AND id {comparison} CAST({id} AS UNIQUEIDENTIFIER)))"
+        ));
+    }
+
+    let where_clause = if clauses.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", clauses.join(" AND "))
+    };
+
+    let sql = format!(
+        r#"
+        SELECT TOP ({limit}) {columns}
+        FROM users
+        {where_clause}
+        ORDER BY users.created_at {order}, users.id {order}
+        "#,
+        limit = query.limit,
+    );
+
+    UserSql { sql, params }
+}
+
+pub fn create_users_sql(users: &[User], placeholder: &str, output_columns: &str) -> UserSql {
+    let mut params = Vec::with_capacity(users.len() * 6);
+    let mut rows = Vec::with_capacity(users.len());
+
+    for user in users {
+        let offset = params.len();
+        params.extend([
+            user.id().to_string(),
+            user.email().to_string(),
+            user.password().to_string(),
+            user.name().to_string(),
+            user.role().as_str().to_string(),
+            user.status().as_str().to_string(),
+        ]);
+
+        let values: Vec<String> = (offset + 1..=offset + 6)
+            .map(|index| format!("{placeholder}{index}"))
+            .collect();
+        rows.push(format!("({})", values.join(", ")));
+    }
+
+    let sql = format!(
+        r#"
+        INSERT INTO users (id, email, password, name, role, status)
+        OUTPUT {output_columns}
+        SELECT CAST(v.id AS UNIQUEIDENTIFIER), v.email, v.password, v.name, v.role, v.status
+        FROM (VALUES {rows}) AS v (id, email, password, name, role, status)
+        WHERE NOT EXISTS (
+            SELECT 1 FROM users WITH (UPDLOCK, HOLDLOCK)
+            WHERE users.email = v.email
+        )
+        "#,
+        rows = rows.join(", "),
+    );
+
+    UserSql { sql, params }
+}
+
+pub fn existing_emails_sql(emails: &[String], placeholder: &str) -> UserSql {
+    let placeholders: Vec<String> = (1..=emails.len())
+        .map(|index| format!("{placeholder}{index}"))
+        .collect();
+
+    let sql = format!(
+        "SELECT email FROM users WHERE email IN ({})",
+        placeholders.join(", ")
+    );
+
+    UserSql {
+        sql,
+        params: emails.to_vec(),
+    }
+}
@@ -0,0 +1,99 @@
+use std::{
+    io::ErrorKind,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::Utc;
+
+use crate::{
+    domain::repositories::{
+        error::{RepositoryError, RepositoryResult},
+        object_storage::ObjectStorage,
+    },
+    infra::security::url_signer::UrlSigner,
+};
+
+pub struct LocalObjectStorage {
+    root: PathBuf,
+    public_url: String,
+    signer: Arc<dyn UrlSigner>,
+}
+
+impl LocalObjectStorage {
+    pub fn new(root: impl Into<PathBuf>, public_url: &str, signer: Arc<dyn UrlSigner>) -> Self {
+        Self {
+            root: root.into(),
+            public_url: public_url.trim_end_matches('/').to_string(),
+            signer,
+        }
+    }
+
+    fn path_for(&self, key: &str) -> RepositoryResult<PathBuf> {
+        let relative = Path::new(key);
+        let is_safe = !key.is_empty()
+            && relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+
+        if !is_safe {
+            return Err(RepositoryError::StorageFailure(format!(
+                "Invalid object key: {key}"
+            )));
+        }
+
+        Ok(self.root.join(relative))
+    }
+}
+
+fn storage_error(e: std::io::Error) -> RepositoryError {
+    RepositoryError::StorageFailure(e.to_string())
+}
+
+#[async_trait::async_trait]
+impl ObjectStorage for LocalObjectStorage {
+    async fn put(&self, key: &str, body: Vec<u8>, _content_type: &str) -> RepositoryResult<()> {
+        let path = self.path_for(key)?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(storage_error)?;
+        }
+
+        tokio::fs::write(path, body).await.map_err(storage_error)
+    }
+
+    async fn get(&self, key: &str) -> RepositoryResult<Option<Vec<u8>>> {
+        let path = self.path_for(key)?;
+
+        match tokio::fs::read(path).await {
+            Ok(body) => Ok(Some(body)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(storage_error(e)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> RepositoryResult<()> {
+        let path = self.path_for(key)?;
+
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(storage_error(e)),
+        }
+    }
+
+    async fn signed_url(&self, key: &str, expires_in: Duration) -> RepositoryResult<String> {
+        self.path_for(key)?;
+
+        let expires_at = Utc::now().timestamp() + expires_in.as_secs() as i64;
+        let signature = self.signer.sign(key, expires_at);
+
+        Ok(format!(
+            "{}/files/{}?expires={}&signature={}",
+            self.public_url, key, expires_at, signature
+        ))
+    }
+}
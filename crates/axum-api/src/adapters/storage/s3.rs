@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use aws_sdk_s3::{
+    Client,
+    config::{BehaviorVersion, Builder, Credentials, Region},
+    presigning::PresigningConfig,
+    primitives::ByteStream,
+};
+
+use crate::domain::repositories::{
+    error::{RepositoryError, RepositoryResult},
+    object_storage::ObjectStorage,
+};
+
+pub struct S3ObjectStorage {
+    client: Client,
+    bucket: String,
+}
+
+impl S3ObjectStorage {
+    pub fn new(client: Client, bucket: &str) -> Self {
+        Self {
+            client,
+            bucket: bucket.to_string(),
+        }
+    }
+
+    pub fn from_credentials(
+        bucket: &str,
+        region: &str,
+        endpoint: Option<&str>,
+        access_key_id: &str,
+        secret_access_key: &str,
+    ) -> Self {
+        let credentials = Credentials::new(access_key_id, secret_access_key, None, None, "static");
+
+        let mut config = Builder::new()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new(region.to_string()))
+            .credentials_provider(credentials)
+            .force_path_style(true);
+
+        if let Some(endpoint) = endpoint {
+            config = config.endpoint_url(endpoint);
+        }
+
+        Self::new(Client::from_conf(config.build()), bucket)
+    }
+}
+
+fn storage_error(e: impl std::error::Error) -> RepositoryError {
+    RepositoryError::StorageFailure(e.to_string())
+}
+
+#[async_trait::async_trait]
+impl ObjectStorage for S3ObjectStorage {
+    async fn put(&self, key: &str, body: Vec<u8>, content_type: &str) -> RepositoryResult<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(body))
+            .send()
+            .await
+            .map_err(storage_error)?;
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> RepositoryResult<Option<Vec<u8>>> {
+        let output = match self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(output) => output,
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_no_such_key()) => {
+                return Ok(None);
+            }
+            Err(e) => return Err(storage_error(e)),
+        };
+
+        let body = output.body.collect().await.map_err(storage_error)?;
+
+        Ok(Some(body.into_bytes().to_vec()))
+    }
+
+    async fn delete(&self, key: &str) -> RepositoryResult<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(storage_error)?;
+
+        Ok(())
+    }
+
+    async fn signed_url(&self, key: &str, expires_in: Duration) -> RepositoryResult<String> {
+        let presigning = PresigningConfig::expires_in(expires_in).map_err(storage_error)?;
+
+        let request = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .presigned(presigning)
+            .await
+            .map_err(storage_error)?;
+
+        Ok(request.uri().to_string())
+    }
+}
@@ -6,7 +6,9 @@ use axum::{
 };
 use thiserror::Error;
 
-use crate::domain::repositories::error::RepositoryError;
+use crate::domain::{
+    entities::user::InvalidStatusTransition, repositories::error::RepositoryError,
+};
 
 #[derive(Error, Debug)]
 pub enum AppError {
@@ -19,9 +21,69 @@ pub enum AppError {
     #[error("Unauthorized")]
     Unauthorized,
 
+    #[error("Forbidden")]
+    Forbidden,
+
+    #[error("Account is suspended")]
+    AccountSuspended,
+
+    #[error("Account has been deleted")]
+    AccountDeleted,
+
+    #[error("Account is locked")]
+    AccountLocked,
+
+    #[error(transparent)]
+    InvalidStatusTransition(#[from] InvalidStatusTransition),
+
     #[error("Invalid token")]
     InvalidToken,
 
+    #[error("Maximum number of concurrent sessions reached")]
+    SessionLimitReached,
+
+    #[error("Trusted device not found")]
+    TrustedDeviceNotFound,
+
+    #[error("Passkey not found")]
+    PasskeyNotFound,
+
+    #[error("Passkey is already registered")]
+    PasskeyAlreadyRegistered,
+
+    #[error("Passkey verification failed: {0}")]
+    PasskeyVerificationFailed(String),
+
+    #[error("Data export not found")]
+    DataExportNotFound,
+
+    #[error("Data export failed: {0}")]
+    ExportFailed(String),
+
+    #[error("File not found")]
+    FileNotFound,
+
+    #[error("Attribute schema not found")]
+    AttributeSchemaNotFound,
+
+    #[error("Attributes not found")]
+    AttributesNotFound,
+
+    #[error("Unsupported media type")]
+    UnsupportedMediaType,
+
+    #[error("Payload too large")]
+    PayloadTooLarge,
+
+    #[error("Invalid image: {0}")]
+    InvalidImage(String),
+
+    #[error("Resource was modified by another request")]
+    PreconditionFailed,
+
+    #[error("Request must be conditional on the current version")]
+    PreconditionRequired,
+
     #[error("Token generation failed: {0}")]
     TokenGenerationFailed(String),
 
@@ -49,10 +111,32 @@ impl AppError {
         match self {
             AppError::EmailAlreadyExists(_) => StatusCode::CONFLICT,
             AppError::UserNotFound => StatusCode::NOT_FOUND,
+            AppError::TrustedDeviceNotFound => StatusCode::NOT_FOUND,
+            AppError::PasskeyNotFound => StatusCode::NOT_FOUND,
+            AppError::PasskeyAlreadyRegistered => StatusCode::CONFLICT,
+            AppError::DataExportNotFound => StatusCode::NOT_FOUND,
+            AppError::FileNotFound => StatusCode::NOT_FOUND,
+            AppError::AttributeSchemaNotFound => StatusCode::NOT_FOUND,
+            AppError::AttributesNotFound => StatusCode::NOT_FOUND,
             AppError::Unauthorized => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden => StatusCode::FORBIDDEN,
+            AppError::AccountSuspended => StatusCode::FORBIDDEN,
+            AppError::AccountDeleted => StatusCode::GONE,
+            AppError::AccountLocked => StatusCode::LOCKED,
+            AppError::InvalidStatusTransition(_) => StatusCode::CONFLICT,
             AppError::InvalidToken => StatusCode::UNAUTHORIZED,
+            AppError::PasskeyVerificationFailed(_) => StatusCode::UNAUTHORIZED,
+            AppError::SessionLimitReached => StatusCode::FORBIDDEN,
+            AppError::PreconditionFailed => StatusCode::PRECONDITION_FAILED,
+            AppError::PreconditionRequired => StatusCode::PRECONDITION_REQUIRED,
+            AppError::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AppError::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::InvalidImage(_) => StatusCode::UNPROCESSABLE_ENTITY,
             AppError::ValidationError(_) => StatusCode::UNPROCESSABLE_ENTITY,
             AppError::JsonRejection(_) => StatusCode::BAD_REQUEST,
+            AppError::RepositoryError(RepositoryError::UniqueViolation { .. }) => {
+                StatusCode::CONFLICT
+            }
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
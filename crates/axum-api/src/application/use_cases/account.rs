@@ -0,0 +1,285 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        app_error::{AppError, AppResult},
+        use_cases::{
+            account_status::AccountStatusUseCase, avatar::AvatarUseCase,
+            data_export::DataExportUseCase, trusted_device::TrustedDeviceUseCase,
+        },
+    },
+    domain::{
+        entities::user::{InvalidStatusTransition, User, UserStatus},
+        events::{
+            outbox::OutboxEvent,
+            user::{UserDeleted, UserEventPublisher},
+        },
+        repositories::{
+            error::RepositoryError, token_cache::TokenCacheRepository,
+            unit_of_work::UnitOfWorkFactory, user::UserRepository,
+        },
+    },
+    infra::security::argon2::PasswordHasherTrait,
+};
+
+const ERASURE_BATCH_SIZE: u32 = 100;
+
+pub struct AccountUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    unit_of_work: Arc<dyn UnitOfWorkFactory>,
+    token_cache_repository: Arc<dyn TokenCacheRepository>,
+    trusted_device_use_case: Arc<TrustedDeviceUseCase>,
+    hasher: Arc<dyn PasswordHasherTrait>,
+    account_status: AccountStatusUseCase,
+    erasure_grace_period: Duration,
+    avatar_use_case: Option<Arc<AvatarUseCase>>,
+    data_export_use_case: Option<Arc<DataExportUseCase>>,
+}
+
+impl AccountUseCase {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        unit_of_work: Arc<dyn UnitOfWorkFactory>,
+        token_cache_repository: Arc<dyn TokenCacheRepository>,
+        trusted_device_use_case: Arc<TrustedDeviceUseCase>,
+        hasher: Arc<dyn PasswordHasherTrait>,
+        event_publisher: Arc<dyn UserEventPublisher>,
+        erasure_grace_period: Duration,
+    ) -> Self {
+        Self {
+            account_status: AccountStatusUseCase::new(user_repository.clone(), event_publisher),
+            user_repository,
+            unit_of_work,
+            token_cache_repository,
+            trusted_device_use_case,
+            hasher,
+            erasure_grace_period,
+            avatar_use_case: None,
+            data_export_use_case: None,
+        }
+    }
+
+    pub fn with_avatars(mut self, avatar_use_case: Arc<AvatarUseCase>) -> Self {
+        self.avatar_use_case = Some(avatar_use_case);
+        self
+    }
+
+    pub fn with_data_exports(mut self, data_export_use_case: Arc<DataExportUseCase>) -> Self {
+        self.data_export_use_case = Some(data_export_use_case);
+        self
+    }
+
+    async fn load_user(&self, user_id: Uuid) -> AppResult<User> {
+        self.user_repository
+            .find_by_id(&user_id.to_string())
+            .await?
+            .ok_or(AppError::UserNotFound)
+    }
+
+    fn verify_password(&self, user: &User, password: &str) -> AppResult<()> {
+        if !self.hasher.verify_password(password, user.password())? {
+            return Err(AppError::Unauthorized);
+        }
+
+        Ok(())
+    }
+
+    async fn purge_sessions(&self, user_id: Uuid) -> AppResult<()> {
+        self.token_cache_repository
+            .revoke_all_refresh_tokens(user_id)
+            .await?;
+        self.trusted_device_use_case
+            .revoke_all_devices(user_id)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn schedule_erasure(&self, mut user: User) -> AppResult<DateTime<Utc>> {
+        let erase_at = Utc::now() + self.erasure_grace_period;
+
+        self.account_status
+            .transition(&mut user, |user| user.schedule_erasure(erase_at))
+            .await?;
+        self.purge_sessions(*user.id()).await?;
+
+        Ok(erase_at)
+    }
+
+    pub async fn deactivate(&self, user_id: Uuid, password: &str) -> AppResult<()> {
+        let mut user = self.load_user(user_id).await?;
+        self.verify_password(&user, password)?;
+
+        self.account_status
+            .transition(&mut user, User::deactivate)
+            .await?;
+        self.purge_sessions(user_id).await
+    }
+
+    pub async fn request_erasure(&self, user_id: Uuid, password: &str) -> AppResult<DateTime<Utc>> {
+        let user = self.load_user(user_id).await?;
+        self.verify_password(&user, password)?;
+
+        self.schedule_erasure(user).await
+    }
+
+    pub async fn suspend(
+        &self,
+        actor_id: Uuid,
+        user_id: Uuid,
+        reason: String,
+        until: Option<DateTime<Utc>>,
+    ) -> AppResult<User> {
+        if actor_id == user_id {
+            return Err(AppError::Forbidden);
+        }
+
+        let mut user = self.load_user(user_id).await?;
+
+        self.account_status
+            .transition(&mut user, |user| user.suspend(reason, until))
+            .await?;
+        self.purge_sessions(user_id).await?;
+
+        Ok(user)
+    }
+
+    pub async fn lock(
+        &self,
+        actor_id: Uuid,
+        user_id: Uuid,
+        reason: String,
+        until: Option<DateTime<Utc>>,
+    ) -> AppResult<User> {
+        if actor_id == user_id {
+            return Err(AppError::Forbidden);
+        }
+
+        let mut user = self.load_user(user_id).await?;
+
+        self.account_status
+            .transition(&mut user, |user| user.lock(reason, until))
+            .await?;
+        self.purge_sessions(user_id).await?;
+
+        Ok(user)
+    }
+
+    pub async fn unlock(&self, user_id: Uuid) -> AppResult<User> {
+        let mut user = self.load_user(user_id).await?;
+
+        if user.status() != UserStatus::Locked {
+            return Err(AppError::InvalidStatusTransition(InvalidStatusTransition {
+                from: user.status(),
+                to: UserStatus::Active,
+            }));
+        }
+
+        self.account_status
+            .transition(&mut user, User::activate)
+            .await?;
+
+        Ok(user)
+    }
+
+    pub async fn reactivate(&self, user_id: Uuid) -> AppResult<User> {
+        let mut user = self.load_user(user_id).await?;
+
+        if user.status() == UserStatus::Active {
+            return Ok(user);
+        }
+
+        self.account_status
+            .transition(&mut user, User::activate)
+            .await?;
+
+        Ok(user)
+    }
+
+    pub async fn erase(&self, actor_id: Uuid, user_id: Uuid) -> AppResult<DateTime<Utc>> {
+        if actor_id == user_id {
+            return Err(AppError::Forbidden);
+        }
+
+        let user = self.load_user(user_id).await?;
+
+        self.schedule_erasure(user).await
+    }
+
+    /// Erases every account whose grace period has ended. A failure is logged and the
+    /// account stays due for the next run; the run stops once a whole batch has failed,
+    /// since listing again would only return the same accounts.
+    pub async fn finalize_pending_erasures(&self) -> AppResult<usize> {
+        let mut erased = 0;
+
+        loop {
+            let users = self
+                .user_repository
+                .list_due_erasures(Utc::now(), ERASURE_BATCH_SIZE)
+                .await?;
+
+            if users.is_empty() {
+                return Ok(erased);
+            }
+
+            let mut settled = 0;
+
+            for user in users {
+                let user_id = *user.id();
+
+                match self.erase_due_user(user).await {
+                    Ok(applied) => {
+                        settled += 1;
+                        erased += usize::from(applied);
+                    }
+                    Err(e) => error!("Failed to erase user {}: {}", user_id, e),
+                }
+            }
+
+            if settled == 0 {
+                return Ok(erased);
+            }
+        }
+    }
+
+    /// Returns `false` when the account left pending deletion after it was listed.
+    async fn erase_due_user(&self, mut user: User) -> AppResult<bool> {
+        let avatar_key = user.avatar_key().map(str::to_string);
+        let change = user.anonymize()?;
+
+        let unit_of_work = self.unit_of_work.begin().await?;
+
+        if !unit_of_work.users().erase(&user).await? {
+            return Ok(false);
+        }
+
+        // Enqueued with the erasure so downstream services hear about every erased account.
+        let event = UserDeleted {
+            user_id: *user.id(),
+            deleted_at: Utc::now(),
+        };
+        let message = event
+            .to_outbox_message()
+            .map_err(RepositoryError::SerializationError)?;
+        unit_of_work.outbox().enqueue(&message).await?;
+
+        unit_of_work.commit().await?;
+
+        self.account_status.publish(&user, &change).await;
+        self.purge_sessions(*user.id()).await?;
+
+        if let (Some(avatar_use_case), Some(avatar_key)) = (&self.avatar_use_case, avatar_key) {
+            avatar_use_case.delete_variants(&avatar_key).await;
+        }
+
+        if let Some(data_export_use_case) = &self.data_export_use_case {
+            data_export_use_case.delete_user_exports(*user.id()).await;
+        }
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use tracing::error;
+
+use crate::{
+    application::app_error::{AppError, AppResult},
+    domain::{
+        entities::user::{InvalidStatusTransition, StatusChange, User, UserStatus},
+        events::user::{UserEventPublisher, UserStatusChanged},
+        repositories::user::UserRepository,
+    },
+};
+
+pub fn check_account_status(user: &User) -> AppResult<()> {
+    if user.can_authenticate() {
+        return Ok(());
+    }
+
+    match user.status() {
+        UserStatus::Locked => Err(AppError::AccountLocked),
+        UserStatus::Suspended => Err(AppError::AccountSuspended),
+        UserStatus::PendingDeletion | UserStatus::Deleted => Err(AppError::AccountDeleted),
+        UserStatus::Active | UserStatus::Deactivated => Err(AppError::Unauthorized),
+    }
+}
+
+#[derive(Clone)]
+pub struct AccountStatusUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    event_publisher: Arc<dyn UserEventPublisher>,
+}
+
+impl AccountStatusUseCase {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        event_publisher: Arc<dyn UserEventPublisher>,
+    ) -> Self {
+        Self {
+            user_repository,
+            event_publisher,
+        }
+    }
+
+    pub async fn transition<F>(&self, user: &mut User, transition: F) -> AppResult<StatusChange>
+    where
+        F: FnOnce(&mut User) -> Result<StatusChange, InvalidStatusTransition>,
+    {
+        let change = transition(user)?;
+
+        if !self
+            .user_repository
+            .update_status(user, change.from)
+            .await?
+        {
+            return Err(AppError::PreconditionFailed);
+        }
+
+        self.publish(user, &change).await;
+
+        Ok(change)
+    }
+
+    pub async fn publish(&self, user: &User, change: &StatusChange) {
+        let event = UserStatusChanged::new(user, change);
+
+        if let Err(e) = self.event_publisher.publish_status_changed(event).await {
+            error!("Failed to publish UserStatusChanged event: {}", e);
+        }
+    }
+}
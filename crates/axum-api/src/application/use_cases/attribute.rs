@@ -0,0 +1,191 @@
+use std::{collections::HashSet, sync::Arc};
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    application::app_error::{AppError, AppResult},
+    domain::{
+        entities::{
+            attribute::{AttributeSchema, UserAttributes, is_valid_namespace},
+            user::Role,
+        },
+        repositories::{attribute::AttributeRepository, user::UserRepository},
+    },
+    infra::json_schema::SchemaValidator,
+};
+
+pub struct AttributeUseCase {
+    attribute_repository: Arc<dyn AttributeRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    validator: Arc<dyn SchemaValidator>,
+}
+
+impl AttributeUseCase {
+    pub fn new(
+        attribute_repository: Arc<dyn AttributeRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        validator: Arc<dyn SchemaValidator>,
+    ) -> Self {
+        Self {
+            attribute_repository,
+            user_repository,
+            validator,
+        }
+    }
+
+    fn check_namespace(namespace: &str) -> AppResult<()> {
+        if !is_valid_namespace(namespace) {
+            return Err(AppError::ValidationError(vec![format!(
+                "Invalid attribute namespace: {namespace}"
+            )]));
+        }
+
+        Ok(())
+    }
+
+    async fn load_schema(&self, namespace: &str) -> AppResult<AttributeSchema> {
+        Self::check_namespace(namespace)?;
+
+        self.attribute_repository
+            .find_schema(namespace)
+            .await?
+            .ok_or(AppError::AttributeSchemaNotFound)
+    }
+
+    async fn ensure_user(&self, user_id: Uuid) -> AppResult<()> {
+        self.user_repository
+            .find_by_id(&user_id.to_string())
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+
+        Ok(())
+    }
+
+    pub async fn put_schema(
+        &self,
+        namespace: &str,
+        schema: Value,
+        user_writable: bool,
+    ) -> AppResult<AttributeSchema> {
+        Self::check_namespace(namespace)?;
+        self.validator.check_schema(&schema)?;
+
+        Ok(self
+            .attribute_repository
+            .upsert_schema(namespace, &schema.to_string(), user_writable)
+            .await?)
+    }
+
+    pub async fn get_schema(&self, namespace: &str) -> AppResult<AttributeSchema> {
+        self.load_schema(namespace).await
+    }
+
+    pub async fn list_schemas(&self) -> AppResult<Vec<AttributeSchema>> {
+        Ok(self.attribute_repository.list_schemas().await?)
+    }
+
+    pub async fn delete_schema(&self, namespace: &str) -> AppResult<()> {
+        Self::check_namespace(namespace)?;
+
+        if !self.attribute_repository.delete_schema(namespace).await? {
+            return Err(AppError::AttributeSchemaNotFound);
+        }
+
+        Ok(())
+    }
+
+    pub async fn set_attributes(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+        data: Value,
+        writer: Role,
+    ) -> AppResult<UserAttributes> {
+        let schema = self.load_schema(namespace).await?;
+
+        if writer != Role::Admin && !schema.user_writable {
+            return Err(AppError::Forbidden);
+        }
+
+        self.validator.validate(&schema, &data)?;
+        self.ensure_user(user_id).await?;
+
+        Ok(self
+            .attribute_repository
+            .upsert(user_id, namespace, &data.to_string())
+            .await?)
+    }
+
+    /// Users only see namespaces they can write; the rest are reported as missing.
+    pub async fn get_attributes(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+        reader: Role,
+    ) -> AppResult<UserAttributes> {
+        Self::check_namespace(namespace)?;
+
+        if reader != Role::Admin {
+            let visible = self
+                .attribute_repository
+                .find_schema(namespace)
+                .await?
+                .is_some_and(|schema| schema.user_writable);
+
+            if !visible {
+                return Err(AppError::AttributesNotFound);
+            }
+        }
+
+        self.attribute_repository
+            .find(user_id, namespace)
+            .await?
+            .ok_or(AppError::AttributesNotFound)
+    }
+
+    pub async fn list_attributes(
+        &self,
+        user_id: Uuid,
+        reader: Role,
+    ) -> AppResult<Vec<UserAttributes>> {
+        let attributes = self.attribute_repository.list_by_user(user_id).await?;
+
+        if reader == Role::Admin {
+            return Ok(attributes);
+        }
+
+        let visible: HashSet<String> = self
+            .attribute_repository
+            .list_schemas()
+            .await?
+            .into_iter()
+            .filter(|schema| schema.user_writable)
+            .map(|schema| schema.namespace)
+            .collect();
+
+        Ok(attributes
+            .into_iter()
+            .filter(|attributes| visible.contains(&attributes.namespace))
+            .collect())
+    }
+
+    pub async fn delete_attributes(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+        writer: Role,
+    ) -> AppResult<()> {
+        let schema = self.load_schema(namespace).await?;
+
+        if writer != Role::Admin && !schema.user_writable {
+            return Err(AppError::Forbidden);
+        }
+
+        if !self.attribute_repository.delete(user_id, namespace).await? {
+            return Err(AppError::AttributesNotFound);
+        }
+
+        Ok(())
+    }
+}
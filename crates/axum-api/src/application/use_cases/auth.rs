@@ -1,86 +1,220 @@
 use std::sync::Arc;
 
-use chrono::Duration;
+use chrono::{Duration, Utc};
 use uuid::Uuid;
 
 use crate::{
-    application::app_error::{AppError, AppResult},
+    application::{
+        app_error::{AppError, AppResult},
+        use_cases::{
+            account_status::{AccountStatusUseCase, check_account_status},
+            trusted_device::TrustedDeviceUseCase,
+            user::create_user_error,
+        },
+    },
     domain::{
-        entities::user::User,
-        events::{user::UserCreated, user::UserEventPublisher},
-        repositories::{token_cache::TokenCacheRepository, user::UserRepository},
+        entities::{
+            session::{ClientInfo, MfaChallenge, RefreshSession},
+            user::{User, UserStatus},
+        },
+        events::{
+            outbox::OutboxEvent,
+            user::{UserCreated, UserEventPublisher},
+        },
+        repositories::{
+            error::RepositoryError,
+            second_factor::SecondFactorRepository,
+            token_cache::{SessionLimit, TokenCacheRepository},
+            unit_of_work::UnitOfWorkFactory,
+            user::UserRepository,
+        },
+    },
+    infra::{
+        config::{SessionConfig, SessionLimitStrategy},
+        security::{
+            argon2::PasswordHasherTrait, jwt::TokenProvider, refresh_token::RefreshTokenProvider,
+        },
     },
-    infra::security::{argon2::PasswordHasherTrait, jwt::TokenProvider},
 };
-use tracing::error;
+
+fn reactivates_on_sign_in(user: &User) -> bool {
+    user.status() == UserStatus::Deactivated || user.restriction_expired()
+}
+
+/// Rejects accounts that cannot sign in, without reactivating the ones that can.
+fn check_can_sign_in(user: &User) -> AppResult<()> {
+    if reactivates_on_sign_in(user) {
+        return Ok(());
+    }
+
+    check_account_status(user)
+}
+
+pub enum LoginOutcome {
+    Authenticated {
+        access_token: String,
+        refresh_token: String,
+    },
+    MfaRequired {
+        mfa_token: String,
+    },
+}
 
 pub struct AuthUseCase {
     hasher: Arc<dyn PasswordHasherTrait>,
     user_repository: Arc<dyn UserRepository>,
+    unit_of_work: Arc<dyn UnitOfWorkFactory>,
     token_cache_repository: Arc<dyn TokenCacheRepository>,
     token_provider: Arc<dyn TokenProvider>,
-    event_publisher: Arc<dyn UserEventPublisher>,
+    refresh_token_provider: Arc<dyn RefreshTokenProvider>,
+    account_status: AccountStatusUseCase,
+    session_config: SessionConfig,
+    trusted_device_use_case: Option<Arc<TrustedDeviceUseCase>>,
+    second_factor_repositories: Vec<Arc<dyn SecondFactorRepository>>,
 }
 
 impl AuthUseCase {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         user_repository: Arc<dyn UserRepository>,
+        unit_of_work: Arc<dyn UnitOfWorkFactory>,
         token_cache_repository: Arc<dyn TokenCacheRepository>,
         hasher: Arc<dyn PasswordHasherTrait>,
         token_provider: Arc<dyn TokenProvider>,
+        refresh_token_provider: Arc<dyn RefreshTokenProvider>,
         event_publisher: Arc<dyn UserEventPublisher>,
+        session_config: SessionConfig,
     ) -> Self {
         Self {
+            account_status: AccountStatusUseCase::new(user_repository.clone(), event_publisher),
             user_repository,
+            unit_of_work,
             token_cache_repository,
             hasher,
             token_provider,
-            event_publisher,
+            refresh_token_provider,
+            session_config,
+            trusted_device_use_case: None,
+            second_factor_repositories: Vec::new(),
         }
     }
 
-    pub async fn register(
+    pub fn with_trusted_devices(
+        mut self,
+        trusted_device_use_case: Arc<TrustedDeviceUseCase>,
+    ) -> Self {
+        self.trusted_device_use_case = Some(trusted_device_use_case);
+        self
+    }
+
+    pub fn with_second_factor(mut self, repository: Arc<dyn SecondFactorRepository>) -> Self {
+        self.second_factor_repositories.push(repository);
+        self
+    }
+
+    /// Keyed hash of the client's device fingerprint, which sessions and sign-in
+    /// challenges are bound to.
+    pub fn device_hash(&self, client: &ClientInfo) -> String {
+        self.refresh_token_provider.hash(&client.device_fingerprint)
+    }
+
+    fn new_session(&self, user_id: Uuid, client: &ClientInfo) -> RefreshSession {
+        RefreshSession::new(user_id, client.client_id.clone(), self.device_hash(client))
+    }
+
+    async fn issue_tokens(
         &self,
-        email: String,
-        password: String,
-        name: String,
+        user: &User,
+        session: RefreshSession,
+        limit: SessionLimit,
     ) -> AppResult<(String, String)> {
-        if self.user_repository.find_by_email(&email).await?.is_some() {
-            return Err(AppError::EmailAlreadyExists(email));
-        }
+        let policy = self.session_config.policy_for(user.role());
+        let idle_timeout = Duration::minutes(policy.idle_timeout_minutes as i64);
+        let remaining_lifetime = session.created_at + Duration::days(7) - Utc::now();
+        let ttl = idle_timeout.min(remaining_lifetime);
 
-        let hashed_password = self.hasher.hash_password(password.as_str())?;
-        // let hashed_password = password.clone();
-        let user = User::new(email.clone(), hashed_password, name);
-        let created_user = self.user_repository.create(&user).await?;
+        if ttl <= Duration::zero() {
+            return Err(AppError::InvalidToken);
+        }
 
         let access_token = self
             .token_provider
-            .generate_token(&created_user.id().to_string(), Duration::minutes(15))?;
+            .generate_token(&user.id().to_string(), Duration::minutes(15))?;
 
-        let refresh_token = Uuid::new_v4().to_string();
+        let refresh_token = self.refresh_token_provider.generate_token();
 
-        self.token_cache_repository
+        if !self
+            .token_cache_repository
             .store_refresh_token(
-                *created_user.id(),
-                &refresh_token,
-                Duration::days(7).num_seconds() as u64,
+                &self.refresh_token_provider.hash(&refresh_token),
+                &session,
+                ttl.num_seconds() as u64,
+                limit,
             )
-            .await?;
+            .await?
+        {
+            return Err(AppError::SessionLimitReached);
+        }
+
+        Ok((access_token, refresh_token))
+    }
+
+    fn session_limit(&self, user: &User) -> SessionLimit {
+        let policy = self.session_config.policy_for(user.role());
+
+        match (policy.max_concurrent_sessions, policy.on_limit) {
+            (0, _) => SessionLimit::Unlimited,
+            (max, SessionLimitStrategy::Reject) => SessionLimit::Reject(max),
+            (max, SessionLimitStrategy::EvictOldest) => SessionLimit::EvictOldest(max),
+        }
+    }
+
+    pub async fn register(
+        &self,
+        email: String,
+        password: String,
+        name: String,
+        client: ClientInfo,
+    ) -> AppResult<(String, String)> {
+        // Hashed up front to keep the transaction short.
+        let hashed_password = self.hasher.hash_password(password.as_str())?;
+        let user = User::new(email.clone(), hashed_password, name);
+
+        let unit_of_work = self.unit_of_work.begin().await?;
+
+        // The unique email index decides, so concurrent registrations cannot both pass.
+        let created_user = match unit_of_work.users().create(&user).await {
+            Ok(created_user) => created_user,
+            Err(e) => {
+                drop(unit_of_work);
+                return Err(create_user_error(self.user_repository.as_ref(), email, e).await);
+            }
+        };
 
         let event = UserCreated {
             user_id: *created_user.id(),
             email: created_user.email().to_string(),
         };
+        let message = event
+            .to_outbox_message()
+            .map_err(RepositoryError::SerializationError)?;
+        unit_of_work.outbox().enqueue(&message).await?;
 
-        if let Err(e) = self.event_publisher.publish_user_created(event).await {
-            error!("Failed to publish UserCreated event: {}", e);
-        }
+        unit_of_work.commit().await?;
 
-        Ok((access_token, refresh_token))
+        let session = self.new_session(*created_user.id(), &client);
+
+        self.issue_tokens(&created_user, session, SessionLimit::Unlimited)
+            .await
     }
 
-    pub async fn login(&self, email: String, password: String) -> AppResult<(String, String)> {
+    pub async fn login(
+        &self,
+        email: String,
+        password: String,
+        client: ClientInfo,
+        trusted_device: Option<String>,
+    ) -> AppResult<LoginOutcome> {
         let user = self
             .user_repository
             .find_by_email(&email)
@@ -97,21 +231,145 @@ impl AuthUseCase {
             return Err(AppError::Unauthorized);
         }
 
-        let access_token = self
-            .token_provider
-            .generate_token(&user.id().to_string(), Duration::minutes(15))?;
+        // Reactivation waits for `complete_login`, so a password alone cannot undo it.
+        check_can_sign_in(&user)?;
+
+        if self
+            .requires_second_factor(&user, trusted_device.as_deref())
+            .await?
+        {
+            let mfa_token = self.refresh_token_provider.generate_token();
+            let challenge = MfaChallenge {
+                user_id: *user.id(),
+                client_id: client.client_id.clone(),
+                device_hash: self.device_hash(&client),
+            };
 
-        let refresh_token = Uuid::new_v4().to_string();
+            self.token_cache_repository
+                .store_mfa_challenge(
+                    &self.refresh_token_provider.hash(&mfa_token),
+                    &challenge,
+                    Duration::minutes(5).num_seconds() as u64,
+                )
+                .await?;
 
-        self.token_cache_repository
-            .store_refresh_token(
-                *user.id(),
-                &refresh_token,
-                Duration::days(7).num_seconds() as u64,
-            )
+            return Ok(LoginOutcome::MfaRequired { mfa_token });
+        }
+
+        let (access_token, refresh_token) = self.complete_login(&user, client).await?;
+
+        Ok(LoginOutcome::Authenticated {
+            access_token,
+            refresh_token,
+        })
+    }
+
+    pub async fn complete_login(
+        &self,
+        user: &User,
+        client: ClientInfo,
+    ) -> AppResult<(String, String)> {
+        let user = self.ensure_can_sign_in(user).await?;
+        let session = self.new_session(*user.id(), &client);
+        self.issue_tokens(&user, session, self.session_limit(&user))
+            .await
+    }
+
+    /// Returns the user as it is after sign-in, so the tokens are issued for a reactivated
+    /// account's new status rather than the status it was loaded with.
+    async fn ensure_can_sign_in(&self, user: &User) -> AppResult<User> {
+        let mut user = user.clone();
+
+        if reactivates_on_sign_in(&user) {
+            self.account_status
+                .transition(&mut user, User::activate)
+                .await?;
+            return Ok(user);
+        }
+
+        check_account_status(&user)?;
+
+        Ok(user)
+    }
+
+    pub async fn take_mfa_challenge(
+        &self,
+        mfa_token: &str,
+        client: &ClientInfo,
+    ) -> AppResult<User> {
+        let challenge = self
+            .token_cache_repository
+            .take_mfa_challenge(&self.refresh_token_provider.hash(mfa_token))
+            .await?
+            .ok_or(AppError::InvalidToken)?;
+
+        let device_hash = self.device_hash(client);
+        if challenge.client_id != client.client_id || challenge.device_hash != device_hash {
+            return Err(AppError::InvalidToken);
+        }
+
+        self.user_repository
+            .find_by_id(&challenge.user_id.to_string())
+            .await?
+            .ok_or(AppError::UserNotFound)
+    }
+
+    async fn requires_second_factor(
+        &self,
+        user: &User,
+        trusted_device: Option<&str>,
+    ) -> AppResult<bool> {
+        let mut enrolled = false;
+
+        for repository in &self.second_factor_repositories {
+            if repository.has_second_factor(*user.id()).await? {
+                enrolled = true;
+                break;
+            }
+        }
+
+        if !enrolled {
+            return Ok(false);
+        }
+
+        match (&self.trusted_device_use_case, trusted_device) {
+            (Some(trusted_device_use_case), Some(cookie)) => Ok(!trusted_device_use_case
+                .is_trusted(*user.id(), cookie)
+                .await?),
+            _ => Ok(true),
+        }
+    }
+
+    pub async fn change_password(
+        &self,
+        user_id: Uuid,
+        current_password: String,
+        new_password: String,
+    ) -> AppResult<()> {
+        let user = self
+            .user_repository
+            .find_by_id(&user_id.to_string())
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+
+        let is_valid = self
+            .hasher
+            .verify_password(current_password.as_str(), user.password())?;
+
+        if !is_valid {
+            return Err(AppError::Unauthorized);
+        }
+
+        let hashed_password = self.hasher.hash_password(new_password.as_str())?;
+        self.user_repository
+            .update_password(&user_id.to_string(), &hashed_password)
             .await?;
 
-        Ok((access_token, refresh_token))
+        if let Some(trusted_device_use_case) = &self.trusted_device_use_case {
+            trusted_device_use_case.revoke_all_devices(user_id).await?;
+        }
+
+        Ok(())
     }
 
     pub async fn revoke_token(&self, jti: &str, exp: i64) -> AppResult<()> {
@@ -126,26 +384,65 @@ impl AuthUseCase {
         Ok(())
     }
 
-    pub async fn refresh_token(&self, refresh_token: &str) -> AppResult<(String, String)> {
-        let user_id_str = self
+    pub async fn refresh_token(
+        &self,
+        refresh_token: &str,
+        client: ClientInfo,
+    ) -> AppResult<(String, String)> {
+        if Uuid::parse_str(refresh_token).is_ok() {
+            return self
+                .migrate_legacy_refresh_token(refresh_token, client)
+                .await;
+        }
+
+        let token_hash = self.refresh_token_provider.hash(refresh_token);
+
+        let mut session = self
             .token_cache_repository
-            .get_refresh_token(refresh_token)
+            .take_refresh_token(&token_hash)
             .await?
             .ok_or(AppError::InvalidToken)?;
 
-        let user_id = Uuid::parse_str(&user_id_str).map_err(|_| AppError::InvalidToken)?;
+        let device_hash = self.device_hash(&client);
+        if !session.is_bound_to(&client.client_id, &device_hash) {
+            return Err(AppError::InvalidToken);
+        }
+
+        let user = self
+            .user_repository
+            .find_by_id(&session.user_id.to_string())
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+
+        check_account_status(&user)?;
+
+        session.touch();
+        self.issue_tokens(&user, session, SessionLimit::Unlimited)
+            .await
+    }
 
-        let _user = self
+    async fn migrate_legacy_refresh_token(
+        &self,
+        refresh_token: &str,
+        client: ClientInfo,
+    ) -> AppResult<(String, String)> {
+        let user_id = self
+            .token_cache_repository
+            .take_legacy_refresh_token(refresh_token)
+            .await?
+            .ok_or(AppError::InvalidToken)?;
+
+        let user = self
             .user_repository
             .find_by_id(&user_id.to_string())
             .await?
             .ok_or(AppError::UserNotFound)?;
 
-        let access_token = self
-            .token_provider
-            .generate_token(&user_id.to_string(), Duration::minutes(15))?;
+        check_account_status(&user)?;
 
-        Ok((access_token, refresh_token.to_string()))
+        let session = self.new_session(user_id, &client);
+        self.issue_tokens(&user, session, self.session_limit(&user))
+            .await
     }
 
     pub async fn is_blacklisted(&self, jti: &str) -> AppResult<bool> {
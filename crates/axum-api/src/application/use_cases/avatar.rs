@@ -0,0 +1,139 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    application::app_error::{AppError, AppResult},
+    domain::{
+        entities::user::User,
+        repositories::{object_storage::ObjectStorage, user::UserRepository},
+    },
+    infra::images::ImageProcessor,
+};
+
+pub const MAX_AVATAR_BYTES: usize = 5 * 1024 * 1024;
+pub const AVATAR_CONTENT_TYPES: [&str; 3] = ["image/jpeg", "image/png", "image/webp"];
+
+const AVATAR_SIZES: [(&str, u32); 3] = [("small", 64), ("medium", 256), ("large", 512)];
+const DEFAULT_AVATAR_VARIANT: &str = "medium";
+
+pub struct AvatarUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    object_storage: Arc<dyn ObjectStorage>,
+    image_processor: Arc<dyn ImageProcessor>,
+    url_ttl: Duration,
+}
+
+impl AvatarUseCase {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        object_storage: Arc<dyn ObjectStorage>,
+        image_processor: Arc<dyn ImageProcessor>,
+        url_ttl: Duration,
+    ) -> Self {
+        Self {
+            user_repository,
+            object_storage,
+            image_processor,
+            url_ttl,
+        }
+    }
+
+    fn variant_key(&self, avatar_key: &str, variant: &str) -> String {
+        format!(
+            "{avatar_key}/{variant}.{}",
+            self.image_processor.extension()
+        )
+    }
+
+    pub async fn upload(
+        &self,
+        user_id: Uuid,
+        data: Vec<u8>,
+        content_type: &str,
+    ) -> AppResult<User> {
+        if data.len() > MAX_AVATAR_BYTES {
+            return Err(AppError::PayloadTooLarge);
+        }
+
+        if !AVATAR_CONTENT_TYPES.contains(&content_type) {
+            return Err(AppError::UnsupportedMediaType);
+        }
+
+        let mut user = self
+            .user_repository
+            .find_by_id(&user_id.to_string())
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+
+        let processor = self.image_processor.clone();
+        let content_type = content_type.to_string();
+        let variants = tokio::task::spawn_blocking(move || {
+            processor.resize_square(&data, &content_type, &AVATAR_SIZES)
+        })
+        .await
+        .map_err(|e| AppError::InvalidImage(e.to_string()))??;
+
+        let avatar_key = format!("avatars/{user_id}/{}", Uuid::new_v4());
+
+        for variant in variants {
+            self.object_storage
+                .put(
+                    &self.variant_key(&avatar_key, variant.name),
+                    variant.data,
+                    self.image_processor.content_type(),
+                )
+                .await?;
+        }
+
+        let previous = user.set_avatar(Some(avatar_key));
+        self.user_repository.update_avatar(&user).await?;
+
+        if let Some(previous) = previous {
+            self.delete_variants(&previous).await;
+        }
+
+        Ok(user)
+    }
+
+    pub async fn delete_variants(&self, avatar_key: &str) {
+        for (variant, _) in AVATAR_SIZES {
+            let key = self.variant_key(avatar_key, variant);
+
+            if let Err(e) = self.object_storage.delete(&key).await {
+                error!("Failed to delete avatar object {}: {}", key, e);
+            }
+        }
+    }
+
+    pub async fn avatar_urls(&self, user: &User) -> AppResult<BTreeMap<&'static str, String>> {
+        let mut urls = BTreeMap::new();
+
+        if let Some(avatar_key) = user.avatar_key() {
+            for (variant, _) in AVATAR_SIZES {
+                let url = self
+                    .object_storage
+                    .signed_url(&self.variant_key(avatar_key, variant), self.url_ttl)
+                    .await?;
+                urls.insert(variant, url);
+            }
+        }
+
+        Ok(urls)
+    }
+
+    pub async fn avatar_url(&self, user: &User) -> AppResult<Option<String>> {
+        match user.avatar_key() {
+            Some(avatar_key) => Ok(Some(
+                self.object_storage
+                    .signed_url(
+                        &self.variant_key(avatar_key, DEFAULT_AVATAR_VARIANT),
+                        self.url_ttl,
+                    )
+                    .await?,
+            )),
+            None => Ok(None),
+        }
+    }
+}
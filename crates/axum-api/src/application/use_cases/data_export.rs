@@ -0,0 +1,403 @@
+use std::{collections::BTreeMap, io::Write, sync::Arc, time::Duration};
+
+use chrono::Utc;
+use serde_json::{Value, json};
+use tracing::{error, warn};
+use uuid::Uuid;
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    application::app_error::{AppError, AppResult},
+    domain::{
+        entities::{
+            data_export::{DataExport, DataExportFormat, DataExportStatus},
+            user::User,
+        },
+        events::user::{UserDataExportReady, UserEventPublisher},
+        repositories::{
+            attribute::AttributeRepository, data_export::DataExportRepository,
+            object_storage::ObjectStorage, passkey::PasskeyRepository,
+            token_cache::TokenCacheRepository, trusted_device::TrustedDeviceRepository,
+        },
+    },
+};
+
+const EXPORT_BATCH_SIZE: usize = 100;
+
+#[async_trait::async_trait]
+pub trait UserDataSource: Send + Sync {
+    fn name(&self) -> &'static str;
+    async fn collect(&self, user: &User) -> AppResult<Value>;
+}
+
+pub struct SessionDataSource {
+    token_cache_repository: Arc<dyn TokenCacheRepository>,
+}
+
+impl SessionDataSource {
+    pub fn new(token_cache_repository: Arc<dyn TokenCacheRepository>) -> Self {
+        Self {
+            token_cache_repository,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserDataSource for SessionDataSource {
+    fn name(&self) -> &'static str {
+        "sessions"
+    }
+
+    async fn collect(&self, user: &User) -> AppResult<Value> {
+        let sessions = self
+            .token_cache_repository
+            .list_refresh_tokens(*user.id())
+            .await?
+            .into_iter()
+            .map(|(_, session)| {
+                json!({
+                    "client_id": session.client_id,
+                    "created_at": session.created_at,
+                    "last_active_at": session.last_active_at,
+                })
+            })
+            .collect();
+
+        Ok(Value::Array(sessions))
+    }
+}
+
+pub struct TrustedDeviceDataSource {
+    trusted_device_repository: Arc<dyn TrustedDeviceRepository>,
+}
+
+impl TrustedDeviceDataSource {
+    pub fn new(trusted_device_repository: Arc<dyn TrustedDeviceRepository>) -> Self {
+        Self {
+            trusted_device_repository,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserDataSource for TrustedDeviceDataSource {
+    fn name(&self) -> &'static str {
+        "trusted_devices"
+    }
+
+    async fn collect(&self, user: &User) -> AppResult<Value> {
+        let devices = self
+            .trusted_device_repository
+            .list_by_user(*user.id())
+            .await?;
+
+        serde_json::to_value(devices).map_err(|e| AppError::ExportFailed(e.to_string()))
+    }
+}
+
+pub struct PasskeyDataSource {
+    passkey_repository: Arc<dyn PasskeyRepository>,
+}
+
+impl PasskeyDataSource {
+    pub fn new(passkey_repository: Arc<dyn PasskeyRepository>) -> Self {
+        Self { passkey_repository }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserDataSource for PasskeyDataSource {
+    fn name(&self) -> &'static str {
+        "passkeys"
+    }
+
+    async fn collect(&self, user: &User) -> AppResult<Value> {
+        let passkeys = self
+            .passkey_repository
+            .list_by_user(*user.id())
+            .await?
+            .into_iter()
+            .map(|passkey| {
+                json!({
+                    "id": passkey.id,
+                    "name": passkey.name,
+                    "transports": passkey.transports,
+                    "created_at": passkey.created_at,
+                    "last_used_at": passkey.last_used_at,
+                })
+            })
+            .collect();
+
+        Ok(Value::Array(passkeys))
+    }
+}
+
+pub struct AttributeDataSource {
+    attribute_repository: Arc<dyn AttributeRepository>,
+}
+
+impl AttributeDataSource {
+    pub fn new(attribute_repository: Arc<dyn AttributeRepository>) -> Self {
+        Self {
+            attribute_repository,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserDataSource for AttributeDataSource {
+    fn name(&self) -> &'static str {
+        "attributes"
+    }
+
+    async fn collect(&self, user: &User) -> AppResult<Value> {
+        let attributes = self
+            .attribute_repository
+            .list_by_user(*user.id())
+            .await?
+            .into_iter()
+            .map(|attributes| (attributes.namespace, attributes.data))
+            .collect();
+
+        Ok(Value::Object(attributes))
+    }
+}
+
+#[derive(Clone)]
+pub struct DataExportUseCase {
+    export_repository: Arc<dyn DataExportRepository>,
+    object_storage: Arc<dyn ObjectStorage>,
+    event_publisher: Arc<dyn UserEventPublisher>,
+    sources: Vec<Arc<dyn UserDataSource>>,
+    url_ttl: Duration,
+    retention: Duration,
+    build_timeout: chrono::Duration,
+}
+
+impl DataExportUseCase {
+    pub fn new(
+        export_repository: Arc<dyn DataExportRepository>,
+        object_storage: Arc<dyn ObjectStorage>,
+        event_publisher: Arc<dyn UserEventPublisher>,
+        url_ttl: Duration,
+        retention: Duration,
+        build_timeout: chrono::Duration,
+    ) -> Self {
+        Self {
+            export_repository,
+            object_storage,
+            event_publisher,
+            sources: Vec::new(),
+            url_ttl,
+            retention,
+            build_timeout,
+        }
+    }
+
+    pub fn with_source(mut self, source: Arc<dyn UserDataSource>) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    pub async fn request_export(
+        &self,
+        user: &User,
+        format: DataExportFormat,
+    ) -> AppResult<DataExport> {
+        let export = DataExport::new(*user.id(), format);
+        self.export_repository
+            .save(&export, self.retention.as_secs())
+            .await?;
+
+        let this = self.clone();
+        let user = user.clone();
+        let pending = export.clone();
+        tokio::spawn(async move { this.build(user, pending).await });
+
+        Ok(export)
+    }
+
+    pub async fn get_export(
+        &self,
+        user_id: Uuid,
+        export_id: Uuid,
+    ) -> AppResult<(DataExport, Option<String>)> {
+        let export = self
+            .export_repository
+            .find(user_id, export_id)
+            .await?
+            .ok_or(AppError::DataExportNotFound)?;
+
+        let download_url = match (&export.status, &export.object_key) {
+            (DataExportStatus::Completed, Some(key)) => {
+                Some(self.object_storage.signed_url(key, self.url_ttl).await?)
+            }
+            _ => None,
+        };
+
+        Ok((export, download_url))
+    }
+
+    /// Fails exports still pending after the build timeout, e.g. because the instance
+    /// building them stopped. Their users can request a new export.
+    pub async fn fail_stalled_exports(&self) -> AppResult<usize> {
+        let requested_before = Utc::now() - self.build_timeout;
+        let mut failed = 0;
+
+        loop {
+            let stalled = self
+                .export_repository
+                .list_stalled(requested_before, EXPORT_BATCH_SIZE)
+                .await?;
+
+            if stalled.is_empty() {
+                return Ok(failed);
+            }
+
+            for mut export in stalled {
+                warn!("Data export {} stalled, marking it as failed", export.id);
+
+                export.fail();
+                self.export_repository
+                    .save(&export, self.retention.as_secs())
+                    .await?;
+                failed += 1;
+            }
+        }
+    }
+
+    /// Deletes the stored objects of exports that have expired.
+    pub async fn purge_expired_objects(&self) -> AppResult<usize> {
+        let mut purged = 0;
+
+        loop {
+            let keys = self
+                .export_repository
+                .list_expired_objects(Utc::now(), EXPORT_BATCH_SIZE)
+                .await?;
+
+            if keys.is_empty() {
+                return Ok(purged);
+            }
+
+            for key in keys {
+                self.delete_object(&key).await?;
+                purged += 1;
+            }
+        }
+    }
+
+    /// Deletes the stored exports of an erased user. Failures are logged, and objects
+    /// left behind are still deleted when they expire.
+    pub async fn delete_user_exports(&self, user_id: Uuid) {
+        let keys = match self.export_repository.list_objects(user_id).await {
+            Ok(keys) => keys,
+            Err(e) => {
+                error!("Failed to list data exports of user {}: {}", user_id, e);
+                return;
+            }
+        };
+
+        for key in keys {
+            if let Err(e) = self.delete_object(&key).await {
+                error!("Failed to delete data export object {}: {}", key, e);
+            }
+        }
+    }
+
+    async fn delete_object(&self, key: &str) -> AppResult<()> {
+        self.object_storage.delete(key).await?;
+        self.export_repository.forget_object(key).await?;
+
+        Ok(())
+    }
+
+    async fn build(&self, user: User, mut export: DataExport) {
+        if let Err(e) = self.try_build(&user, &mut export).await {
+            error!("Failed to build data export {}: {}", export.id, e);
+
+            export.fail();
+            if let Err(e) = self
+                .export_repository
+                .save(&export, self.retention.as_secs())
+                .await
+            {
+                error!("Failed to mark data export {} as failed: {}", export.id, e);
+            }
+        }
+    }
+
+    async fn try_build(&self, user: &User, export: &mut DataExport) -> AppResult<()> {
+        let mut sections = BTreeMap::new();
+        sections.insert(
+            "profile",
+            json!({
+                "id": user.id(),
+                "email": user.email(),
+                "name": user.name(),
+                "role": user.role().as_str(),
+                "status": user.status().as_str(),
+                "created_at": user.created_at(),
+                "updated_at": user.updated_at(),
+            }),
+        );
+
+        for source in &self.sources {
+            sections.insert(source.name(), source.collect(user).await?);
+        }
+
+        let body = match export.format {
+            DataExportFormat::Json => serde_json::to_vec_pretty(&sections)
+                .map_err(|e| AppError::ExportFailed(e.to_string()))?,
+            DataExportFormat::Zip => zip_sections(&sections)?,
+        };
+
+        let key = export.object_key();
+        self.object_storage
+            .put(&key, body, export.format.content_type())
+            .await?;
+
+        export.complete(key.clone());
+        self.export_repository
+            .save(export, self.retention.as_secs())
+            .await?;
+
+        let download_url = self.object_storage.signed_url(&key, self.url_ttl).await?;
+        let expires_at = Utc::now()
+            + chrono::Duration::from_std(self.url_ttl).unwrap_or_else(|_| chrono::Duration::zero());
+
+        let event = UserDataExportReady {
+            user_id: *user.id(),
+            email: user.email().to_string(),
+            export_id: export.id,
+            download_url,
+            expires_at,
+        };
+
+        if let Err(e) = self.event_publisher.publish_data_export_ready(event).await {
+            error!("Failed to publish UserDataExportReady event: {}", e);
+        }
+
+        Ok(())
+    }
+}
+
+fn zip_sections(sections: &BTreeMap<&str, Value>) -> AppResult<Vec<u8>> {
+    let export_error = |e: &dyn std::fmt::Display| AppError::ExportFailed(e.to_string());
+
+    let mut writer = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (name, value) in sections {
+        writer
+            .start_file(format!("{name}.json"), options)
+            .map_err(|e| export_error(&e))?;
+
+        let json = serde_json::to_vec_pretty(value).map_err(|e| export_error(&e))?;
+        writer.write_all(&json).map_err(|e| export_error(&e))?;
+    }
+
+    let cursor = writer.finish().map_err(|e| export_error(&e))?;
+
+    Ok(cursor.into_inner())
+}
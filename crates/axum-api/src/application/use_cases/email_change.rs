@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        app_error::{AppError, AppResult},
+        use_cases::trusted_device::TrustedDeviceUseCase,
+    },
+    domain::{
+        entities::{email_change::PendingEmailChange, user::User},
+        events::user::{EmailChangeRequested, UserEmailChanged, UserEventPublisher},
+        repositories::{
+            email_change::EmailChangeRepository, token_cache::TokenCacheRepository,
+            user::UserRepository,
+        },
+    },
+    infra::security::{
+        argon2::PasswordHasherTrait, email_change_token::EmailChangeTokenProvider,
+        refresh_token::RefreshTokenProvider,
+    },
+};
+
+const EMAIL_CHANGE_TTL_HOURS: i64 = 24;
+
+pub struct EmailChangeUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    email_change_repository: Arc<dyn EmailChangeRepository>,
+    token_cache_repository: Arc<dyn TokenCacheRepository>,
+    trusted_device_use_case: Arc<TrustedDeviceUseCase>,
+    hasher: Arc<dyn PasswordHasherTrait>,
+    token_provider: Arc<dyn RefreshTokenProvider>,
+    email_change_tokens: Arc<dyn EmailChangeTokenProvider>,
+    event_publisher: Arc<dyn UserEventPublisher>,
+}
+
+impl EmailChangeUseCase {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        email_change_repository: Arc<dyn EmailChangeRepository>,
+        token_cache_repository: Arc<dyn TokenCacheRepository>,
+        trusted_device_use_case: Arc<TrustedDeviceUseCase>,
+        hasher: Arc<dyn PasswordHasherTrait>,
+        token_provider: Arc<dyn RefreshTokenProvider>,
+        email_change_tokens: Arc<dyn EmailChangeTokenProvider>,
+        event_publisher: Arc<dyn UserEventPublisher>,
+    ) -> Self {
+        Self {
+            user_repository,
+            email_change_repository,
+            token_cache_repository,
+            trusted_device_use_case,
+            hasher,
+            token_provider,
+            email_change_tokens,
+            event_publisher,
+        }
+    }
+
+    async fn load_user(&self, user_id: Uuid) -> AppResult<User> {
+        self.user_repository
+            .find_by_id(&user_id.to_string())
+            .await?
+            .ok_or(AppError::UserNotFound)
+    }
+
+    pub async fn request_change(
+        &self,
+        user_id: Uuid,
+        new_email: String,
+        password: &str,
+    ) -> AppResult<DateTime<Utc>> {
+        let new_email = new_email.trim().to_lowercase();
+        let user = self.load_user(user_id).await?;
+
+        if !self.hasher.verify_password(password, user.password())? {
+            return Err(AppError::Unauthorized);
+        }
+
+        if user.email().eq_ignore_ascii_case(&new_email) {
+            return Err(AppError::ValidationError(vec![
+                "New email must differ from the current email".to_string(),
+            ]));
+        }
+
+        if self
+            .user_repository
+            .find_by_email(&new_email)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::EmailAlreadyExists(new_email));
+        }
+
+        let change_id = Uuid::new_v4();
+        let token = self.email_change_tokens.token(change_id);
+        let ttl = Duration::hours(EMAIL_CHANGE_TTL_HOURS);
+        let change = PendingEmailChange::new(user_id, user.email().to_string(), new_email, ttl);
+
+        self.email_change_repository
+            .store(
+                &self.token_provider.hash(&token),
+                &change,
+                ttl.num_seconds() as u64,
+            )
+            .await?;
+
+        let event = EmailChangeRequested {
+            user_id,
+            change_id,
+            old_email: change.old_email,
+            new_email: change.new_email,
+            expires_at: change.expires_at,
+        };
+
+        if let Err(e) = self
+            .event_publisher
+            .publish_email_change_requested(event)
+            .await
+        {
+            error!("Failed to publish EmailChangeRequested event: {}", e);
+        }
+
+        Ok(change.expires_at)
+    }
+
+    pub async fn confirm_change(&self, token: &str) -> AppResult<User> {
+        let change = self
+            .email_change_repository
+            .take(&self.token_provider.hash(token))
+            .await?
+            .ok_or(AppError::InvalidToken)?;
+
+        let mut user = self.load_user(change.user_id).await?;
+
+        if user.email() != change.old_email || !user.can_authenticate() {
+            return Err(AppError::InvalidToken);
+        }
+
+        user.change_email(change.new_email.clone());
+
+        if !self
+            .user_repository
+            .update_email(&user, &change.old_email)
+            .await?
+        {
+            return Err(self.rejected_change(&change).await?);
+        }
+
+        self.token_cache_repository
+            .revoke_all_refresh_tokens(change.user_id)
+            .await?;
+        self.trusted_device_use_case
+            .revoke_all_devices(change.user_id)
+            .await?;
+
+        let event = UserEmailChanged {
+            user_id: change.user_id,
+            old_email: change.old_email,
+            new_email: change.new_email,
+            changed_at: Utc::now(),
+        };
+
+        if let Err(e) = self.event_publisher.publish_email_changed(event).await {
+            error!("Failed to publish UserEmailChanged event: {}", e);
+        }
+
+        Ok(user)
+    }
+
+    /// Tells apart the two reasons `update_email` applies nothing: the account moved on
+    /// since the change was requested, or another account took the new address.
+    async fn rejected_change(&self, change: &PendingEmailChange) -> AppResult<AppError> {
+        let current = self
+            .user_repository
+            .find_by_id(&change.user_id.to_string())
+            .await?;
+
+        match current {
+            Some(user) if user.email().eq_ignore_ascii_case(&change.old_email) => {
+                Ok(AppError::EmailAlreadyExists(change.new_email.clone()))
+            }
+            _ => Ok(AppError::InvalidToken),
+        }
+    }
+}
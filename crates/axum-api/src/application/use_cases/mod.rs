@@ -1,2 +1,13 @@
+pub mod account;
+pub mod account_status;
+pub mod attribute;
 pub mod auth;
+pub mod avatar;
+pub mod data_export;
+pub mod email_change;
+pub mod outbox;
+pub mod passkey;
+pub mod trusted_device;
 pub mod user;
+pub mod user_admin;
+pub mod user_import;
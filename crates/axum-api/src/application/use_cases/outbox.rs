@@ -0,0 +1,195 @@
+use std::{
+    collections::HashSet,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use chrono::Utc;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use crate::{
+    application::app_error::AppResult,
+    domain::{
+        entities::outbox::OutboxBacklog, events::outbox::OutboxPublisher,
+        repositories::outbox::OutboxRepository,
+    },
+    infra::config::OutboxConfig,
+};
+
+/// Relay counters since startup, and the backlog as of the last time it was read.
+#[derive(Debug, Default)]
+pub struct OutboxMetrics {
+    dispatched: AtomicU64,
+    publish_failures: AtomicU64,
+    backlog: Mutex<OutboxBacklog>,
+}
+
+impl OutboxMetrics {
+    pub fn dispatched_total(&self) -> u64 {
+        self.dispatched.load(Ordering::Relaxed)
+    }
+
+    /// Failed publish attempts, including ones that later succeeded on a retry.
+    pub fn publish_failures_total(&self) -> u64 {
+        self.publish_failures.load(Ordering::Relaxed)
+    }
+
+    pub fn backlog(&self) -> OutboxBacklog {
+        self.backlog.lock().unwrap().clone()
+    }
+}
+
+pub struct OutboxUseCase {
+    outbox_repository: Arc<dyn OutboxRepository>,
+    publisher: Arc<dyn OutboxPublisher>,
+    config: OutboxConfig,
+    metrics: OutboxMetrics,
+    relay_id: Uuid,
+}
+
+impl OutboxUseCase {
+    pub fn new(
+        outbox_repository: Arc<dyn OutboxRepository>,
+        publisher: Arc<dyn OutboxPublisher>,
+        config: OutboxConfig,
+    ) -> Self {
+        Self {
+            outbox_repository,
+            publisher,
+            config,
+            metrics: OutboxMetrics::default(),
+            relay_id: Uuid::new_v4(),
+        }
+    }
+
+    pub fn metrics(&self) -> &OutboxMetrics {
+        &self.metrics
+    }
+
+    /// Publishes pending messages oldest first and returns how many were dispatched.
+    ///
+    /// A message that fails is retried on a later pass, after a backoff that doubles with
+    /// each failure, and later messages for the same aggregate wait behind it. Once it
+    /// has failed `max_attempts` times it is dead-lettered and no longer holds them up.
+    /// Delivery is at-least-once: a message whose dispatch could not be recorded after
+    /// publishing is published again.
+    ///
+    /// Only the instance holding the relay lease publishes; the others return 0. The lease
+    /// is renewed before every message, and a relay that lost it stops mid-pass.
+    pub async fn relay_pending(&self) -> AppResult<usize> {
+        if !self.hold_relay_lease().await? {
+            return Ok(0);
+        }
+
+        let now = Utc::now();
+        let messages = self
+            .outbox_repository
+            .list_pending(now, self.config.max_attempts, self.config.batch_size)
+            .await?;
+
+        let mut blocked = HashSet::new();
+        let mut dispatched = 0;
+
+        for message in messages {
+            if blocked.contains(&message.aggregate_id) {
+                continue;
+            }
+
+            if !self.hold_relay_lease().await? {
+                break;
+            }
+
+            match self.publisher.publish(&message).await {
+                Ok(()) => {
+                    self.outbox_repository.mark_dispatched(&message.id).await?;
+                    self.metrics.dispatched.fetch_add(1, Ordering::Relaxed);
+                    dispatched += 1;
+                }
+                Err(e) => {
+                    self.metrics
+                        .publish_failures
+                        .fetch_add(1, Ordering::Relaxed);
+
+                    if message.attempts + 1 >= self.config.max_attempts {
+                        error!(
+                            "Dead-lettered outbox message {} after {} attempts: {}",
+                            message.id,
+                            message.attempts + 1,
+                            e
+                        );
+                    } else {
+                        warn!("Failed to publish outbox message {}: {}", message.id, e);
+                    }
+
+                    self.outbox_repository
+                        .record_failure(
+                            &message.id,
+                            &e.to_string(),
+                            now + self.retry_delay(message.attempts),
+                        )
+                        .await?;
+                    blocked.insert(message.aggregate_id);
+                }
+            }
+        }
+
+        self.backlog().await?;
+
+        Ok(dispatched)
+    }
+
+    /// Deletes messages dispatched longer ago than the retention period and returns how
+    /// many, in batches so no single statement holds locks for long.
+    pub async fn purge_dispatched(&self) -> AppResult<u64> {
+        let before = Utc::now() - chrono::Duration::hours(self.config.retention_hours);
+        let mut purged = 0;
+
+        loop {
+            let deleted = self
+                .outbox_repository
+                .delete_dispatched(before, self.config.batch_size)
+                .await?;
+            purged += deleted;
+
+            if deleted < u64::from(self.config.batch_size) {
+                return Ok(purged);
+            }
+        }
+    }
+
+    async fn hold_relay_lease(&self) -> AppResult<bool> {
+        Ok(self
+            .outbox_repository
+            .acquire_relay_lease(
+                &self.relay_id,
+                Duration::from_millis(self.config.relay_lease_ms),
+            )
+            .await?)
+    }
+
+    /// Reads the backlog and records it for the metrics endpoint.
+    pub async fn backlog(&self) -> AppResult<OutboxBacklog> {
+        let backlog = self
+            .outbox_repository
+            .backlog(self.config.max_attempts)
+            .await?;
+        *self.metrics.backlog.lock().unwrap() = backlog.clone();
+
+        Ok(backlog)
+    }
+
+    /// Wait before retrying a message that has now failed `previous_attempts + 1` times.
+    fn retry_delay(&self, previous_attempts: u32) -> chrono::Duration {
+        let delay_ms = self
+            .config
+            .retry_backoff_ms
+            .saturating_mul(1 << previous_attempts.min(32))
+            .min(self.config.max_backoff_ms);
+
+        chrono::Duration::milliseconds(i64::try_from(delay_ms).unwrap_or(i64::MAX))
+    }
+}
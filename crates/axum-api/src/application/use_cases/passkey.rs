@@ -0,0 +1,297 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        app_error::{AppError, AppResult},
+        use_cases::{auth::AuthUseCase, trusted_device::TrustedDeviceUseCase},
+    },
+    domain::{
+        entities::{
+            passkey::{Passkey, PasskeyCeremony, PasskeyCeremonyKind},
+            session::ClientInfo,
+            user::User,
+        },
+        repositories::{
+            passkey::{PasskeyCeremonyRepository, PasskeyRepository},
+            user::UserRepository,
+        },
+    },
+    infra::security::webauthn::{
+        AuthenticatorAssertion, AuthenticatorAttestation, PasskeyVerifier,
+    },
+};
+
+const CEREMONY_TTL_SECS: u64 = 300;
+
+pub struct PasskeyChallenge {
+    pub ceremony_id: String,
+    pub challenge: String,
+    pub credentials: Vec<Passkey>,
+}
+
+pub struct PasskeyLogin {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub trusted_device_cookie: Option<String>,
+}
+
+pub struct PasskeyUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    passkey_repository: Arc<dyn PasskeyRepository>,
+    ceremony_repository: Arc<dyn PasskeyCeremonyRepository>,
+    verifier: Arc<dyn PasskeyVerifier>,
+    auth_use_case: Arc<AuthUseCase>,
+    trusted_device_use_case: Arc<TrustedDeviceUseCase>,
+}
+
+impl PasskeyUseCase {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        passkey_repository: Arc<dyn PasskeyRepository>,
+        ceremony_repository: Arc<dyn PasskeyCeremonyRepository>,
+        verifier: Arc<dyn PasskeyVerifier>,
+        auth_use_case: Arc<AuthUseCase>,
+        trusted_device_use_case: Arc<TrustedDeviceUseCase>,
+    ) -> Self {
+        Self {
+            user_repository,
+            passkey_repository,
+            ceremony_repository,
+            verifier,
+            auth_use_case,
+            trusted_device_use_case,
+        }
+    }
+
+    async fn start_ceremony(
+        &self,
+        kind: PasskeyCeremonyKind,
+        user_id: Option<Uuid>,
+        client: Option<&ClientInfo>,
+    ) -> AppResult<PasskeyChallenge> {
+        let credentials = match user_id {
+            Some(user_id) => self.passkey_repository.list_by_user(user_id).await?,
+            None => Vec::new(),
+        };
+
+        let ceremony_id = Uuid::new_v4().to_string();
+        let ceremony = PasskeyCeremony {
+            kind,
+            challenge: self.verifier.generate_challenge(),
+            user_id,
+            client_id: client.map(|client| client.client_id.clone()),
+            device_hash: client.map(|client| self.auth_use_case.device_hash(client)),
+        };
+
+        self.ceremony_repository
+            .store(&ceremony_id, &ceremony, CEREMONY_TTL_SECS)
+            .await?;
+
+        Ok(PasskeyChallenge {
+            ceremony_id,
+            challenge: ceremony.challenge,
+            credentials,
+        })
+    }
+
+    async fn take_ceremony(
+        &self,
+        ceremony_id: &str,
+        kind: PasskeyCeremonyKind,
+    ) -> AppResult<PasskeyCeremony> {
+        match self.ceremony_repository.take(ceremony_id).await? {
+            Some(ceremony) if ceremony.kind == kind => Ok(ceremony),
+            _ => Err(AppError::PasskeyVerificationFailed(
+                "Unknown or expired ceremony".to_string(),
+            )),
+        }
+    }
+
+    async fn verify_assertion(
+        &self,
+        ceremony: &PasskeyCeremony,
+        credential_id: &[u8],
+        assertion: &AuthenticatorAssertion,
+        require_user_verification: bool,
+    ) -> AppResult<User> {
+        let passkey = self
+            .passkey_repository
+            .find_by_credential_id(credential_id)
+            .await?
+            .ok_or(AppError::PasskeyNotFound)?;
+
+        if ceremony
+            .user_id
+            .is_some_and(|user_id| user_id != passkey.user_id)
+        {
+            return Err(AppError::PasskeyNotFound);
+        }
+
+        let sign_count = self.verifier.verify_assertion(
+            &ceremony.challenge,
+            assertion,
+            &passkey.public_key,
+            passkey.sign_count,
+            require_user_verification,
+        )?;
+
+        self.passkey_repository
+            .update_sign_count(passkey.id, sign_count)
+            .await?;
+
+        self.user_repository
+            .find_by_id(&passkey.user_id.to_string())
+            .await?
+            .ok_or(AppError::UserNotFound)
+    }
+
+    pub async fn start_registration(&self, user_id: Uuid) -> AppResult<PasskeyChallenge> {
+        self.start_ceremony(PasskeyCeremonyKind::Registration, Some(user_id), None)
+            .await
+    }
+
+    pub async fn finish_registration(
+        &self,
+        user_id: Uuid,
+        ceremony_id: &str,
+        attestation: AuthenticatorAttestation,
+        transports: Vec<String>,
+        name: String,
+    ) -> AppResult<Passkey> {
+        let ceremony = self
+            .take_ceremony(ceremony_id, PasskeyCeremonyKind::Registration)
+            .await?;
+
+        if ceremony.user_id != Some(user_id) {
+            return Err(AppError::PasskeyVerificationFailed(
+                "Unknown or expired ceremony".to_string(),
+            ));
+        }
+
+        let credential =
+            self.verifier
+                .verify_registration(&ceremony.challenge, &attestation, false)?;
+
+        if self
+            .passkey_repository
+            .find_by_credential_id(&credential.credential_id)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::PasskeyAlreadyRegistered);
+        }
+
+        let passkey = Passkey::new(
+            user_id,
+            credential.credential_id,
+            credential.public_key,
+            credential.sign_count,
+            transports,
+            name,
+        );
+        self.passkey_repository.create(&passkey).await?;
+
+        Ok(passkey)
+    }
+
+    pub async fn list_passkeys(&self, user_id: Uuid) -> AppResult<Vec<Passkey>> {
+        Ok(self.passkey_repository.list_by_user(user_id).await?)
+    }
+
+    pub async fn delete_passkey(&self, user_id: Uuid, passkey_id: Uuid) -> AppResult<()> {
+        if !self.passkey_repository.delete(user_id, passkey_id).await? {
+            return Err(AppError::PasskeyNotFound);
+        }
+
+        Ok(())
+    }
+
+    pub async fn start_login(&self) -> AppResult<PasskeyChallenge> {
+        self.start_ceremony(PasskeyCeremonyKind::Authentication, None, None)
+            .await
+    }
+
+    pub async fn finish_login(
+        &self,
+        ceremony_id: &str,
+        credential_id: &[u8],
+        assertion: AuthenticatorAssertion,
+        client: ClientInfo,
+    ) -> AppResult<(String, String)> {
+        let ceremony = self
+            .take_ceremony(ceremony_id, PasskeyCeremonyKind::Authentication)
+            .await?;
+
+        let user = self
+            .verify_assertion(&ceremony, credential_id, &assertion, true)
+            .await?;
+
+        self.auth_use_case.complete_login(&user, client).await
+    }
+
+    pub async fn start_second_factor(
+        &self,
+        mfa_token: &str,
+        client: &ClientInfo,
+    ) -> AppResult<PasskeyChallenge> {
+        let user = self
+            .auth_use_case
+            .take_mfa_challenge(mfa_token, client)
+            .await?;
+
+        self.start_ceremony(
+            PasskeyCeremonyKind::SecondFactor,
+            Some(*user.id()),
+            Some(client),
+        )
+        .await
+    }
+
+    pub async fn finish_second_factor(
+        &self,
+        ceremony_id: &str,
+        credential_id: &[u8],
+        assertion: AuthenticatorAssertion,
+        client: ClientInfo,
+        remember_device: Option<String>,
+    ) -> AppResult<PasskeyLogin> {
+        let ceremony = self
+            .take_ceremony(ceremony_id, PasskeyCeremonyKind::SecondFactor)
+            .await?;
+
+        let device_hash = self.auth_use_case.device_hash(&client);
+        if ceremony.client_id.as_deref() != Some(client.client_id.as_str())
+            || ceremony.device_hash.as_deref() != Some(device_hash.as_str())
+        {
+            return Err(AppError::PasskeyVerificationFailed(
+                "Unknown or expired ceremony".to_string(),
+            ));
+        }
+
+        let user = self
+            .verify_assertion(&ceremony, credential_id, &assertion, false)
+            .await?;
+
+        let (access_token, refresh_token) =
+            self.auth_use_case.complete_login(&user, client).await?;
+
+        let trusted_device_cookie = match remember_device {
+            Some(label) => {
+                let (_, cookie) = self
+                    .trusted_device_use_case
+                    .trust_device(*user.id(), label)
+                    .await?;
+                Some(cookie)
+            }
+            None => None,
+        };
+
+        Ok(PasskeyLogin {
+            access_token,
+            refresh_token,
+            trusted_device_cookie,
+        })
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+use uuid::Uuid;
+
+use crate::{
+    application::app_error::{AppError, AppResult},
+    domain::{
+        entities::trusted_device::TrustedDevice,
+        repositories::trusted_device::TrustedDeviceRepository,
+    },
+    infra::security::device_cookie::DeviceCookieSigner,
+};
+
+pub struct TrustedDeviceUseCase {
+    trusted_device_repository: Arc<dyn TrustedDeviceRepository>,
+    cookie_signer: Arc<dyn DeviceCookieSigner>,
+    trust_period: Duration,
+}
+
+impl TrustedDeviceUseCase {
+    pub fn new(
+        trusted_device_repository: Arc<dyn TrustedDeviceRepository>,
+        cookie_signer: Arc<dyn DeviceCookieSigner>,
+        trust_period: Duration,
+    ) -> Self {
+        Self {
+            trusted_device_repository,
+            cookie_signer,
+            trust_period,
+        }
+    }
+
+    pub fn trust_period(&self) -> Duration {
+        self.trust_period
+    }
+
+    pub async fn trust_device(
+        &self,
+        user_id: Uuid,
+        label: String,
+    ) -> AppResult<(TrustedDevice, String)> {
+        let device = TrustedDevice::new(user_id, label, self.trust_period);
+        self.trusted_device_repository.save(&device).await?;
+
+        let cookie = self.cookie_signer.sign(user_id, device.id);
+
+        Ok((device, cookie))
+    }
+
+    pub async fn is_trusted(&self, user_id: Uuid, cookie: &str) -> AppResult<bool> {
+        let Some((cookie_user_id, device_id)) = self.cookie_signer.verify(cookie) else {
+            return Ok(false);
+        };
+
+        if cookie_user_id != user_id {
+            return Ok(false);
+        }
+
+        let Some(mut device) = self
+            .trusted_device_repository
+            .find(user_id, device_id)
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        if device.is_expired() {
+            self.trusted_device_repository
+                .revoke(user_id, device_id)
+                .await?;
+            return Ok(false);
+        }
+
+        device.touch();
+        self.trusted_device_repository.save(&device).await?;
+
+        Ok(true)
+    }
+
+    pub async fn list_devices(&self, user_id: Uuid) -> AppResult<Vec<TrustedDevice>> {
+        Ok(self.trusted_device_repository.list_by_user(user_id).await?)
+    }
+
+    pub async fn revoke_device(&self, user_id: Uuid, device_id: Uuid) -> AppResult<()> {
+        if !self
+            .trusted_device_repository
+            .revoke(user_id, device_id)
+            .await?
+        {
+            return Err(AppError::TrustedDeviceNotFound);
+        }
+
+        Ok(())
+    }
+
+    pub async fn revoke_all_devices(&self, user_id: Uuid) -> AppResult<()> {
+        Ok(self.trusted_device_repository.revoke_all(user_id).await?)
+    }
+}
@@ -1,17 +1,57 @@
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
+use tracing::error;
+use uuid::Uuid;
+
 use crate::{
-    application::app_error::AppResult,
-    domain::{entities::user::User, repositories::user::UserRepository},
+    application::app_error::{AppError, AppResult},
+    domain::{
+        entities::user::{ProfileChanges, User},
+        events::user::{UserEventPublisher, UserUpdated},
+        repositories::{
+            error::RepositoryError,
+            user::{UserCursor, UserListQuery, UserRepository},
+        },
+    },
 };
 
+/// Maps the error of a failed user insert. A unique violation the database did not name
+/// may still be the email index, so the email is looked up to tell.
+pub async fn create_user_error(
+    user_repository: &dyn UserRepository,
+    email: String,
+    error: RepositoryError,
+) -> AppError {
+    if error.is_unnamed_unique_violation() {
+        match user_repository.find_by_email(&email).await {
+            Ok(Some(_)) => return AppError::EmailAlreadyExists(email),
+            Ok(None) => {}
+            Err(e) => return e.into(),
+        }
+    }
+
+    if error.is_duplicate_email() {
+        return AppError::EmailAlreadyExists(email);
+    }
+
+    error.into()
+}
+
 pub struct UserUseCase {
     user_repository: Arc<dyn UserRepository>,
+    event_publisher: Arc<dyn UserEventPublisher>,
 }
 
 impl UserUseCase {
-    pub fn new(user_repository: Arc<dyn UserRepository>) -> Self {
-        Self { user_repository }
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        event_publisher: Arc<dyn UserEventPublisher>,
+    ) -> Self {
+        Self {
+            user_repository,
+            event_publisher,
+        }
     }
 
     pub async fn get_user_by_id(&self, id: &str) -> AppResult<Option<User>> {
@@ -19,4 +59,67 @@ impl UserUseCase {
 
         Ok(user)
     }
+
+    pub async fn list_users(
+        &self,
+        mut query: UserListQuery,
+    ) -> AppResult<(Vec<User>, Option<UserCursor>)> {
+        let limit = query.limit as usize;
+        query.limit += 1;
+
+        let mut users = self.user_repository.list(&query).await?;
+
+        let next_cursor = if users.len() > limit {
+            users.truncate(limit);
+            users.last().map(|user| UserCursor {
+                created_at: user.created_at(),
+                id: *user.id(),
+            })
+        } else {
+            None
+        };
+
+        Ok((users, next_cursor))
+    }
+
+    pub async fn update_profile(
+        &self,
+        user_id: Uuid,
+        expected_updated_at: Option<DateTime<Utc>>,
+        changes: ProfileChanges,
+    ) -> AppResult<User> {
+        let mut user = self
+            .user_repository
+            .find_by_id(&user_id.to_string())
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+
+        let current_updated_at = user.updated_at();
+        if expected_updated_at.is_some_and(|expected| expected != current_updated_at) {
+            return Err(AppError::PreconditionFailed);
+        }
+
+        let applied = user.apply_profile_changes(changes);
+        if applied.is_empty() {
+            return Ok(user);
+        }
+
+        let updated_user = self
+            .user_repository
+            .update_profile(&user, current_updated_at)
+            .await?
+            .ok_or(AppError::PreconditionFailed)?;
+
+        let event = UserUpdated {
+            user_id,
+            changes: applied,
+            updated_at: updated_user.updated_at(),
+        };
+
+        if let Err(e) = self.event_publisher.publish_user_updated(event).await {
+            error!("Failed to publish UserUpdated event: {}", e);
+        }
+
+        Ok(updated_user)
+    }
 }
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+use validator::{ValidateEmail, ValidateLength};
+
+use crate::{
+    application::{
+        app_error::{AppError, AppResult},
+        use_cases::{trusted_device::TrustedDeviceUseCase, user::create_user_error},
+    },
+    domain::{
+        entities::user::{Role, User},
+        events::{outbox::OutboxEvent, user::UserCreated},
+        repositories::{
+            error::RepositoryError, token_cache::TokenCacheRepository,
+            unit_of_work::UnitOfWorkFactory, user::UserRepository,
+        },
+    },
+    infra::security::argon2::PasswordHasherTrait,
+};
+
+const MIN_PASSWORD_LENGTH: u64 = 8;
+
+pub struct UserAdminUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    unit_of_work: Arc<dyn UnitOfWorkFactory>,
+    token_cache_repository: Arc<dyn TokenCacheRepository>,
+    trusted_device_use_case: Arc<TrustedDeviceUseCase>,
+    hasher: Arc<dyn PasswordHasherTrait>,
+}
+
+impl UserAdminUseCase {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        unit_of_work: Arc<dyn UnitOfWorkFactory>,
+        token_cache_repository: Arc<dyn TokenCacheRepository>,
+        trusted_device_use_case: Arc<TrustedDeviceUseCase>,
+        hasher: Arc<dyn PasswordHasherTrait>,
+    ) -> Self {
+        Self {
+            user_repository,
+            unit_of_work,
+            token_cache_repository,
+            trusted_device_use_case,
+            hasher,
+        }
+    }
+
+    fn password_error(password: &str) -> Option<String> {
+        (!password.validate_length(Some(MIN_PASSWORD_LENGTH), None, None))
+            .then(|| format!("Password must be at least {MIN_PASSWORD_LENGTH} characters"))
+    }
+
+    pub async fn find_user(&self, identifier: &str) -> AppResult<User> {
+        let user = match Uuid::parse_str(identifier) {
+            Ok(id) => self.user_repository.find_by_id(&id.to_string()).await?,
+            Err(_) => self.user_repository.find_by_email(identifier).await?,
+        };
+
+        user.ok_or(AppError::UserNotFound)
+    }
+
+    pub async fn create_admin(
+        &self,
+        email: String,
+        name: String,
+        password: &str,
+    ) -> AppResult<User> {
+        let mut errors = Vec::new();
+
+        if !email.validate_email() {
+            errors.push("Invalid email format".to_string());
+        }
+        if !name.validate_length(Some(1), Some(100), None) {
+            errors.push("Name must be 1-100 characters".to_string());
+        }
+        errors.extend(Self::password_error(password));
+        if !errors.is_empty() {
+            return Err(AppError::ValidationError(errors));
+        }
+
+        let mut user = User::new(email.clone(), self.hasher.hash_password(password)?, name);
+        user.set_role(Role::Admin);
+
+        let unit_of_work = self.unit_of_work.begin().await?;
+
+        let created_user = match unit_of_work.users().create(&user).await {
+            Ok(created_user) => created_user,
+            Err(e) => {
+                drop(unit_of_work);
+                return Err(create_user_error(self.user_repository.as_ref(), email, e).await);
+            }
+        };
+
+        let event = UserCreated {
+            user_id: *created_user.id(),
+            email: created_user.email().to_string(),
+        };
+        let message = event
+            .to_outbox_message()
+            .map_err(RepositoryError::SerializationError)?;
+        unit_of_work.outbox().enqueue(&message).await?;
+
+        unit_of_work.commit().await?;
+
+        Ok(created_user)
+    }
+
+    pub async fn reset_password(&self, user_id: Uuid, password: &str) -> AppResult<()> {
+        if let Some(error) = Self::password_error(password) {
+            return Err(AppError::ValidationError(vec![error]));
+        }
+
+        let hashed_password = self.hasher.hash_password(password)?;
+        self.user_repository
+            .update_password(&user_id.to_string(), &hashed_password)
+            .await?;
+
+        self.revoke_sessions(user_id).await
+    }
+
+    pub async fn active_sessions(&self, user_id: Uuid) -> AppResult<usize> {
+        Ok(self
+            .token_cache_repository
+            .list_refresh_tokens(user_id)
+            .await?
+            .len())
+    }
+
+    pub async fn revoke_sessions(&self, user_id: Uuid) -> AppResult<()> {
+        self.token_cache_repository
+            .revoke_all_refresh_tokens(user_id)
+            .await?;
+        self.trusted_device_use_case
+            .revoke_all_devices(user_id)
+            .await?;
+
+        Ok(())
+    }
+}
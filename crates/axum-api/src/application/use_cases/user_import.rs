@@ -0,0 +1,532 @@
+use std::{collections::HashSet, io, str::FromStr, sync::Arc};
+
+use csv_core::ReadRecordResult;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, Lines};
+use tracing::error;
+use validator::Validate;
+
+use crate::{
+    application::app_error::{AppError, AppResult},
+    domain::{
+        entities::user::User,
+        events::{outbox::OutboxEvent, user::UserCreated},
+        repositories::{
+            error::RepositoryError, unit_of_work::UnitOfWorkFactory, user::UserRepository,
+        },
+    },
+    infra::security::argon2::PasswordHasherTrait,
+};
+
+const IMPORT_BATCH_SIZE: usize = 200;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    #[default]
+    Csv,
+    Jsonl,
+}
+
+impl FromStr for ImportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ImportFormat::Csv),
+            "jsonl" => Ok(ImportFormat::Jsonl),
+            other => Err(format!("Unknown import format: {other}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImportOptions {
+    pub dry_run: bool,
+    pub send_welcome_events: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ImportRecord {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    name: String,
+
+    #[validate(email(message = "Invalid email format"))]
+    email: String,
+
+    #[serde(default)]
+    #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
+    password: Option<String>,
+
+    #[serde(default)]
+    password_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportRowStatus {
+    Created,
+    Valid,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportRowResult {
+    pub line: usize,
+    pub email: Option<String>,
+    pub status: ImportRowStatus,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub rows: Vec<ImportRowResult>,
+    /// Set when the stream failed part-way. Rows read before the failure were still
+    /// imported and are listed in `rows`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ImportReport {
+    fn push(
+        &mut self,
+        line: usize,
+        email: Option<String>,
+        errors: Vec<String>,
+        success: ImportRowStatus,
+    ) {
+        let status = if errors.is_empty() {
+            self.succeeded += 1;
+            success
+        } else {
+            self.failed += 1;
+            ImportRowStatus::Failed
+        };
+
+        self.total += 1;
+        self.rows.push(ImportRowResult {
+            line,
+            email,
+            status,
+            errors,
+        });
+    }
+}
+
+struct PendingRow {
+    line: usize,
+    record: ImportRecord,
+}
+
+pub struct UserImportUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    unit_of_work: Arc<dyn UnitOfWorkFactory>,
+    hasher: Arc<dyn PasswordHasherTrait>,
+}
+
+impl UserImportUseCase {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        unit_of_work: Arc<dyn UnitOfWorkFactory>,
+        hasher: Arc<dyn PasswordHasherTrait>,
+    ) -> Self {
+        Self {
+            user_repository,
+            unit_of_work,
+            hasher,
+        }
+    }
+
+    pub async fn import<R>(
+        &self,
+        reader: R,
+        format: ImportFormat,
+        options: ImportOptions,
+    ) -> AppResult<ImportReport>
+    where
+        R: AsyncBufRead + Unpin + Send,
+    {
+        let mut report = ImportReport {
+            dry_run: options.dry_run,
+            ..Default::default()
+        };
+
+        let mut records = ImportRecords::new(reader, format);
+        let mut seen_emails = HashSet::new();
+        let mut pending = Vec::with_capacity(IMPORT_BATCH_SIZE);
+
+        loop {
+            let (line_number, parsed) = match records.next().await {
+                Ok(Some(row)) => row,
+                Ok(None) => break,
+                Err(ReadError::InvalidHeader(e)) => {
+                    return Err(AppError::ValidationError(vec![format!(
+                        "Invalid CSV header: {e}"
+                    )]));
+                }
+                Err(ReadError::Io(e)) => {
+                    error!("Import stream failed: {}", e);
+                    report.error = Some(format!("Failed to read import: {e}"));
+                    break;
+                }
+            };
+
+            let record = match parsed {
+                Ok(record) => record,
+                Err(e) => {
+                    report.push(line_number, None, vec![e], ImportRowStatus::Failed);
+                    continue;
+                }
+            };
+
+            let errors = self.validate_record(&record, &mut seen_emails);
+            if !errors.is_empty() {
+                report.push(
+                    line_number,
+                    Some(record.email),
+                    errors,
+                    ImportRowStatus::Failed,
+                );
+                continue;
+            }
+
+            pending.push(PendingRow {
+                line: line_number,
+                record,
+            });
+
+            if pending.len() == IMPORT_BATCH_SIZE {
+                self.flush(&mut pending, options, &mut report).await;
+            }
+        }
+
+        self.flush(&mut pending, options, &mut report).await;
+
+        Ok(report)
+    }
+
+    fn validate_record(
+        &self,
+        record: &ImportRecord,
+        seen_emails: &mut HashSet<String>,
+    ) -> Vec<String> {
+        let mut errors: Vec<String> = match record.validate() {
+            Ok(()) => Vec::new(),
+            Err(e) => e
+                .field_errors()
+                .into_iter()
+                .flat_map(|(field, field_errors)| {
+                    field_errors.iter().map(move |error| match &error.message {
+                        Some(message) => message.to_string(),
+                        None => format!("{field} is invalid"),
+                    })
+                })
+                .collect(),
+        };
+
+        match (&record.password, &record.password_hash) {
+            (Some(_), Some(_)) => {
+                errors.push("Provide either password or password_hash, not both".to_string())
+            }
+            (None, None) => errors.push("Either password or password_hash is required".to_string()),
+            (None, Some(hash)) if !self.hasher.is_supported_hash(hash) => {
+                errors.push("Unsupported password hash format".to_string())
+            }
+            _ => {}
+        }
+
+        if errors.is_empty() && !seen_emails.insert(record.email.to_lowercase()) {
+            errors.push("Duplicate email in import file".to_string());
+        }
+
+        errors
+    }
+
+    async fn flush(
+        &self,
+        pending: &mut Vec<PendingRow>,
+        options: ImportOptions,
+        report: &mut ImportReport,
+    ) {
+        if pending.is_empty() {
+            return;
+        }
+
+        let rows = std::mem::take(pending);
+
+        let result = if options.dry_run {
+            self.check_batch(&rows).await
+        } else {
+            self.create_batch(&rows, options.send_welcome_events).await
+        };
+
+        let success = if options.dry_run {
+            ImportRowStatus::Valid
+        } else {
+            ImportRowStatus::Created
+        };
+
+        match result {
+            Ok(conflicts) => {
+                for row in rows {
+                    let errors = if conflicts.contains(&row.record.email.to_lowercase()) {
+                        vec![format!("Email {} already exists", row.record.email)]
+                    } else {
+                        Vec::new()
+                    };
+
+                    report.push(row.line, Some(row.record.email), errors, success);
+                }
+            }
+            Err(e) => {
+                error!("Failed to import user batch: {}", e);
+
+                for row in rows {
+                    report.push(
+                        row.line,
+                        Some(row.record.email),
+                        vec![e.to_string()],
+                        success,
+                    );
+                }
+            }
+        }
+    }
+
+    async fn check_batch(&self, rows: &[PendingRow]) -> AppResult<HashSet<String>> {
+        let emails: Vec<String> = rows.iter().map(|row| row.record.email.clone()).collect();
+
+        let existing = self.user_repository.find_existing_emails(&emails).await?;
+
+        Ok(existing.iter().map(|email| email.to_lowercase()).collect())
+    }
+
+    async fn create_batch(
+        &self,
+        rows: &[PendingRow],
+        send_welcome_events: bool,
+    ) -> AppResult<HashSet<String>> {
+        let hasher = self.hasher.clone();
+        let records: Vec<ImportRecord> = rows.iter().map(|row| row.record.clone()).collect();
+
+        let users = tokio::task::spawn_blocking(move || {
+            records
+                .into_iter()
+                .map(|record| {
+                    let password = match (record.password, record.password_hash) {
+                        (Some(password), _) => hasher.hash_password(&password)?,
+                        (None, Some(hash)) => hash,
+                        (None, None) => unreachable!("records are validated before import"),
+                    };
+
+                    Ok(User::new(record.email, password, record.name))
+                })
+                .collect::<AppResult<Vec<User>>>()
+        })
+        .await
+        .map_err(|e| AppError::PasswordHashingFailed(e.to_string()))??;
+
+        let unit_of_work = self.unit_of_work.begin().await?;
+
+        let created = unit_of_work.users().create_many(&users).await?;
+        let created_emails: HashSet<String> = created
+            .iter()
+            .map(|user| user.email().to_lowercase())
+            .collect();
+
+        if send_welcome_events {
+            for user in &created {
+                let event = UserCreated {
+                    user_id: *user.id(),
+                    email: user.email().to_string(),
+                };
+                let message = event
+                    .to_outbox_message()
+                    .map_err(RepositoryError::SerializationError)?;
+                unit_of_work.outbox().enqueue(&message).await?;
+            }
+        }
+
+        unit_of_work.commit().await?;
+
+        Ok(users
+            .iter()
+            .map(|user| user.email().to_lowercase())
+            .filter(|email| !created_emails.contains(email))
+            .collect())
+    }
+}
+
+/// Why reading an import stopped before the end of the stream.
+enum ReadError {
+    InvalidHeader(String),
+    Io(io::Error),
+}
+
+/// A record with the line it starts on, or why it could not be parsed.
+type ParsedRecord = (usize, Result<ImportRecord, String>);
+
+enum ImportRecords<R> {
+    Csv {
+        records: Box<CsvRecords<R>>,
+        headers: Option<csv::StringRecord>,
+    },
+    Jsonl {
+        lines: Lines<R>,
+        line: usize,
+    },
+}
+
+impl<R: AsyncBufRead + Unpin> ImportRecords<R> {
+    fn new(reader: R, format: ImportFormat) -> Self {
+        match format {
+            ImportFormat::Csv => ImportRecords::Csv {
+                records: Box::new(CsvRecords::new(reader)),
+                headers: None,
+            },
+            ImportFormat::Jsonl => ImportRecords::Jsonl {
+                lines: reader.lines(),
+                line: 0,
+            },
+        }
+    }
+
+    /// Returns the next non-blank record, or `None` at the end of the stream.
+    async fn next(&mut self) -> Result<Option<ParsedRecord>, ReadError> {
+        match self {
+            ImportRecords::Csv { records, headers } => {
+                while let Some((line, record)) = records.next().await.map_err(ReadError::Io)? {
+                    if record
+                        .as_ref()
+                        .is_ok_and(|record| record.iter().all(str::is_empty))
+                    {
+                        continue;
+                    }
+
+                    let Some(headers) = headers else {
+                        *headers = Some(record.map_err(ReadError::InvalidHeader)?);
+                        continue;
+                    };
+
+                    let parsed = record.and_then(|record| {
+                        record.deserialize(Some(headers)).map_err(|e| e.to_string())
+                    });
+
+                    return Ok(Some((line, parsed)));
+                }
+
+                Ok(None)
+            }
+            ImportRecords::Jsonl { lines, line } => {
+                while let Some(text) = lines.next_line().await.map_err(ReadError::Io)? {
+                    *line += 1;
+
+                    if text.trim().is_empty() {
+                        continue;
+                    }
+
+                    let parsed = serde_json::from_str(&text).map_err(|e| e.to_string());
+                    return Ok(Some((*line, parsed)));
+                }
+
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Parses CSV from an async stream with one parser for the whole stream, so quoted
+/// fields may span lines. Fields are trimmed.
+struct CsvRecords<R> {
+    reader: R,
+    parser: csv_core::Reader,
+    line: usize,
+    fields: Vec<u8>,
+    ends: Vec<usize>,
+}
+
+impl<R: AsyncBufRead + Unpin> CsvRecords<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            parser: csv_core::Reader::new(),
+            line: 1,
+            fields: vec![0; 1024],
+            ends: vec![0; 16],
+        }
+    }
+
+    /// Returns the next record with the line it starts on, or `None` at the end of the
+    /// stream. A record that is not valid UTF-8 is returned as an error message.
+    async fn next(&mut self) -> io::Result<Option<(usize, Result<csv::StringRecord, String>)>> {
+        self.skip_blank_lines().await?;
+
+        let line = self.line;
+        let (mut fields_len, mut ends_len) = (0, 0);
+
+        loop {
+            let input = self.reader.fill_buf().await?;
+            let (result, read, written, ended) = self.parser.read_record(
+                input,
+                &mut self.fields[fields_len..],
+                &mut self.ends[ends_len..],
+            );
+            self.line += newlines(&input[..read]);
+            self.reader.consume(read);
+            fields_len += written;
+            ends_len += ended;
+
+            match result {
+                ReadRecordResult::InputEmpty => {}
+                ReadRecordResult::OutputFull => self.fields.resize(self.fields.len() * 2, 0),
+                ReadRecordResult::OutputEndsFull => self.ends.resize(self.ends.len() * 2, 0),
+                ReadRecordResult::Record => {
+                    return Ok(Some((line, self.record(fields_len, ends_len))));
+                }
+                ReadRecordResult::End => return Ok(None),
+            }
+        }
+    }
+
+    /// Consumes line breaks before the next record so that its line number is exact.
+    async fn skip_blank_lines(&mut self) -> io::Result<()> {
+        loop {
+            let input = self.reader.fill_buf().await?;
+            let blank = input
+                .iter()
+                .take_while(|byte| matches!(byte, b'\r' | b'\n'))
+                .count();
+            let exhausted = !input.is_empty() && blank == input.len();
+
+            self.line += newlines(&input[..blank]);
+            self.reader.consume(blank);
+
+            if !exhausted {
+                return Ok(());
+            }
+        }
+    }
+
+    fn record(&self, fields_len: usize, ends_len: usize) -> Result<csv::StringRecord, String> {
+        let fields = std::str::from_utf8(&self.fields[..fields_len])
+            .map_err(|e| format!("Invalid UTF-8: {e}"))?;
+
+        let mut start = 0;
+        Ok(self.ends[..ends_len]
+            .iter()
+            .map(|&end| {
+                let field = fields[start..end].trim();
+                start = end;
+                field
+            })
+            .collect())
+    }
+}
+
+fn newlines(bytes: &[u8]) -> usize {
+    bytes.iter().filter(|&&byte| byte == b'\n').count()
+}
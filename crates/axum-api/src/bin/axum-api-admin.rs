@@ -0,0 +1,614 @@
+use std::{
+    io::{self, BufRead},
+    path::PathBuf,
+    sync::Arc,
+};
+
+use anyhow::{Context, Result};
+#[cfg(any(feature = "mssql-sqlx", feature = "mssql-tiberius"))]
+use axum_api::infra::migrations::MSSQL_MIGRATIONS;
+#[cfg(feature = "mssql-sqlx")]
+use axum_api::infra::{migrations::mssql_sqlx::SqlxMigrationExecutor, mssql_sqlx::init_mssql_db};
+#[cfg(feature = "mssql-tiberius")]
+use axum_api::infra::{
+    migrations::mssql_tiberius::TiberiusMigrationExecutor, mssql_tiberius::init_mssql_tiberius,
+};
+#[cfg(feature = "postgres")]
+use axum_api::infra::{
+    migrations::{POSTGRES_MIGRATIONS, postgres::PgMigrationExecutor},
+    postgres::init_postgres_db,
+};
+#[cfg(feature = "sqlite")]
+use axum_api::infra::{
+    migrations::{SQLITE_MIGRATIONS, sqlite::SqliteMigrationExecutor},
+    sqlite::init_sqlite_db,
+};
+use axum_api::{
+    adapters::http::app_state::AppState,
+    application::use_cases::user_import::{
+        ImportFormat, ImportOptions, ImportReport, ImportRowStatus,
+    },
+    domain::{
+        entities::user::{Role, User, UserStatus},
+        repositories::user::{SortDirection, UserFilter, UserListQuery},
+    },
+    infra::{
+        config::{AppConfig, DatabaseConfig},
+        migrations::{MigrationStatus, Migrator},
+        setup::init_app_state,
+    },
+};
+use chrono::{DateTime, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
+use dotenvy::dotenv;
+use rand::{Rng, distributions::Alphanumeric};
+use serde::Serialize;
+use tokio::{fs::File, io::BufReader};
+use uuid::Uuid;
+
+const EXPORT_PAGE_SIZE: u32 = 500;
+const GENERATED_PASSWORD_LENGTH: usize = 24;
+const SYSTEM_ACTOR: Uuid = Uuid::nil();
+
+#[derive(Parser)]
+#[command(name = "axum-api-admin", about = "Operational user management")]
+struct Cli {
+    /// Output format
+    #[arg(long, value_enum, global = true, default_value_t = OutputFormat::Table)]
+    output: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Table,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum MigrateAction {
+    /// Apply pending migrations
+    Up {
+        /// Stop after applying this version
+        #[arg(long)]
+        target: Option<i64>,
+    },
+
+    /// Revert the most recently applied migrations
+    Down {
+        /// Number of migrations to revert
+        #[arg(long, default_value_t = 1)]
+        steps: usize,
+    },
+
+    /// Show applied and pending migrations
+    Status,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Manage database schema migrations
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+
+    #[command(flatten)]
+    User(UserCommand),
+}
+
+/// Commands that run against the application state, unlike migrations which only need
+/// a database connection.
+#[derive(Subcommand)]
+enum UserCommand {
+    /// Import users from a CSV or JSONL file
+    Import {
+        /// Path to the file to import
+        file: PathBuf,
+
+        /// Input format; inferred from the file extension when omitted
+        #[arg(long)]
+        format: Option<ImportFormat>,
+
+        /// Validate every row without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Publish a UserCreated event for every imported user
+        #[arg(long)]
+        send_welcome: bool,
+    },
+
+    /// Create a user with the admin role
+    CreateAdmin {
+        #[arg(long)]
+        email: String,
+
+        #[arg(long)]
+        name: String,
+
+        /// Read the password from the first line of stdin instead of generating one
+        #[arg(long)]
+        password_stdin: bool,
+    },
+
+    /// Set a new password and revoke every session of the user
+    ResetPassword {
+        /// User ID or email
+        user: String,
+
+        /// Read the password from the first line of stdin instead of generating one
+        #[arg(long)]
+        password_stdin: bool,
+    },
+
+    /// Lock an account and revoke its sessions
+    Lock {
+        /// User ID or email
+        user: String,
+
+        #[arg(long)]
+        reason: String,
+
+        /// RFC 3339 timestamp after which the lock expires
+        #[arg(long)]
+        until: Option<DateTime<Utc>>,
+    },
+
+    /// Unlock a locked account
+    Unlock {
+        /// User ID or email
+        user: String,
+    },
+
+    /// Revoke every refresh token and trusted device of the user
+    RevokeSessions {
+        /// User ID or email
+        user: String,
+    },
+
+    /// Look up a user by ID or email
+    Lookup {
+        /// User ID or email
+        user: String,
+    },
+
+    /// Export users matching the given filters
+    ExportUsers {
+        #[arg(long)]
+        status: Option<UserStatus>,
+
+        #[arg(long)]
+        role: Option<Role>,
+
+        #[arg(long)]
+        email_prefix: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct UserRecord {
+    id: Uuid,
+    email: String,
+    name: String,
+    role: Role,
+    status: UserStatus,
+    status_reason: Option<String>,
+    status_until: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    active_sessions: Option<usize>,
+}
+
+impl From<&User> for UserRecord {
+    fn from(user: &User) -> Self {
+        Self {
+            id: *user.id(),
+            email: user.email().to_string(),
+            name: user.name().to_string(),
+            role: user.role(),
+            status: user.status(),
+            status_reason: user.status_reason().map(str::to_string),
+            status_until: user.status_until(),
+            created_at: user.created_at(),
+            updated_at: user.updated_at(),
+            active_sessions: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct PasswordResult {
+    user: UserRecord,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generated_password: Option<String>,
+}
+
+fn read_or_generate_password(password_stdin: bool) -> Result<(String, bool)> {
+    if password_stdin {
+        let mut password = String::new();
+        io::stdin()
+            .lock()
+            .read_line(&mut password)
+            .context("failed to read password from stdin")?;
+
+        return Ok((password.trim_end_matches(['\r', '\n']).to_string(), false));
+    }
+
+    let password = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(GENERATED_PASSWORD_LENGTH)
+        .map(char::from)
+        .collect();
+
+    Ok((password, true))
+}
+
+fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(index, header)| {
+            rows.iter()
+                .map(|row| row[index].chars().count())
+                .fold(header.len(), usize::max)
+        })
+        .collect();
+
+    let render = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", render(headers.to_vec()));
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("  ")
+    );
+    for row in rows {
+        println!("{}", render(row.iter().map(String::as_str).collect()));
+    }
+}
+
+fn print_users(output: OutputFormat, users: &[UserRecord]) -> Result<()> {
+    match output {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(users)?),
+        OutputFormat::Table => {
+            let with_sessions = users.iter().any(|user| user.active_sessions.is_some());
+            let mut headers = vec!["ID", "EMAIL", "NAME", "ROLE", "STATUS", "UNTIL", "CREATED"];
+            if with_sessions {
+                headers.push("SESSIONS");
+            }
+
+            let rows: Vec<Vec<String>> = users
+                .iter()
+                .map(|user| {
+                    let mut row = vec![
+                        user.id.to_string(),
+                        user.email.clone(),
+                        user.name.clone(),
+                        user.role.to_string(),
+                        user.status.to_string(),
+                        user.status_until
+                            .map(|until| until.to_rfc3339())
+                            .unwrap_or_default(),
+                        user.created_at.to_rfc3339(),
+                    ];
+                    if with_sessions {
+                        row.push(user.active_sessions.unwrap_or_default().to_string());
+                    }
+                    row
+                })
+                .collect();
+
+            print_table(&headers, &rows);
+        }
+    }
+
+    Ok(())
+}
+
+fn print_password_result(output: OutputFormat, result: PasswordResult) -> Result<()> {
+    match output {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&result)?),
+        OutputFormat::Table => {
+            print_users(output, std::slice::from_ref(&result.user))?;
+            if let Some(password) = result.generated_password {
+                println!();
+                println!("Generated password: {password}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_import_report(output: OutputFormat, report: &ImportReport) -> Result<()> {
+    match output {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(report)?),
+        OutputFormat::Table => {
+            let rows: Vec<Vec<String>> = report
+                .rows
+                .iter()
+                .map(|row| {
+                    vec![
+                        row.line.to_string(),
+                        row.email.clone().unwrap_or_default(),
+                        match row.status {
+                            ImportRowStatus::Created => "created",
+                            ImportRowStatus::Valid => "valid",
+                            ImportRowStatus::Failed => "failed",
+                        }
+                        .to_string(),
+                        row.errors.join("; "),
+                    ]
+                })
+                .collect();
+
+            print_table(&["LINE", "EMAIL", "STATUS", "ERRORS"], &rows);
+            println!();
+            println!(
+                "{} rows, {} succeeded, {} failed{}",
+                report.total,
+                report.succeeded,
+                report.failed,
+                if report.dry_run { " (dry run)" } else { "" }
+            );
+
+            if let Some(error) = &report.error {
+                println!("Import stopped early: {error}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_migrations(output: OutputFormat, statuses: &[MigrationStatus]) -> Result<()> {
+    match output {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(statuses)?),
+        OutputFormat::Table => {
+            let rows: Vec<Vec<String>> = statuses
+                .iter()
+                .map(|status| {
+                    vec![
+                        status.version.to_string(),
+                        status.name.clone(),
+                        status.state.as_str().to_string(),
+                        status
+                            .applied_at
+                            .map(|applied_at| applied_at.to_rfc3339())
+                            .unwrap_or_default(),
+                    ]
+                })
+                .collect();
+
+            print_table(&["VERSION", "NAME", "STATE", "APPLIED"], &rows);
+        }
+    }
+
+    Ok(())
+}
+
+async fn migrate(action: MigrateAction, output: OutputFormat) -> Result<()> {
+    let config = AppConfig::from_env();
+    let migrator = match &config.database {
+        #[cfg(feature = "mssql-sqlx")]
+        DatabaseConfig::MssqlSqlx(mssql) => Migrator::new(
+            Arc::new(SqlxMigrationExecutor::new(init_mssql_db(mssql).await?)),
+            MSSQL_MIGRATIONS,
+        ),
+        #[cfg(not(feature = "mssql-sqlx"))]
+        DatabaseConfig::MssqlSqlx(_) => {
+            anyhow::bail!("DB_DRIVER=mssql_sqlx requires the `mssql-sqlx` feature")
+        }
+        #[cfg(feature = "mssql-tiberius")]
+        DatabaseConfig::MssqlTiberius(mssql) => Migrator::new(
+            Arc::new(TiberiusMigrationExecutor::new(
+                init_mssql_tiberius(mssql).await?,
+            )),
+            MSSQL_MIGRATIONS,
+        ),
+        #[cfg(not(feature = "mssql-tiberius"))]
+        DatabaseConfig::MssqlTiberius(_) => {
+            anyhow::bail!("DB_DRIVER=mssql_tiberius requires the `mssql-tiberius` feature")
+        }
+        #[cfg(feature = "postgres")]
+        DatabaseConfig::Postgres(postgres) => Migrator::new(
+            Arc::new(PgMigrationExecutor::new(init_postgres_db(postgres).await?)),
+            POSTGRES_MIGRATIONS,
+        ),
+        #[cfg(not(feature = "postgres"))]
+        DatabaseConfig::Postgres(_) => {
+            anyhow::bail!("DB_DRIVER=postgres requires the `postgres` feature")
+        }
+        #[cfg(feature = "sqlite")]
+        DatabaseConfig::Sqlite(sqlite) => Migrator::new(
+            Arc::new(SqliteMigrationExecutor::new(init_sqlite_db(sqlite).await?)),
+            SQLITE_MIGRATIONS,
+        ),
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseConfig::Sqlite(_) => {
+            anyhow::bail!("DB_DRIVER=sqlite requires the `sqlite` feature")
+        }
+    };
+
+    match action {
+        MigrateAction::Up { target } => {
+            migrator.up(target).await?;
+        }
+        MigrateAction::Down { steps } => {
+            migrator.down(steps).await?;
+        }
+        MigrateAction::Status => {}
+    }
+
+    print_migrations(output, &migrator.status().await?)
+}
+
+async fn export_users(app_state: &AppState, filter: UserFilter) -> Result<Vec<UserRecord>> {
+    let mut records = Vec::new();
+    let mut after = None;
+
+    loop {
+        let (users, next_cursor) = app_state
+            .user_use_case
+            .list_users(UserListQuery {
+                filter: filter.clone(),
+                direction: SortDirection::Asc,
+                after,
+                limit: EXPORT_PAGE_SIZE,
+            })
+            .await?;
+
+        records.extend(users.iter().map(UserRecord::from));
+
+        match next_cursor {
+            Some(cursor) => after = Some(cursor),
+            None => return Ok(records),
+        }
+    }
+}
+
+async fn run(command: UserCommand, output: OutputFormat) -> Result<()> {
+    let app_state = init_app_state().await?;
+    let admin = &app_state.user_admin_use_case;
+
+    match command {
+        UserCommand::Import {
+            file,
+            format,
+            dry_run,
+            send_welcome,
+        } => {
+            let format = format.unwrap_or_else(|| {
+                match file.extension().and_then(|extension| extension.to_str()) {
+                    Some("jsonl") | Some("ndjson") => ImportFormat::Jsonl,
+                    _ => ImportFormat::Csv,
+                }
+            });
+
+            let reader = BufReader::new(File::open(&file).await?);
+            let report = app_state
+                .user_import_use_case
+                .import(
+                    reader,
+                    format,
+                    ImportOptions {
+                        dry_run,
+                        send_welcome_events: send_welcome,
+                    },
+                )
+                .await?;
+
+            print_import_report(output, &report)?;
+        }
+        UserCommand::CreateAdmin {
+            email,
+            name,
+            password_stdin,
+        } => {
+            let (password, generated) = read_or_generate_password(password_stdin)?;
+            let user = admin.create_admin(email, name, &password).await?;
+
+            print_password_result(
+                output,
+                PasswordResult {
+                    user: UserRecord::from(&user),
+                    generated_password: generated.then_some(password),
+                },
+            )?;
+        }
+        UserCommand::ResetPassword {
+            user,
+            password_stdin,
+        } => {
+            let user = admin.find_user(&user).await?;
+            let (password, generated) = read_or_generate_password(password_stdin)?;
+            admin.reset_password(*user.id(), &password).await?;
+
+            print_password_result(
+                output,
+                PasswordResult {
+                    user: UserRecord::from(&user),
+                    generated_password: generated.then_some(password),
+                },
+            )?;
+        }
+        UserCommand::Lock {
+            user,
+            reason,
+            until,
+        } => {
+            let user = admin.find_user(&user).await?;
+            let user = app_state
+                .account_use_case
+                .lock(SYSTEM_ACTOR, *user.id(), reason, until)
+                .await?;
+
+            print_users(output, &[UserRecord::from(&user)])?;
+        }
+        UserCommand::Unlock { user } => {
+            let user = admin.find_user(&user).await?;
+            let user = app_state.account_use_case.unlock(*user.id()).await?;
+
+            print_users(output, &[UserRecord::from(&user)])?;
+        }
+        UserCommand::RevokeSessions { user } => {
+            let user = admin.find_user(&user).await?;
+            admin.revoke_sessions(*user.id()).await?;
+
+            let mut record = UserRecord::from(&user);
+            record.active_sessions = Some(admin.active_sessions(*user.id()).await?);
+            print_users(output, &[record])?;
+        }
+        UserCommand::Lookup { user } => {
+            let user = admin.find_user(&user).await?;
+
+            let mut record = UserRecord::from(&user);
+            record.active_sessions = Some(admin.active_sessions(*user.id()).await?);
+            print_users(output, &[record])?;
+        }
+        UserCommand::ExportUsers {
+            status,
+            role,
+            email_prefix,
+        } => {
+            let filter = UserFilter {
+                email_prefix,
+                status,
+                role,
+                ..UserFilter::default()
+            };
+
+            print_users(output, &export_users(&app_state, filter).await?)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Migrate { action } => migrate(action, cli.output).await,
+        Command::User(command) => run(command, cli.output).await,
+    }
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+pub const MAX_NAMESPACE_LENGTH: usize = 64;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributeSchema {
+    pub namespace: String,
+    pub schema: Value,
+    pub user_writable: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserAttributes {
+    pub user_id: Uuid,
+    pub namespace: String,
+    pub data: Value,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub fn is_valid_namespace(namespace: &str) -> bool {
+    let mut chars = namespace.chars();
+
+    namespace.len() <= MAX_NAMESPACE_LENGTH
+        && chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+pub fn is_valid_attribute_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataExportFormat {
+    #[default]
+    Json,
+    Zip,
+}
+
+impl DataExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DataExportFormat::Json => "json",
+            DataExportFormat::Zip => "zip",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            DataExportFormat::Json => "application/json",
+            DataExportFormat::Zip => "application/zip",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataExportStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataExport {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub format: DataExportFormat,
+    pub status: DataExportStatus,
+    pub object_key: Option<String>,
+    pub requested_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl DataExport {
+    pub fn new(user_id: Uuid, format: DataExportFormat) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            format,
+            status: DataExportStatus::Pending,
+            object_key: None,
+            requested_at: Utc::now(),
+            completed_at: None,
+        }
+    }
+
+    /// Prefix shared by the object keys of every export of `user_id`.
+    pub fn object_prefix(user_id: Uuid) -> String {
+        format!("exports/{user_id}/")
+    }
+
+    pub fn object_key(&self) -> String {
+        format!(
+            "{}{}.{}",
+            Self::object_prefix(self.user_id),
+            self.id,
+            self.format.extension()
+        )
+    }
+
+    pub fn complete(&mut self, object_key: String) {
+        self.status = DataExportStatus::Completed;
+        self.object_key = Some(object_key);
+        self.completed_at = Some(Utc::now());
+    }
+
+    pub fn fail(&mut self) {
+        self.status = DataExportStatus::Failed;
+        self.completed_at = Some(Utc::now());
+    }
+}
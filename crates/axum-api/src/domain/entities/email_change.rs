@@ -0,0 +1,26 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingEmailChange {
+    pub user_id: Uuid,
+    pub old_email: String,
+    pub new_email: String,
+    pub requested_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl PendingEmailChange {
+    pub fn new(user_id: Uuid, old_email: String, new_email: String, ttl: Duration) -> Self {
+        let now = Utc::now();
+
+        Self {
+            user_id,
+            old_email,
+            new_email,
+            requested_at: now,
+            expires_at: now + ttl,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+/// Time-ordered primary key for rows in clustered tables.
+///
+/// SQL Server sorts UNIQUEIDENTIFIER by its last six bytes first, so a UUIDv7 would
+/// still land at random positions in the index. These ids carry the Unix time in
+/// milliseconds in those bytes instead, like `NEWSEQUENTIALID()`, and are random
+/// elsewhere. The result is an RFC 9562 version 8 UUID with 74 random bits.
+///
+/// PostgreSQL and SQLite compare ids from the first byte, which is random, so there the
+/// ids land at random positions in the primary key index just as v4 ids did.
+pub fn new_id() -> Uuid {
+    let mut bytes: [u8; 16] = rand::random();
+    let millis = Utc::now().timestamp_millis().to_be_bytes();
+    bytes[10..].copy_from_slice(&millis[2..]);
+
+    Uuid::new_v8(bytes)
+}
@@ -1 +1,9 @@
+pub mod attribute;
+pub mod data_export;
+pub mod email_change;
+pub mod id;
+pub mod outbox;
+pub mod passkey;
+pub mod session;
+pub mod trusted_device;
 pub mod user;
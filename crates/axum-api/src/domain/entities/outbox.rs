@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::entities::id::new_id;
+
+/// An event stored in the same transaction as the change it describes, until the relay
+/// publishes it. Messages for one aggregate are published in the order they were stored.
+#[derive(Debug, Clone)]
+pub struct OutboxMessage {
+    pub id: Uuid,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    pub payload: String,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    /// Set after a failure; the message is not published again before then.
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OutboxMessage {
+    pub fn new(aggregate_id: Uuid, event_type: &str, payload: String) -> Self {
+        Self {
+            id: new_id(),
+            aggregate_id,
+            event_type: event_type.to_string(),
+            payload,
+            attempts: 0,
+            last_error: None,
+            next_attempt_at: None,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Messages not yet published. `failing` counts pending ones with at least one failed
+/// attempt, and `dead_lettered` those the relay gave up on after too many.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OutboxBacklog {
+    pub pending: u64,
+    pub failing: u64,
+    pub dead_lettered: u64,
+    pub oldest_pending_at: Option<DateTime<Utc>>,
+}
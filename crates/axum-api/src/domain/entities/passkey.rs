@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::entities::id::new_id;
+
+#[derive(Debug, Clone)]
+pub struct Passkey {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub credential_id: Vec<u8>,
+    pub public_key: Vec<u8>,
+    pub sign_count: u32,
+    pub transports: Vec<String>,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl Passkey {
+    pub fn new(
+        user_id: Uuid,
+        credential_id: Vec<u8>,
+        public_key: Vec<u8>,
+        sign_count: u32,
+        transports: Vec<String>,
+        name: String,
+    ) -> Self {
+        Self {
+            id: new_id(),
+            user_id,
+            credential_id,
+            public_key,
+            sign_count,
+            transports,
+            name,
+            created_at: Utc::now(),
+            last_used_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PasskeyCeremonyKind {
+    Registration,
+    Authentication,
+    SecondFactor,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasskeyCeremony {
+    pub kind: PasskeyCeremonyKind,
+    pub challenge: String,
+    pub user_id: Option<Uuid>,
+    pub client_id: Option<String>,
+    pub device_hash: Option<String>,
+}
@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub client_id: String,
+    pub device_fingerprint: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshSession {
+    pub user_id: Uuid,
+    pub client_id: String,
+    pub device_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub last_active_at: DateTime<Utc>,
+}
+
+impl RefreshSession {
+    pub fn new(user_id: Uuid, client_id: String, device_hash: String) -> Self {
+        let now = Utc::now();
+
+        Self {
+            user_id,
+            client_id,
+            device_hash,
+            created_at: now,
+            last_active_at: now,
+        }
+    }
+
+    pub fn touch(&mut self) {
+        self.last_active_at = Utc::now();
+    }
+
+    pub fn is_bound_to(&self, client_id: &str, device_hash: &str) -> bool {
+        self.client_id == client_id && self.device_hash == device_hash
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MfaChallenge {
+    pub user_id: Uuid,
+    pub client_id: String,
+    pub device_hash: String,
+}
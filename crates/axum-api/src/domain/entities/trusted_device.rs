@@ -0,0 +1,36 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrustedDevice {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub label: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl TrustedDevice {
+    pub fn new(user_id: Uuid, label: String, trust_period: Duration) -> Self {
+        let now = Utc::now();
+
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            label,
+            created_at: now,
+            last_used_at: now,
+            expires_at: now + trust_period,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+
+    pub fn touch(&mut self) {
+        self.last_used_at = Utc::now();
+    }
+}
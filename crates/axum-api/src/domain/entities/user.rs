@@ -1,12 +1,164 @@
+use std::{fmt, str::FromStr};
+
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use uuid::Uuid;
 
+use crate::domain::entities::id::new_id;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    #[default]
+    User,
+    Admin,
+}
+
+impl Role {
+    pub const ALL: [Role; 2] = [Role::User, Role::Admin];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user" => Ok(Role::User),
+            "admin" => Ok(Role::Admin),
+            other => Err(format!("Unknown role: {other}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserStatus {
+    #[default]
+    Active,
+    Deactivated,
+    Locked,
+    Suspended,
+    PendingDeletion,
+    Deleted,
+}
+
+impl UserStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserStatus::Active => "active",
+            UserStatus::Deactivated => "deactivated",
+            UserStatus::Locked => "locked",
+            UserStatus::Suspended => "suspended",
+            UserStatus::PendingDeletion => "pending_deletion",
+            UserStatus::Deleted => "deleted",
+        }
+    }
+
+    pub fn can_transition_to(&self, to: UserStatus) -> bool {
+        let allowed: &[UserStatus] = match self {
+            UserStatus::Active => &[
+                UserStatus::Deactivated,
+                UserStatus::Locked,
+                UserStatus::Suspended,
+                UserStatus::PendingDeletion,
+            ],
+            UserStatus::Deactivated => &[
+                UserStatus::Active,
+                UserStatus::Suspended,
+                UserStatus::PendingDeletion,
+            ],
+            UserStatus::Locked => &[
+                UserStatus::Active,
+                UserStatus::Locked,
+                UserStatus::Suspended,
+                UserStatus::PendingDeletion,
+            ],
+            UserStatus::Suspended => &[
+                UserStatus::Active,
+                UserStatus::Suspended,
+                UserStatus::PendingDeletion,
+            ],
+            UserStatus::PendingDeletion => &[UserStatus::Active, UserStatus::Deleted],
+            UserStatus::Deleted => &[],
+        };
+
+        allowed.contains(&to)
+    }
+}
+
+impl fmt::Display for UserStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for UserStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "active" => Ok(UserStatus::Active),
+            "deactivated" => Ok(UserStatus::Deactivated),
+            "locked" => Ok(UserStatus::Locked),
+            "suspended" => Ok(UserStatus::Suspended),
+            "pending_deletion" => Ok(UserStatus::PendingDeletion),
+            "deleted" => Ok(UserStatus::Deleted),
+            other => Err(format!("Unknown user status: {other}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("Account cannot transition from {from} to {to}")]
+pub struct InvalidStatusTransition {
+    pub from: UserStatus,
+    pub to: UserStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusChange {
+    pub from: UserStatus,
+    pub to: UserStatus,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileChanges {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl ProfileChanges {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct User {
     id: Uuid,
     email: String,
     password: String,
     name: String,
+    role: Role,
+    status: UserStatus,
+    status_reason: Option<String>,
+    status_until: Option<DateTime<Utc>>,
+    avatar_key: Option<String>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
@@ -16,20 +168,31 @@ impl User {
         let now = Utc::now();
 
         User {
-            id: Uuid::new_v4(),
+            id: new_id(),
             email,
             password,
             name,
+            role: Role::User,
+            status: UserStatus::Active,
+            status_reason: None,
+            status_until: None,
+            avatar_key: None,
             created_at: now,
             updated_at: now,
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn from_db(
         id: Uuid,
         email: String,
         password: String,
         name: String,
+        role: Role,
+        status: UserStatus,
+        status_reason: Option<String>,
+        status_until: Option<DateTime<Utc>>,
+        avatar_key: Option<String>,
         created_at: DateTime<Utc>,
         updated_at: DateTime<Utc>,
     ) -> Self {
@@ -38,6 +201,11 @@ impl User {
             email,
             password,
             name,
+            role,
+            status,
+            status_reason,
+            status_until,
+            avatar_key,
             created_at,
             updated_at,
         }
@@ -59,6 +227,112 @@ impl User {
         &self.name
     }
 
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    pub fn status(&self) -> UserStatus {
+        self.status
+    }
+
+    pub fn status_reason(&self) -> Option<&str> {
+        self.status_reason.as_deref()
+    }
+
+    pub fn status_until(&self) -> Option<DateTime<Utc>> {
+        self.status_until
+    }
+
+    pub fn avatar_key(&self) -> Option<&str> {
+        self.avatar_key.as_deref()
+    }
+
+    pub fn restriction_expired(&self) -> bool {
+        matches!(self.status, UserStatus::Locked | UserStatus::Suspended)
+            && self.status_until.is_some_and(|until| until <= Utc::now())
+    }
+
+    pub fn can_authenticate(&self) -> bool {
+        self.status == UserStatus::Active || self.restriction_expired()
+    }
+
+    fn transition(
+        &mut self,
+        to: UserStatus,
+        reason: Option<String>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<StatusChange, InvalidStatusTransition> {
+        let from = self.status;
+
+        if !from.can_transition_to(to) {
+            return Err(InvalidStatusTransition { from, to });
+        }
+
+        self.status = to;
+        self.status_reason = reason;
+        self.status_until = until;
+
+        Ok(StatusChange {
+            from,
+            to,
+            changed_at: Utc::now(),
+        })
+    }
+
+    pub fn activate(&mut self) -> Result<StatusChange, InvalidStatusTransition> {
+        self.transition(UserStatus::Active, None, None)
+    }
+
+    pub fn deactivate(&mut self) -> Result<StatusChange, InvalidStatusTransition> {
+        self.transition(UserStatus::Deactivated, None, None)
+    }
+
+    pub fn lock(
+        &mut self,
+        reason: String,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<StatusChange, InvalidStatusTransition> {
+        self.transition(UserStatus::Locked, Some(reason), until)
+    }
+
+    pub fn suspend(
+        &mut self,
+        reason: String,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<StatusChange, InvalidStatusTransition> {
+        self.transition(UserStatus::Suspended, Some(reason), until)
+    }
+
+    pub fn schedule_erasure(
+        &mut self,
+        erase_at: DateTime<Utc>,
+    ) -> Result<StatusChange, InvalidStatusTransition> {
+        self.transition(UserStatus::PendingDeletion, None, Some(erase_at))
+    }
+
+    pub fn anonymize(&mut self) -> Result<StatusChange, InvalidStatusTransition> {
+        let change = self.transition(UserStatus::Deleted, None, None)?;
+
+        self.email = format!("deleted-{}@users.invalid", self.id);
+        self.password = String::new();
+        self.name = "Deleted user".to_string();
+        self.avatar_key = None;
+
+        Ok(change)
+    }
+
+    pub fn set_avatar(&mut self, avatar_key: Option<String>) -> Option<String> {
+        std::mem::replace(&mut self.avatar_key, avatar_key)
+    }
+
+    pub fn set_role(&mut self, role: Role) {
+        self.role = role;
+    }
+
+    pub fn change_email(&mut self, email: String) {
+        self.email = email;
+    }
+
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
@@ -66,4 +340,15 @@ impl User {
     pub fn updated_at(&self) -> DateTime<Utc> {
         self.updated_at
     }
+
+    pub fn apply_profile_changes(&mut self, changes: ProfileChanges) -> ProfileChanges {
+        let mut applied = ProfileChanges::default();
+
+        if let Some(name) = changes.name.filter(|name| *name != self.name) {
+            self.name = name.clone();
+            applied.name = Some(name);
+        }
+
+        applied
+    }
 }
@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::outbox::OutboxMessage,
+    events::{
+        error::KafkaResult,
+        user::{UserCreated, UserDeleted},
+    },
+};
+
+/// Events published through the outbox instead of directly.
+pub trait OutboxEvent: Serialize {
+    const EVENT_TYPE: &'static str;
+
+    fn aggregate_id(&self) -> Uuid;
+
+    fn to_outbox_message(&self) -> serde_json::Result<OutboxMessage> {
+        Ok(OutboxMessage::new(
+            self.aggregate_id(),
+            Self::EVENT_TYPE,
+            serde_json::to_string(self)?,
+        ))
+    }
+}
+
+impl OutboxEvent for UserCreated {
+    const EVENT_TYPE: &'static str = "UserCreated";
+
+    fn aggregate_id(&self) -> Uuid {
+        self.user_id
+    }
+}
+
+impl OutboxEvent for UserDeleted {
+    const EVENT_TYPE: &'static str = "UserDeleted";
+
+    fn aggregate_id(&self) -> Uuid {
+        self.user_id
+    }
+}
+
+#[async_trait]
+pub trait OutboxPublisher: Send + Sync {
+    async fn publish(&self, message: &OutboxMessage) -> KafkaResult<()>;
+}
@@ -1,7 +1,11 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 
-use crate::domain::events::error::KafkaResult;
+use crate::domain::{
+    entities::user::{ProfileChanges, StatusChange, User, UserStatus},
+    events::error::KafkaResult,
+};
 
 #[derive(Debug, Clone, Serialize, serde::Deserialize)]
 pub struct UserCreated {
@@ -9,7 +13,74 @@ pub struct UserCreated {
     pub email: String,
 }
 
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+pub struct UserUpdated {
+    pub user_id: uuid::Uuid,
+    pub changes: ProfileChanges,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+pub struct UserDeleted {
+    pub user_id: uuid::Uuid,
+    pub deleted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+pub struct UserDataExportReady {
+    pub user_id: uuid::Uuid,
+    pub email: String,
+    pub export_id: uuid::Uuid,
+    pub download_url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+pub struct EmailChangeRequested {
+    pub user_id: uuid::Uuid,
+    /// The confirmation token is derived from this id; see `EmailChangeTokenProvider`.
+    pub change_id: uuid::Uuid,
+    pub old_email: String,
+    pub new_email: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+pub struct UserEmailChanged {
+    pub user_id: uuid::Uuid,
+    pub old_email: String,
+    pub new_email: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+pub struct UserStatusChanged {
+    pub user_id: uuid::Uuid,
+    pub from: UserStatus,
+    pub to: UserStatus,
+    pub reason: Option<String>,
+    pub until: Option<DateTime<Utc>>,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl UserStatusChanged {
+    pub fn new(user: &User, change: &StatusChange) -> Self {
+        Self {
+            user_id: *user.id(),
+            from: change.from,
+            to: change.to,
+            reason: user.status_reason().map(str::to_string),
+            until: user.status_until(),
+            changed_at: change.changed_at,
+        }
+    }
+}
+
 #[async_trait]
 pub trait UserEventPublisher: Send + Sync {
-    async fn publish_user_created(&self, event: UserCreated) -> KafkaResult<()>;
+    async fn publish_user_updated(&self, event: UserUpdated) -> KafkaResult<()>;
+    async fn publish_data_export_ready(&self, event: UserDataExportReady) -> KafkaResult<()>;
+    async fn publish_email_change_requested(&self, event: EmailChangeRequested) -> KafkaResult<()>;
+    async fn publish_email_changed(&self, event: UserEmailChanged) -> KafkaResult<()>;
+    async fn publish_status_changed(&self, event: UserStatusChanged) -> KafkaResult<()>;
 }
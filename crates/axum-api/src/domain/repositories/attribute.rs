@@ -0,0 +1,32 @@
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::attribute::{AttributeSchema, UserAttributes},
+    repositories::error::RepositoryResult,
+};
+
+#[async_trait::async_trait]
+pub trait AttributeRepository: Send + Sync {
+    async fn upsert_schema(
+        &self,
+        namespace: &str,
+        schema: &str,
+        user_writable: bool,
+    ) -> RepositoryResult<AttributeSchema>;
+    async fn find_schema(&self, namespace: &str) -> RepositoryResult<Option<AttributeSchema>>;
+    async fn list_schemas(&self) -> RepositoryResult<Vec<AttributeSchema>>;
+    async fn delete_schema(&self, namespace: &str) -> RepositoryResult<bool>;
+    async fn upsert(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+        data: &str,
+    ) -> RepositoryResult<UserAttributes>;
+    async fn find(
+        &self,
+        user_id: Uuid,
+        namespace: &str,
+    ) -> RepositoryResult<Option<UserAttributes>>;
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<UserAttributes>>;
+    async fn delete(&self, user_id: Uuid, namespace: &str) -> RepositoryResult<bool>;
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{entities::data_export::DataExport, repositories::error::RepositoryResult};
+
+#[async_trait::async_trait]
+pub trait DataExportRepository: Send + Sync {
+    /// Stores `export` for `ttl_secs`. Pending exports are indexed until they settle, and
+    /// the object of a completed export is tracked until it expires with the export.
+    async fn save(&self, export: &DataExport, ttl_secs: u64) -> RepositoryResult<()>;
+    async fn find(&self, user_id: Uuid, export_id: Uuid) -> RepositoryResult<Option<DataExport>>;
+    /// Exports still pending that were requested before `requested_before`, oldest first.
+    async fn list_stalled(
+        &self,
+        requested_before: DateTime<Utc>,
+        limit: usize,
+    ) -> RepositoryResult<Vec<DataExport>>;
+    /// Keys of tracked export objects whose exports expired at or before `now`.
+    async fn list_expired_objects(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> RepositoryResult<Vec<String>>;
+    /// Keys of every tracked export object of `user_id`.
+    async fn list_objects(&self, user_id: Uuid) -> RepositoryResult<Vec<String>>;
+    /// Stops tracking an object once it has been deleted from storage.
+    async fn forget_object(&self, object_key: &str) -> RepositoryResult<()>;
+}
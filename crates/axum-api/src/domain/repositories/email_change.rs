@@ -0,0 +1,14 @@
+use crate::domain::{
+    entities::email_change::PendingEmailChange, repositories::error::RepositoryResult,
+};
+
+#[async_trait::async_trait]
+pub trait EmailChangeRepository: Send + Sync {
+    async fn store(
+        &self,
+        token_hash: &str,
+        change: &PendingEmailChange,
+        ttl_secs: u64,
+    ) -> RepositoryResult<()>;
+    async fn take(&self, token_hash: &str) -> RepositoryResult<Option<PendingEmailChange>>;
+}
@@ -1,11 +1,60 @@
+#[cfg(feature = "mssql-tiberius")]
 use bb8::RunError;
+#[cfg(feature = "mssql-tiberius")]
 use bb8_tiberius::Error as Bb8TiberiusError;
 use thiserror::Error;
 
+/// Database error codes raised when a unique index rejects a row.
+const UNIQUE_VIOLATION_CODES: &[&str] = &[
+    "23505", // PostgreSQL unique_violation
+    "1555",  // SQLite SQLITE_CONSTRAINT_PRIMARYKEY
+    "2067",  // SQLite SQLITE_CONSTRAINT_UNIQUE
+];
+
+/// Names under which the unique index on `users.email` is reported. SQLite names the
+/// indexed column instead of the index.
+const USERS_EMAIL_CONSTRAINTS: &[&str] = &["idx_users_email_unique", "users.email"];
+
+/// SQL Server error numbers for duplicate keys: 2601 for unique indexes, 2627 for unique
+/// and primary key constraints.
+#[cfg(any(feature = "mssql-sqlx", feature = "mssql-tiberius"))]
+const MSSQL_UNIQUE_VIOLATION_NUMBERS: &[u32] = &[2601, 2627];
+
+/// Index or constraint named by a SQL Server 2601 or 2627 message, e.g. "Cannot insert
+/// duplicate key row in object 'dbo.users' with unique index 'idx_users_email_unique'."
+///
+/// Messages are localized to the login's language and only English ones are recognized;
+/// in other languages the violation is reported without a constraint name.
+#[cfg(any(feature = "mssql-sqlx", feature = "mssql-tiberius"))]
+fn mssql_violated_constraint(message: &str) -> Option<&str> {
+    if !message.contains("Cannot insert duplicate key") {
+        return None;
+    }
+
+    let (_, rest) = message
+        .split_once("with unique index '")
+        .or_else(|| message.split_once(" constraint '"))?;
+
+    rest.split_once('\'').map(|(constraint, _)| constraint)
+}
+
+/// Error number of a SQL Server error raised through sqlx, which keeps the number private
+/// and only shows it in the error's `Debug` output.
+#[cfg(feature = "mssql-sqlx")]
+fn mssql_error_number(error: &sqlx::mssql::MssqlDatabaseError) -> Option<u32> {
+    let debug = format!("{error:?}");
+    let (_, rest) = debug.split_once("\", number: ")?;
+
+    rest.split_once(',')?.0.parse().ok()
+}
+
 #[derive(Error, Debug)]
 pub enum RepositoryError {
     #[error("SQL failure: {0}")]
-    SqlXFailure(#[from] sqlx::Error),
+    SqlXFailure(sqlx::Error),
+
+    #[error("Unique constraint {constraint} violated")]
+    UniqueViolation { constraint: String },
 
     #[error("Invalid UUID format")]
     InvalidUuidFormat,
@@ -13,9 +62,11 @@ pub enum RepositoryError {
     #[error("Redis failure: {0}")]
     RedisFailure(#[from] redis::RedisError),
 
+    #[cfg(feature = "mssql-tiberius")]
     #[error("Tiberius failure: {0}")]
-    TiberiusError(#[from] tiberius::error::Error),
+    TiberiusError(tiberius::error::Error),
 
+    #[cfg(feature = "mssql-tiberius")]
     #[error("Connection pool failure: {0}")]
     PoolFailure(#[from] RunError<Bb8TiberiusError>),
 
@@ -24,6 +75,114 @@ pub enum RepositoryError {
 
     #[error("Data conversion error: {0}")]
     ConversionError(String),
+
+    #[error("Storage failure: {0}")]
+    StorageFailure(String),
+
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+}
+
+impl RepositoryError {
+    /// Whether a write was rejected because another user already has the email, as
+    /// opposed to any other unique index, such as the primary key.
+    pub fn is_duplicate_email(&self) -> bool {
+        matches!(
+            self,
+            RepositoryError::UniqueViolation { constraint }
+                if USERS_EMAIL_CONSTRAINTS.contains(&constraint.as_str())
+        )
+    }
+
+    /// Whether a unique index rejected a write without saying which one, as SQL Server
+    /// does in languages other than English. Callers that need to know whether the email
+    /// was taken have to look it up.
+    pub fn is_unnamed_unique_violation(&self) -> bool {
+        matches!(
+            self,
+            RepositoryError::UniqueViolation { constraint } if constraint.is_empty()
+        )
+    }
+
+    /// Whether the database could not be reached, as opposed to a query or its result
+    /// being rejected.
+    pub fn is_connection_failure(&self) -> bool {
+        match self {
+            RepositoryError::SqlXFailure(error) => matches!(
+                error,
+                sqlx::Error::Io(_)
+                    | sqlx::Error::Tls(_)
+                    | sqlx::Error::PoolTimedOut
+                    | sqlx::Error::PoolClosed
+                    | sqlx::Error::WorkerCrashed
+            ),
+            #[cfg(feature = "mssql-tiberius")]
+            RepositoryError::TiberiusError(error) => matches!(
+                error,
+                tiberius::error::Error::Io { .. }
+                    | tiberius::error::Error::Tls(_)
+                    | tiberius::error::Error::Routing { .. }
+            ),
+            // The pool only fails while connecting or waiting for a connection.
+            #[cfg(feature = "mssql-tiberius")]
+            RepositoryError::PoolFailure(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl From<sqlx::Error> for RepositoryError {
+    fn from(error: sqlx::Error) -> Self {
+        if let Some(database_error) = error.as_database_error()
+            && let Some(code) = database_error.code()
+            && UNIQUE_VIOLATION_CODES.contains(&code.as_ref())
+        {
+            // SQLite only names the violated columns in its message.
+            let constraint = database_error.constraint().unwrap_or_else(|| {
+                database_error
+                    .message()
+                    .trim_start_matches("UNIQUE constraint failed: ")
+            });
+
+            return RepositoryError::UniqueViolation {
+                constraint: constraint.to_string(),
+            };
+        }
+
+        #[cfg(feature = "mssql-sqlx")]
+        if let Some(database_error) = error.as_database_error()
+            && let Some(mssql_error) =
+                database_error.try_downcast_ref::<sqlx::mssql::MssqlDatabaseError>()
+            && mssql_error_number(mssql_error)
+                .is_some_and(|number| MSSQL_UNIQUE_VIOLATION_NUMBERS.contains(&number))
+        {
+            let constraint =
+                mssql_violated_constraint(database_error.message()).unwrap_or_default();
+
+            return RepositoryError::UniqueViolation {
+                constraint: constraint.to_string(),
+            };
+        }
+
+        RepositoryError::SqlXFailure(error)
+    }
+}
+
+#[cfg(feature = "mssql-tiberius")]
+impl From<tiberius::error::Error> for RepositoryError {
+    fn from(error: tiberius::error::Error) -> Self {
+        if let tiberius::error::Error::Server(token) = &error
+            && MSSQL_UNIQUE_VIOLATION_NUMBERS.contains(&token.code())
+        {
+            let constraint = mssql_violated_constraint(token.message()).unwrap_or_default();
+
+            return RepositoryError::UniqueViolation {
+                constraint: constraint.to_string(),
+            };
+        }
+
+        RepositoryError::TiberiusError(error)
+    }
 }
 
 pub type RepositoryResult<T> = Result<T, RepositoryError>;
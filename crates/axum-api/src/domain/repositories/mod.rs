@@ -1,3 +1,12 @@
+pub mod attribute;
+pub mod data_export;
+pub mod email_change;
 pub mod error;
+pub mod object_storage;
+pub mod outbox;
+pub mod passkey;
+pub mod second_factor;
 pub mod token_cache;
+pub mod trusted_device;
+pub mod unit_of_work;
 pub mod user;
@@ -0,0 +1,11 @@
+use std::time::Duration;
+
+use crate::domain::repositories::error::RepositoryResult;
+
+#[async_trait::async_trait]
+pub trait ObjectStorage: Send + Sync {
+    async fn put(&self, key: &str, body: Vec<u8>, content_type: &str) -> RepositoryResult<()>;
+    async fn get(&self, key: &str) -> RepositoryResult<Option<Vec<u8>>>;
+    async fn delete(&self, key: &str) -> RepositoryResult<()>;
+    async fn signed_url(&self, key: &str, expires_in: Duration) -> RepositoryResult<String>;
+}
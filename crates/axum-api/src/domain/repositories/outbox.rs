@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::outbox::{OutboxBacklog, OutboxMessage},
+    repositories::error::RepositoryResult,
+};
+
+#[async_trait]
+pub trait OutboxRepository: Send + Sync {
+    async fn enqueue(&self, message: &OutboxMessage) -> RepositoryResult<()>;
+
+    /// Messages ready to publish at `now`, in the order they were enqueued. Leaves out
+    /// dead letters (messages that failed `max_attempts` times), retries that are not due
+    /// yet, and later messages of an aggregate that has a retry not due yet.
+    async fn list_pending(
+        &self,
+        now: DateTime<Utc>,
+        max_attempts: u32,
+        limit: u32,
+    ) -> RepositoryResult<Vec<OutboxMessage>>;
+
+    async fn mark_dispatched(&self, id: &Uuid) -> RepositoryResult<()>;
+
+    async fn record_failure(
+        &self,
+        id: &Uuid,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> RepositoryResult<()>;
+
+    /// Counts messages with fewer than `max_attempts` failures as pending and the rest as
+    /// dead-lettered.
+    async fn backlog(&self, max_attempts: u32) -> RepositoryResult<OutboxBacklog>;
+
+    /// Deletes up to `limit` messages dispatched before `before` and returns how many.
+    async fn delete_dispatched(&self, before: DateTime<Utc>, limit: u32) -> RepositoryResult<u64>;
+
+    /// Takes or renews the lease that lets one relay at a time publish, for `ttl` from
+    /// now by the database clock. Returns `false` while another holder's lease is live.
+    async fn acquire_relay_lease(&self, holder: &Uuid, ttl: Duration) -> RepositoryResult<bool>;
+}
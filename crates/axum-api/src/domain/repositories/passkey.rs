@@ -0,0 +1,29 @@
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::passkey::{Passkey, PasskeyCeremony},
+    repositories::error::RepositoryResult,
+};
+
+#[async_trait::async_trait]
+pub trait PasskeyRepository: Send + Sync {
+    async fn create(&self, passkey: &Passkey) -> RepositoryResult<()>;
+    async fn find_by_credential_id(
+        &self,
+        credential_id: &[u8],
+    ) -> RepositoryResult<Option<Passkey>>;
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<Passkey>>;
+    async fn update_sign_count(&self, id: Uuid, sign_count: u32) -> RepositoryResult<()>;
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> RepositoryResult<bool>;
+}
+
+#[async_trait::async_trait]
+pub trait PasskeyCeremonyRepository: Send + Sync {
+    async fn store(
+        &self,
+        ceremony_id: &str,
+        ceremony: &PasskeyCeremony,
+        ttl_secs: u64,
+    ) -> RepositoryResult<()>;
+    async fn take(&self, ceremony_id: &str) -> RepositoryResult<Option<PasskeyCeremony>>;
+}
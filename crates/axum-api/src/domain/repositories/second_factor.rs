@@ -0,0 +1,8 @@
+use uuid::Uuid;
+
+use crate::domain::repositories::error::RepositoryResult;
+
+#[async_trait::async_trait]
+pub trait SecondFactorRepository: Send + Sync {
+    async fn has_second_factor(&self, user_id: Uuid) -> RepositoryResult<bool>;
+}
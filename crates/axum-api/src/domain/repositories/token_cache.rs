@@ -1,16 +1,54 @@
-use crate::domain::repositories::error::RepositoryResult;
+use crate::domain::{
+    entities::session::{MfaChallenge, RefreshSession},
+    repositories::error::RepositoryResult,
+};
 
 use uuid::Uuid;
 
+/// How many sessions a user may hold once a new one is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionLimit {
+    Unlimited,
+    /// Refuses the new session while the user already has this many.
+    Reject(usize),
+    /// Ends the user's oldest sessions to make room for the new one.
+    EvictOldest(usize),
+}
+
 #[async_trait::async_trait]
 pub trait TokenCacheRepository: Send + Sync {
+    /// Stores the session after applying `limit` to the user's other sessions, in one
+    /// step so concurrent sign-ins cannot exceed it. Returns false when it was refused.
     async fn store_refresh_token(
+        &self,
+        token_hash: &str,
+        session: &RefreshSession,
+        ttl_secs: u64,
+        limit: SessionLimit,
+    ) -> RepositoryResult<bool>;
+    /// Atomically removes and returns the session, so concurrent refreshes with the
+    /// same token cannot both succeed.
+    async fn take_refresh_token(
+        &self,
+        token_hash: &str,
+    ) -> RepositoryResult<Option<RefreshSession>>;
+    async fn list_refresh_tokens(
         &self,
         user_id: Uuid,
-        token_id: &str,
+    ) -> RepositoryResult<Vec<(String, RefreshSession)>>;
+    async fn revoke_refresh_token(&self, user_id: Uuid, token_hash: &str) -> RepositoryResult<()>;
+    async fn revoke_all_refresh_tokens(&self, user_id: Uuid) -> RepositoryResult<()>;
+    async fn take_legacy_refresh_token(&self, token_id: &str) -> RepositoryResult<Option<Uuid>>;
+    async fn store_mfa_challenge(
+        &self,
+        challenge_hash: &str,
+        challenge: &MfaChallenge,
         ttl_secs: u64,
     ) -> RepositoryResult<()>;
-    async fn get_refresh_token(&self, token_id: &str) -> RepositoryResult<Option<String>>;
+    async fn take_mfa_challenge(
+        &self,
+        challenge_hash: &str,
+    ) -> RepositoryResult<Option<MfaChallenge>>;
     async fn blacklist_access_token(&self, jti: &str, ttl_secs: u64) -> RepositoryResult<()>;
     async fn is_access_token_blacklisted(&self, jti: &str) -> RepositoryResult<bool>;
 }
@@ -0,0 +1,15 @@
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::trusted_device::TrustedDevice, repositories::error::RepositoryResult,
+};
+
+#[async_trait::async_trait]
+pub trait TrustedDeviceRepository: Send + Sync {
+    async fn save(&self, device: &TrustedDevice) -> RepositoryResult<()>;
+    async fn find(&self, user_id: Uuid, device_id: Uuid)
+    -> RepositoryResult<Option<TrustedDevice>>;
+    async fn list_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<TrustedDevice>>;
+    async fn revoke(&self, user_id: Uuid, device_id: Uuid) -> RepositoryResult<bool>;
+    async fn revoke_all(&self, user_id: Uuid) -> RepositoryResult<()>;
+}
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+
+use crate::domain::repositories::{
+    error::RepositoryResult, outbox::OutboxRepository, user::UserRepository,
+};
+
+/// Repository handles that share one database transaction.
+///
+/// Nothing is persisted until `commit`; dropping a unit of work without committing it
+/// rolls the transaction back.
+#[async_trait]
+pub trait UnitOfWork: Send + Sync {
+    fn users(&self) -> &dyn UserRepository;
+
+    fn outbox(&self) -> &dyn OutboxRepository;
+
+    async fn commit(self: Box<Self>) -> RepositoryResult<()>;
+
+    async fn rollback(self: Box<Self>) -> RepositoryResult<()>;
+}
+
+#[async_trait]
+pub trait UnitOfWorkFactory: Send + Sync {
+    async fn begin(&self) -> RepositoryResult<Box<dyn UnitOfWork>>;
+}
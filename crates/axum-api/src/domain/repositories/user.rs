@@ -1,8 +1,85 @@
-use crate::domain::{entities::user::User, repositories::error::RepositoryResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::user::{Role, User, UserStatus},
+    repositories::error::RepositoryResult,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct UserFilter {
+    pub email_prefix: Option<String>,
+    pub name: Option<String>,
+    pub status: Option<UserStatus>,
+    pub role: Option<Role>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    pub attributes: Vec<AttributeFilter>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeFilter {
+    pub namespace: String,
+    pub path: Vec<String>,
+    pub value: String,
+}
+
+impl AttributeFilter {
+    pub fn json_path(&self) -> String {
+        self.path
+            .iter()
+            .fold(String::from("$"), |path, key| format!("{path}.\"{key}\""))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+#[derive(Debug, Clone)]
+pub struct UserListQuery {
+    pub filter: UserFilter,
+    pub direction: SortDirection,
+    pub after: Option<UserCursor>,
+    pub limit: u32,
+}
 
 #[async_trait::async_trait]
 pub trait UserRepository: Send + Sync {
     async fn create(&self, user: &User) -> RepositoryResult<User>;
+    async fn create_many(&self, users: &[User]) -> RepositoryResult<Vec<User>>;
+    async fn find_existing_emails(&self, emails: &[String]) -> RepositoryResult<Vec<String>>;
     async fn find_by_email(&self, email: &str) -> RepositoryResult<Option<User>>;
     async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<User>>;
+    async fn list(&self, query: &UserListQuery) -> RepositoryResult<Vec<User>>;
+    async fn update_password(&self, id: &str, password: &str) -> RepositoryResult<()>;
+    async fn update_profile(
+        &self,
+        user: &User,
+        expected_updated_at: DateTime<Utc>,
+    ) -> RepositoryResult<Option<User>>;
+    /// Returns `false` without writing when the stored email no longer matches
+    /// `previous_email` or another account holds the new one.
+    async fn update_email(&self, user: &User, previous_email: &str) -> RepositoryResult<bool>;
+    async fn update_avatar(&self, user: &User) -> RepositoryResult<()>;
+    async fn update_status(&self, user: &User, previous: UserStatus) -> RepositoryResult<bool>;
+    async fn list_due_erasures(
+        &self,
+        due_before: DateTime<Utc>,
+        limit: u32,
+    ) -> RepositoryResult<Vec<User>>;
+    /// Anonymizes `user` and drops their credentials and attributes, but only while the
+    /// stored account is still pending deletion. Returns `false` when it no longer is.
+    async fn erase(&self, user: &User) -> RepositoryResult<bool>;
 }
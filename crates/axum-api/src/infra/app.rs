@@ -1,8 +1,8 @@
 use axum::{
     Router,
     http::{
-        Method,
-        header::{AUTHORIZATION, CONTENT_TYPE},
+        HeaderName, Method,
+        header::{AUTHORIZATION, CONTENT_TYPE, ETAG, IF_MATCH},
     },
     middleware,
 };
@@ -11,8 +11,16 @@ use tower_http::cors::CorsLayer;
 use crate::{
     adapters::http::{
         app_state::AppState,
-        middlewares::auth_middleware::auth_middleware,
-        routes::{auth::auth_routes, user::user_routes},
+        extractors::client_info::{CLIENT_ID_HEADER, DEVICE_FINGERPRINT_HEADER},
+        middlewares::{
+            admin_middleware::admin_middleware,
+            auth_middleware::auth_middleware,
+            read_consistency_middleware::{primary_reads_middleware, read_consistency_middleware},
+        },
+        routes::{
+            admin::admin_routes, auth::auth_routes, files::file_routes, metrics::metrics_routes,
+            user::user_routes,
+        },
     },
     infra::setup::init_tracing,
 };
@@ -29,21 +37,43 @@ pub fn create_app(app_state: AppState) -> Router {
             Method::DELETE,
         ])
         .allow_credentials(true)
-        .allow_headers([CONTENT_TYPE, AUTHORIZATION]);
+        .allow_headers([
+            CONTENT_TYPE,
+            AUTHORIZATION,
+            IF_MATCH,
+            HeaderName::from_static(CLIENT_ID_HEADER),
+            HeaderName::from_static(DEVICE_FINGERPRINT_HEADER),
+        ])
+        .expose_headers([ETAG]);
 
     Router::new()
         .nest(
             "/auth",
-            auth_routes(app_state.clone()).with_state(app_state.clone()),
+            auth_routes(app_state.clone())
+                .layer(middleware::from_fn(primary_reads_middleware))
+                .with_state(app_state.clone()),
         )
         .nest(
             "/users",
-            user_routes()
+            user_routes(app_state.clone()).with_state(app_state.clone()),
+        )
+        .nest(
+            "/admin",
+            admin_routes()
+                .layer(middleware::from_fn(admin_middleware))
                 .layer(middleware::from_fn_with_state(
                     app_state.clone(),
                     auth_middleware,
                 ))
                 .with_state(app_state.clone()),
         )
+        .nest("/files", file_routes().with_state(app_state.clone()))
+        .layer(middleware::from_fn(read_consistency_middleware))
         .layer(cors)
 }
+
+/// Routes for the internal listener, which has no authentication and must not be
+/// exposed publicly.
+pub fn create_metrics_app(app_state: AppState) -> Router {
+    metrics_routes().with_state(app_state)
+}
@@ -1,12 +1,29 @@
-use std::env;
+use std::{collections::HashMap, env, str::FromStr, time::Duration};
+
+use crate::domain::entities::user::Role;
 
 #[derive(Debug, Clone)]
 pub struct AppConfig {
     pub port: u16,
+    /// Port of the internal listener serving `/metrics`, kept apart from the public API.
+    pub metrics_port: u16,
     pub jwt_secret: String,
+    pub refresh_token_secret: String,
+    pub trusted_device_secret: String,
+    /// Shared with the mailer, which derives confirmation tokens from email change ids.
+    pub email_change_token_secret: String,
+    pub trusted_device_days: i64,
     pub redis: RedisConfig,
-    pub mssql: MssqlConfig,
+    pub database: DatabaseConfig,
     pub kafka_brokers: String,
+    pub session: SessionConfig,
+    pub webauthn: WebAuthnConfig,
+    pub account: AccountConfig,
+    pub outbox: OutboxConfig,
+    pub storage: StorageConfig,
+    pub export: ExportConfig,
+    pub avatar: AvatarConfig,
+    pub migrations: MigrationConfig,
 }
 
 #[derive(Debug, Clone)]
@@ -16,6 +33,108 @@ pub struct RedisConfig {
     pub password: Option<String>,
 }
 
+#[derive(Debug, Clone)]
+pub enum DatabaseConfig {
+    MssqlSqlx(MssqlConfig),
+    MssqlTiberius(MssqlConfig),
+    Postgres(PostgresConfig),
+    Sqlite(SqliteConfig),
+}
+
+impl DatabaseConfig {
+    fn from_env() -> Self {
+        match env::var("DB_DRIVER")
+            .unwrap_or_else(|_| "mssql".into())
+            .as_str()
+        {
+            "mssql" | "mssql_sqlx" => DatabaseConfig::MssqlSqlx(MssqlConfig::from_env()),
+            "mssql_tiberius" => DatabaseConfig::MssqlTiberius(MssqlConfig::from_env()),
+            "postgres" => DatabaseConfig::Postgres(PostgresConfig::from_env()),
+            "sqlite" => DatabaseConfig::Sqlite(SqliteConfig::from_env()),
+            other => panic!("Unknown DB_DRIVER: {other}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PoolConfig {
+    pub max_size: u32,
+    pub min_idle: u32,
+    pub connect_timeout: Duration,
+    pub idle_timeout: Option<Duration>,
+    pub max_lifetime: Option<Duration>,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 100,
+            min_idle: 0,
+            connect_timeout: Duration::from_secs(30),
+            idle_timeout: Some(Duration::from_secs(600)),
+            max_lifetime: Some(Duration::from_secs(1800)),
+        }
+    }
+}
+
+impl PoolConfig {
+    /// Reads `<PREFIX>_POOL_*`. A timeout or lifetime of `0` disables it.
+    pub fn from_env(prefix: &str) -> Self {
+        Self::from_env_or(prefix, Self::default())
+    }
+
+    /// Like `from_env`, falling back to `defaults` for unset variables.
+    pub fn from_env_or(prefix: &str, defaults: Self) -> Self {
+        fn var<T: FromStr>(prefix: &str, name: &str, default: T) -> T {
+            let key = format!("{prefix}_POOL_{name}");
+            env::var(&key)
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map_or(default, |v| {
+                    v.parse()
+                        .unwrap_or_else(|_| panic!("{key} must be a number"))
+                })
+        }
+
+        let secs = |duration: Option<Duration>| duration.map_or(0, |d| d.as_secs());
+        let enabled = |secs: u64| (secs > 0).then(|| Duration::from_secs(secs));
+
+        let max_size = var(prefix, "MAX_SIZE", defaults.max_size);
+        let min_idle = var(prefix, "MIN_IDLE", defaults.min_idle);
+        let connect_timeout = var(
+            prefix,
+            "CONNECT_TIMEOUT_SECS",
+            defaults.connect_timeout.as_secs(),
+        );
+
+        assert!(max_size > 0, "{prefix}_POOL_MAX_SIZE must be at least 1");
+        assert!(
+            min_idle <= max_size,
+            "{prefix}_POOL_MIN_IDLE must not exceed {prefix}_POOL_MAX_SIZE"
+        );
+        assert!(
+            connect_timeout > 0,
+            "{prefix}_POOL_CONNECT_TIMEOUT_SECS must be at least 1"
+        );
+
+        Self {
+            max_size,
+            min_idle,
+            connect_timeout: Duration::from_secs(connect_timeout),
+            idle_timeout: enabled(var(
+                prefix,
+                "IDLE_TIMEOUT_SECS",
+                secs(defaults.idle_timeout),
+            )),
+            max_lifetime: enabled(var(
+                prefix,
+                "MAX_LIFETIME_SECS",
+                secs(defaults.max_lifetime),
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MssqlConfig {
     pub host: String,
@@ -23,6 +142,339 @@ pub struct MssqlConfig {
     pub database: String,
     pub username: String,
     pub password: String,
+    pub pool: PoolConfig,
+    pub read_replicas: Option<ReadReplicaConfig>,
+}
+
+impl MssqlConfig {
+    pub fn from_env() -> Self {
+        Self {
+            host: env::var("MSSQL_HOST").expect("MSSQL_HOST must be set"),
+            port: env::var("MSSQL_PORT")
+                .unwrap_or_else(|_| "1433".into())
+                .parse()
+                .expect("MSSQL_PORT must be a number"),
+            database: env::var("MSSQL_DATABASE").expect("MSSQL_DATABASE must be set"),
+            username: env::var("MSSQL_USERNAME").expect("MSSQL_USERNAME must be set"),
+            password: env::var("MSSQL_PASSWORD").expect("MSSQL_PASSWORD must be set"),
+            pool: PoolConfig::from_env("MSSQL"),
+            read_replicas: ReadReplicaConfig::from_env(),
+        }
+    }
+}
+
+/// Replicas share the primary's database and credentials.
+#[derive(Debug, Clone)]
+pub struct ReadReplicaConfig {
+    pub hosts: Vec<ReplicaHost>,
+    pub pool: PoolConfig,
+    /// How long an unreachable replica is skipped before it is tried again.
+    pub retry_after: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplicaHost {
+    pub host: String,
+    pub port: u16,
+}
+
+impl ReadReplicaConfig {
+    /// Reads `MSSQL_READ_REPLICAS` as comma-separated `host[:port]` entries; unset or
+    /// empty disables replicas.
+    fn from_env() -> Option<Self> {
+        let hosts: Vec<ReplicaHost> = env::var("MSSQL_READ_REPLICAS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| match entry.rsplit_once(':') {
+                Some((host, port)) => ReplicaHost {
+                    host: host.to_string(),
+                    port: port
+                        .parse()
+                        .expect("MSSQL_READ_REPLICAS ports must be numbers"),
+                },
+                None => ReplicaHost {
+                    host: entry.to_string(),
+                    port: 1433,
+                },
+            })
+            .collect();
+
+        if hosts.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            hosts,
+            // A replica that is down should fail over to the primary quickly rather than
+            // hold the request for the primary's connect timeout.
+            pool: PoolConfig::from_env_or(
+                "MSSQL_READ_REPLICA",
+                PoolConfig {
+                    connect_timeout: Duration::from_secs(2),
+                    ..PoolConfig::default()
+                },
+            ),
+            retry_after: Duration::from_secs(
+                env::var("MSSQL_READ_REPLICA_RETRY_SECS")
+                    .ok()
+                    .filter(|v| !v.is_empty())
+                    .map_or(30, |v| {
+                        v.parse()
+                            .expect("MSSQL_READ_REPLICA_RETRY_SECS must be a number")
+                    }),
+            ),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PostgresConfig {
+    pub host: String,
+    pub port: u16,
+    pub database: String,
+    pub username: String,
+    pub password: String,
+    pub pool: PoolConfig,
+}
+
+impl PostgresConfig {
+    pub fn from_env() -> Self {
+        Self {
+            host: env::var("POSTGRES_HOST").expect("POSTGRES_HOST must be set"),
+            port: env::var("POSTGRES_PORT")
+                .unwrap_or_else(|_| "5432".into())
+                .parse()
+                .expect("POSTGRES_PORT must be a number"),
+            database: env::var("POSTGRES_DATABASE").expect("POSTGRES_DATABASE must be set"),
+            username: env::var("POSTGRES_USERNAME").expect("POSTGRES_USERNAME must be set"),
+            password: env::var("POSTGRES_PASSWORD").expect("POSTGRES_PASSWORD must be set"),
+            pool: PoolConfig::from_env("POSTGRES"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SqliteConfig {
+    /// Database file, or `:memory:` for a private in-memory database.
+    pub path: String,
+    /// Ignored for in-memory databases, which always use a single connection.
+    pub pool: PoolConfig,
+}
+
+impl SqliteConfig {
+    pub const IN_MEMORY: &str = ":memory:";
+
+    pub fn from_env() -> Self {
+        Self {
+            path: env::var("SQLITE_PATH").unwrap_or_else(|_| "axum-api.db".into()),
+            pool: PoolConfig::from_env("SQLITE"),
+        }
+    }
+
+    pub fn is_in_memory(&self) -> bool {
+        self.path == Self::IN_MEMORY
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AccountConfig {
+    pub erasure_grace_days: i64,
+    pub erasure_job_interval_secs: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct OutboxConfig {
+    pub relay_interval_ms: u64,
+    pub batch_size: u32,
+    /// Failed publishes after which a message is dead-lettered and no longer retried.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubling after each further failure up to
+    /// `max_backoff_ms`. Retries wait for a later pass instead of holding up this one.
+    pub retry_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    /// How long dispatched messages are kept before the cleanup job deletes them.
+    pub retention_hours: i64,
+    pub cleanup_interval_secs: u64,
+    /// How long a relay keeps the right to publish without renewing it. Only one
+    /// instance relays at a time; another takes over once the holder's lease lapses.
+    pub relay_lease_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+pub enum StorageConfig {
+    Local {
+        root: String,
+        public_url: String,
+        signing_secret: String,
+    },
+    S3 {
+        bucket: String,
+        region: String,
+        endpoint: Option<String>,
+        access_key_id: String,
+        secret_access_key: String,
+    },
+}
+
+impl StorageConfig {
+    fn from_env() -> Self {
+        match env::var("STORAGE_BACKEND")
+            .unwrap_or_else(|_| "local".into())
+            .as_str()
+        {
+            "local" => StorageConfig::Local {
+                root: env::var("STORAGE_LOCAL_ROOT").unwrap_or_else(|_| "./storage".into()),
+                public_url: env::var("STORAGE_PUBLIC_URL")
+                    .unwrap_or_else(|_| "http://localhost:4001".into()),
+                signing_secret: env::var("STORAGE_SIGNING_SECRET")
+                    .expect("STORAGE_SIGNING_SECRET must be set"),
+            },
+            "s3" => StorageConfig::S3 {
+                bucket: env::var("S3_BUCKET").expect("S3_BUCKET must be set"),
+                region: env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".into()),
+                endpoint: env::var("S3_ENDPOINT").ok().filter(|v| !v.is_empty()),
+                access_key_id: env::var("S3_ACCESS_KEY_ID").expect("S3_ACCESS_KEY_ID must be set"),
+                secret_access_key: env::var("S3_SECRET_ACCESS_KEY")
+                    .expect("S3_SECRET_ACCESS_KEY must be set"),
+            },
+            other => panic!("Unknown STORAGE_BACKEND: {other}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportConfig {
+    pub url_ttl_minutes: u64,
+    pub retention_days: u64,
+    /// How long an export may stay pending before it is considered stalled and failed.
+    pub build_timeout_minutes: i64,
+    pub cleanup_interval_secs: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct MigrationConfig {
+    pub run_on_startup: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct AvatarConfig {
+    pub url_ttl_minutes: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebAuthnConfig {
+    pub rp_id: String,
+    pub rp_name: String,
+    pub origin: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionLimitStrategy {
+    EvictOldest,
+    Reject,
+}
+
+impl FromStr for SessionLimitStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "evict_oldest" => Ok(SessionLimitStrategy::EvictOldest),
+            "reject" => Ok(SessionLimitStrategy::Reject),
+            other => Err(format!("Unknown session limit strategy: {other}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SessionPolicy {
+    pub idle_timeout_minutes: u64,
+    pub max_concurrent_sessions: usize,
+    pub on_limit: SessionLimitStrategy,
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionConfig {
+    pub default_policy: SessionPolicy,
+    pub role_overrides: HashMap<Role, SessionPolicy>,
+}
+
+/// A timeout of zero would expire every session as it is issued.
+fn idle_timeout_minutes(value: &str, message: &str) -> u64 {
+    value
+        .parse()
+        .ok()
+        .filter(|minutes| *minutes > 0)
+        .expect(message)
+}
+
+impl SessionConfig {
+    pub fn policy_for(&self, role: Role) -> SessionPolicy {
+        self.role_overrides
+            .get(&role)
+            .copied()
+            .unwrap_or(self.default_policy)
+    }
+
+    fn from_env() -> Self {
+        let default_policy = SessionPolicy {
+            idle_timeout_minutes: idle_timeout_minutes(
+                &env::var("SESSION_IDLE_TIMEOUT_MINUTES").unwrap_or_else(|_| "60".into()),
+                "SESSION_IDLE_TIMEOUT_MINUTES must be a positive number",
+            ),
+            max_concurrent_sessions: env::var("SESSION_MAX_CONCURRENT")
+                .unwrap_or_else(|_| "5".into())
+                .parse()
+                .expect("SESSION_MAX_CONCURRENT must be a number"),
+            on_limit: env::var("SESSION_LIMIT_STRATEGY")
+                .unwrap_or_else(|_| "evict_oldest".into())
+                .parse()
+                .expect("SESSION_LIMIT_STRATEGY must be evict_oldest or reject"),
+        };
+
+        let role_overrides = Role::ALL
+            .into_iter()
+            .filter_map(|role| {
+                let suffix = role.as_str().to_uppercase();
+                let idle = env::var(format!("SESSION_IDLE_TIMEOUT_MINUTES_{suffix}")).ok();
+                let max = env::var(format!("SESSION_MAX_CONCURRENT_{suffix}")).ok();
+                let strategy = env::var(format!("SESSION_LIMIT_STRATEGY_{suffix}")).ok();
+
+                if idle.is_none() && max.is_none() && strategy.is_none() {
+                    return None;
+                }
+
+                let policy = SessionPolicy {
+                    idle_timeout_minutes: idle.map_or(default_policy.idle_timeout_minutes, |v| {
+                        idle_timeout_minutes(
+                            &v,
+                            "SESSION_IDLE_TIMEOUT_MINUTES_<ROLE> must be a positive number",
+                        )
+                    }),
+                    max_concurrent_sessions: max.map_or(
+                        default_policy.max_concurrent_sessions,
+                        |v| {
+                            v.parse()
+                                .expect("SESSION_MAX_CONCURRENT_<ROLE> must be a number")
+                        },
+                    ),
+                    on_limit: strategy.map_or(default_policy.on_limit, |v| {
+                        v.parse()
+                            .expect("SESSION_LIMIT_STRATEGY_<ROLE> must be evict_oldest or reject")
+                    }),
+                };
+
+                Some((role, policy))
+            })
+            .collect();
+
+        Self {
+            default_policy,
+            role_overrides,
+        }
+    }
 }
 
 impl AppConfig {
@@ -31,8 +483,22 @@ impl AppConfig {
             .unwrap_or_else(|_| "4001".into())
             .parse()
             .expect("PORT must be a number");
+        let metrics_port = env::var("METRICS_PORT")
+            .unwrap_or_else(|_| "9464".into())
+            .parse()
+            .expect("METRICS_PORT must be a number");
 
         let jwt_secret = env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+        let refresh_token_secret =
+            env::var("REFRESH_TOKEN_SECRET").expect("REFRESH_TOKEN_SECRET must be set");
+        let trusted_device_secret =
+            env::var("TRUSTED_DEVICE_SECRET").expect("TRUSTED_DEVICE_SECRET must be set");
+        let email_change_token_secret =
+            env::var("EMAIL_CHANGE_TOKEN_SECRET").expect("EMAIL_CHANGE_TOKEN_SECRET must be set");
+        let trusted_device_days = env::var("TRUSTED_DEVICE_DAYS")
+            .unwrap_or_else(|_| "30".into())
+            .parse()
+            .expect("TRUSTED_DEVICE_DAYS must be a number");
 
         let redis = RedisConfig {
             host: env::var("REDIS_HOST").expect("REDIS_HOST must be set"),
@@ -43,25 +509,120 @@ impl AppConfig {
             password: env::var("REDIS_PASSWORD").ok().filter(|v| !v.is_empty()),
         };
 
-        let mssql = MssqlConfig {
-            host: env::var("MSSQL_HOST").expect("MSSQL_HOST must be set"),
-            port: env::var("MSSQL_PORT")
-                .unwrap_or_else(|_| "1433".into())
+        let database = DatabaseConfig::from_env();
+
+        let kafka_brokers = env::var("KAFKA_BROKERS").unwrap_or_else(|_| "localhost:9092".into());
+
+        let session = SessionConfig::from_env();
+
+        let webauthn = WebAuthnConfig {
+            rp_id: env::var("WEBAUTHN_RP_ID").unwrap_or_else(|_| "localhost".into()),
+            rp_name: env::var("WEBAUTHN_RP_NAME").unwrap_or_else(|_| "Axum API".into()),
+            origin: env::var("WEBAUTHN_ORIGIN").unwrap_or_else(|_| "http://localhost:3000".into()),
+        };
+
+        let account = AccountConfig {
+            erasure_grace_days: env::var("ACCOUNT_ERASURE_GRACE_DAYS")
+                .unwrap_or_else(|_| "30".into())
                 .parse()
-                .expect("MSSQL_PORT must be a number"),
-            database: env::var("MSSQL_DATABASE").expect("MSSQL_DATABASE must be set"),
-            username: env::var("MSSQL_USERNAME").expect("MSSQL_USERNAME must be set"),
-            password: env::var("MSSQL_PASSWORD").expect("MSSQL_PASSWORD must be set"),
+                .expect("ACCOUNT_ERASURE_GRACE_DAYS must be a number"),
+            erasure_job_interval_secs: env::var("ACCOUNT_ERASURE_JOB_INTERVAL_SECS")
+                .unwrap_or_else(|_| "3600".into())
+                .parse()
+                .expect("ACCOUNT_ERASURE_JOB_INTERVAL_SECS must be a number"),
         };
 
-        let kafka_brokers = env::var("KAFKA_BROKERS").unwrap_or_else(|_| "localhost:9092".into());
+        let outbox = OutboxConfig {
+            relay_interval_ms: env::var("OUTBOX_RELAY_INTERVAL_MS")
+                .unwrap_or_else(|_| "1000".into())
+                .parse()
+                .expect("OUTBOX_RELAY_INTERVAL_MS must be a number"),
+            batch_size: env::var("OUTBOX_BATCH_SIZE")
+                .unwrap_or_else(|_| "100".into())
+                .parse()
+                .expect("OUTBOX_BATCH_SIZE must be a number"),
+            max_attempts: env::var("OUTBOX_MAX_ATTEMPTS")
+                .unwrap_or_else(|_| "10".into())
+                .parse()
+                .expect("OUTBOX_MAX_ATTEMPTS must be a number"),
+            retry_backoff_ms: env::var("OUTBOX_RETRY_BACKOFF_MS")
+                .unwrap_or_else(|_| "1000".into())
+                .parse()
+                .expect("OUTBOX_RETRY_BACKOFF_MS must be a number"),
+            max_backoff_ms: env::var("OUTBOX_MAX_BACKOFF_MS")
+                .unwrap_or_else(|_| "300000".into())
+                .parse()
+                .expect("OUTBOX_MAX_BACKOFF_MS must be a number"),
+            retention_hours: env::var("OUTBOX_RETENTION_HOURS")
+                .unwrap_or_else(|_| "168".into())
+                .parse()
+                .expect("OUTBOX_RETENTION_HOURS must be a number"),
+            cleanup_interval_secs: env::var("OUTBOX_CLEANUP_INTERVAL_SECS")
+                .unwrap_or_else(|_| "3600".into())
+                .parse()
+                .expect("OUTBOX_CLEANUP_INTERVAL_SECS must be a number"),
+            relay_lease_ms: env::var("OUTBOX_RELAY_LEASE_MS")
+                .unwrap_or_else(|_| "30000".into())
+                .parse()
+                .expect("OUTBOX_RELAY_LEASE_MS must be a number"),
+        };
+
+        let storage = StorageConfig::from_env();
+
+        let export = ExportConfig {
+            url_ttl_minutes: env::var("EXPORT_URL_TTL_MINUTES")
+                .unwrap_or_else(|_| "60".into())
+                .parse()
+                .expect("EXPORT_URL_TTL_MINUTES must be a number"),
+            retention_days: env::var("EXPORT_RETENTION_DAYS")
+                .unwrap_or_else(|_| "7".into())
+                .parse()
+                .expect("EXPORT_RETENTION_DAYS must be a number"),
+            build_timeout_minutes: env::var("EXPORT_BUILD_TIMEOUT_MINUTES")
+                .unwrap_or_else(|_| "15".into())
+                .parse()
+                .expect("EXPORT_BUILD_TIMEOUT_MINUTES must be a number"),
+            cleanup_interval_secs: env::var("EXPORT_CLEANUP_INTERVAL_SECS")
+                .unwrap_or_else(|_| "300".into())
+                .parse()
+                .expect("EXPORT_CLEANUP_INTERVAL_SECS must be a number"),
+        };
+
+        let avatar = AvatarConfig {
+            url_ttl_minutes: env::var("AVATAR_URL_TTL_MINUTES")
+                .unwrap_or_else(|_| "1440".into())
+                .parse()
+                .expect("AVATAR_URL_TTL_MINUTES must be a number"),
+        };
+
+        // SQLite databases are created on demand, so their schema is applied by default.
+        let migrations = MigrationConfig {
+            run_on_startup: env::var("MIGRATE_ON_STARTUP")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(|v| v.parse().expect("MIGRATE_ON_STARTUP must be true or false"))
+                .unwrap_or(matches!(database, DatabaseConfig::Sqlite(_))),
+        };
 
         Self {
             port,
+            metrics_port,
             jwt_secret,
+            refresh_token_secret,
+            trusted_device_secret,
+            email_change_token_secret,
+            trusted_device_days,
             redis,
-            mssql,
+            database,
             kafka_brokers,
+            session,
+            webauthn,
+            account,
+            outbox,
+            storage,
+            export,
+            avatar,
+            migrations,
         }
     }
 }
@@ -0,0 +1,107 @@
+use std::io::Cursor;
+
+use image::{
+    DynamicImage, ImageFormat, ImageReader, Limits, codecs::jpeg::JpegEncoder,
+    imageops::FilterType, metadata::Orientation,
+};
+
+use crate::application::app_error::AppError;
+
+const MAX_IMAGE_DIMENSION: u32 = 8192;
+const JPEG_QUALITY: u8 = 85;
+
+pub struct ImageVariant {
+    pub name: &'static str,
+    pub data: Vec<u8>,
+}
+
+pub trait ImageProcessor: Send + Sync {
+    fn content_type(&self) -> &'static str;
+    fn extension(&self) -> &'static str;
+    fn resize_square(
+        &self,
+        data: &[u8],
+        content_type: &str,
+        sizes: &[(&'static str, u32)],
+    ) -> Result<Vec<ImageVariant>, AppError>;
+}
+
+#[derive(Default)]
+pub struct JpegImageProcessor;
+
+impl JpegImageProcessor {
+    fn decode(data: &[u8], content_type: &str) -> Result<DynamicImage, AppError> {
+        let format =
+            ImageFormat::from_mime_type(content_type).ok_or(AppError::UnsupportedMediaType)?;
+        let detected = image::guess_format(data).map_err(|_| AppError::UnsupportedMediaType)?;
+
+        if detected != format {
+            return Err(AppError::UnsupportedMediaType);
+        }
+
+        let mut limits = Limits::default();
+        limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+        limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+
+        let mut reader = ImageReader::with_format(Cursor::new(data), format);
+        reader.limits(limits);
+
+        let mut image = reader
+            .decode()
+            .map_err(|e| AppError::InvalidImage(e.to_string()))?;
+
+        if let Some(orientation) = Self::exif_orientation(data) {
+            image.apply_orientation(orientation);
+        }
+
+        Ok(image)
+    }
+
+    fn exif_orientation(data: &[u8]) -> Option<Orientation> {
+        let exif = exif::Reader::new()
+            .read_from_container(&mut Cursor::new(data))
+            .ok()?;
+        let value = exif
+            .get_field(exif::Tag::Orientation, exif::In::PRIMARY)?
+            .value
+            .get_uint(0)?;
+
+        Orientation::from_exif(u8::try_from(value).ok()?)
+    }
+}
+
+impl ImageProcessor for JpegImageProcessor {
+    fn content_type(&self) -> &'static str {
+        "image/jpeg"
+    }
+
+    fn extension(&self) -> &'static str {
+        "jpg"
+    }
+
+    fn resize_square(
+        &self,
+        data: &[u8],
+        content_type: &str,
+        sizes: &[(&'static str, u32)],
+    ) -> Result<Vec<ImageVariant>, AppError> {
+        let image = Self::decode(data, content_type)?;
+
+        sizes
+            .iter()
+            .map(|&(name, size)| {
+                // Re-encoding from raw pixels drops EXIF and any other embedded metadata.
+                let resized = image
+                    .resize_to_fill(size, size, FilterType::Lanczos3)
+                    .to_rgb8();
+
+                let mut data = Vec::new();
+                JpegEncoder::new_with_quality(&mut data, JPEG_QUALITY)
+                    .encode_image(&resized)
+                    .map_err(|e| AppError::InvalidImage(e.to_string()))?;
+
+                Ok(ImageVariant { name, data })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,88 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::application::use_cases::{
+    account::AccountUseCase, data_export::DataExportUseCase, outbox::OutboxUseCase,
+};
+
+pub fn spawn_erasure_job(
+    account_use_case: Arc<AccountUseCase>,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            match account_use_case.finalize_pending_erasures().await {
+                Ok(0) => {}
+                Ok(erased) => info!("Finalized {} pending account erasures", erased),
+                Err(e) => error!("Failed to finalize pending account erasures: {}", e),
+            }
+        }
+    })
+}
+
+pub fn spawn_outbox_relay(
+    outbox_use_case: Arc<OutboxUseCase>,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = outbox_use_case.relay_pending().await {
+                error!("Failed to relay outbox messages: {}", e);
+            }
+        }
+    })
+}
+
+pub fn spawn_outbox_cleanup(
+    outbox_use_case: Arc<OutboxUseCase>,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            match outbox_use_case.purge_dispatched().await {
+                Ok(0) => {}
+                Ok(purged) => info!("Purged {} dispatched outbox messages", purged),
+                Err(e) => error!("Failed to purge dispatched outbox messages: {}", e),
+            }
+        }
+    })
+}
+
+pub fn spawn_data_export_cleanup(
+    data_export_use_case: Arc<DataExportUseCase>,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            match data_export_use_case.fail_stalled_exports().await {
+                Ok(0) => {}
+                Ok(failed) => info!("Failed {} stalled data exports", failed),
+                Err(e) => error!("Failed to check for stalled data exports: {}", e),
+            }
+
+            match data_export_use_case.purge_expired_objects().await {
+                Ok(0) => {}
+                Ok(purged) => info!("Deleted {} expired data exports", purged),
+                Err(e) => error!("Failed to delete expired data exports: {}", e),
+            }
+        }
+    })
+}
@@ -0,0 +1,91 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use jsonschema::Validator;
+use serde_json::Value;
+
+use crate::{application::app_error::AppError, domain::entities::attribute::AttributeSchema};
+
+pub trait SchemaValidator: Send + Sync {
+    fn check_schema(&self, schema: &Value) -> Result<(), AppError>;
+    fn validate(&self, schema: &AttributeSchema, instance: &Value) -> Result<(), AppError>;
+}
+
+struct CompiledSchema {
+    updated_at: DateTime<Utc>,
+    validator: Arc<Validator>,
+}
+
+/// Compiles each namespace's schema once per version. A schema is recompiled when its
+/// `updated_at` changes, so an upsert takes effect on the next write.
+#[derive(Default)]
+pub struct JsonSchemaValidator {
+    compiled: Mutex<HashMap<String, CompiledSchema>>,
+}
+
+impl JsonSchemaValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn compile(schema: &Value) -> Result<Validator, AppError> {
+        jsonschema::validator_for(schema)
+            .map_err(|e| AppError::ValidationError(vec![format!("Invalid schema: {}", e)]))
+    }
+
+    fn validator(&self, schema: &AttributeSchema) -> Result<Arc<Validator>, AppError> {
+        if let Some(compiled) = self.compiled.lock().unwrap().get(&schema.namespace)
+            && compiled.updated_at == schema.updated_at
+        {
+            return Ok(compiled.validator.clone());
+        }
+
+        let validator = Arc::new(Self::compile(&schema.schema)?);
+        self.compiled.lock().unwrap().insert(
+            schema.namespace.clone(),
+            CompiledSchema {
+                updated_at: schema.updated_at,
+                validator: validator.clone(),
+            },
+        );
+
+        Ok(validator)
+    }
+}
+
+impl SchemaValidator for JsonSchemaValidator {
+    fn check_schema(&self, schema: &Value) -> Result<(), AppError> {
+        if !schema.is_object() {
+            return Err(AppError::ValidationError(vec![
+                "Schema must be a JSON object".to_string(),
+            ]));
+        }
+
+        Self::compile(schema).map(|_| ())
+    }
+
+    fn validate(&self, schema: &AttributeSchema, instance: &Value) -> Result<(), AppError> {
+        let validator = self.validator(schema)?;
+
+        let errors: Vec<String> = validator
+            .iter_errors(instance)
+            .map(|error| {
+                let path = error.instance_path().to_string();
+                if path.is_empty() {
+                    error.to_string()
+                } else {
+                    format!("{}: {}", path, error)
+                }
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::ValidationError(errors))
+        }
+    }
+}
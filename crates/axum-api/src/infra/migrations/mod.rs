@@ -0,0 +1,390 @@
+#[cfg(feature = "mssql-sqlx")]
+pub mod mssql_sqlx;
+#[cfg(feature = "mssql-tiberius")]
+pub mod mssql_tiberius;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::domain::repositories::error::{RepositoryError, RepositoryResult};
+
+#[cfg(any(feature = "mssql-sqlx", feature = "mssql-tiberius"))]
+const MSSQL_ACQUIRE_LOCK: &str = r#"
+    DECLARE @result INT;
+    EXEC @result = sp_getapplock
+        @Resource = 'schema_migrations',
+        @LockMode = 'Exclusive',
+        @LockOwner = 'Transaction',
+        @LockTimeout = 60000;
+    IF @result < 0
+        THROW 50000, 'Could not acquire the schema migration lock', 1;
+"#;
+
+#[cfg(any(feature = "mssql-sqlx", feature = "mssql-tiberius"))]
+const MSSQL_CREATE_MIGRATIONS_TABLE: &str = r#"
+    IF NOT EXISTS (
+        SELECT * FROM sys.tables WHERE name = 'schema_migrations'
+    )
+    BEGIN
+        CREATE TABLE schema_migrations (
+            version BIGINT NOT NULL
+                CONSTRAINT pk_schema_migrations PRIMARY KEY,
+            name NVARCHAR(255) NOT NULL,
+            checksum CHAR(64) NOT NULL,
+            applied_at DATETIME2 NOT NULL DEFAULT SYSUTCDATETIME()
+        );
+    END
+"#;
+
+#[cfg(feature = "mssql-tiberius")]
+const MSSQL_SELECT_APPLIED: &str = r#"
+    SELECT version, name, checksum, applied_at
+    FROM schema_migrations
+    ORDER BY version
+"#;
+
+macro_rules! migration {
+    ($dialect:literal, $version:literal, $name:literal) => {
+        Migration {
+            version: $version,
+            name: $name,
+            up: include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/migrations/",
+                $dialect,
+                "/",
+                $name,
+                ".up.sql"
+            )),
+            down: include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/migrations/",
+                $dialect,
+                "/",
+                $name,
+                ".down.sql"
+            )),
+        }
+    };
+}
+
+pub static MSSQL_MIGRATIONS: &[Migration] = &[
+    migration!("mssql", 1, "0001_create_users"),
+    migration!("mssql", 2, "0002_create_passkey_credentials"),
+    migration!("mssql", 3, "0003_create_user_attributes"),
+    migration!("mssql", 4, "0004_add_users_status_check"),
+    migration!("mssql", 5, "0005_create_outbox"),
+];
+
+pub static POSTGRES_MIGRATIONS: &[Migration] = &[
+    migration!("postgres", 1, "0001_create_users"),
+    migration!("postgres", 2, "0002_create_passkey_credentials"),
+    migration!("postgres", 3, "0003_create_user_attributes"),
+    migration!("postgres", 4, "0004_add_users_status_check"),
+    migration!("postgres", 5, "0005_create_outbox"),
+];
+
+pub static SQLITE_MIGRATIONS: &[Migration] = &[
+    migration!("sqlite", 1, "0001_create_users"),
+    migration!("sqlite", 2, "0002_create_passkey_credentials"),
+    migration!("sqlite", 3, "0003_create_user_attributes"),
+    migration!("sqlite", 4, "0004_add_users_status_check"),
+    migration!("sqlite", 5, "0005_create_outbox"),
+];
+
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub up: &'static str,
+    pub down: &'static str,
+}
+
+impl Migration {
+    pub fn checksum(&self) -> String {
+        hex::encode(Sha256::digest(self.up.replace("\r\n", "\n").as_bytes()))
+    }
+
+    pub fn script(&self, direction: MigrationDirection) -> &'static str {
+        match direction {
+            MigrationDirection::Up => self.up,
+            MigrationDirection::Down => self.down,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationDirection {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub checksum: String,
+    pub applied_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    ChecksumMismatch,
+    Unknown,
+}
+
+impl MigrationState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MigrationState::Applied => "applied",
+            MigrationState::Pending => "pending",
+            MigrationState::ChecksumMismatch => "checksum_mismatch",
+            MigrationState::Unknown => "unknown",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    pub state: MigrationState,
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+
+    #[error("Checksum mismatch for applied migration {version} ({name})")]
+    ChecksumMismatch { version: i64, name: String },
+
+    #[error("Database contains migration {0} which is unknown to this build")]
+    UnknownVersion(i64),
+
+    #[error("Migration {0} does not exist")]
+    TargetNotFound(i64),
+}
+
+pub type MigrationResult<T> = Result<T, MigrationError>;
+
+#[async_trait::async_trait]
+pub trait MigrationExecutor: Send + Sync {
+    async fn applied(&self) -> RepositoryResult<Vec<AppliedMigration>>;
+    async fn apply(
+        &self,
+        migration: &Migration,
+        direction: MigrationDirection,
+    ) -> RepositoryResult<bool>;
+}
+
+/// Splits a T-SQL script on `GO` lines, as sqlcmd does. A `GO` line inside a string
+/// literal or a block comment belongs to the batch rather than ending it.
+pub fn split_batches(script: &str) -> Vec<String> {
+    let mut batches = Vec::new();
+    let mut current = String::new();
+    let mut scanner = BatchScanner::default();
+
+    for line in script.lines() {
+        if scanner.at_top_level() && line.trim().eq_ignore_ascii_case("GO") {
+            batches.push(std::mem::take(&mut current));
+        } else {
+            scanner.scan(line);
+            current.push_str(line);
+            current.push('\n');
+        }
+    }
+    batches.push(current);
+
+    batches
+        .into_iter()
+        .filter(|batch| !batch.trim().is_empty())
+        .collect()
+}
+
+/// Tracks whether the script so far has left a string literal or block comment open.
+#[derive(Default)]
+struct BatchScanner {
+    in_string: bool,
+    comment_depth: usize,
+}
+
+impl BatchScanner {
+    fn at_top_level(&self) -> bool {
+        !self.in_string && self.comment_depth == 0
+    }
+
+    fn scan(&mut self, line: &str) {
+        let mut chars = line.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if self.in_string {
+                // A doubled quote is an escaped quote, not the end of the literal.
+                if c == '\'' && chars.next_if_eq(&'\'').is_none() {
+                    self.in_string = false;
+                }
+            } else if c == '/' && chars.next_if_eq(&'*').is_some() {
+                // T-SQL block comments nest.
+                self.comment_depth += 1;
+            } else if self.comment_depth > 0 {
+                if c == '*' && chars.next_if_eq(&'/').is_some() {
+                    self.comment_depth -= 1;
+                }
+            } else if c == '\'' {
+                self.in_string = true;
+            } else if c == '-' && chars.next_if_eq(&'-').is_some() {
+                return;
+            }
+        }
+    }
+}
+
+pub struct Migrator {
+    executor: Arc<dyn MigrationExecutor>,
+    migrations: &'static [Migration],
+}
+
+impl Migrator {
+    pub fn new(executor: Arc<dyn MigrationExecutor>, migrations: &'static [Migration]) -> Self {
+        Self {
+            executor,
+            migrations,
+        }
+    }
+
+    async fn verified_applied(&self) -> MigrationResult<HashMap<i64, AppliedMigration>> {
+        let known: HashMap<i64, &Migration> = self
+            .migrations
+            .iter()
+            .map(|migration| (migration.version, migration))
+            .collect();
+
+        let mut applied = HashMap::new();
+
+        for record in self.executor.applied().await? {
+            let migration = known
+                .get(&record.version)
+                .ok_or(MigrationError::UnknownVersion(record.version))?;
+
+            if migration.checksum() != record.checksum {
+                return Err(MigrationError::ChecksumMismatch {
+                    version: record.version,
+                    name: record.name,
+                });
+            }
+
+            applied.insert(record.version, record);
+        }
+
+        Ok(applied)
+    }
+
+    pub async fn up(&self, target: Option<i64>) -> MigrationResult<Vec<Migration>> {
+        if let Some(target) = target {
+            self.migrations
+                .iter()
+                .find(|migration| migration.version == target)
+                .ok_or(MigrationError::TargetNotFound(target))?;
+        }
+
+        let applied = self.verified_applied().await?;
+        let mut ran = Vec::new();
+
+        for migration in self.migrations {
+            if target.is_some_and(|target| migration.version > target) {
+                break;
+            }
+
+            if applied.contains_key(&migration.version) {
+                continue;
+            }
+
+            if self
+                .executor
+                .apply(migration, MigrationDirection::Up)
+                .await?
+            {
+                ran.push(*migration);
+            }
+        }
+
+        Ok(ran)
+    }
+
+    pub async fn down(&self, steps: usize) -> MigrationResult<Vec<Migration>> {
+        let applied = self.verified_applied().await?;
+        let mut ran = Vec::new();
+
+        for migration in self
+            .migrations
+            .iter()
+            .rev()
+            .filter(|migration| applied.contains_key(&migration.version))
+            .take(steps)
+        {
+            if self
+                .executor
+                .apply(migration, MigrationDirection::Down)
+                .await?
+            {
+                ran.push(*migration);
+            }
+        }
+
+        Ok(ran)
+    }
+
+    pub async fn status(&self) -> MigrationResult<Vec<MigrationStatus>> {
+        let mut applied: HashMap<i64, AppliedMigration> = self
+            .executor
+            .applied()
+            .await?
+            .into_iter()
+            .map(|record| (record.version, record))
+            .collect();
+
+        let mut statuses: Vec<MigrationStatus> = self
+            .migrations
+            .iter()
+            .map(|migration| {
+                let record = applied.remove(&migration.version);
+                let state = match &record {
+                    None => MigrationState::Pending,
+                    Some(record) if record.checksum != migration.checksum() => {
+                        MigrationState::ChecksumMismatch
+                    }
+                    Some(_) => MigrationState::Applied,
+                };
+
+                MigrationStatus {
+                    version: migration.version,
+                    name: migration.name.to_string(),
+                    state,
+                    applied_at: record.map(|record| record.applied_at),
+                }
+            })
+            .collect();
+
+        statuses.extend(applied.into_values().map(|record| MigrationStatus {
+            version: record.version,
+            name: record.name,
+            state: MigrationState::Unknown,
+            applied_at: Some(record.applied_at),
+        }));
+        statuses.sort_by_key(|status| status.version);
+
+        Ok(statuses)
+    }
+}
@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+
+use crate::{
+    adapters::persistence::sqlx::entities::{datetime2_columns, decode_datetime2},
+    domain::repositories::error::RepositoryResult,
+    infra::{
+        migrations::{
+            AppliedMigration, MSSQL_ACQUIRE_LOCK, MSSQL_CREATE_MIGRATIONS_TABLE, Migration,
+            MigrationDirection, MigrationExecutor, split_batches,
+        },
+        mssql_sqlx::MssqlPool,
+    },
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct AppliedMigrationRow {
+    version: i64,
+    name: String,
+    checksum: String,
+    applied_at_secs: i64,
+    applied_at_nanos: i32,
+}
+
+fn select_applied() -> String {
+    format!(
+        "SELECT version, name, checksum, {} FROM schema_migrations ORDER BY version",
+        datetime2_columns("applied_at", "applied_at")
+    )
+}
+
+pub struct SqlxMigrationExecutor {
+    pool: MssqlPool,
+}
+
+impl SqlxMigrationExecutor {
+    pub fn new(pool: MssqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl MigrationExecutor for SqlxMigrationExecutor {
+    async fn applied(&self) -> RepositoryResult<Vec<AppliedMigration>> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(MSSQL_ACQUIRE_LOCK).execute(&mut tx).await?;
+        sqlx::query(MSSQL_CREATE_MIGRATIONS_TABLE)
+            .execute(&mut tx)
+            .await?;
+        tx.commit().await?;
+
+        let rows = sqlx::query_as::<_, AppliedMigrationRow>(&select_applied())
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(AppliedMigration {
+                    version: row.version,
+                    name: row.name,
+                    checksum: row.checksum.trim_end().to_string(),
+                    applied_at: decode_datetime2(
+                        "applied_at",
+                        row.applied_at_secs,
+                        row.applied_at_nanos,
+                    )?,
+                })
+            })
+            .collect()
+    }
+
+    async fn apply(
+        &self,
+        migration: &Migration,
+        direction: MigrationDirection,
+    ) -> RepositoryResult<bool> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(MSSQL_ACQUIRE_LOCK).execute(&mut tx).await?;
+
+        let recorded: i32 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM schema_migrations WHERE version = @p1")
+                .bind(migration.version)
+                .fetch_one(&mut tx)
+                .await?;
+
+        if (recorded > 0) != (direction == MigrationDirection::Down) {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        for batch in split_batches(migration.script(direction)) {
+            sqlx::query(&batch).execute(&mut tx).await?;
+        }
+
+        match direction {
+            MigrationDirection::Up => {
+                sqlx::query(
+                    "INSERT INTO schema_migrations (version, name, checksum) VALUES (@p1, @p2, @p3)",
+                )
+                .bind(migration.version)
+                .bind(migration.name)
+                .bind(migration.checksum())
+                .execute(&mut tx)
+                .await?;
+            }
+            MigrationDirection::Down => {
+                sqlx::query("DELETE FROM schema_migrations WHERE version = @p1")
+                    .bind(migration.version)
+                    .execute(&mut tx)
+                    .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use bb8::PooledConnection;
+
+use crate::{
+    adapters::persistence::tiberius::row::{required, timestamp},
+    domain::repositories::error::{RepositoryError, RepositoryResult},
+    infra::{
+        migrations::{
+            AppliedMigration, MSSQL_ACQUIRE_LOCK, MSSQL_CREATE_MIGRATIONS_TABLE,
+            MSSQL_SELECT_APPLIED, Migration, MigrationDirection, MigrationExecutor, split_batches,
+        },
+        mssql_tiberius::{TiberiusConnectionManager, TiberiusPool},
+    },
+};
+
+type Connection<'a> = PooledConnection<'a, TiberiusConnectionManager>;
+
+pub struct TiberiusMigrationExecutor {
+    pool: TiberiusPool,
+}
+
+impl TiberiusMigrationExecutor {
+    pub fn new(pool: TiberiusPool) -> Self {
+        Self { pool }
+    }
+
+    async fn batch(conn: &mut Connection<'_>, sql: &str) -> RepositoryResult<()> {
+        conn.simple_query(sql).await?.into_results().await?;
+        Ok(())
+    }
+
+    async fn apply_in_transaction(
+        conn: &mut Connection<'_>,
+        migration: &Migration,
+        direction: MigrationDirection,
+    ) -> RepositoryResult<bool> {
+        Self::batch(conn, MSSQL_ACQUIRE_LOCK).await?;
+
+        let recorded: i32 = conn
+            .query(
+                "SELECT COUNT(*) AS recorded FROM schema_migrations WHERE version = @P1",
+                &[&migration.version],
+            )
+            .await?
+            .into_row()
+            .await?
+            .and_then(|row| row.get("recorded"))
+            .ok_or(RepositoryError::NoRowFound)?;
+
+        if (recorded > 0) != (direction == MigrationDirection::Down) {
+            return Ok(false);
+        }
+
+        for batch in split_batches(migration.script(direction)) {
+            Self::batch(conn, &batch).await?;
+        }
+
+        match direction {
+            MigrationDirection::Up => {
+                conn.execute(
+                    "INSERT INTO schema_migrations (version, name, checksum) VALUES (@P1, @P2, @P3)",
+                    &[&migration.version, &migration.name, &migration.checksum()],
+                )
+                .await?;
+            }
+            MigrationDirection::Down => {
+                conn.execute(
+                    "DELETE FROM schema_migrations WHERE version = @P1",
+                    &[&migration.version],
+                )
+                .await?;
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl MigrationExecutor for TiberiusMigrationExecutor {
+    async fn applied(&self) -> RepositoryResult<Vec<AppliedMigration>> {
+        let mut conn = self.pool.get().await?;
+
+        Self::batch(
+            &mut conn,
+            &format!(
+                "SET XACT_ABORT ON; BEGIN TRANSACTION; {} {} COMMIT TRANSACTION;",
+                MSSQL_ACQUIRE_LOCK, MSSQL_CREATE_MIGRATIONS_TABLE
+            ),
+        )
+        .await?;
+
+        let rows = conn
+            .query(MSSQL_SELECT_APPLIED, &[])
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(AppliedMigration {
+                    version: required(&row, "version")?,
+                    name: required::<&str>(&row, "name")?.to_string(),
+                    checksum: required::<&str>(&row, "checksum")?.trim_end().to_string(),
+                    applied_at: timestamp(&row, "applied_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn apply(
+        &self,
+        migration: &Migration,
+        direction: MigrationDirection,
+    ) -> RepositoryResult<bool> {
+        let mut conn = self.pool.get().await?;
+
+        Self::batch(&mut conn, "SET XACT_ABORT ON; BEGIN TRANSACTION;").await?;
+        conn.set_broken(true);
+
+        let result = Self::apply_in_transaction(&mut conn, migration, direction).await;
+        let finish = match result {
+            Ok(true) => "COMMIT TRANSACTION;",
+            _ => "IF @@TRANCOUNT > 0 ROLLBACK TRANSACTION;",
+        };
+        Self::batch(&mut conn, finish).await?;
+        conn.set_broken(false);
+
+        result
+    }
+}
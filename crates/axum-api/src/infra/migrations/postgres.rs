@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Executor;
+
+use crate::{
+    domain::repositories::error::RepositoryResult,
+    infra::{
+        migrations::{AppliedMigration, Migration, MigrationDirection, MigrationExecutor},
+        postgres::PgPool,
+    },
+};
+
+const PG_ACQUIRE_LOCK: &str = "SELECT pg_advisory_xact_lock(hashtext('schema_migrations'))";
+
+const PG_CREATE_MIGRATIONS_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS schema_migrations (
+        version BIGINT NOT NULL
+            CONSTRAINT pk_schema_migrations PRIMARY KEY,
+        name VARCHAR(255) NOT NULL,
+        checksum CHAR(64) NOT NULL,
+        applied_at TIMESTAMPTZ NOT NULL DEFAULT now()
+    )
+"#;
+
+#[derive(Debug, sqlx::FromRow)]
+struct AppliedMigrationRow {
+    version: i64,
+    name: String,
+    checksum: String,
+    applied_at: DateTime<Utc>,
+}
+
+pub struct PgMigrationExecutor {
+    pool: PgPool,
+}
+
+impl PgMigrationExecutor {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl MigrationExecutor for PgMigrationExecutor {
+    async fn applied(&self) -> RepositoryResult<Vec<AppliedMigration>> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(PG_ACQUIRE_LOCK).execute(&mut tx).await?;
+        sqlx::query(PG_CREATE_MIGRATIONS_TABLE)
+            .execute(&mut tx)
+            .await?;
+        tx.commit().await?;
+
+        let rows = sqlx::query_as::<_, AppliedMigrationRow>(
+            "SELECT version, name, checksum, applied_at FROM schema_migrations ORDER BY version",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| AppliedMigration {
+                version: row.version,
+                name: row.name,
+                checksum: row.checksum.trim_end().to_string(),
+                applied_at: row.applied_at,
+            })
+            .collect())
+    }
+
+    async fn apply(
+        &self,
+        migration: &Migration,
+        direction: MigrationDirection,
+    ) -> RepositoryResult<bool> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(PG_ACQUIRE_LOCK).execute(&mut tx).await?;
+
+        let recorded: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM schema_migrations WHERE version = $1")
+                .bind(migration.version)
+                .fetch_one(&mut tx)
+                .await?;
+
+        if (recorded > 0) != (direction == MigrationDirection::Down) {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        // Scripts hold several statements, so they go through the simple query protocol.
+        (&mut tx).execute(migration.script(direction)).await?;
+
+        match direction {
+            MigrationDirection::Up => {
+                sqlx::query(
+                    "INSERT INTO schema_migrations (version, name, checksum) VALUES ($1, $2, $3)",
+                )
+                .bind(migration.version)
+                .bind(migration.name)
+                .bind(migration.checksum())
+                .execute(&mut tx)
+                .await?;
+            }
+            MigrationDirection::Down => {
+                sqlx::query("DELETE FROM schema_migrations WHERE version = $1")
+                    .bind(migration.version)
+                    .execute(&mut tx)
+                    .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
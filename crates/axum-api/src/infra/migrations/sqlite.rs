@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::Executor;
+
+use crate::{
+    adapters::persistence::codec::{decode_timestamp, encode_timestamp},
+    domain::repositories::error::RepositoryResult,
+    infra::{
+        migrations::{AppliedMigration, Migration, MigrationDirection, MigrationExecutor},
+        sqlite::SqlitePool,
+    },
+};
+
+const SQLITE_CREATE_MIGRATIONS_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS schema_migrations (
+        version INTEGER NOT NULL
+            CONSTRAINT pk_schema_migrations PRIMARY KEY,
+        name TEXT NOT NULL,
+        checksum TEXT NOT NULL,
+        applied_at TEXT NOT NULL
+    )
+"#;
+
+#[derive(Debug, sqlx::FromRow)]
+struct AppliedMigrationRow {
+    version: i64,
+    name: String,
+    checksum: String,
+    applied_at: String,
+}
+
+pub struct SqliteMigrationExecutor {
+    pool: SqlitePool,
+}
+
+impl SqliteMigrationExecutor {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl MigrationExecutor for SqliteMigrationExecutor {
+    async fn applied(&self) -> RepositoryResult<Vec<AppliedMigration>> {
+        sqlx::query(SQLITE_CREATE_MIGRATIONS_TABLE)
+            .execute(&self.pool)
+            .await?;
+
+        let rows = sqlx::query_as::<_, AppliedMigrationRow>(
+            "SELECT version, name, checksum, applied_at FROM schema_migrations ORDER BY version",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(AppliedMigration {
+                    version: row.version,
+                    name: row.name,
+                    checksum: row.checksum,
+                    applied_at: decode_timestamp("applied_at", &row.applied_at)?,
+                })
+            })
+            .collect()
+    }
+
+    async fn apply(
+        &self,
+        migration: &Migration,
+        direction: MigrationDirection,
+    ) -> RepositoryResult<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        // A write statement takes SQLite's database-wide write lock, standing in for an applock.
+        sqlx::query("DELETE FROM schema_migrations WHERE 0")
+            .execute(&mut tx)
+            .await?;
+
+        let recorded: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM schema_migrations WHERE version = ?1")
+                .bind(migration.version)
+                .fetch_one(&mut tx)
+                .await?;
+
+        if (recorded > 0) != (direction == MigrationDirection::Down) {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        (&mut tx).execute(migration.script(direction)).await?;
+
+        match direction {
+            MigrationDirection::Up => {
+                sqlx::query(
+                    "INSERT INTO schema_migrations (version, name, checksum, applied_at) \
+                     VALUES (?1, ?2, ?3, ?4)",
+                )
+                .bind(migration.version)
+                .bind(migration.name)
+                .bind(migration.checksum())
+                .bind(encode_timestamp(Utc::now()))
+                .execute(&mut tx)
+                .await?;
+            }
+            MigrationDirection::Down => {
+                sqlx::query("DELETE FROM schema_migrations WHERE version = ?1")
+                    .bind(migration.version)
+                    .execute(&mut tx)
+                    .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
@@ -1,8 +1,20 @@
 pub mod app;
 pub mod config;
+pub mod images;
+pub mod jobs;
+pub mod json_schema;
 pub mod kafka;
+pub mod migrations;
+#[cfg(feature = "mssql-sqlx")]
 pub mod mssql_sqlx;
+#[cfg(feature = "mssql-tiberius")]
 pub mod mssql_tiberius;
+pub mod pool;
+#[cfg(feature = "postgres")]
+pub mod postgres;
 pub mod redis;
 pub mod security;
 pub mod setup;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod storage;
@@ -1,17 +1,42 @@
-use crate::infra::config::MssqlConfig;
+use crate::infra::{
+    config::{MssqlConfig, ReadReplicaConfig},
+    pool::sqlx_pool_options,
+};
 use sqlx::{Mssql, Pool};
 
 pub type MssqlPool = Pool<Mssql>;
 
-pub async fn init_mssql_db(config: &MssqlConfig) -> anyhow::Result<MssqlPool> {
-    let database_url = format!(
+fn database_url(config: &MssqlConfig, host: &str, port: u16) -> String {
+    format!(
         "sqlserver://{}:{}@{}:{}/{}",
-        config.username, config.password, config.host, config.port, config.database
-    );
-    let pool = sqlx::mssql::MssqlPoolOptions::new()
-        .max_connections(100)
-        .connect(&database_url)
+        config.username, config.password, host, port, config.database
+    )
+}
+
+pub async fn init_mssql_db(config: &MssqlConfig) -> anyhow::Result<MssqlPool> {
+    let pool = sqlx_pool_options(&config.pool)
+        .connect(&database_url(config, &config.host, config.port))
         .await?;
 
     Ok(pool)
 }
+
+/// Replica pools connect lazily, so a replica that is down at startup is only skipped.
+pub fn init_mssql_read_replicas(
+    config: &MssqlConfig,
+    replicas: &ReadReplicaConfig,
+) -> anyhow::Result<Vec<MssqlPool>> {
+    replicas
+        .hosts
+        .iter()
+        .map(|replica| {
+            Ok(
+                sqlx_pool_options(&replicas.pool).connect_lazy(&database_url(
+                    config,
+                    &replica.host,
+                    replica.port,
+                ))?,
+            )
+        })
+        .collect()
+}
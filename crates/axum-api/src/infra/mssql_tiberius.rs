@@ -1,17 +1,80 @@
-use std::time::Duration;
+use std::ops::{Deref, DerefMut};
 
-use bb8::Pool;
+use bb8::{Builder, ManageConnection, Pool};
 use bb8_tiberius::ConnectionManager;
 use tiberius::{AuthMethod, Config};
 
-use crate::infra::config::MssqlConfig;
+use crate::infra::config::{MssqlConfig, PoolConfig, ReadReplicaConfig};
 
-pub type TiberiusPool = Pool<ConnectionManager>;
+pub type TiberiusPool = Pool<TiberiusConnectionManager>;
 
-pub async fn init_mssql_tiberius(cfg: &MssqlConfig) -> anyhow::Result<TiberiusPool> {
+type Client = <ConnectionManager as ManageConnection>::Connection;
+
+/// A pooled tiberius client that can be marked broken, so the pool closes it instead of
+/// handing it out again, e.g. while it may still hold an open transaction.
+pub struct TiberiusClient {
+    client: Client,
+    broken: bool,
+}
+
+impl TiberiusClient {
+    pub fn set_broken(&mut self, broken: bool) {
+        self.broken = broken;
+    }
+}
+
+impl Deref for TiberiusClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        &self.client
+    }
+}
+
+impl DerefMut for TiberiusClient {
+    fn deref_mut(&mut self) -> &mut Client {
+        &mut self.client
+    }
+}
+
+pub struct TiberiusConnectionManager {
+    inner: ConnectionManager,
+}
+
+impl TiberiusConnectionManager {
+    pub fn new(config: Config) -> Self {
+        Self {
+            inner: ConnectionManager::new(config),
+        }
+    }
+}
+
+impl ManageConnection for TiberiusConnectionManager {
+    type Connection = TiberiusClient;
+    type Error = bb8_tiberius::Error;
+
+    async fn connect(&self) -> Result<TiberiusClient, Self::Error> {
+        let client = self.inner.connect().await?;
+
+        Ok(TiberiusClient {
+            client,
+            broken: false,
+        })
+    }
+
+    async fn is_valid(&self, conn: &mut TiberiusClient) -> Result<(), Self::Error> {
+        self.inner.is_valid(&mut conn.client).await
+    }
+
+    fn has_broken(&self, conn: &mut TiberiusClient) -> bool {
+        conn.broken || self.inner.has_broken(&mut conn.client)
+    }
+}
+
+fn client_config(cfg: &MssqlConfig, host: &str, port: u16) -> Config {
     let mut config = Config::new();
-    config.host(&cfg.host);
-    config.port(cfg.port);
+    config.host(host);
+    config.port(port);
     config.database(&cfg.database);
     config.authentication(AuthMethod::sql_server(
         cfg.username.clone(),
@@ -19,13 +82,38 @@ pub async fn init_mssql_tiberius(cfg: &MssqlConfig) -> anyhow::Result<TiberiusPo
     ));
     config.trust_cert();
     config.encryption(tiberius::EncryptionLevel::Off);
+    config
+}
+
+fn pool_builder(pool: &PoolConfig) -> Builder<TiberiusConnectionManager> {
+    Pool::builder()
+        .max_size(pool.max_size)
+        .min_idle(pool.min_idle)
+        .connection_timeout(pool.connect_timeout)
+        .idle_timeout(pool.idle_timeout)
+        .max_lifetime(pool.max_lifetime)
+}
 
-    let manager = ConnectionManager::new(config);
-    let pool = Pool::builder()
-        .max_size(100)
-        .connection_timeout(Duration::from_secs(300))
-        .build(manager)
-        .await?;
+pub async fn init_mssql_tiberius(cfg: &MssqlConfig) -> anyhow::Result<TiberiusPool> {
+    let manager = TiberiusConnectionManager::new(client_config(cfg, &cfg.host, cfg.port));
+    let pool = pool_builder(&cfg.pool).build(manager).await?;
 
     Ok(pool)
 }
+
+/// Replica pools connect lazily, so a replica that is down at startup is only skipped.
+pub fn init_mssql_tiberius_read_replicas(
+    cfg: &MssqlConfig,
+    replicas: &ReadReplicaConfig,
+) -> Vec<TiberiusPool> {
+    replicas
+        .hosts
+        .iter()
+        .map(|replica| {
+            let mut config = client_config(cfg, &replica.host, replica.port);
+            config.readonly(true);
+
+            pool_builder(&replicas.pool).build_unchecked(TiberiusConnectionManager::new(config))
+        })
+        .collect()
+}
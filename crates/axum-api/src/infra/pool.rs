@@ -0,0 +1,12 @@
+use sqlx::{Database, pool::PoolOptions};
+
+use crate::infra::config::PoolConfig;
+
+pub fn sqlx_pool_options<DB: Database>(config: &PoolConfig) -> PoolOptions<DB> {
+    PoolOptions::new()
+        .max_connections(config.max_size)
+        .min_connections(config.min_idle)
+        .acquire_timeout(config.connect_timeout)
+        .idle_timeout(config.idle_timeout)
+        .max_lifetime(config.max_lifetime)
+}
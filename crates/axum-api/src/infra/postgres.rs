@@ -0,0 +1,16 @@
+use crate::infra::{config::PostgresConfig, pool::sqlx_pool_options};
+use sqlx::{Pool, Postgres};
+
+pub type PgPool = Pool<Postgres>;
+
+pub async fn init_postgres_db(config: &PostgresConfig) -> anyhow::Result<PgPool> {
+    let database_url = format!(
+        "postgres://{}:{}@{}:{}/{}",
+        config.username, config.password, config.host, config.port, config.database
+    );
+    let pool = sqlx_pool_options(&config.pool)
+        .connect(&database_url)
+        .await?;
+
+    Ok(pool)
+}
@@ -1,5 +1,5 @@
 use argon2::{
-    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+    Algorithm, Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
     password_hash::{SaltString, rand_core::OsRng},
 };
 
@@ -8,6 +8,7 @@ use crate::application::app_error::AppError;
 pub trait PasswordHasherTrait: Send + Sync {
     fn hash_password(&self, password: &str) -> Result<String, AppError>;
     fn verify_password(&self, password: &str, hash: &str) -> Result<bool, AppError>;
+    fn is_supported_hash(&self, hash: &str) -> bool;
 }
 
 #[derive(Default)]
@@ -34,4 +35,8 @@ impl PasswordHasherTrait for Argon2PasswordHasher {
             .map(|_| true)
             .map_err(|e| AppError::PasswordVerificationFailed(e.to_string()))
     }
+
+    fn is_supported_hash(&self, hash: &str) -> bool {
+        PasswordHash::new(hash).is_ok_and(|parsed| Algorithm::try_from(parsed.algorithm).is_ok())
+    }
 }
@@ -0,0 +1,53 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub trait DeviceCookieSigner: Send + Sync {
+    fn sign(&self, user_id: Uuid, device_id: Uuid) -> String;
+    fn verify(&self, value: &str) -> Option<(Uuid, Uuid)>;
+}
+
+#[derive(Clone)]
+pub struct HmacDeviceCookieSigner {
+    secret: Vec<u8>,
+}
+
+impl HmacDeviceCookieSigner {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            secret: secret.as_bytes().to_vec(),
+        }
+    }
+
+    fn mac(&self, payload: &str) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any size");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+impl DeviceCookieSigner for HmacDeviceCookieSigner {
+    fn sign(&self, user_id: Uuid, device_id: Uuid) -> String {
+        let payload = format!("{user_id}.{device_id}");
+        let signature = hex::encode(self.mac(&payload).finalize().into_bytes());
+
+        format!("{payload}.{signature}")
+    }
+
+    fn verify(&self, value: &str) -> Option<(Uuid, Uuid)> {
+        let (payload, signature) = value.rsplit_once('.')?;
+        let signature = hex::decode(signature).ok()?;
+
+        self.mac(payload).verify_slice(&signature).ok()?;
+
+        let (user_id, device_id) = payload.split_once('.')?;
+
+        Some((
+            Uuid::parse_str(user_id).ok()?,
+            Uuid::parse_str(device_id).ok()?,
+        ))
+    }
+}
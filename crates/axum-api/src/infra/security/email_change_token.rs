@@ -0,0 +1,36 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Derives the confirmation token of an email change from its id. Events carry only the
+/// id, and the mailer rebuilds the token with the shared secret, so reading the topic is
+/// not enough to confirm someone else's change.
+pub trait EmailChangeTokenProvider: Send + Sync {
+    fn token(&self, change_id: Uuid) -> String;
+}
+
+#[derive(Clone)]
+pub struct HmacEmailChangeTokenProvider {
+    secret: Vec<u8>,
+}
+
+impl HmacEmailChangeTokenProvider {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            secret: secret.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl EmailChangeTokenProvider for HmacEmailChangeTokenProvider {
+    fn token(&self, change_id: Uuid) -> String {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any size");
+        mac.update(change_id.to_string().as_bytes());
+
+        URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+    }
+}
@@ -1,2 +1,7 @@
 pub mod argon2;
+pub mod device_cookie;
+pub mod email_change_token;
 pub mod jwt;
+pub mod refresh_token;
+pub mod url_signer;
+pub mod webauthn;
@@ -0,0 +1,43 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use hmac::{Hmac, Mac};
+use rand::{RngCore, rngs::OsRng};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const TOKEN_BYTES: usize = 32;
+
+pub trait RefreshTokenProvider: Send + Sync {
+    fn generate_token(&self) -> String;
+    fn hash(&self, value: &str) -> String;
+}
+
+#[derive(Clone)]
+pub struct HmacRefreshTokenProvider {
+    secret: Vec<u8>,
+}
+
+impl HmacRefreshTokenProvider {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            secret: secret.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl RefreshTokenProvider for HmacRefreshTokenProvider {
+    fn generate_token(&self) -> String {
+        let mut bytes = [0u8; TOKEN_BYTES];
+        OsRng.fill_bytes(&mut bytes);
+
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    fn hash(&self, value: &str) -> String {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any size");
+        mac.update(value.as_bytes());
+
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
@@ -0,0 +1,43 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub trait UrlSigner: Send + Sync {
+    fn sign(&self, path: &str, expires_at: i64) -> String;
+    fn verify(&self, path: &str, expires_at: i64, signature: &str) -> bool;
+}
+
+#[derive(Clone)]
+pub struct HmacUrlSigner {
+    secret: Vec<u8>,
+}
+
+impl HmacUrlSigner {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            secret: secret.as_bytes().to_vec(),
+        }
+    }
+
+    fn mac(&self, path: &str, expires_at: i64) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any size");
+        mac.update(format!("{path}:{expires_at}").as_bytes());
+        mac
+    }
+}
+
+impl UrlSigner for HmacUrlSigner {
+    fn sign(&self, path: &str, expires_at: i64) -> String {
+        hex::encode(self.mac(path, expires_at).finalize().into_bytes())
+    }
+
+    fn verify(&self, path: &str, expires_at: i64, signature: &str) -> bool {
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+
+        self.mac(path, expires_at).verify_slice(&signature).is_ok()
+    }
+}
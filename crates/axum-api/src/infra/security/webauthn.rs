@@ -0,0 +1,338 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use ciborium::Value;
+use p256::ecdsa::{Signature, VerifyingKey, signature::Verifier};
+use rand::{RngCore, rngs::OsRng};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::application::app_error::AppError;
+
+const CHALLENGE_BYTES: usize = 32;
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+const COSE_KEY_TYPE: i128 = 1;
+const COSE_KEY_ALG: i128 = 3;
+const COSE_EC2_CRV: i128 = -1;
+const COSE_EC2_X: i128 = -2;
+const COSE_EC2_Y: i128 = -3;
+const COSE_KTY_EC2: i128 = 2;
+const COSE_CRV_P256: i128 = 1;
+
+/// COSE algorithm identifier for ECDSA over P-256 with SHA-256, the only one accepted.
+pub const COSE_ALG_ES256: i64 = -7;
+
+pub struct AuthenticatorAttestation {
+    pub client_data_json: Vec<u8>,
+    pub attestation_object: Vec<u8>,
+}
+
+pub struct AuthenticatorAssertion {
+    pub client_data_json: Vec<u8>,
+    pub authenticator_data: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifiedCredential {
+    pub credential_id: Vec<u8>,
+    pub public_key: Vec<u8>,
+    pub sign_count: u32,
+}
+
+pub trait PasskeyVerifier: Send + Sync {
+    fn generate_challenge(&self) -> String;
+    fn verify_registration(
+        &self,
+        challenge: &str,
+        attestation: &AuthenticatorAttestation,
+        require_user_verification: bool,
+    ) -> Result<VerifiedCredential, AppError>;
+    fn verify_assertion(
+        &self,
+        challenge: &str,
+        assertion: &AuthenticatorAssertion,
+        public_key: &[u8],
+        stored_sign_count: u32,
+        require_user_verification: bool,
+    ) -> Result<u32, AppError>;
+}
+
+#[derive(Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    ceremony_type: String,
+    challenge: String,
+    origin: String,
+}
+
+struct AuthenticatorData<'a> {
+    rp_id_hash: &'a [u8],
+    flags: u8,
+    sign_count: u32,
+    attested_credential_data: &'a [u8],
+}
+
+#[derive(Clone)]
+pub struct WebAuthnVerifier {
+    rp_id: String,
+    origin: String,
+}
+
+impl WebAuthnVerifier {
+    pub fn new(rp_id: &str, origin: &str) -> Self {
+        Self {
+            rp_id: rp_id.to_string(),
+            origin: origin.to_string(),
+        }
+    }
+
+    fn fail(reason: &str) -> AppError {
+        AppError::PasskeyVerificationFailed(reason.to_string())
+    }
+
+    fn verify_client_data(
+        &self,
+        client_data_json: &[u8],
+        ceremony_type: &str,
+        challenge: &str,
+    ) -> Result<(), AppError> {
+        let client_data: ClientData = serde_json::from_slice(client_data_json)
+            .map_err(|_| Self::fail("Malformed client data"))?;
+
+        if client_data.ceremony_type != ceremony_type {
+            return Err(Self::fail("Unexpected ceremony type"));
+        }
+
+        if client_data.challenge != challenge {
+            return Err(Self::fail("Challenge mismatch"));
+        }
+
+        if client_data.origin != self.origin {
+            return Err(Self::fail("Origin mismatch"));
+        }
+
+        Ok(())
+    }
+
+    fn parse_authenticator_data<'a>(
+        &self,
+        data: &'a [u8],
+        require_user_verification: bool,
+    ) -> Result<AuthenticatorData<'a>, AppError> {
+        if data.len() < 37 {
+            return Err(Self::fail("Authenticator data is too short"));
+        }
+
+        let auth_data = AuthenticatorData {
+            rp_id_hash: &data[..32],
+            flags: data[32],
+            sign_count: u32::from_be_bytes([data[33], data[34], data[35], data[36]]),
+            attested_credential_data: &data[37..],
+        };
+
+        if auth_data.rp_id_hash != &Sha256::digest(self.rp_id.as_bytes())[..] {
+            return Err(Self::fail("Relying party mismatch"));
+        }
+
+        if auth_data.flags & FLAG_USER_PRESENT == 0 {
+            return Err(Self::fail("User presence is required"));
+        }
+
+        if require_user_verification && auth_data.flags & FLAG_USER_VERIFIED == 0 {
+            return Err(Self::fail("User verification is required"));
+        }
+
+        Ok(auth_data)
+    }
+
+    fn signed_payload(authenticator_data: &[u8], client_data_json: &[u8]) -> Vec<u8> {
+        let mut payload = authenticator_data.to_vec();
+        payload.extend_from_slice(&Sha256::digest(client_data_json));
+        payload
+    }
+
+    fn verify_signature(
+        public_key: &[u8],
+        payload: &[u8],
+        signature: &[u8],
+    ) -> Result<(), AppError> {
+        let verifying_key = VerifyingKey::from_sec1_bytes(public_key)
+            .map_err(|_| Self::fail("Invalid credential public key"))?;
+        let signature =
+            Signature::from_der(signature).map_err(|_| Self::fail("Malformed signature"))?;
+
+        verifying_key
+            .verify(payload, &signature)
+            .map_err(|_| Self::fail("Invalid signature"))
+    }
+
+    fn map_get<'a>(map: &'a [(Value, Value)], key: &str) -> Option<&'a Value> {
+        map.iter()
+            .find(|(k, _)| k.as_text() == Some(key))
+            .map(|(_, v)| v)
+    }
+
+    fn cose_get(map: &[(Value, Value)], key: i128) -> Option<&Value> {
+        map.iter()
+            .find(|(k, _)| k.as_integer().map(i128::from) == Some(key))
+            .map(|(_, v)| v)
+    }
+
+    fn cose_int(map: &[(Value, Value)], key: i128) -> Option<i128> {
+        Self::cose_get(map, key)
+            .and_then(Value::as_integer)
+            .map(i128::from)
+    }
+
+    fn parse_cose_key(cose_key: &mut &[u8]) -> Result<Vec<u8>, AppError> {
+        let value: Value = ciborium::from_reader(cose_key)
+            .map_err(|_| Self::fail("Malformed credential public key"))?;
+        let map = value
+            .as_map()
+            .ok_or_else(|| Self::fail("Malformed credential public key"))?;
+
+        if Self::cose_int(map, COSE_KEY_TYPE) != Some(COSE_KTY_EC2)
+            || Self::cose_int(map, COSE_KEY_ALG) != Some(i128::from(COSE_ALG_ES256))
+            || Self::cose_int(map, COSE_EC2_CRV) != Some(COSE_CRV_P256)
+        {
+            return Err(Self::fail("Only ES256 credentials are supported"));
+        }
+
+        let x = Self::cose_get(map, COSE_EC2_X).and_then(Value::as_bytes);
+        let y = Self::cose_get(map, COSE_EC2_Y).and_then(Value::as_bytes);
+
+        match (x, y) {
+            (Some(x), Some(y)) if x.len() == 32 && y.len() == 32 => {
+                let mut public_key = Vec::with_capacity(65);
+                public_key.push(0x04);
+                public_key.extend_from_slice(x);
+                public_key.extend_from_slice(y);
+                Ok(public_key)
+            }
+            _ => Err(Self::fail("Malformed credential public key")),
+        }
+    }
+
+    fn verify_attestation_statement(
+        format: &str,
+        statement: &[(Value, Value)],
+        public_key: &[u8],
+        payload: &[u8],
+    ) -> Result<(), AppError> {
+        match format {
+            "none" => Ok(()),
+            "packed" => {
+                if Self::map_get(statement, "x5c").is_some() {
+                    return Err(Self::fail("Certificate attestation is not supported"));
+                }
+
+                let alg = Self::map_get(statement, "alg")
+                    .and_then(Value::as_integer)
+                    .map(i128::from);
+                if alg != Some(i128::from(COSE_ALG_ES256)) {
+                    return Err(Self::fail("Unsupported attestation algorithm"));
+                }
+
+                let signature = Self::map_get(statement, "sig")
+                    .and_then(Value::as_bytes)
+                    .ok_or_else(|| Self::fail("Missing attestation signature"))?;
+
+                Self::verify_signature(public_key, payload, signature)
+            }
+            _ => Err(Self::fail("Unsupported attestation format")),
+        }
+    }
+}
+
+impl PasskeyVerifier for WebAuthnVerifier {
+    fn generate_challenge(&self) -> String {
+        let mut bytes = [0u8; CHALLENGE_BYTES];
+        OsRng.fill_bytes(&mut bytes);
+
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    fn verify_registration(
+        &self,
+        challenge: &str,
+        attestation: &AuthenticatorAttestation,
+        require_user_verification: bool,
+    ) -> Result<VerifiedCredential, AppError> {
+        self.verify_client_data(&attestation.client_data_json, "webauthn.create", challenge)?;
+
+        let attestation_object: Value =
+            ciborium::from_reader(attestation.attestation_object.as_slice())
+                .map_err(|_| Self::fail("Malformed attestation object"))?;
+        let attestation_object = attestation_object
+            .as_map()
+            .ok_or_else(|| Self::fail("Malformed attestation object"))?;
+
+        let format = Self::map_get(attestation_object, "fmt")
+            .and_then(Value::as_text)
+            .ok_or_else(|| Self::fail("Missing attestation format"))?;
+        let statement = Self::map_get(attestation_object, "attStmt")
+            .and_then(Value::as_map)
+            .ok_or_else(|| Self::fail("Missing attestation statement"))?;
+        let raw_auth_data = Self::map_get(attestation_object, "authData")
+            .and_then(Value::as_bytes)
+            .ok_or_else(|| Self::fail("Missing authenticator data"))?;
+
+        let auth_data = self.parse_authenticator_data(raw_auth_data, require_user_verification)?;
+
+        if auth_data.flags & FLAG_ATTESTED_CREDENTIAL_DATA == 0 {
+            return Err(Self::fail("Missing attested credential data"));
+        }
+
+        let attested = auth_data.attested_credential_data;
+        if attested.len() < 18 {
+            return Err(Self::fail("Attested credential data is too short"));
+        }
+
+        let credential_id_len = u16::from_be_bytes([attested[16], attested[17]]) as usize;
+        if attested.len() < 18 + credential_id_len {
+            return Err(Self::fail("Attested credential data is too short"));
+        }
+
+        let credential_id = attested[18..18 + credential_id_len].to_vec();
+        let mut cose_key = &attested[18 + credential_id_len..];
+        let public_key = Self::parse_cose_key(&mut cose_key)?;
+
+        let payload = Self::signed_payload(raw_auth_data, &attestation.client_data_json);
+        Self::verify_attestation_statement(format, statement, &public_key, &payload)?;
+
+        Ok(VerifiedCredential {
+            credential_id,
+            public_key,
+            sign_count: auth_data.sign_count,
+        })
+    }
+
+    fn verify_assertion(
+        &self,
+        challenge: &str,
+        assertion: &AuthenticatorAssertion,
+        public_key: &[u8],
+        stored_sign_count: u32,
+        require_user_verification: bool,
+    ) -> Result<u32, AppError> {
+        self.verify_client_data(&assertion.client_data_json, "webauthn.get", challenge)?;
+
+        let auth_data = self
+            .parse_authenticator_data(&assertion.authenticator_data, require_user_verification)?;
+
+        let payload =
+            Self::signed_payload(&assertion.authenticator_data, &assertion.client_data_json);
+        Self::verify_signature(public_key, &payload, &assertion.signature)?;
+
+        if (auth_data.sign_count != 0 || stored_sign_count != 0)
+            && auth_data.sign_count <= stored_sign_count
+        {
+            return Err(Self::fail("Sign count did not increase"));
+        }
+
+        Ok(auth_data.sign_count)
+    }
+}
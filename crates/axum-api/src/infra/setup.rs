@@ -1,61 +1,421 @@
 use std::sync::Arc;
 
+use tracing::info;
 use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
 
+#[cfg(feature = "mssql-sqlx")]
+use crate::adapters::persistence::transaction::SqlxConnectionSource;
+#[cfg(any(feature = "mssql-sqlx", feature = "postgres", feature = "sqlite"))]
+use crate::adapters::persistence::transaction::SqlxUnitOfWorkFactory;
+#[cfg(feature = "postgres")]
+use crate::{
+    adapters::persistence::postgres::repositories::{
+        attribute::PgAttributeRepository, outbox::PgOutboxRepository, passkey::PgPasskeyRepository,
+        user::PgUserRepository,
+    },
+    infra::{
+        migrations::{POSTGRES_MIGRATIONS, postgres::PgMigrationExecutor},
+        postgres::init_postgres_db,
+    },
+};
+#[cfg(any(feature = "mssql-sqlx", feature = "mssql-tiberius"))]
+use crate::{adapters::persistence::replica::ReplicaSet, infra::migrations::MSSQL_MIGRATIONS};
+#[cfg(feature = "sqlite")]
+use crate::{
+    adapters::persistence::sqlite::repositories::{
+        attribute::SqliteAttributeRepository, outbox::SqliteOutboxRepository,
+        passkey::SqlitePasskeyRepository, user::SqliteUserRepository,
+    },
+    infra::{
+        migrations::{SQLITE_MIGRATIONS, sqlite::SqliteMigrationExecutor},
+        sqlite::init_sqlite_db,
+    },
+};
+#[cfg(feature = "mssql-sqlx")]
+use crate::{
+    adapters::persistence::sqlx::repositories::{
+        attribute::SqlXAttributeRepository, outbox::SqlXOutboxRepository,
+        passkey::SqlXPasskeyRepository, user::SqlXUserRepository,
+    },
+    infra::{
+        migrations::mssql_sqlx::SqlxMigrationExecutor,
+        mssql_sqlx::{init_mssql_db, init_mssql_read_replicas},
+    },
+};
+#[cfg(feature = "mssql-tiberius")]
+use crate::{
+    adapters::persistence::tiberius::{
+        repositories::{
+            attribute::TiberiusAttributeRepository, outbox::TiberiusOutboxRepository,
+            passkey::TiberiusPasskeyRepository, user::TiberiusUserRepository,
+        },
+        transaction::{TiberiusConnectionSource, TiberiusUnitOfWorkFactory},
+    },
+    infra::{
+        migrations::mssql_tiberius::TiberiusMigrationExecutor,
+        mssql_tiberius::{init_mssql_tiberius, init_mssql_tiberius_read_replicas},
+    },
+};
 use crate::{
     adapters::{
         http::app_state::AppState,
         messaging::kafka::producer::KafkaProducer,
-        persistence::{
-            redis::token::AuthTokenCacheRepository,
-            sqlx::repositories::user::SqlXUserRepository,
-            // tiberius::repositories::user::TiberiusUserRepository,
-            // tiberius::repositories::user::TiberiusUserRepository,
+        persistence::redis::{
+            data_export::RedisDataExportRepository, email_change::RedisEmailChangeRepository,
+            passkey_ceremony::RedisPasskeyCeremonyRepository, token::AuthTokenCacheRepository,
+            trusted_device::RedisTrustedDeviceRepository,
+        },
+    },
+    application::use_cases::{
+        account::AccountUseCase,
+        attribute::AttributeUseCase,
+        auth::AuthUseCase,
+        avatar::AvatarUseCase,
+        data_export::{
+            AttributeDataSource, DataExportUseCase, PasskeyDataSource, SessionDataSource,
+            TrustedDeviceDataSource,
         },
+        email_change::EmailChangeUseCase,
+        outbox::OutboxUseCase,
+        passkey::PasskeyUseCase,
+        trusted_device::TrustedDeviceUseCase,
+        user::UserUseCase,
+        user_admin::UserAdminUseCase,
+        user_import::UserImportUseCase,
+    },
+    domain::repositories::{
+        attribute::AttributeRepository, outbox::OutboxRepository, passkey::PasskeyRepository,
+        second_factor::SecondFactorRepository, unit_of_work::UnitOfWorkFactory,
+        user::UserRepository,
     },
-    application::use_cases::{auth::AuthUseCase, user::UserUseCase},
     infra::{
-        config::AppConfig,
+        config::{AppConfig, DatabaseConfig},
+        images::JpegImageProcessor,
+        json_schema::JsonSchemaValidator,
         kafka::init_kafka_producer,
-        mssql_sqlx::init_mssql_db,
-        // mssql_tiberius::init_mssql_tiberius,
+        migrations::Migrator,
         redis::init_redis,
-        security::{argon2::Argon2PasswordHasher, jwt::JwtTokenProvider},
+        security::{
+            argon2::Argon2PasswordHasher, device_cookie::HmacDeviceCookieSigner,
+            email_change_token::HmacEmailChangeTokenProvider, jwt::JwtTokenProvider,
+            refresh_token::HmacRefreshTokenProvider, webauthn::WebAuthnVerifier,
+        },
+        storage::init_storage,
     },
 };
 
+struct Persistence {
+    user_repository: Arc<dyn UserRepository>,
+    unit_of_work: Arc<dyn UnitOfWorkFactory>,
+    outbox_repository: Arc<dyn OutboxRepository>,
+    passkey_repository: Arc<dyn PasskeyRepository>,
+    second_factor_repository: Arc<dyn SecondFactorRepository>,
+    attribute_repository: Arc<dyn AttributeRepository>,
+    migrator: Migrator,
+}
+
+async fn init_persistence(config: &DatabaseConfig) -> anyhow::Result<Persistence> {
+    match config {
+        #[cfg(feature = "mssql-sqlx")]
+        DatabaseConfig::MssqlSqlx(mssql) => {
+            let mssql_pool = init_mssql_db(mssql).await?;
+            let passkey_repository = Arc::new(SqlXPasskeyRepository::new(mssql_pool.clone()));
+            let migration_executor = SqlxMigrationExecutor::new(mssql_pool.clone());
+            let read_replicas = match &mssql.read_replicas {
+                Some(replicas) => ReplicaSet::new(
+                    init_mssql_read_replicas(mssql, replicas)?
+                        .into_iter()
+                        .map(SqlxConnectionSource::Pool)
+                        .collect(),
+                    replicas.retry_after,
+                ),
+                None => ReplicaSet::default(),
+            };
+            info!(
+                "Routing user reads to {} read replicas",
+                read_replicas.len()
+            );
+
+            Ok(Persistence {
+                user_repository: Arc::new(
+                    SqlXUserRepository::new(mssql_pool.clone())
+                        .with_read_replicas(Arc::new(read_replicas)),
+                ),
+                unit_of_work: Arc::new(SqlxUnitOfWorkFactory::new(
+                    mssql_pool.clone(),
+                    |source| Box::new(SqlXUserRepository::from_source(source)),
+                    |source| Box::new(SqlXOutboxRepository::from_source(source)),
+                )),
+                outbox_repository: Arc::new(SqlXOutboxRepository::new(mssql_pool.clone())),
+                passkey_repository: passkey_repository.clone(),
+                second_factor_repository: passkey_repository,
+                attribute_repository: Arc::new(SqlXAttributeRepository::new(mssql_pool)),
+                migrator: Migrator::new(Arc::new(migration_executor), MSSQL_MIGRATIONS),
+            })
+        }
+        #[cfg(not(feature = "mssql-sqlx"))]
+        DatabaseConfig::MssqlSqlx(_) => {
+            anyhow::bail!("DB_DRIVER=mssql_sqlx requires the `mssql-sqlx` feature")
+        }
+        #[cfg(feature = "mssql-tiberius")]
+        DatabaseConfig::MssqlTiberius(mssql) => {
+            let mssql_pool = init_mssql_tiberius(mssql).await?;
+            let passkey_repository = Arc::new(TiberiusPasskeyRepository::new(mssql_pool.clone()));
+            let migration_executor = TiberiusMigrationExecutor::new(mssql_pool.clone());
+            let read_replicas = match &mssql.read_replicas {
+                Some(replicas) => ReplicaSet::new(
+                    init_mssql_tiberius_read_replicas(mssql, replicas)
+                        .into_iter()
+                        .map(TiberiusConnectionSource::Pool)
+                        .collect(),
+                    replicas.retry_after,
+                ),
+                None => ReplicaSet::default(),
+            };
+            info!(
+                "Routing user reads to {} read replicas",
+                read_replicas.len()
+            );
+
+            Ok(Persistence {
+                user_repository: Arc::new(
+                    TiberiusUserRepository::new(mssql_pool.clone())
+                        .with_read_replicas(Arc::new(read_replicas)),
+                ),
+                unit_of_work: Arc::new(TiberiusUnitOfWorkFactory::new(mssql_pool.clone())),
+                outbox_repository: Arc::new(TiberiusOutboxRepository::new(mssql_pool.clone())),
+                passkey_repository: passkey_repository.clone(),
+                second_factor_repository: passkey_repository,
+                attribute_repository: Arc::new(TiberiusAttributeRepository::new(mssql_pool)),
+                migrator: Migrator::new(Arc::new(migration_executor), MSSQL_MIGRATIONS),
+            })
+        }
+        #[cfg(not(feature = "mssql-tiberius"))]
+        DatabaseConfig::MssqlTiberius(_) => {
+            anyhow::bail!("DB_DRIVER=mssql_tiberius requires the `mssql-tiberius` feature")
+        }
+        #[cfg(feature = "postgres")]
+        DatabaseConfig::Postgres(postgres) => {
+            let pg_pool = init_postgres_db(postgres).await?;
+            let passkey_repository = Arc::new(PgPasskeyRepository::new(pg_pool.clone()));
+            let migration_executor = PgMigrationExecutor::new(pg_pool.clone());
+
+            Ok(Persistence {
+                user_repository: Arc::new(PgUserRepository::new(pg_pool.clone())),
+                unit_of_work: Arc::new(SqlxUnitOfWorkFactory::new(
+                    pg_pool.clone(),
+                    |source| Box::new(PgUserRepository::from_source(source)),
+                    |source| Box::new(PgOutboxRepository::from_source(source)),
+                )),
+                outbox_repository: Arc::new(PgOutboxRepository::new(pg_pool.clone())),
+                passkey_repository: passkey_repository.clone(),
+                second_factor_repository: passkey_repository,
+                attribute_repository: Arc::new(PgAttributeRepository::new(pg_pool)),
+                migrator: Migrator::new(Arc::new(migration_executor), POSTGRES_MIGRATIONS),
+            })
+        }
+        #[cfg(not(feature = "postgres"))]
+        DatabaseConfig::Postgres(_) => {
+            anyhow::bail!("DB_DRIVER=postgres requires the `postgres` feature")
+        }
+        #[cfg(feature = "sqlite")]
+        DatabaseConfig::Sqlite(sqlite) => {
+            let sqlite_pool = init_sqlite_db(sqlite).await?;
+            let passkey_repository = Arc::new(SqlitePasskeyRepository::new(sqlite_pool.clone()));
+            let migration_executor = SqliteMigrationExecutor::new(sqlite_pool.clone());
+
+            Ok(Persistence {
+                user_repository: Arc::new(SqliteUserRepository::new(sqlite_pool.clone())),
+                unit_of_work: Arc::new(SqlxUnitOfWorkFactory::new(
+                    sqlite_pool.clone(),
+                    |source| Box::new(SqliteUserRepository::from_source(source)),
+                    |source| Box::new(SqliteOutboxRepository::from_source(source)),
+                )),
+                outbox_repository: Arc::new(SqliteOutboxRepository::new(sqlite_pool.clone())),
+                passkey_repository: passkey_repository.clone(),
+                second_factor_repository: passkey_repository,
+                attribute_repository: Arc::new(SqliteAttributeRepository::new(sqlite_pool)),
+                migrator: Migrator::new(Arc::new(migration_executor), SQLITE_MIGRATIONS),
+            })
+        }
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseConfig::Sqlite(_) => {
+            anyhow::bail!("DB_DRIVER=sqlite requires the `sqlite` feature")
+        }
+    }
+}
+
 pub async fn init_app_state() -> anyhow::Result<AppState> {
     let config = AppConfig::from_env();
     let hasher = Argon2PasswordHasher::default();
     let token_provider = JwtTokenProvider::new(config.jwt_secret.as_str());
+    let refresh_token_provider =
+        Arc::new(HmacRefreshTokenProvider::new(&config.refresh_token_secret));
+    let device_cookie_signer = HmacDeviceCookieSigner::new(&config.trusted_device_secret);
+    let passkey_verifier = WebAuthnVerifier::new(&config.webauthn.rp_id, &config.webauthn.origin);
+
+    let Persistence {
+        user_repository,
+        unit_of_work,
+        outbox_repository,
+        passkey_repository,
+        second_factor_repository,
+        attribute_repository,
+        migrator,
+    } = init_persistence(&config.database).await?;
 
-    // let mssql_pool = init_mssql_tiberius(&config.mssql).await?;
-    let mssql_pool = init_mssql_db(&config.mssql).await?;
+    if config.migrations.run_on_startup {
+        let applied = migrator.up(None).await?;
+
+        for migration in applied {
+            info!(
+                "Applied migration {} ({})",
+                migration.version, migration.name
+            );
+        }
+    }
 
     let redis_client = init_redis(&config.redis).await?;
 
+    let storage = init_storage(&config.storage);
+
     let kafka_producer = init_kafka_producer(&config.kafka_brokers)?;
     let user_event_producer = KafkaProducer::new(kafka_producer);
 
-    let user_repository = SqlXUserRepository::new(mssql_pool);
-    // let user_repository = TiberiusUserRepository::new(mssql_pool);
+    let token_cache_repository = Arc::new(AuthTokenCacheRepository::new(redis_client.clone()));
+    let trusted_device_repository =
+        Arc::new(RedisTrustedDeviceRepository::new(redis_client.clone()));
+    let passkey_ceremony_repository = RedisPasskeyCeremonyRepository::new(redis_client.clone());
+    let data_export_repository = RedisDataExportRepository::new(redis_client.clone());
+    let email_change_repository = RedisEmailChangeRepository::new(redis_client.clone());
+
+    let trusted_device_use_case = Arc::new(TrustedDeviceUseCase::new(
+        trusted_device_repository.clone(),
+        Arc::new(device_cookie_signer),
+        chrono::Duration::days(config.trusted_device_days),
+    ));
 
-    let token_cache_repository = AuthTokenCacheRepository::new(redis_client.clone());
+    let user_event_producer = Arc::new(user_event_producer);
+    let hasher = Arc::new(hasher);
 
-    let user_use_case = UserUseCase::new(Arc::new(user_repository.clone()));
+    let outbox_use_case = Arc::new(OutboxUseCase::new(
+        outbox_repository,
+        user_event_producer.clone(),
+        config.outbox.clone(),
+    ));
+
+    let user_use_case = UserUseCase::new(user_repository.clone(), user_event_producer.clone());
+    let user_import_use_case = UserImportUseCase::new(
+        user_repository.clone(),
+        unit_of_work.clone(),
+        hasher.clone(),
+    );
     let auth_use_case = AuthUseCase::new(
-        Arc::new(user_repository),
-        Arc::new(token_cache_repository),
-        Arc::new(hasher),
+        user_repository.clone(),
+        unit_of_work.clone(),
+        token_cache_repository.clone(),
+        hasher.clone(),
         Arc::new(token_provider.clone()),
-        Arc::new(user_event_producer),
+        refresh_token_provider.clone(),
+        user_event_producer.clone(),
+        config.session.clone(),
+    )
+    .with_trusted_devices(trusted_device_use_case.clone())
+    .with_second_factor(second_factor_repository);
+    let auth_use_case = Arc::new(auth_use_case);
+
+    let avatar_use_case = Arc::new(AvatarUseCase::new(
+        user_repository.clone(),
+        storage.object_storage.clone(),
+        Arc::new(JpegImageProcessor),
+        std::time::Duration::from_secs(config.avatar.url_ttl_minutes * 60),
+    ));
+
+    let data_export_use_case = DataExportUseCase::new(
+        Arc::new(data_export_repository),
+        storage.object_storage.clone(),
+        user_event_producer.clone(),
+        std::time::Duration::from_secs(config.export.url_ttl_minutes * 60),
+        std::time::Duration::from_secs(config.export.retention_days * 24 * 60 * 60),
+        chrono::Duration::minutes(config.export.build_timeout_minutes),
+    )
+    .with_source(Arc::new(SessionDataSource::new(
+        token_cache_repository.clone(),
+    )))
+    .with_source(Arc::new(TrustedDeviceDataSource::new(
+        trusted_device_repository,
+    )))
+    .with_source(Arc::new(PasskeyDataSource::new(passkey_repository.clone())))
+    .with_source(Arc::new(AttributeDataSource::new(
+        attribute_repository.clone(),
+    )));
+    let data_export_use_case = Arc::new(data_export_use_case);
+
+    let attribute_use_case = AttributeUseCase::new(
+        attribute_repository,
+        user_repository.clone(),
+        Arc::new(JsonSchemaValidator::new()),
+    );
+
+    let email_change_use_case = EmailChangeUseCase::new(
+        user_repository.clone(),
+        Arc::new(email_change_repository),
+        token_cache_repository.clone(),
+        trusted_device_use_case.clone(),
+        hasher.clone(),
+        refresh_token_provider,
+        Arc::new(HmacEmailChangeTokenProvider::new(
+            &config.email_change_token_secret,
+        )),
+        user_event_producer.clone(),
+    );
+
+    let user_admin_use_case = UserAdminUseCase::new(
+        user_repository.clone(),
+        unit_of_work.clone(),
+        token_cache_repository.clone(),
+        trusted_device_use_case.clone(),
+        hasher.clone(),
+    );
+
+    let account_use_case = AccountUseCase::new(
+        user_repository.clone(),
+        unit_of_work,
+        token_cache_repository,
+        trusted_device_use_case.clone(),
+        hasher,
+        user_event_producer,
+        chrono::Duration::days(config.account.erasure_grace_days),
+    )
+    .with_avatars(avatar_use_case.clone())
+    .with_data_exports(data_export_use_case.clone());
+
+    let passkey_use_case = PasskeyUseCase::new(
+        user_repository,
+        passkey_repository,
+        Arc::new(passkey_ceremony_repository),
+        Arc::new(passkey_verifier),
+        auth_use_case.clone(),
+        trusted_device_use_case.clone(),
     );
 
     Ok(AppState {
         config: Arc::new(config),
         user_use_case: Arc::new(user_use_case),
-        auth_use_case: Arc::new(auth_use_case),
+        user_admin_use_case: Arc::new(user_admin_use_case),
+        user_import_use_case: Arc::new(user_import_use_case),
+        account_use_case: Arc::new(account_use_case),
+        attribute_use_case: Arc::new(attribute_use_case),
+        auth_use_case,
+        passkey_use_case: Arc::new(passkey_use_case),
+        trusted_device_use_case,
+        avatar_use_case,
+        data_export_use_case,
+        email_change_use_case: Arc::new(email_change_use_case),
+        outbox_use_case,
         token_provider: Arc::new(token_provider),
+        object_storage: storage.object_storage,
+        file_url_signer: storage.url_signer,
     })
 }
 
@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use crate::infra::{config::SqliteConfig, pool::sqlx_pool_options};
+use sqlx::{
+    Pool, Sqlite,
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+};
+
+pub type SqlitePool = Pool<Sqlite>;
+
+pub async fn init_sqlite_db(config: &SqliteConfig) -> anyhow::Result<SqlitePool> {
+    let options = SqliteConnectOptions::new()
+        .foreign_keys(true)
+        .busy_timeout(Duration::from_secs(5));
+
+    let pool = if config.is_in_memory() {
+        // Each connection to `:memory:` opens its own database, so keep exactly one alive.
+        SqlitePoolOptions::new()
+            .acquire_timeout(config.pool.connect_timeout)
+            .max_connections(1)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await?
+    } else {
+        sqlx_pool_options(&config.pool)
+            .connect_with(
+                options
+                    .filename(&config.path)
+                    .create_if_missing(true)
+                    .journal_mode(SqliteJournalMode::Wal),
+            )
+            .await?
+    };
+
+    Ok(pool)
+}
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use crate::{
+    adapters::storage::{local::LocalObjectStorage, s3::S3ObjectStorage},
+    domain::repositories::object_storage::ObjectStorage,
+    infra::{
+        config::StorageConfig,
+        security::url_signer::{HmacUrlSigner, UrlSigner},
+    },
+};
+
+pub struct Storage {
+    pub object_storage: Arc<dyn ObjectStorage>,
+    pub url_signer: Option<Arc<dyn UrlSigner>>,
+}
+
+pub fn init_storage(cfg: &StorageConfig) -> Storage {
+    match cfg {
+        StorageConfig::Local {
+            root,
+            public_url,
+            signing_secret,
+        } => {
+            let signer: Arc<dyn UrlSigner> = Arc::new(HmacUrlSigner::new(signing_secret));
+
+            Storage {
+                object_storage: Arc::new(LocalObjectStorage::new(root, public_url, signer.clone())),
+                url_signer: Some(signer),
+            }
+        }
+        StorageConfig::S3 {
+            bucket,
+            region,
+            endpoint,
+            access_key_id,
+            secret_access_key,
+        } => Storage {
+            object_storage: Arc::new(S3ObjectStorage::from_credentials(
+                bucket,
+                region,
+                endpoint.as_deref(),
+                access_key_id,
+                secret_access_key,
+            )),
+            url_signer: None,
+        },
+    }
+}
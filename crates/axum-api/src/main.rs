@@ -1,5 +1,13 @@
 use anyhow::Result;
-use axum_api::infra::{app::create_app, setup::init_app_state};
+use std::time::Duration;
+
+use axum_api::infra::{
+    app::{create_app, create_metrics_app},
+    jobs::{
+        spawn_data_export_cleanup, spawn_erasure_job, spawn_outbox_cleanup, spawn_outbox_relay,
+    },
+    setup::init_app_state,
+};
 use dotenvy::dotenv;
 use tokio::net::TcpListener;
 use tracing::info;
@@ -10,9 +18,32 @@ async fn main() -> Result<()> {
 
     let app_state = init_app_state().await?;
     let app = create_app(app_state.clone());
+    let metrics_app = create_metrics_app(app_state.clone());
+
+    spawn_erasure_job(
+        app_state.account_use_case.clone(),
+        Duration::from_secs(app_state.config.account.erasure_job_interval_secs),
+    );
+
+    spawn_outbox_relay(
+        app_state.outbox_use_case.clone(),
+        Duration::from_millis(app_state.config.outbox.relay_interval_ms),
+    );
+
+    spawn_outbox_cleanup(
+        app_state.outbox_use_case.clone(),
+        Duration::from_secs(app_state.config.outbox.cleanup_interval_secs),
+    );
+
+    spawn_data_export_cleanup(
+        app_state.data_export_use_case.clone(),
+        Duration::from_secs(app_state.config.export.cleanup_interval_secs),
+    );
 
     // let user_use_case = app_state.user_use_case.clone();
-    // let grpc_user_service = UserService::new(user_use_case);
+    // let avatar_use_case = app_state.avatar_use_case.clone();
+    // let attribute_use_case = app_state.attribute_use_case.clone();
+    // let grpc_user_service = UserService::new(user_use_case, avatar_use_case, attribute_use_case);
     // let grpc_addr = "[::]:50051".parse()?;
 
     // tokio::spawn(async move {
@@ -24,6 +55,18 @@ async fn main() -> Result<()> {
     //         .unwrap();
     // });
 
+    let metrics_addr = format!("[::]:{}", app_state.config.metrics_port);
+    let metrics_listener = TcpListener::bind(&metrics_addr).await?;
+
+    info!(
+        "Metrics listening at {}",
+        &metrics_listener.local_addr().unwrap()
+    );
+
+    tokio::spawn(async move {
+        axum::serve(metrics_listener, metrics_app).await.unwrap();
+    });
+
     let addr = format!("[::]:{}", app_state.config.port);
     let listener = TcpListener::bind(&addr).await?;
 
@@ -0,0 +1,213 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+use std::time::Duration as StdDuration;
+
+use axum_api::{
+    application::{app_error::AppError, use_cases::account::AccountUseCase},
+    domain::{
+        entities::{
+            data_export::DataExportFormat,
+            session::RefreshSession,
+            user::{User, UserStatus},
+        },
+        repositories::{
+            token_cache::{SessionLimit, TokenCacheRepository},
+            user::UserRepository,
+        },
+    },
+};
+use chrono::Duration;
+use common::{Fakes, PASSWORD};
+
+type Harness = common::Harness<AccountUseCase>;
+
+fn harness(erasure_grace_period: Duration) -> Harness {
+    Fakes::new().harness(|fakes| fakes.account_use_case(erasure_grace_period))
+}
+
+/// Stores an active user with a refresh session and a trusted device.
+async fn user(harness: &Harness, label: &str) -> User {
+    let user = harness.user(label);
+
+    harness
+        .cache
+        .store_refresh_token(
+            &format!("{label}-token"),
+            &RefreshSession::new(*user.id(), "web".to_string(), "laptop".to_string()),
+            3600,
+            SessionLimit::Unlimited,
+        )
+        .await
+        .unwrap();
+    harness
+        .trusted_devices
+        .trust_device(*user.id(), "Laptop".to_string())
+        .await
+        .unwrap();
+
+    user
+}
+
+async fn status(harness: &Harness, user: &User) -> UserStatus {
+    harness
+        .users
+        .find_by_id(&user.id().to_string())
+        .await
+        .unwrap()
+        .unwrap()
+        .status()
+}
+
+async fn has_sessions(harness: &Harness, user: &User) -> bool {
+    let sessions = harness.cache.list_refresh_tokens(*user.id()).await.unwrap();
+    let devices = harness
+        .trusted_devices
+        .list_devices(*user.id())
+        .await
+        .unwrap();
+
+    !sessions.is_empty() || !devices.is_empty()
+}
+
+#[tokio::test]
+async fn deactivation_requires_the_password_and_ends_every_session() {
+    let harness = harness(Duration::days(30));
+    let user = user(&harness, "ada").await;
+
+    let result = harness
+        .use_case
+        .deactivate(*user.id(), "wrong password")
+        .await;
+    assert!(matches!(result, Err(AppError::Unauthorized)));
+    assert_eq!(status(&harness, &user).await, UserStatus::Active);
+
+    harness
+        .use_case
+        .deactivate(*user.id(), PASSWORD)
+        .await
+        .unwrap();
+
+    assert_eq!(status(&harness, &user).await, UserStatus::Deactivated);
+    assert!(!has_sessions(&harness, &user).await);
+}
+
+#[tokio::test]
+async fn admins_cannot_restrict_their_own_account() {
+    let harness = harness(Duration::days(30));
+    let admin = user(&harness, "admin").await;
+    let id = *admin.id();
+
+    let suspended = harness
+        .use_case
+        .suspend(id, id, "Testing".to_string(), None)
+        .await;
+    let locked = harness
+        .use_case
+        .lock(id, id, "Testing".to_string(), None)
+        .await;
+    let erased = harness.use_case.erase(id, id).await;
+
+    assert!(matches!(suspended, Err(AppError::Forbidden)));
+    assert!(matches!(locked, Err(AppError::Forbidden)));
+    assert!(matches!(erased, Err(AppError::Forbidden)));
+    assert_eq!(status(&harness, &admin).await, UserStatus::Active);
+}
+
+#[tokio::test]
+async fn unlocking_only_reopens_locked_accounts() {
+    let harness = harness(Duration::days(30));
+    let admin = user(&harness, "admin").await;
+    let suspended = user(&harness, "suspended").await;
+    let locked = user(&harness, "locked").await;
+
+    harness
+        .use_case
+        .suspend(*admin.id(), *suspended.id(), "Abuse".to_string(), None)
+        .await
+        .unwrap();
+    harness
+        .use_case
+        .lock(*admin.id(), *locked.id(), "Compromised".to_string(), None)
+        .await
+        .unwrap();
+    assert!(!has_sessions(&harness, &locked).await);
+
+    let result = harness.use_case.unlock(*suspended.id()).await;
+    assert!(matches!(result, Err(AppError::InvalidStatusTransition(_))));
+    assert_eq!(status(&harness, &suspended).await, UserStatus::Suspended);
+
+    let unlocked = harness.use_case.unlock(*locked.id()).await.unwrap();
+    assert_eq!(unlocked.status(), UserStatus::Active);
+}
+
+#[tokio::test]
+async fn erasure_waits_for_the_grace_period() {
+    let harness = harness(Duration::days(30));
+    let user = user(&harness, "ada").await;
+
+    harness
+        .use_case
+        .request_erasure(*user.id(), PASSWORD)
+        .await
+        .unwrap();
+
+    assert_eq!(status(&harness, &user).await, UserStatus::PendingDeletion);
+    assert!(!has_sessions(&harness, &user).await);
+    assert_eq!(
+        harness.use_case.finalize_pending_erasures().await.unwrap(),
+        0
+    );
+    assert_eq!(status(&harness, &user).await, UserStatus::PendingDeletion);
+}
+
+#[tokio::test]
+async fn due_erasures_anonymize_the_account_and_delete_its_exports() {
+    let harness = harness(Duration::zero());
+    let erased = user(&harness, "ada").await;
+    let kept = user(&harness, "grace").await;
+    let exports = harness.data_export_use_case(StdDuration::from_secs(3600));
+
+    for owner in [&erased, &kept] {
+        exports
+            .request_export(owner, DataExportFormat::Json)
+            .await
+            .unwrap();
+    }
+    while harness.storage.keys().len() < 2 {
+        tokio::time::sleep(StdDuration::from_millis(5)).await;
+    }
+
+    harness
+        .use_case
+        .request_erasure(*erased.id(), PASSWORD)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        harness.use_case.finalize_pending_erasures().await.unwrap(),
+        1
+    );
+    assert_eq!(
+        harness.use_case.finalize_pending_erasures().await.unwrap(),
+        0
+    );
+
+    let stored = harness
+        .users
+        .find_by_id(&erased.id().to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.status(), UserStatus::Deleted);
+    assert_ne!(stored.email(), erased.email());
+
+    let messages = harness.outbox.messages();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].event_type, "UserDeleted");
+
+    let keys = harness.storage.keys();
+    assert_eq!(keys.len(), 1);
+    assert!(keys[0].starts_with(&format!("exports/{}/", kept.id())));
+}
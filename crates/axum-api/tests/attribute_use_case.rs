@@ -0,0 +1,260 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+use axum_api::{
+    application::{app_error::AppError, use_cases::attribute::AttributeUseCase},
+    domain::entities::{
+        attribute::{AttributeSchema, UserAttributes},
+        user::Role,
+    },
+    infra::json_schema::{JsonSchemaValidator, SchemaValidator},
+};
+use chrono::Utc;
+use common::Fakes;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+type Harness = common::Harness<AttributeUseCase>;
+
+fn harness() -> Harness {
+    Fakes::new().harness(Fakes::attribute_use_case)
+}
+
+fn preferences_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "theme": { "enum": ["light", "dark"] }
+        },
+        "additionalProperties": false
+    })
+}
+
+fn namespaces(attributes: Vec<UserAttributes>) -> Vec<String> {
+    attributes
+        .into_iter()
+        .map(|attributes| attributes.namespace)
+        .collect()
+}
+
+#[tokio::test]
+async fn rejects_invalid_namespaces_and_schemas() {
+    let harness = harness();
+
+    let namespace = harness
+        .use_case
+        .put_schema("Preferences", preferences_schema(), true)
+        .await;
+    let schema = harness
+        .use_case
+        .put_schema("preferences", json!({ "type": 42 }), true)
+        .await;
+
+    assert!(matches!(namespace, Err(AppError::ValidationError(_))));
+    assert!(matches!(schema, Err(AppError::ValidationError(_))));
+    assert!(harness.use_case.list_schemas().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn validates_documents_against_the_namespace_schema() {
+    let harness = harness();
+    let user = harness.user("ada");
+    harness
+        .use_case
+        .put_schema("preferences", preferences_schema(), true)
+        .await
+        .unwrap();
+
+    let invalid = harness
+        .use_case
+        .set_attributes(
+            *user.id(),
+            "preferences",
+            json!({ "theme": "blue" }),
+            Role::User,
+        )
+        .await;
+    assert!(matches!(invalid, Err(AppError::ValidationError(_))));
+
+    harness
+        .use_case
+        .set_attributes(
+            *user.id(),
+            "preferences",
+            json!({ "theme": "dark" }),
+            Role::User,
+        )
+        .await
+        .unwrap();
+
+    let stored = harness
+        .use_case
+        .get_attributes(*user.id(), "preferences", Role::User)
+        .await
+        .unwrap();
+    assert_eq!(stored.data, json!({ "theme": "dark" }));
+}
+
+#[tokio::test]
+async fn only_admins_write_namespaces_users_cannot() {
+    let harness = harness();
+    let user = harness.user("ada");
+    harness
+        .use_case
+        .put_schema("billing", json!({ "type": "object" }), false)
+        .await
+        .unwrap();
+
+    let by_user = harness
+        .use_case
+        .set_attributes(*user.id(), "billing", json!({ "plan": "pro" }), Role::User)
+        .await;
+    assert!(matches!(by_user, Err(AppError::Forbidden)));
+
+    harness
+        .use_case
+        .set_attributes(*user.id(), "billing", json!({ "plan": "pro" }), Role::Admin)
+        .await
+        .unwrap();
+
+    let deleted_by_user = harness
+        .use_case
+        .delete_attributes(*user.id(), "billing", Role::User)
+        .await;
+    assert!(matches!(deleted_by_user, Err(AppError::Forbidden)));
+
+    harness
+        .use_case
+        .delete_attributes(*user.id(), "billing", Role::Admin)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn rejects_documents_for_unknown_users_and_namespaces() {
+    let harness = harness();
+    harness
+        .use_case
+        .put_schema("preferences", preferences_schema(), true)
+        .await
+        .unwrap();
+
+    let unknown_user = harness
+        .use_case
+        .set_attributes(Uuid::new_v4(), "preferences", json!({}), Role::Admin)
+        .await;
+    let unknown_namespace = harness
+        .use_case
+        .set_attributes(Uuid::new_v4(), "missing", json!({}), Role::Admin)
+        .await;
+
+    assert!(matches!(unknown_user, Err(AppError::UserNotFound)));
+    assert!(matches!(
+        unknown_namespace,
+        Err(AppError::AttributeSchemaNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn users_only_read_namespaces_they_can_write() {
+    let harness = harness();
+    let user = harness.user("ada");
+    harness
+        .use_case
+        .put_schema("preferences", preferences_schema(), true)
+        .await
+        .unwrap();
+    harness
+        .use_case
+        .put_schema("billing", json!({ "type": "object" }), false)
+        .await
+        .unwrap();
+    for (namespace, data) in [
+        ("preferences", json!({ "theme": "dark" })),
+        ("billing", json!({ "plan": "pro" })),
+    ] {
+        harness
+            .use_case
+            .set_attributes(*user.id(), namespace, data, Role::Admin)
+            .await
+            .unwrap();
+    }
+
+    let by_user = harness
+        .use_case
+        .list_attributes(*user.id(), Role::User)
+        .await
+        .unwrap();
+    let by_admin = harness
+        .use_case
+        .list_attributes(*user.id(), Role::Admin)
+        .await
+        .unwrap();
+    assert_eq!(namespaces(by_user), ["preferences"]);
+    assert_eq!(namespaces(by_admin), ["billing", "preferences"]);
+
+    let by_user = harness
+        .use_case
+        .get_attributes(*user.id(), "billing", Role::User)
+        .await;
+    assert!(matches!(by_user, Err(AppError::AttributesNotFound)));
+    harness
+        .use_case
+        .get_attributes(*user.id(), "billing", Role::Admin)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn validates_against_the_latest_version_of_a_schema() {
+    let harness = harness();
+    let user = harness.user("ada");
+    harness
+        .use_case
+        .put_schema("preferences", preferences_schema(), true)
+        .await
+        .unwrap();
+
+    let sepia = json!({ "theme": "sepia" });
+    let result = harness
+        .use_case
+        .set_attributes(*user.id(), "preferences", sepia.clone(), Role::User)
+        .await;
+    assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+    let mut schema = preferences_schema();
+    schema["properties"]["theme"]["enum"] = json!(["light", "dark", "sepia"]);
+    harness
+        .use_case
+        .put_schema("preferences", schema, true)
+        .await
+        .unwrap();
+
+    harness
+        .use_case
+        .set_attributes(*user.id(), "preferences", sepia, Role::User)
+        .await
+        .unwrap();
+}
+
+#[test]
+fn reuses_the_compiled_schema_until_its_version_changes() {
+    let validator = JsonSchemaValidator::new();
+    let mut schema = AttributeSchema {
+        namespace: "preferences".to_string(),
+        schema: preferences_schema(),
+        user_writable: true,
+        updated_at: Utc::now(),
+    };
+    let sepia = json!({ "theme": "sepia" });
+
+    assert!(validator.validate(&schema, &sepia).is_err());
+
+    // Same version: the cached validator still applies even though the document changed.
+    schema.schema = json!({ "type": "object" });
+    assert!(validator.validate(&schema, &sepia).is_err());
+
+    schema.updated_at += chrono::Duration::seconds(1);
+    assert!(validator.validate(&schema, &sepia).is_ok());
+}
@@ -0,0 +1,643 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+use std::time::Duration;
+
+use axum_api::{
+    adapters::memory::{events::RecordedEvent, security::InsecurePasswordHasher},
+    application::{
+        app_error::AppError,
+        use_cases::auth::{AuthUseCase, LoginOutcome},
+    },
+    domain::{
+        entities::user::{User, UserStatus},
+        repositories::{token_cache::TokenCacheRepository, user::UserRepository},
+    },
+    infra::{
+        config::{SessionLimitStrategy, SessionPolicy},
+        security::{argon2::PasswordHasherTrait, jwt::TokenProvider},
+    },
+};
+use chrono::Utc;
+use common::{Fakes, PASSWORD, client, default_policy, policy};
+use uuid::Uuid;
+
+type Harness = common::Harness<AuthUseCase>;
+
+fn harness_with(default_policy: SessionPolicy) -> Harness {
+    Fakes::new().harness(|fakes| fakes.auth_use_case(default_policy))
+}
+
+fn harness() -> Harness {
+    harness_with(default_policy())
+}
+
+async fn register(harness: &Harness, email: &str) -> (User, String) {
+    let (_, refresh_token) = harness
+        .use_case
+        .register(
+            email.to_string(),
+            PASSWORD.to_string(),
+            "Ada".to_string(),
+            client("laptop"),
+        )
+        .await
+        .unwrap();
+
+    let user = harness.users.find_by_email(email).await.unwrap().unwrap();
+
+    (user, refresh_token)
+}
+
+async fn login(harness: &Harness, email: &str, device: &str) -> Result<LoginOutcome, AppError> {
+    harness
+        .use_case
+        .login(
+            email.to_string(),
+            PASSWORD.to_string(),
+            client(device),
+            None,
+        )
+        .await
+}
+
+async fn authenticated(harness: &Harness, email: &str, device: &str) -> (String, String) {
+    match login(harness, email, device).await.unwrap() {
+        LoginOutcome::Authenticated {
+            access_token,
+            refresh_token,
+        } => (access_token, refresh_token),
+        LoginOutcome::MfaRequired { .. } => panic!("expected tokens, got an MFA challenge"),
+    }
+}
+
+async fn set_status(harness: &Harness, user: &User, change: impl FnOnce(&mut User)) {
+    let mut user = user.clone();
+    let previous = user.status();
+    change(&mut user);
+
+    assert!(harness.users.update_status(&user, previous).await.unwrap());
+}
+
+#[tokio::test]
+async fn register_issues_tokens_and_queues_user_created() {
+    let harness = harness();
+
+    let (access_token, refresh_token) = harness
+        .use_case
+        .register(
+            "ada@example.com".to_string(),
+            PASSWORD.to_string(),
+            "Ada".to_string(),
+            client("laptop"),
+        )
+        .await
+        .unwrap();
+
+    let user = harness
+        .users
+        .find_by_email("ada@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_ne!(user.password(), PASSWORD);
+    assert!(
+        InsecurePasswordHasher::new()
+            .verify_password(PASSWORD, user.password())
+            .unwrap()
+    );
+
+    let claims = harness.tokens.decode_token(&access_token).unwrap();
+    assert_eq!(claims.sub, user.id().to_string());
+
+    let sessions = harness.cache.list_refresh_tokens(*user.id()).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_ne!(
+        sessions[0].0, refresh_token,
+        "only the token hash is stored"
+    );
+
+    let messages = harness.outbox.messages();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].event_type, "UserCreated");
+    assert_eq!(messages[0].aggregate_id, *user.id());
+    assert!(messages[0].payload.contains("ada@example.com"));
+    assert!(
+        harness.events.events().is_empty(),
+        "the outbox relay publishes it"
+    );
+}
+
+#[tokio::test]
+async fn register_rejects_taken_email_regardless_of_case() {
+    let harness = harness();
+    register(&harness, "ada@example.com").await;
+
+    let result = harness
+        .use_case
+        .register(
+            "ADA@example.com".to_string(),
+            PASSWORD.to_string(),
+            "Imposter".to_string(),
+            client("laptop"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::EmailAlreadyExists(_))));
+    assert_eq!(harness.users.len(), 1);
+}
+
+#[tokio::test]
+async fn register_rejects_taken_email_reported_without_an_index_name() {
+    let harness = harness();
+    register(&harness, "ada@example.com").await;
+    // SQL Server only names the index in English messages.
+    harness.users.set_unnamed_violations(true);
+
+    let result = harness
+        .use_case
+        .register(
+            "ada@example.com".to_string(),
+            PASSWORD.to_string(),
+            "Imposter".to_string(),
+            client("laptop"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::EmailAlreadyExists(_))));
+    assert_eq!(harness.users.len(), 1);
+}
+
+#[tokio::test]
+async fn register_stores_nothing_when_the_transaction_fails() {
+    let harness = harness();
+    harness.unit_of_work.set_failing(true);
+
+    let result = harness
+        .use_case
+        .register(
+            "ada@example.com".to_string(),
+            PASSWORD.to_string(),
+            "Ada".to_string(),
+            client("laptop"),
+        )
+        .await;
+
+    assert!(result.is_err());
+    assert!(harness.users.is_empty());
+    assert!(harness.outbox.messages().is_empty());
+}
+
+#[tokio::test]
+async fn login_checks_credentials() {
+    let harness = harness();
+    register(&harness, "ada@example.com").await;
+
+    let (access_token, _) = authenticated(&harness, "ada@example.com", "phone").await;
+    assert!(harness.tokens.decode_token(&access_token).is_ok());
+
+    let wrong_password = harness
+        .use_case
+        .login(
+            "ada@example.com".to_string(),
+            "wrong password".to_string(),
+            client("phone"),
+            None,
+        )
+        .await;
+    assert!(matches!(wrong_password, Err(AppError::Unauthorized)));
+
+    let unknown = login(&harness, "nobody@example.com", "phone").await;
+    assert!(matches!(unknown, Err(AppError::UserNotFound)));
+}
+
+#[tokio::test]
+async fn login_rejects_restricted_accounts() {
+    let harness = harness();
+    let (user, _) = register(&harness, "ada@example.com").await;
+
+    set_status(&harness, &user, |user| {
+        user.lock(
+            "Too many attempts".to_string(),
+            Some(Utc::now() + chrono::Duration::hours(1)),
+        )
+        .unwrap();
+    })
+    .await;
+
+    let result = login(&harness, "ada@example.com", "phone").await;
+    assert!(matches!(result, Err(AppError::AccountLocked)));
+}
+
+#[tokio::test]
+async fn login_reactivates_expired_restrictions() {
+    let harness = harness();
+    let (user, _) = register(&harness, "ada@example.com").await;
+
+    set_status(&harness, &user, |user| {
+        user.suspend(
+            "Review".to_string(),
+            Some(Utc::now() - chrono::Duration::minutes(1)),
+        )
+        .unwrap();
+    })
+    .await;
+    harness.events.take();
+
+    authenticated(&harness, "ada@example.com", "phone").await;
+
+    let stored = harness
+        .users
+        .find_by_id(&user.id().to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.status(), UserStatus::Active);
+
+    assert!(matches!(
+        harness.events.events().as_slice(),
+        [RecordedEvent::StatusChanged(event)]
+            if event.from == UserStatus::Suspended && event.to == UserStatus::Active
+    ));
+}
+
+#[tokio::test]
+async fn login_requires_second_factor_when_enrolled() {
+    let harness = harness();
+    let (user, _) = register(&harness, "ada@example.com").await;
+    harness.second_factor.enroll(*user.id());
+
+    let LoginOutcome::MfaRequired { mfa_token } =
+        login(&harness, "ada@example.com", "phone").await.unwrap()
+    else {
+        panic!("expected an MFA challenge");
+    };
+
+    let other_device = harness
+        .use_case
+        .take_mfa_challenge(&mfa_token, &client("tablet"))
+        .await;
+    assert!(matches!(other_device, Err(AppError::InvalidToken)));
+
+    let LoginOutcome::MfaRequired { mfa_token } =
+        login(&harness, "ada@example.com", "phone").await.unwrap()
+    else {
+        panic!("expected an MFA challenge");
+    };
+
+    let challenged = harness
+        .use_case
+        .take_mfa_challenge(&mfa_token, &client("phone"))
+        .await
+        .unwrap();
+    assert_eq!(challenged.id(), user.id());
+
+    let replayed = harness
+        .use_case
+        .take_mfa_challenge(&mfa_token, &client("phone"))
+        .await;
+    assert!(matches!(replayed, Err(AppError::InvalidToken)));
+
+    harness.second_factor.unenroll(*user.id());
+    authenticated(&harness, "ada@example.com", "phone").await;
+}
+
+#[tokio::test]
+async fn deactivated_accounts_stay_deactivated_until_the_second_factor_passes() {
+    let harness = harness();
+    let (user, _) = register(&harness, "ada@example.com").await;
+    harness.second_factor.enroll(*user.id());
+    set_status(&harness, &user, |user| {
+        user.deactivate().unwrap();
+    })
+    .await;
+    harness.events.take();
+
+    let LoginOutcome::MfaRequired { mfa_token } =
+        login(&harness, "ada@example.com", "phone").await.unwrap()
+    else {
+        panic!("expected an MFA challenge");
+    };
+
+    let stored = harness
+        .users
+        .find_by_id(&user.id().to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.status(), UserStatus::Deactivated);
+    assert!(harness.events.events().is_empty());
+
+    let challenged = harness
+        .use_case
+        .take_mfa_challenge(&mfa_token, &client("phone"))
+        .await
+        .unwrap();
+    harness
+        .use_case
+        .complete_login(&challenged, client("phone"))
+        .await
+        .unwrap();
+
+    let stored = harness
+        .users
+        .find_by_id(&user.id().to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.status(), UserStatus::Active);
+    assert!(matches!(
+        harness.events.events().as_slice(),
+        [RecordedEvent::StatusChanged(event)]
+            if event.from == UserStatus::Deactivated && event.to == UserStatus::Active
+    ));
+}
+
+#[tokio::test(start_paused = true)]
+async fn mfa_challenges_expire() {
+    let harness = harness();
+    let (user, _) = register(&harness, "ada@example.com").await;
+    harness.second_factor.enroll(*user.id());
+
+    let LoginOutcome::MfaRequired { mfa_token } =
+        login(&harness, "ada@example.com", "phone").await.unwrap()
+    else {
+        panic!("expected an MFA challenge");
+    };
+
+    tokio::time::advance(Duration::from_secs(5 * 60 + 1)).await;
+
+    let expired = harness
+        .use_case
+        .take_mfa_challenge(&mfa_token, &client("phone"))
+        .await;
+    assert!(matches!(expired, Err(AppError::InvalidToken)));
+}
+
+#[tokio::test]
+async fn refresh_rotates_the_refresh_token() {
+    let harness = harness();
+    let (user, refresh_token) = register(&harness, "ada@example.com").await;
+
+    let (access_token, rotated) = harness
+        .use_case
+        .refresh_token(&refresh_token, client("laptop"))
+        .await
+        .unwrap();
+    assert_ne!(rotated, refresh_token);
+    assert_eq!(
+        harness.tokens.decode_token(&access_token).unwrap().sub,
+        user.id().to_string()
+    );
+
+    let reused = harness
+        .use_case
+        .refresh_token(&refresh_token, client("laptop"))
+        .await;
+    assert!(matches!(reused, Err(AppError::InvalidToken)));
+
+    assert_eq!(
+        harness
+            .cache
+            .list_refresh_tokens(*user.id())
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn concurrent_refreshes_with_one_token_rotate_once() {
+    let harness = harness();
+    let (user, refresh_token) = register(&harness, "ada@example.com").await;
+
+    let (first, second) = tokio::join!(
+        harness
+            .use_case
+            .refresh_token(&refresh_token, client("laptop")),
+        harness
+            .use_case
+            .refresh_token(&refresh_token, client("laptop")),
+    );
+
+    assert_eq!(
+        [first.is_ok(), second.is_ok()]
+            .iter()
+            .filter(|ok| **ok)
+            .count(),
+        1
+    );
+    assert_eq!(
+        harness
+            .cache
+            .list_refresh_tokens(*user.id())
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn refresh_is_bound_to_the_issuing_device() {
+    let harness = harness();
+    let (_, refresh_token) = register(&harness, "ada@example.com").await;
+
+    let stolen = harness
+        .use_case
+        .refresh_token(&refresh_token, client("attacker"))
+        .await;
+    assert!(matches!(stolen, Err(AppError::InvalidToken)));
+
+    let revoked = harness
+        .use_case
+        .refresh_token(&refresh_token, client("laptop"))
+        .await;
+    assert!(matches!(revoked, Err(AppError::InvalidToken)));
+}
+
+#[tokio::test]
+async fn refresh_rejects_restricted_accounts() {
+    let harness = harness();
+    let (user, refresh_token) = register(&harness, "ada@example.com").await;
+
+    set_status(&harness, &user, |user| {
+        user.suspend("Review".to_string(), None).unwrap();
+    })
+    .await;
+
+    let result = harness
+        .use_case
+        .refresh_token(&refresh_token, client("laptop"))
+        .await;
+    assert!(matches!(result, Err(AppError::AccountSuspended)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn refresh_tokens_expire_after_the_idle_timeout() {
+    let harness = harness();
+    let (_, refresh_token) = register(&harness, "ada@example.com").await;
+
+    tokio::time::advance(Duration::from_secs(60 * 60 + 1)).await;
+
+    let result = harness
+        .use_case
+        .refresh_token(&refresh_token, client("laptop"))
+        .await;
+    assert!(matches!(result, Err(AppError::InvalidToken)));
+}
+
+#[tokio::test]
+async fn session_limit_evicts_the_oldest_session() {
+    let harness = harness_with(policy(2, SessionLimitStrategy::EvictOldest));
+    let (user, first) = register(&harness, "ada@example.com").await;
+
+    let (_, second) = authenticated(&harness, "ada@example.com", "laptop").await;
+    let (_, third) = authenticated(&harness, "ada@example.com", "laptop").await;
+
+    assert_eq!(
+        harness
+            .cache
+            .list_refresh_tokens(*user.id())
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+
+    let evicted = harness
+        .use_case
+        .refresh_token(&first, client("laptop"))
+        .await;
+    assert!(matches!(evicted, Err(AppError::InvalidToken)));
+
+    for token in [second, third] {
+        harness
+            .use_case
+            .refresh_token(&token, client("laptop"))
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn session_limit_can_reject_new_sessions() {
+    let harness = harness_with(policy(1, SessionLimitStrategy::Reject));
+    register(&harness, "ada@example.com").await;
+
+    let result = login(&harness, "ada@example.com", "phone").await;
+    assert!(matches!(result, Err(AppError::SessionLimitReached)));
+}
+
+#[tokio::test]
+async fn concurrent_sign_ins_cannot_exceed_the_session_limit() {
+    let harness = harness_with(policy(2, SessionLimitStrategy::Reject));
+    let (user, _) = register(&harness, "ada@example.com").await;
+
+    let results = futures::future::join_all(
+        ["phone", "tablet", "desktop"].map(|device| login(&harness, "ada@example.com", device)),
+    )
+    .await;
+
+    assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+    assert_eq!(
+        harness
+            .cache
+            .list_refresh_tokens(*user.id())
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn revoked_access_tokens_stay_blacklisted_until_they_expire() {
+    let harness = harness();
+    let exp = Utc::now().timestamp() + 60;
+
+    harness.use_case.revoke_token("jti-1", exp).await.unwrap();
+    assert!(harness.use_case.is_blacklisted("jti-1").await.unwrap());
+
+    tokio::time::advance(Duration::from_secs(61)).await;
+    assert!(!harness.use_case.is_blacklisted("jti-1").await.unwrap());
+
+    harness
+        .use_case
+        .revoke_token("jti-2", Utc::now().timestamp() - 1)
+        .await
+        .unwrap();
+    assert!(!harness.use_case.is_blacklisted("jti-2").await.unwrap());
+}
+
+#[tokio::test]
+async fn change_password_requires_the_current_password() {
+    let harness = harness();
+    let (user, _) = register(&harness, "ada@example.com").await;
+
+    let wrong = harness
+        .use_case
+        .change_password(
+            *user.id(),
+            "wrong password".to_string(),
+            "new password".to_string(),
+        )
+        .await;
+    assert!(matches!(wrong, Err(AppError::Unauthorized)));
+
+    harness
+        .use_case
+        .change_password(*user.id(), PASSWORD.to_string(), "new password".to_string())
+        .await
+        .unwrap();
+
+    let old = login(&harness, "ada@example.com", "phone").await;
+    assert!(matches!(old, Err(AppError::Unauthorized)));
+
+    harness
+        .use_case
+        .login(
+            "ada@example.com".to_string(),
+            "new password".to_string(),
+            client("phone"),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let missing = harness
+        .use_case
+        .change_password(
+            Uuid::new_v4(),
+            PASSWORD.to_string(),
+            "new password".to_string(),
+        )
+        .await;
+    assert!(matches!(missing, Err(AppError::UserNotFound)));
+}
+
+#[tokio::test]
+async fn legacy_refresh_tokens_are_migrated_once() {
+    let harness = harness();
+    let (user, _) = register(&harness, "ada@example.com").await;
+
+    let legacy = Uuid::new_v4().to_string();
+    harness
+        .cache
+        .store_legacy_refresh_token(&legacy, *user.id(), 3600);
+
+    let (_, refresh_token) = harness
+        .use_case
+        .refresh_token(&legacy, client("laptop"))
+        .await
+        .unwrap();
+    assert!(Uuid::parse_str(&refresh_token).is_err());
+
+    let replayed = harness
+        .use_case
+        .refresh_token(&legacy, client("laptop"))
+        .await;
+    assert!(matches!(replayed, Err(AppError::InvalidToken)));
+}
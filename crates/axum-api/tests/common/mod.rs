@@ -0,0 +1,256 @@
+//! In-memory fakes shared by the use-case tests. A test builds the use case it covers
+//! from a [`Fakes`] and inspects the same fakes afterwards through its [`Harness`].
+
+// Each test crate uses only the builders for the use cases it covers.
+#![allow(dead_code)]
+
+use std::{collections::HashMap, ops::Deref, sync::Arc, time::Duration as StdDuration};
+
+use axum_api::{
+    adapters::memory::{
+        attribute::InMemoryAttributeRepository,
+        data_export::InMemoryDataExportRepository,
+        email_change::InMemoryEmailChangeRepository,
+        events::RecordingEventPublisher,
+        object_storage::InMemoryObjectStorage,
+        outbox::{InMemoryOutboxRepository, RecordingOutboxPublisher},
+        passkey::{InMemoryPasskeyCeremonyRepository, InMemoryPasskeyRepository},
+        second_factor::InMemorySecondFactorRepository,
+        security::{InMemoryTokenProvider, InsecurePasswordHasher},
+        token_cache::InMemoryTokenCache,
+        trusted_device::InMemoryTrustedDeviceRepository,
+        unit_of_work::InMemoryUnitOfWorkFactory,
+        user::InMemoryUserRepository,
+    },
+    application::use_cases::{
+        account::AccountUseCase, attribute::AttributeUseCase, auth::AuthUseCase,
+        data_export::DataExportUseCase, email_change::EmailChangeUseCase, outbox::OutboxUseCase,
+        passkey::PasskeyUseCase, trusted_device::TrustedDeviceUseCase, user::UserUseCase,
+        user_admin::UserAdminUseCase, user_import::UserImportUseCase,
+    },
+    domain::entities::{session::ClientInfo, user::User},
+    infra::{
+        config::{OutboxConfig, SessionConfig, SessionLimitStrategy, SessionPolicy},
+        json_schema::JsonSchemaValidator,
+        security::{
+            argon2::PasswordHasherTrait, device_cookie::HmacDeviceCookieSigner,
+            email_change_token::HmacEmailChangeTokenProvider,
+            refresh_token::HmacRefreshTokenProvider, webauthn::WebAuthnVerifier,
+        },
+    },
+};
+use chrono::Duration;
+
+pub const PASSWORD: &str = "correct horse battery staple";
+
+pub struct Fakes {
+    pub users: Arc<InMemoryUserRepository>,
+    pub outbox: Arc<InMemoryOutboxRepository>,
+    pub unit_of_work: Arc<InMemoryUnitOfWorkFactory>,
+    pub cache: Arc<InMemoryTokenCache>,
+    pub tokens: Arc<InMemoryTokenProvider>,
+    pub events: Arc<RecordingEventPublisher>,
+    pub publisher: Arc<RecordingOutboxPublisher>,
+    pub second_factor: Arc<InMemorySecondFactorRepository>,
+    pub trusted_devices: Arc<TrustedDeviceUseCase>,
+    pub passkeys: Arc<InMemoryPasskeyRepository>,
+    pub exports: Arc<InMemoryDataExportRepository>,
+    pub storage: Arc<InMemoryObjectStorage>,
+    pub email_change_tokens: HmacEmailChangeTokenProvider,
+}
+
+impl Fakes {
+    pub fn new() -> Self {
+        let users = Arc::new(InMemoryUserRepository::new());
+        let outbox = Arc::new(InMemoryOutboxRepository::new());
+        let unit_of_work = Arc::new(InMemoryUnitOfWorkFactory::new(
+            users.clone(),
+            outbox.clone(),
+        ));
+
+        Self {
+            users,
+            outbox,
+            unit_of_work,
+            cache: Arc::new(InMemoryTokenCache::new()),
+            tokens: Arc::new(InMemoryTokenProvider::new()),
+            events: Arc::new(RecordingEventPublisher::new()),
+            publisher: Arc::new(RecordingOutboxPublisher::new()),
+            second_factor: Arc::new(InMemorySecondFactorRepository::new()),
+            trusted_devices: Arc::new(TrustedDeviceUseCase::new(
+                Arc::new(InMemoryTrustedDeviceRepository::new()),
+                Arc::new(HmacDeviceCookieSigner::new("test-device-secret")),
+                Duration::days(30),
+            )),
+            passkeys: Arc::new(InMemoryPasskeyRepository::new()),
+            exports: Arc::new(InMemoryDataExportRepository::new()),
+            storage: Arc::new(InMemoryObjectStorage::new()),
+            email_change_tokens: HmacEmailChangeTokenProvider::new("test-email-change-secret"),
+        }
+    }
+
+    /// Builds the use case under test on top of these fakes.
+    pub fn harness<U>(self, build: impl FnOnce(&Fakes) -> U) -> Harness<U> {
+        let use_case = build(&self);
+
+        Harness {
+            fakes: self,
+            use_case,
+        }
+    }
+
+    /// Stores an active user whose password is [`PASSWORD`].
+    pub fn user(&self, label: &str) -> User {
+        let user = new_user(label);
+        self.users.insert(user.clone()).unwrap();
+        user
+    }
+
+    pub fn account_use_case(&self, erasure_grace_period: Duration) -> AccountUseCase {
+        AccountUseCase::new(
+            self.users.clone(),
+            self.unit_of_work.clone(),
+            self.cache.clone(),
+            self.trusted_devices.clone(),
+            Arc::new(InsecurePasswordHasher::new()),
+            self.events.clone(),
+            erasure_grace_period,
+        )
+        .with_data_exports(Arc::new(
+            self.data_export_use_case(StdDuration::from_secs(3600)),
+        ))
+    }
+
+    pub fn attribute_use_case(&self) -> AttributeUseCase {
+        AttributeUseCase::new(
+            Arc::new(InMemoryAttributeRepository::new()),
+            self.users.clone(),
+            Arc::new(JsonSchemaValidator::new()),
+        )
+    }
+
+    pub fn auth_use_case(&self, default_policy: SessionPolicy) -> AuthUseCase {
+        AuthUseCase::new(
+            self.users.clone(),
+            self.unit_of_work.clone(),
+            self.cache.clone(),
+            Arc::new(InsecurePasswordHasher::new()),
+            self.tokens.clone(),
+            Arc::new(HmacRefreshTokenProvider::new("test-refresh-secret")),
+            self.events.clone(),
+            SessionConfig {
+                default_policy,
+                role_overrides: HashMap::new(),
+            },
+        )
+        .with_second_factor(self.second_factor.clone())
+    }
+
+    pub fn data_export_use_case(&self, retention: StdDuration) -> DataExportUseCase {
+        DataExportUseCase::new(
+            self.exports.clone(),
+            self.storage.clone(),
+            self.events.clone(),
+            StdDuration::from_secs(3600),
+            retention,
+            Duration::minutes(15),
+        )
+    }
+
+    pub fn email_change_use_case(&self) -> EmailChangeUseCase {
+        EmailChangeUseCase::new(
+            self.users.clone(),
+            Arc::new(InMemoryEmailChangeRepository::new()),
+            self.cache.clone(),
+            self.trusted_devices.clone(),
+            Arc::new(InsecurePasswordHasher::new()),
+            Arc::new(HmacRefreshTokenProvider::new("test-refresh-secret")),
+            Arc::new(self.email_change_tokens.clone()),
+            self.events.clone(),
+        )
+    }
+
+    pub fn outbox_use_case(&self, config: OutboxConfig) -> OutboxUseCase {
+        OutboxUseCase::new(self.outbox.clone(), self.publisher.clone(), config)
+    }
+
+    pub fn passkey_use_case(&self, rp_id: &str, origin: &str) -> PasskeyUseCase {
+        PasskeyUseCase::new(
+            self.users.clone(),
+            self.passkeys.clone(),
+            Arc::new(InMemoryPasskeyCeremonyRepository::new()),
+            Arc::new(WebAuthnVerifier::new(rp_id, origin)),
+            Arc::new(self.auth_use_case(default_policy())),
+            self.trusted_devices.clone(),
+        )
+    }
+
+    pub fn user_use_case(&self) -> UserUseCase {
+        UserUseCase::new(self.users.clone(), self.events.clone())
+    }
+
+    pub fn user_admin_use_case(&self) -> UserAdminUseCase {
+        UserAdminUseCase::new(
+            self.users.clone(),
+            self.unit_of_work.clone(),
+            self.cache.clone(),
+            self.trusted_devices.clone(),
+            Arc::new(InsecurePasswordHasher::new()),
+        )
+    }
+
+    pub fn user_import_use_case(&self) -> UserImportUseCase {
+        UserImportUseCase::new(
+            self.users.clone(),
+            self.unit_of_work.clone(),
+            Arc::new(InsecurePasswordHasher::new()),
+        )
+    }
+}
+
+/// The use case under test along with the fakes it was built from, which it derefs to.
+pub struct Harness<U> {
+    pub fakes: Fakes,
+    pub use_case: U,
+}
+
+impl<U> Deref for Harness<U> {
+    type Target = Fakes;
+
+    fn deref(&self) -> &Fakes {
+        &self.fakes
+    }
+}
+
+/// An active user whose password is [`PASSWORD`], not yet stored anywhere.
+pub fn new_user(label: &str) -> User {
+    let password = InsecurePasswordHasher::new()
+        .hash_password(PASSWORD)
+        .unwrap();
+
+    User::new(
+        format!("{label}@example.com"),
+        password,
+        format!("User {label}"),
+    )
+}
+
+pub fn policy(max_concurrent_sessions: usize, on_limit: SessionLimitStrategy) -> SessionPolicy {
+    SessionPolicy {
+        idle_timeout_minutes: 60,
+        max_concurrent_sessions,
+        on_limit,
+    }
+}
+
+/// Five sessions per user, ending the oldest to make room for a new one.
+pub fn default_policy() -> SessionPolicy {
+    policy(5, SessionLimitStrategy::EvictOldest)
+}
+
+pub fn client(device: &str) -> ClientInfo {
+    ClientInfo {
+        client_id: "web".to_string(),
+        device_fingerprint: device.to_string(),
+    }
+}
@@ -0,0 +1,142 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+use std::time::Duration;
+
+use axum_api::{
+    adapters::memory::events::RecordedEvent,
+    application::use_cases::data_export::DataExportUseCase,
+    domain::{
+        entities::data_export::{DataExport, DataExportFormat, DataExportStatus},
+        repositories::{data_export::DataExportRepository, object_storage::ObjectStorage},
+    },
+};
+use chrono::Utc;
+use common::{Fakes, new_user};
+use uuid::Uuid;
+
+const RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+type Harness = common::Harness<DataExportUseCase>;
+
+fn harness() -> Harness {
+    Fakes::new().harness(|fakes| fakes.data_export_use_case(RETENTION))
+}
+
+/// Stores a completed export with its object, as a finished build would.
+async fn completed(harness: &Harness, user_id: Uuid, ttl_secs: u64) -> DataExport {
+    let mut export = DataExport::new(user_id, DataExportFormat::Json);
+    let key = export.object_key();
+
+    harness
+        .storage
+        .put(&key, b"{}".to_vec(), "application/json")
+        .await
+        .unwrap();
+    export.complete(key);
+    harness.exports.save(&export, ttl_secs).await.unwrap();
+
+    export
+}
+
+#[tokio::test]
+async fn builds_requested_exports_in_the_background() {
+    let harness = harness();
+    let user = new_user("ada");
+
+    let export = harness
+        .use_case
+        .request_export(&user, DataExportFormat::Json)
+        .await
+        .unwrap();
+    assert_eq!(export.status, DataExportStatus::Pending);
+
+    let (export, download_url) = loop {
+        let (export, download_url) = harness
+            .use_case
+            .get_export(*user.id(), export.id)
+            .await
+            .unwrap();
+
+        if export.status != DataExportStatus::Pending {
+            break (export, download_url);
+        }
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    };
+
+    assert_eq!(export.status, DataExportStatus::Completed);
+    assert!(download_url.is_some());
+    assert_eq!(harness.storage.keys(), [export.object_key()]);
+    assert!(matches!(
+        harness.events.events().as_slice(),
+        [RecordedEvent::DataExportReady(_)]
+    ));
+}
+
+#[tokio::test]
+async fn fails_exports_pending_past_the_build_timeout() {
+    let harness = harness();
+    let user_id = Uuid::new_v4();
+
+    let mut stalled = DataExport::new(user_id, DataExportFormat::Zip);
+    stalled.requested_at = Utc::now() - chrono::Duration::hours(1);
+    harness.exports.save(&stalled, 3600).await.unwrap();
+
+    let recent = DataExport::new(user_id, DataExportFormat::Json);
+    harness.exports.save(&recent, 3600).await.unwrap();
+
+    assert_eq!(harness.use_case.fail_stalled_exports().await.unwrap(), 1);
+    assert_eq!(harness.use_case.fail_stalled_exports().await.unwrap(), 0);
+
+    let (stalled, _) = harness
+        .use_case
+        .get_export(user_id, stalled.id)
+        .await
+        .unwrap();
+    assert_eq!(stalled.status, DataExportStatus::Failed);
+
+    let (recent, _) = harness
+        .use_case
+        .get_export(user_id, recent.id)
+        .await
+        .unwrap();
+    assert_eq!(recent.status, DataExportStatus::Pending);
+}
+
+#[tokio::test]
+async fn deletes_the_objects_of_expired_exports() {
+    let harness = harness();
+    let user_id = Uuid::new_v4();
+
+    completed(&harness, user_id, 0).await;
+    let live = completed(&harness, user_id, 3600).await;
+
+    assert_eq!(harness.use_case.purge_expired_objects().await.unwrap(), 1);
+    assert_eq!(harness.use_case.purge_expired_objects().await.unwrap(), 0);
+    assert_eq!(harness.storage.keys(), [live.object_key()]);
+}
+
+#[tokio::test]
+async fn deletes_every_export_of_an_erased_user() {
+    let harness = harness();
+    let erased = Uuid::new_v4();
+    let other = Uuid::new_v4();
+
+    completed(&harness, erased, 3600).await;
+    completed(&harness, erased, 3600).await;
+    let kept = completed(&harness, other, 3600).await;
+
+    harness.use_case.delete_user_exports(erased).await;
+
+    assert_eq!(harness.storage.keys(), [kept.object_key()]);
+    assert!(
+        harness
+            .exports
+            .list_objects(erased)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
@@ -0,0 +1,133 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+use axum_api::{
+    adapters::memory::events::RecordedEvent,
+    application::{app_error::AppError, use_cases::email_change::EmailChangeUseCase},
+    domain::{
+        entities::{session::RefreshSession, user::User},
+        events::user::EmailChangeRequested,
+        repositories::{
+            token_cache::{SessionLimit, TokenCacheRepository},
+            user::UserRepository,
+        },
+    },
+    infra::security::email_change_token::EmailChangeTokenProvider,
+};
+use common::{Fakes, PASSWORD};
+use uuid::Uuid;
+
+type Harness = common::Harness<EmailChangeUseCase>;
+
+fn harness() -> Harness {
+    Fakes::new().harness(Fakes::email_change_use_case)
+}
+
+/// Requests a change and returns its event along with the token the mailer would send.
+async fn request(
+    harness: &Harness,
+    user: &User,
+    new_email: &str,
+) -> (EmailChangeRequested, String) {
+    harness
+        .use_case
+        .request_change(*user.id(), new_email.to_string(), PASSWORD)
+        .await
+        .unwrap();
+
+    match harness.events.take().as_slice() {
+        [RecordedEvent::EmailChangeRequested(event)] => {
+            let token = harness.email_change_tokens.token(event.change_id);
+            (event.clone(), token)
+        }
+        events => panic!("unexpected events: {events:?}"),
+    }
+}
+
+#[tokio::test]
+async fn confirms_a_normalized_address_and_ends_every_session() {
+    let harness = harness();
+    let user = harness.user("ada");
+    harness
+        .cache
+        .store_refresh_token(
+            "ada-token",
+            &RefreshSession::new(*user.id(), "web".to_string(), "laptop".to_string()),
+            3600,
+            SessionLimit::Unlimited,
+        )
+        .await
+        .unwrap();
+
+    let (event, token) = request(&harness, &user, "  Countess@Example.com ").await;
+    assert_eq!(event.new_email, "countess@example.com");
+
+    let changed = harness.use_case.confirm_change(&token).await.unwrap();
+
+    assert_eq!(changed.email(), "countess@example.com");
+    assert!(
+        harness
+            .cache
+            .list_refresh_tokens(*user.id())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(matches!(
+        harness.events.events().as_slice(),
+        [RecordedEvent::EmailChanged(_)]
+    ));
+}
+
+#[tokio::test]
+async fn tokens_are_single_use_and_bound_to_their_change() {
+    let harness = harness();
+    let user = harness.user("ada");
+
+    let (_, token) = request(&harness, &user, "countess@example.com").await;
+    let forged = harness.email_change_tokens.token(Uuid::new_v4());
+    assert_ne!(forged, token);
+
+    let result = harness.use_case.confirm_change(&forged).await;
+    assert!(matches!(result, Err(AppError::InvalidToken)));
+
+    harness.use_case.confirm_change(&token).await.unwrap();
+
+    let result = harness.use_case.confirm_change(&token).await;
+    assert!(matches!(result, Err(AppError::InvalidToken)));
+}
+
+#[tokio::test]
+async fn rejects_an_address_claimed_after_the_request() {
+    let harness = harness();
+    let ada = harness.user("ada");
+
+    let (_, token) = request(&harness, &ada, "countess@example.com").await;
+    harness.user("countess");
+
+    let result = harness.use_case.confirm_change(&token).await;
+
+    assert!(matches!(result, Err(AppError::EmailAlreadyExists(_))));
+}
+
+#[tokio::test]
+async fn rejects_changes_superseded_by_a_newer_one() {
+    let harness = harness();
+    let user = harness.user("ada");
+
+    let (_, first) = request(&harness, &user, "countess@example.com").await;
+    let (_, second) = request(&harness, &user, "lovelace@example.com").await;
+    harness.use_case.confirm_change(&second).await.unwrap();
+
+    let result = harness.use_case.confirm_change(&first).await;
+
+    assert!(matches!(result, Err(AppError::InvalidToken)));
+    let stored = harness
+        .users
+        .find_by_id(&user.id().to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.email(), "lovelace@example.com");
+}
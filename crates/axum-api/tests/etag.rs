@@ -0,0 +1,66 @@
+use axum::http::{HeaderMap, HeaderValue, header};
+use axum_api::{
+    adapters::http::etag::{etag, if_match},
+    application::app_error::AppError,
+};
+use chrono::{DateTime, Utc};
+
+fn if_match_header(value: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::IF_MATCH, HeaderValue::from_str(value).unwrap());
+    headers
+}
+
+fn parse(value: &str) -> Result<Option<DateTime<Utc>>, AppError> {
+    if_match(&if_match_header(value))
+}
+
+#[test]
+fn round_trips_nanosecond_timestamps() {
+    let updated_at = DateTime::from_timestamp(1_700_000_000, 123_456_789).unwrap();
+
+    let tag = etag(updated_at);
+    assert_eq!(tag, "\"1700000000.123456789\"");
+    assert_eq!(parse(&tag).unwrap(), Some(updated_at));
+
+    let whole_second = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    assert_eq!(parse(&etag(whole_second)).unwrap(), Some(whole_second));
+}
+
+#[test]
+fn requires_exactly_nine_fraction_digits() {
+    for tag in [
+        "\"1700000000.123456\"",
+        "\"1700000000.1234567890\"",
+        "\"1700000000.+23456789\"",
+        "\"1700000000.\"",
+        "\"1700000000123456\"",
+        "\"not-a-tag\"",
+    ] {
+        assert!(
+            matches!(parse(tag), Err(AppError::PreconditionFailed)),
+            "{tag}"
+        );
+    }
+}
+
+#[test]
+fn rejects_weak_and_unquoted_tags() {
+    let tag = etag(DateTime::from_timestamp(1_700_000_000, 123_456_789).unwrap());
+
+    for value in [format!("W/{tag}"), tag.trim_matches('"').to_string()] {
+        assert!(
+            matches!(parse(&value), Err(AppError::PreconditionFailed)),
+            "{value}"
+        );
+    }
+}
+
+#[test]
+fn requires_the_header_unless_the_client_opts_out_with_a_wildcard() {
+    assert!(matches!(
+        if_match(&HeaderMap::new()),
+        Err(AppError::PreconditionRequired)
+    ));
+    assert_eq!(parse("*").unwrap(), None);
+}
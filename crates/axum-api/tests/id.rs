@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use axum_api::domain::entities::id::new_id;
+use uuid::Uuid;
+
+/// The order in which SQL Server compares UNIQUEIDENTIFIER bytes: the last six first,
+/// then the remaining groups from right to left.
+fn sql_server_key(id: &Uuid) -> [u8; 16] {
+    let bytes = id.as_bytes();
+    let mut key = [0; 16];
+
+    for (slot, index) in [10, 11, 12, 13, 14, 15, 8, 9, 6, 7, 4, 5, 0, 1, 2, 3]
+        .into_iter()
+        .enumerate()
+    {
+        key[slot] = bytes[index];
+    }
+
+    key
+}
+
+#[tokio::test]
+async fn ids_sort_in_creation_order_as_sql_server_compares_them() {
+    let mut ids = Vec::new();
+    for _ in 0..5 {
+        ids.push(new_id());
+        tokio::time::sleep(Duration::from_millis(2)).await;
+    }
+
+    let mut sorted = ids.clone();
+    sorted.sort_by_key(sql_server_key);
+
+    assert_eq!(sorted, ids);
+}
+
+#[test]
+fn ids_are_version_8_and_unique_within_a_millisecond() {
+    let first = new_id();
+    let second = new_id();
+
+    assert_eq!(first.get_version_num(), 8);
+    assert_ne!(first, second);
+}
@@ -0,0 +1,234 @@
+use std::sync::{Arc, Mutex};
+
+use axum_api::{
+    domain::repositories::error::RepositoryResult,
+    infra::migrations::{
+        AppliedMigration, Migration, MigrationDirection, MigrationError, MigrationExecutor,
+        MigrationState, Migrator, split_batches,
+    },
+};
+use chrono::Utc;
+
+static MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "0001_create_users",
+        up: "CREATE TABLE users (id INT);",
+        down: "DROP TABLE users;",
+    },
+    Migration {
+        version: 2,
+        name: "0002_create_passkeys",
+        up: "CREATE TABLE passkeys (id INT);",
+        down: "DROP TABLE passkeys;",
+    },
+    Migration {
+        version: 3,
+        name: "0003_create_outbox",
+        up: "CREATE TABLE outbox (id INT);",
+        down: "DROP TABLE outbox;",
+    },
+];
+
+/// Records applied migrations the way a `schema_migrations` table would.
+#[derive(Default)]
+struct RecordingExecutor {
+    applied: Mutex<Vec<AppliedMigration>>,
+}
+
+impl RecordingExecutor {
+    fn with_applied(records: &[(i64, &str)]) -> Self {
+        let executor = Self::default();
+        *executor.applied.lock().unwrap() = records
+            .iter()
+            .map(|(version, checksum)| AppliedMigration {
+                version: *version,
+                name: format!("migration {version}"),
+                checksum: checksum.to_string(),
+                applied_at: Utc::now(),
+            })
+            .collect();
+        executor
+    }
+
+    fn versions(&self) -> Vec<i64> {
+        let mut versions: Vec<i64> = self
+            .applied
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|record| record.version)
+            .collect();
+        versions.sort();
+        versions
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationExecutor for RecordingExecutor {
+    async fn applied(&self) -> RepositoryResult<Vec<AppliedMigration>> {
+        Ok(self.applied.lock().unwrap().clone())
+    }
+
+    async fn apply(
+        &self,
+        migration: &Migration,
+        direction: MigrationDirection,
+    ) -> RepositoryResult<bool> {
+        let mut applied = self.applied.lock().unwrap();
+
+        match direction {
+            MigrationDirection::Up => applied.push(AppliedMigration {
+                version: migration.version,
+                name: migration.name.to_string(),
+                checksum: migration.checksum(),
+                applied_at: Utc::now(),
+            }),
+            MigrationDirection::Down => {
+                applied.retain(|record| record.version != migration.version)
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+fn migrator(executor: &Arc<RecordingExecutor>) -> Migrator {
+    Migrator::new(executor.clone(), MIGRATIONS)
+}
+
+fn versions(migrations: &[Migration]) -> Vec<i64> {
+    migrations
+        .iter()
+        .map(|migration| migration.version)
+        .collect()
+}
+
+#[test]
+fn splits_batches_on_go_lines_in_any_case() {
+    let batches = split_batches("SELECT 1;\ngo\nSELECT 2;\n  Go  \t\nSELECT 3;\nGO\n\nGO\n");
+
+    assert_eq!(batches, ["SELECT 1;\n", "SELECT 2;\n", "SELECT 3;\n"]);
+}
+
+#[test]
+fn keeps_go_lines_inside_strings_and_block_comments() {
+    let script = "\
+INSERT INTO notes VALUES ('it''s
+GO
+on');
+/* outer /* nested */
+GO
+*/
+-- a line comment doesn't open a string
+GO
+SELECT 2;
+";
+
+    let batches = split_batches(script);
+
+    assert_eq!(batches.len(), 2);
+    assert!(batches[0].contains("'it''s\nGO\non'"));
+    assert!(batches[0].contains("nested */\nGO\n*/"));
+    assert_eq!(batches[1], "SELECT 2;\n");
+}
+
+#[test]
+fn does_not_split_on_lines_merely_containing_go() {
+    let batches = split_batches("SELECT 'GO';\nGOTO done;\nGO;\n");
+
+    assert_eq!(batches, ["SELECT 'GO';\nGOTO done;\nGO;\n"]);
+}
+
+#[tokio::test]
+async fn applies_pending_migrations_in_order() {
+    let executor = Arc::new(RecordingExecutor::default());
+
+    let ran = migrator(&executor).up(None).await.unwrap();
+    assert_eq!(versions(&ran), [1, 2, 3]);
+
+    assert!(migrator(&executor).up(None).await.unwrap().is_empty());
+    assert_eq!(executor.versions(), [1, 2, 3]);
+}
+
+#[tokio::test]
+async fn stops_at_the_target_version() {
+    let executor = Arc::new(RecordingExecutor::default());
+
+    let ran = migrator(&executor).up(Some(2)).await.unwrap();
+    assert_eq!(versions(&ran), [1, 2]);
+
+    let result = migrator(&executor).up(Some(9)).await;
+    assert!(matches!(result, Err(MigrationError::TargetNotFound(9))));
+    assert_eq!(executor.versions(), [1, 2]);
+}
+
+#[tokio::test]
+async fn reverts_the_most_recent_migrations() {
+    let executor = Arc::new(RecordingExecutor::default());
+    migrator(&executor).up(None).await.unwrap();
+
+    let ran = migrator(&executor).down(2).await.unwrap();
+    assert_eq!(versions(&ran), [3, 2]);
+    assert_eq!(executor.versions(), [1]);
+
+    let ran = migrator(&executor).down(5).await.unwrap();
+    assert_eq!(versions(&ran), [1]);
+    assert!(executor.versions().is_empty());
+}
+
+#[tokio::test]
+async fn refuses_to_run_when_an_applied_migration_changed() {
+    let executor = Arc::new(RecordingExecutor::with_applied(&[(1, "edited")]));
+
+    let up = migrator(&executor).up(None).await;
+    let down = migrator(&executor).down(1).await;
+
+    for result in [up, down] {
+        assert!(matches!(
+            result,
+            Err(MigrationError::ChecksumMismatch { version: 1, .. })
+        ));
+    }
+    assert_eq!(executor.versions(), [1]);
+}
+
+#[tokio::test]
+async fn refuses_to_run_when_the_database_is_ahead_of_the_build() {
+    let executor = Arc::new(RecordingExecutor::with_applied(&[
+        (1, &MIGRATIONS[0].checksum()),
+        (4, "unknown"),
+    ]));
+
+    let result = migrator(&executor).up(None).await;
+
+    assert!(matches!(result, Err(MigrationError::UnknownVersion(4))));
+    assert_eq!(executor.versions(), [1, 4]);
+}
+
+#[tokio::test]
+async fn reports_the_state_of_every_migration() {
+    let executor = Arc::new(RecordingExecutor::with_applied(&[
+        (1, &MIGRATIONS[0].checksum()),
+        (2, "edited"),
+        (4, "unknown"),
+    ]));
+
+    let states: Vec<(i64, MigrationState)> = migrator(&executor)
+        .status()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|status| (status.version, status.state))
+        .collect();
+
+    assert_eq!(
+        states,
+        [
+            (1, MigrationState::Applied),
+            (2, MigrationState::ChecksumMismatch),
+            (3, MigrationState::Pending),
+            (4, MigrationState::Unknown),
+        ]
+    );
+}
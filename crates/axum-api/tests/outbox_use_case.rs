@@ -0,0 +1,302 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+use std::time::Duration;
+
+use axum_api::{
+    application::use_cases::outbox::OutboxUseCase,
+    domain::{entities::outbox::OutboxMessage, repositories::outbox::OutboxRepository},
+    infra::config::OutboxConfig,
+};
+use chrono::{Duration as ChronoDuration, Utc};
+use common::Fakes;
+use uuid::Uuid;
+
+type Harness = common::Harness<OutboxUseCase>;
+
+fn config(batch_size: u32, max_attempts: u32) -> OutboxConfig {
+    OutboxConfig {
+        relay_interval_ms: 1000,
+        batch_size,
+        max_attempts,
+        retry_backoff_ms: 0,
+        max_backoff_ms: 0,
+        retention_hours: 24,
+        cleanup_interval_secs: 3600,
+        relay_lease_ms: 60_000,
+    }
+}
+
+fn harness_with(batch_size: u32, max_attempts: u32) -> Harness {
+    harness_with_config(config(batch_size, max_attempts))
+}
+
+fn harness_with_config(config: OutboxConfig) -> Harness {
+    Fakes::new().harness(|fakes| fakes.outbox_use_case(config))
+}
+
+fn harness() -> Harness {
+    harness_with(100, 3)
+}
+
+async fn enqueue(harness: &Harness, aggregate_id: Uuid, payload: &str) -> OutboxMessage {
+    let message = OutboxMessage::new(aggregate_id, "UserCreated", payload.to_string());
+    harness.outbox.enqueue(&message).await.unwrap();
+
+    message
+}
+
+fn payloads(messages: &[OutboxMessage]) -> Vec<&str> {
+    messages
+        .iter()
+        .map(|message| message.payload.as_str())
+        .collect()
+}
+
+#[tokio::test]
+async fn relays_pending_messages_in_order_and_marks_them_dispatched() {
+    let harness = harness();
+    let aggregate = Uuid::new_v4();
+    enqueue(&harness, aggregate, "first").await;
+    enqueue(&harness, Uuid::new_v4(), "other").await;
+    enqueue(&harness, aggregate, "second").await;
+
+    assert_eq!(harness.use_case.relay_pending().await.unwrap(), 3);
+    assert_eq!(
+        payloads(&harness.publisher.published()),
+        ["first", "other", "second"]
+    );
+    assert!(
+        harness
+            .outbox
+            .list_pending(Utc::now(), 3, 100)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    assert_eq!(harness.use_case.relay_pending().await.unwrap(), 0);
+    assert_eq!(
+        harness.publisher.published().len(),
+        3,
+        "nothing is sent twice"
+    );
+    assert_eq!(harness.use_case.metrics().dispatched_total(), 3);
+}
+
+#[tokio::test]
+async fn relays_at_most_one_batch_per_pass() {
+    let harness = harness_with(2, 3);
+    for payload in ["one", "two", "three"] {
+        enqueue(&harness, Uuid::new_v4(), payload).await;
+    }
+
+    assert_eq!(harness.use_case.relay_pending().await.unwrap(), 2);
+    assert_eq!(harness.use_case.relay_pending().await.unwrap(), 1);
+    assert_eq!(
+        payloads(&harness.publisher.published()),
+        ["one", "two", "three"]
+    );
+}
+
+#[tokio::test]
+async fn keeps_messages_pending_when_every_attempt_fails() {
+    let harness = harness();
+    let message = enqueue(&harness, Uuid::new_v4(), "first").await;
+    harness.publisher.set_failing(true);
+
+    assert_eq!(harness.use_case.relay_pending().await.unwrap(), 0);
+
+    let pending = harness
+        .outbox
+        .list_pending(Utc::now(), 3, 100)
+        .await
+        .unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, message.id);
+    assert_eq!(pending[0].attempts, 1, "one attempt is made per pass");
+    assert!(pending[0].last_error.is_some());
+    assert_eq!(harness.use_case.metrics().publish_failures_total(), 1);
+
+    harness.publisher.set_failing(false);
+
+    assert_eq!(harness.use_case.relay_pending().await.unwrap(), 1);
+    assert_eq!(payloads(&harness.publisher.published()), ["first"]);
+}
+
+#[tokio::test]
+async fn failing_aggregates_hold_back_only_their_own_later_messages() {
+    let harness = harness();
+    let stuck = Uuid::new_v4();
+    let healthy = Uuid::new_v4();
+    enqueue(&harness, stuck, "stuck-1").await;
+    enqueue(&harness, healthy, "healthy-1").await;
+    enqueue(&harness, stuck, "stuck-2").await;
+    enqueue(&harness, healthy, "healthy-2").await;
+    harness.publisher.set_failing_aggregate(stuck, true);
+
+    assert_eq!(harness.use_case.relay_pending().await.unwrap(), 2);
+    assert_eq!(
+        payloads(&harness.publisher.published()),
+        ["healthy-1", "healthy-2"]
+    );
+
+    let pending = harness
+        .outbox
+        .list_pending(Utc::now(), 3, 100)
+        .await
+        .unwrap();
+    assert_eq!(payloads(&pending), ["stuck-1", "stuck-2"]);
+    assert_eq!(pending[1].attempts, 0, "later messages are never attempted");
+
+    harness.publisher.set_failing_aggregate(stuck, false);
+
+    assert_eq!(harness.use_case.relay_pending().await.unwrap(), 2);
+    assert_eq!(
+        payloads(&harness.publisher.published()),
+        ["healthy-1", "healthy-2", "stuck-1", "stuck-2"]
+    );
+}
+
+#[tokio::test]
+async fn reports_the_backlog_after_each_pass() {
+    let harness = harness();
+    let stuck = Uuid::new_v4();
+    let first = enqueue(&harness, stuck, "stuck-1").await;
+    enqueue(&harness, stuck, "stuck-2").await;
+    enqueue(&harness, Uuid::new_v4(), "healthy").await;
+    harness.publisher.set_failing_aggregate(stuck, true);
+
+    harness.use_case.relay_pending().await.unwrap();
+
+    let backlog = harness.use_case.metrics().backlog();
+    assert_eq!(backlog.pending, 2);
+    assert_eq!(backlog.failing, 1);
+    assert_eq!(backlog.oldest_pending_at, Some(first.created_at));
+
+    harness.publisher.set_failing_aggregate(stuck, false);
+    harness.use_case.relay_pending().await.unwrap();
+
+    let backlog = harness.use_case.backlog().await.unwrap();
+    assert_eq!(backlog.pending, 0);
+    assert_eq!(backlog.oldest_pending_at, None);
+    assert_eq!(harness.use_case.metrics().backlog().pending, 0);
+    assert_eq!(harness.use_case.metrics().dispatched_total(), 3);
+    assert_eq!(harness.use_case.metrics().publish_failures_total(), 1);
+}
+
+#[tokio::test]
+async fn only_the_relay_holding_the_lease_publishes() {
+    let harness = harness();
+    let standby = OutboxUseCase::new(
+        harness.outbox.clone(),
+        harness.publisher.clone(),
+        OutboxConfig {
+            relay_lease_ms: 50,
+            ..config(100, 3)
+        },
+    );
+    enqueue(&harness, Uuid::new_v4(), "first").await;
+
+    assert_eq!(standby.relay_pending().await.unwrap(), 1);
+    assert_eq!(
+        harness.use_case.relay_pending().await.unwrap(),
+        0,
+        "the standby still holds the lease"
+    );
+
+    enqueue(&harness, Uuid::new_v4(), "second").await;
+    tokio::time::sleep(Duration::from_millis(60)).await;
+
+    assert_eq!(harness.use_case.relay_pending().await.unwrap(), 1);
+    assert_eq!(
+        standby.relay_pending().await.unwrap(),
+        0,
+        "a lapsed lease is taken over"
+    );
+    assert_eq!(
+        payloads(&harness.publisher.published()),
+        ["first", "second"]
+    );
+}
+
+#[tokio::test]
+async fn retries_wait_for_their_backoff_without_holding_up_other_aggregates() {
+    let harness = harness_with_config(OutboxConfig {
+        retry_backoff_ms: 60_000,
+        max_backoff_ms: 60_000,
+        ..config(100, 3)
+    });
+    let stuck = Uuid::new_v4();
+    enqueue(&harness, stuck, "stuck-1").await;
+    harness.publisher.set_failing_aggregate(stuck, true);
+
+    assert_eq!(harness.use_case.relay_pending().await.unwrap(), 0);
+    harness.publisher.set_failing_aggregate(stuck, false);
+    enqueue(&harness, stuck, "stuck-2").await;
+    enqueue(&harness, Uuid::new_v4(), "healthy").await;
+
+    assert_eq!(harness.use_case.relay_pending().await.unwrap(), 1);
+    assert_eq!(payloads(&harness.publisher.published()), ["healthy"]);
+
+    let due = Utc::now() + ChronoDuration::minutes(2);
+    let pending = harness.outbox.list_pending(due, 3, 100).await.unwrap();
+    assert_eq!(
+        payloads(&pending),
+        ["stuck-1", "stuck-2"],
+        "both go out in order once the retry is due"
+    );
+}
+
+#[tokio::test]
+async fn dead_letters_messages_that_keep_failing() {
+    let harness = harness_with(100, 2);
+    let poisoned = Uuid::new_v4();
+    let poison = enqueue(&harness, poisoned, "poison").await;
+    enqueue(&harness, poisoned, "after").await;
+    harness.publisher.set_failing_aggregate(poisoned, true);
+
+    assert_eq!(harness.use_case.relay_pending().await.unwrap(), 0);
+    assert_eq!(harness.use_case.relay_pending().await.unwrap(), 0);
+
+    let backlog = harness.use_case.metrics().backlog();
+    assert_eq!(backlog.dead_lettered, 1);
+    assert_eq!(backlog.pending, 1);
+
+    harness.publisher.set_failing_aggregate(poisoned, false);
+
+    assert_eq!(harness.use_case.relay_pending().await.unwrap(), 1);
+    assert_eq!(
+        payloads(&harness.publisher.published()),
+        ["after"],
+        "the dead letter no longer blocks its aggregate"
+    );
+
+    let stored = harness.outbox.messages();
+    let dead_letter = stored
+        .iter()
+        .find(|message| message.id == poison.id)
+        .unwrap();
+    assert_eq!(dead_letter.attempts, 2);
+}
+
+#[tokio::test]
+async fn purges_dispatched_messages_past_retention() {
+    let harness = harness_with_config(OutboxConfig {
+        retention_hours: 0,
+        ..config(1, 3)
+    });
+    let stuck = Uuid::new_v4();
+    for payload in ["one", "two"] {
+        enqueue(&harness, Uuid::new_v4(), payload).await;
+    }
+    enqueue(&harness, stuck, "pending").await;
+    harness.publisher.set_failing_aggregate(stuck, true);
+
+    while harness.use_case.relay_pending().await.unwrap() > 0 {}
+
+    assert_eq!(harness.use_case.purge_dispatched().await.unwrap(), 2);
+    assert_eq!(payloads(&harness.outbox.messages()), ["pending"]);
+    assert_eq!(harness.use_case.purge_dispatched().await.unwrap(), 0);
+}
@@ -0,0 +1,358 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+use axum_api::{
+    application::{
+        app_error::AppError,
+        use_cases::{
+            auth::LoginOutcome,
+            passkey::{PasskeyChallenge, PasskeyUseCase},
+        },
+    },
+    domain::{
+        entities::{passkey::Passkey, session::ClientInfo, user::User},
+        repositories::passkey::PasskeyRepository,
+    },
+    infra::security::webauthn::{AuthenticatorAssertion, AuthenticatorAttestation, COSE_ALG_ES256},
+};
+use ciborium::Value;
+use p256::ecdsa::{Signature, SigningKey, signature::Signer};
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+
+use common::{Fakes, PASSWORD, default_policy};
+
+const RP_ID: &str = "example.com";
+const ORIGIN: &str = "https://example.com";
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+/// A software authenticator holding a single ES256 credential.
+struct Authenticator {
+    credential_id: Vec<u8>,
+    signing_key: SigningKey,
+}
+
+impl Authenticator {
+    fn new(credential_id: &[u8]) -> Self {
+        Self {
+            credential_id: credential_id.to_vec(),
+            signing_key: SigningKey::random(&mut OsRng),
+        }
+    }
+
+    fn client_data(ceremony_type: &str, challenge: &str) -> Vec<u8> {
+        serde_json::to_vec(&serde_json::json!({
+            "type": ceremony_type,
+            "challenge": challenge,
+            "origin": ORIGIN,
+        }))
+        .unwrap()
+    }
+
+    fn authenticator_data(flags: u8, sign_count: u32) -> Vec<u8> {
+        let mut data = Sha256::digest(RP_ID.as_bytes()).to_vec();
+        data.push(flags);
+        data.extend_from_slice(&sign_count.to_be_bytes());
+        data
+    }
+
+    fn cose_key(&self) -> Vec<u8> {
+        let point = self.signing_key.verifying_key().to_encoded_point(false);
+        let key = Value::Map(vec![
+            (Value::from(1), Value::from(2)),
+            (Value::from(3), Value::from(COSE_ALG_ES256)),
+            (Value::from(-1), Value::from(1)),
+            (Value::from(-2), Value::Bytes(point.x().unwrap().to_vec())),
+            (Value::from(-3), Value::Bytes(point.y().unwrap().to_vec())),
+        ]);
+
+        let mut encoded = Vec::new();
+        ciborium::into_writer(&key, &mut encoded).unwrap();
+        encoded
+    }
+
+    fn sign(&self, authenticator_data: &[u8], client_data_json: &[u8]) -> Vec<u8> {
+        let mut payload = authenticator_data.to_vec();
+        payload.extend_from_slice(&Sha256::digest(client_data_json));
+
+        let signature: Signature = self.signing_key.sign(&payload);
+        signature.to_der().as_bytes().to_vec()
+    }
+
+    fn attest(&self, challenge: &str, sign_count: u32) -> AuthenticatorAttestation {
+        let mut auth_data = Self::authenticator_data(
+            FLAG_USER_PRESENT | FLAG_ATTESTED_CREDENTIAL_DATA,
+            sign_count,
+        );
+        auth_data.extend_from_slice(&[0; 16]);
+        auth_data.extend_from_slice(&(self.credential_id.len() as u16).to_be_bytes());
+        auth_data.extend_from_slice(&self.credential_id);
+        auth_data.extend_from_slice(&self.cose_key());
+
+        let client_data_json = Self::client_data("webauthn.create", challenge);
+        let statement = vec![
+            (Value::from("alg"), Value::from(COSE_ALG_ES256)),
+            (
+                Value::from("sig"),
+                Value::Bytes(self.sign(&auth_data, &client_data_json)),
+            ),
+        ];
+        let attestation_object = Value::Map(vec![
+            (Value::from("fmt"), Value::from("packed")),
+            (Value::from("attStmt"), Value::Map(statement)),
+            (Value::from("authData"), Value::Bytes(auth_data)),
+        ]);
+
+        let mut encoded = Vec::new();
+        ciborium::into_writer(&attestation_object, &mut encoded).unwrap();
+
+        AuthenticatorAttestation {
+            client_data_json,
+            attestation_object: encoded,
+        }
+    }
+
+    fn assert(&self, challenge: &str, sign_count: u32) -> AuthenticatorAssertion {
+        let authenticator_data =
+            Self::authenticator_data(FLAG_USER_PRESENT | FLAG_USER_VERIFIED, sign_count);
+        let client_data_json = Self::client_data("webauthn.get", challenge);
+        let signature = self.sign(&authenticator_data, &client_data_json);
+
+        AuthenticatorAssertion {
+            client_data_json,
+            authenticator_data,
+            signature,
+        }
+    }
+}
+
+type Harness = common::Harness<PasskeyUseCase>;
+
+fn harness() -> Harness {
+    Fakes::new().harness(|fakes| fakes.passkey_use_case(RP_ID, ORIGIN))
+}
+
+fn client() -> ClientInfo {
+    common::client("laptop")
+}
+
+async fn register(harness: &Harness, user: &User, authenticator: &Authenticator) -> Passkey {
+    let PasskeyChallenge {
+        ceremony_id,
+        challenge,
+        ..
+    } = harness
+        .use_case
+        .start_registration(*user.id())
+        .await
+        .unwrap();
+
+    harness
+        .use_case
+        .finish_registration(
+            *user.id(),
+            &ceremony_id,
+            authenticator.attest(&challenge, 5),
+            vec!["internal".to_string()],
+            "Laptop".to_string(),
+        )
+        .await
+        .unwrap()
+}
+
+async fn sign_in(
+    harness: &Harness,
+    authenticator: &Authenticator,
+    sign_count: u32,
+) -> Result<(String, String), AppError> {
+    let challenge = harness.use_case.start_login().await.unwrap();
+
+    harness
+        .use_case
+        .finish_login(
+            &challenge.ceremony_id,
+            &authenticator.credential_id,
+            authenticator.assert(&challenge.challenge, sign_count),
+            client(),
+        )
+        .await
+}
+
+async fn stored_sign_count(harness: &Harness, authenticator: &Authenticator) -> u32 {
+    harness
+        .passkeys
+        .find_by_credential_id(&authenticator.credential_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .sign_count
+}
+
+#[tokio::test]
+async fn registers_a_passkey_and_signs_in_with_it() {
+    let harness = harness();
+    let user = harness.user("ada");
+    let authenticator = Authenticator::new(b"credential-1");
+
+    let passkey = register(&harness, &user, &authenticator).await;
+    assert_eq!(passkey.user_id, *user.id());
+    assert_eq!(passkey.sign_count, 5);
+
+    sign_in(&harness, &authenticator, 6).await.unwrap();
+
+    assert_eq!(stored_sign_count(&harness, &authenticator).await, 6);
+}
+
+#[tokio::test]
+async fn rejects_registering_a_credential_twice() {
+    let harness = harness();
+    let user = harness.user("ada");
+    let authenticator = Authenticator::new(b"credential-1");
+
+    register(&harness, &user, &authenticator).await;
+
+    let challenge = harness
+        .use_case
+        .start_registration(*user.id())
+        .await
+        .unwrap();
+    assert_eq!(challenge.credentials.len(), 1);
+
+    let result = harness
+        .use_case
+        .finish_registration(
+            *user.id(),
+            &challenge.ceremony_id,
+            authenticator.attest(&challenge.challenge, 0),
+            Vec::new(),
+            "Again".to_string(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::PasskeyAlreadyRegistered)));
+}
+
+#[tokio::test]
+async fn rejects_assertions_whose_sign_count_did_not_increase() {
+    let harness = harness();
+    let user = harness.user("ada");
+    let authenticator = Authenticator::new(b"credential-1");
+
+    register(&harness, &user, &authenticator).await;
+
+    for sign_count in [5, 4] {
+        let result = sign_in(&harness, &authenticator, sign_count).await;
+        assert!(matches!(
+            result,
+            Err(AppError::PasskeyVerificationFailed(_))
+        ));
+    }
+
+    assert_eq!(stored_sign_count(&harness, &authenticator).await, 5);
+}
+
+#[tokio::test]
+async fn rejects_assertions_from_unknown_credentials() {
+    let harness = harness();
+    let user = harness.user("ada");
+
+    register(&harness, &user, &Authenticator::new(b"credential-1")).await;
+
+    let result = sign_in(&harness, &Authenticator::new(b"credential-2"), 1).await;
+
+    assert!(matches!(result, Err(AppError::PasskeyNotFound)));
+}
+
+#[tokio::test]
+async fn rejects_assertions_signed_by_another_key() {
+    let harness = harness();
+    let user = harness.user("ada");
+    let authenticator = Authenticator::new(b"credential-1");
+
+    register(&harness, &user, &authenticator).await;
+
+    let impostor = Authenticator::new(b"credential-1");
+    let result = sign_in(&harness, &impostor, 6).await;
+
+    assert!(matches!(
+        result,
+        Err(AppError::PasskeyVerificationFailed(_))
+    ));
+}
+
+#[tokio::test]
+async fn ceremonies_can_only_be_completed_once() {
+    let harness = harness();
+    let user = harness.user("ada");
+    let authenticator = Authenticator::new(b"credential-1");
+
+    register(&harness, &user, &authenticator).await;
+
+    let challenge = harness.use_case.start_login().await.unwrap();
+    for (sign_count, succeeds) in [(6, true), (7, false)] {
+        let result = harness
+            .use_case
+            .finish_login(
+                &challenge.ceremony_id,
+                &authenticator.credential_id,
+                authenticator.assert(&challenge.challenge, sign_count),
+                client(),
+            )
+            .await;
+
+        assert_eq!(result.is_ok(), succeeds);
+    }
+}
+
+/// Signs in with the password and starts the passkey ceremony the MFA challenge asks for.
+async fn start_second_factor(harness: &Harness, user: &User) -> PasskeyChallenge {
+    let outcome = harness
+        .auth_use_case(default_policy())
+        .login(
+            user.email().to_string(),
+            PASSWORD.to_string(),
+            client(),
+            None,
+        )
+        .await
+        .unwrap();
+    let LoginOutcome::MfaRequired { mfa_token } = outcome else {
+        panic!("expected an MFA challenge");
+    };
+
+    harness
+        .use_case
+        .start_second_factor(&mfa_token, &client())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn second_factor_ceremonies_are_bound_to_the_device_that_started_them() {
+    let harness = harness();
+    let user = harness.user("ada");
+    let authenticator = Authenticator::new(b"credential-1");
+    register(&harness, &user, &authenticator).await;
+    harness.second_factor.enroll(*user.id());
+
+    for (device, sign_count, succeeds) in [("phone", 6, false), ("laptop", 7, true)] {
+        let challenge = start_second_factor(&harness, &user).await;
+
+        let result = harness
+            .use_case
+            .finish_second_factor(
+                &challenge.ceremony_id,
+                &authenticator.credential_id,
+                authenticator.assert(&challenge.challenge, sign_count),
+                common::client(device),
+                None,
+            )
+            .await;
+
+        assert_eq!(result.is_ok(), succeeds, "{device}");
+    }
+}
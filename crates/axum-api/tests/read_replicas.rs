@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use axum_api::{
+    adapters::persistence::replica::{ReplicaSet, consistent_reads, primary_reads, record_write},
+    domain::repositories::error::{RepositoryError, RepositoryResult},
+};
+
+const PRIMARY: &str = "primary";
+
+fn replicas(names: &[&'static str], retry_after: Duration) -> ReplicaSet<&'static str> {
+    ReplicaSet::new(names.to_vec(), retry_after)
+}
+
+/// Reads through `replicas`, answering with the name of the source that served it.
+async fn read(replicas: &ReplicaSet<&'static str>, failing: &[&str]) -> &'static str {
+    replicas
+        .read(&PRIMARY, |source| async move {
+            if failing.contains(&source) {
+                return Err(RepositoryError::SqlXFailure(sqlx::Error::PoolTimedOut));
+            }
+
+            RepositoryResult::Ok(source)
+        })
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn reads_outside_a_request_use_the_primary() {
+    let replicas = replicas(&["a"], Duration::from_secs(60));
+
+    assert_eq!(read(&replicas, &[]).await, PRIMARY);
+}
+
+#[tokio::test]
+async fn requests_read_from_replicas_in_turn_until_they_write() {
+    let replicas = replicas(&["a", "b"], Duration::from_secs(60));
+
+    consistent_reads(async {
+        assert_eq!(read(&replicas, &[]).await, "a");
+        assert_eq!(read(&replicas, &[]).await, "b");
+        assert_eq!(read(&replicas, &[]).await, "a");
+
+        record_write();
+
+        assert_eq!(read(&replicas, &[]).await, PRIMARY);
+        assert_eq!(read(&replicas, &[]).await, PRIMARY);
+    })
+    .await;
+
+    consistent_reads(async {
+        assert_eq!(
+            read(&replicas, &[]).await,
+            "b",
+            "a write only pins its own request"
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn primary_reads_skip_replicas_without_pinning_the_request() {
+    let replicas = replicas(&["a", "b"], Duration::from_secs(60));
+
+    consistent_reads(async {
+        assert_eq!(primary_reads(read(&replicas, &[])).await, PRIMARY);
+        assert_eq!(read(&replicas, &[]).await, "a");
+
+        primary_reads(async { record_write() }).await;
+
+        assert_eq!(
+            read(&replicas, &[]).await,
+            PRIMARY,
+            "a write inside still pins the request"
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn uses_the_primary_without_replicas() {
+    let replicas = ReplicaSet::default();
+
+    consistent_reads(async {
+        assert_eq!(read(&replicas, &[]).await, PRIMARY);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn skips_failed_replicas_until_they_may_have_recovered() {
+    let replicas = replicas(&["a", "b"], Duration::from_millis(50));
+
+    consistent_reads(async {
+        assert_eq!(
+            read(&replicas, &["a"]).await,
+            PRIMARY,
+            "a failed read falls back to the primary"
+        );
+        assert_eq!(read(&replicas, &["a"]).await, "b");
+        assert_eq!(read(&replicas, &["a"]).await, "b");
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        let mut served = [read(&replicas, &[]).await, read(&replicas, &[]).await];
+        served.sort();
+        assert_eq!(served, ["a", "b"]);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn falls_back_to_the_primary_while_every_replica_is_unhealthy() {
+    let replicas = replicas(&["a", "b"], Duration::from_secs(60));
+
+    consistent_reads(async {
+        assert_eq!(read(&replicas, &["a", "b"]).await, PRIMARY);
+        assert_eq!(read(&replicas, &["a", "b"]).await, PRIMARY);
+        assert_eq!(read(&replicas, &[]).await, PRIMARY);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn trusts_a_replica_that_finds_nothing() {
+    let replicas = replicas(&["a"], Duration::from_secs(60));
+
+    let found = consistent_reads(replicas.read(&PRIMARY, |source| async move {
+        RepositoryResult::Ok((source == PRIMARY).then_some(source))
+    }))
+    .await
+    .unwrap();
+
+    assert_eq!(found, None);
+}
+
+#[tokio::test]
+async fn query_errors_are_returned_without_marking_the_replica_unhealthy() {
+    let replicas = replicas(&["a"], Duration::from_secs(60));
+
+    consistent_reads(async {
+        let rejected = replicas
+            .read(&PRIMARY, |source| async move {
+                if source == PRIMARY {
+                    return RepositoryResult::Ok(source);
+                }
+
+                Err(RepositoryError::ConversionError(format!(
+                    "{source} returned a bad row"
+                )))
+            })
+            .await;
+        assert!(matches!(rejected, Err(RepositoryError::ConversionError(_))));
+
+        assert_eq!(read(&replicas, &[]).await, "a");
+    })
+    .await;
+}
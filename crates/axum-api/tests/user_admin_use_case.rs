@@ -0,0 +1,155 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+use axum_api::{
+    adapters::memory::security::InsecurePasswordHasher,
+    application::{app_error::AppError, use_cases::user_admin::UserAdminUseCase},
+    domain::{
+        entities::{session::RefreshSession, user::Role},
+        repositories::{
+            token_cache::{SessionLimit, TokenCacheRepository},
+            user::UserRepository,
+        },
+    },
+    infra::security::argon2::PasswordHasherTrait,
+};
+use common::Fakes;
+
+type Harness = common::Harness<UserAdminUseCase>;
+
+fn harness() -> Harness {
+    Fakes::new().harness(Fakes::user_admin_use_case)
+}
+
+#[tokio::test]
+async fn creates_admins_and_enqueues_user_created() {
+    let harness = harness();
+
+    let admin = harness
+        .use_case
+        .create_admin(
+            "root@example.com".to_string(),
+            "Root".to_string(),
+            "long enough",
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(admin.role(), Role::Admin);
+    assert_eq!(
+        harness
+            .use_case
+            .find_user("ROOT@example.com")
+            .await
+            .unwrap()
+            .id(),
+        admin.id()
+    );
+    assert_eq!(
+        harness
+            .use_case
+            .find_user(&admin.id().to_string())
+            .await
+            .unwrap()
+            .email(),
+        "root@example.com"
+    );
+
+    let messages = harness.outbox.messages();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].event_type, "UserCreated");
+}
+
+#[tokio::test]
+async fn rejects_invalid_and_duplicate_admins() {
+    let harness = harness();
+
+    let invalid = harness
+        .use_case
+        .create_admin("not-an-email".to_string(), String::new(), "short")
+        .await;
+    match invalid {
+        Err(AppError::ValidationError(errors)) => assert_eq!(errors.len(), 3),
+        other => panic!("expected a validation error, got {other:?}"),
+    }
+
+    harness
+        .use_case
+        .create_admin(
+            "root@example.com".to_string(),
+            "Root".to_string(),
+            "long enough",
+        )
+        .await
+        .unwrap();
+    let duplicate = harness
+        .use_case
+        .create_admin(
+            "Root@Example.com".to_string(),
+            "Root".to_string(),
+            "long enough",
+        )
+        .await;
+
+    assert!(matches!(duplicate, Err(AppError::EmailAlreadyExists(_))));
+    assert_eq!(harness.users.len(), 1);
+    assert_eq!(harness.outbox.messages().len(), 1);
+}
+
+#[tokio::test]
+async fn password_resets_revoke_every_session() {
+    let harness = harness();
+    let admin = harness
+        .use_case
+        .create_admin(
+            "root@example.com".to_string(),
+            "Root".to_string(),
+            "long enough",
+        )
+        .await
+        .unwrap();
+    harness
+        .cache
+        .store_refresh_token(
+            "token",
+            &RefreshSession::new(*admin.id(), "web".to_string(), "laptop".to_string()),
+            3600,
+            SessionLimit::Unlimited,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        harness.use_case.active_sessions(*admin.id()).await.unwrap(),
+        1
+    );
+
+    let short = harness.use_case.reset_password(*admin.id(), "short").await;
+    assert!(matches!(short, Err(AppError::ValidationError(_))));
+    assert_eq!(
+        harness.use_case.active_sessions(*admin.id()).await.unwrap(),
+        1
+    );
+
+    harness
+        .use_case
+        .reset_password(*admin.id(), "a new password")
+        .await
+        .unwrap();
+
+    let stored = harness
+        .users
+        .find_by_id(&admin.id().to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        InsecurePasswordHasher::new()
+            .verify_password("a new password", stored.password())
+            .unwrap()
+    );
+    assert_eq!(
+        harness.use_case.active_sessions(*admin.id()).await.unwrap(),
+        0
+    );
+}
@@ -0,0 +1,223 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+use std::io;
+
+use axum_api::{
+    application::{
+        app_error::AppError,
+        use_cases::user_import::{
+            ImportFormat, ImportOptions, ImportReport, ImportRowStatus, UserImportUseCase,
+        },
+    },
+    domain::{entities::user::User, repositories::user::UserRepository},
+};
+use common::Fakes;
+use futures::stream;
+use tokio_util::io::StreamReader;
+
+type Harness = common::Harness<UserImportUseCase>;
+
+fn harness() -> Harness {
+    Fakes::new().harness(Fakes::user_import_use_case)
+}
+
+async fn import(
+    harness: &Harness,
+    input: &'static str,
+    format: ImportFormat,
+    options: ImportOptions,
+) -> ImportReport {
+    harness
+        .use_case
+        .import(input.as_bytes(), format, options)
+        .await
+        .unwrap()
+}
+
+fn statuses(report: &ImportReport) -> Vec<(usize, ImportRowStatus)> {
+    report
+        .rows
+        .iter()
+        .map(|row| (row.line, row.status))
+        .collect()
+}
+
+#[tokio::test]
+async fn imports_csv_with_quoted_fields_spanning_lines() {
+    let harness = harness();
+
+    let report = import(
+        &harness,
+        "name,email,password\n\
+         \"Lovelace, Ada\",ada@example.com,analytical\n\
+         \n\
+         \"Grace\nHopper\", grace@example.com , compilers\n\
+         Alan,alan@example.com,enigmatic\n",
+        ImportFormat::Csv,
+        ImportOptions::default(),
+    )
+    .await;
+
+    assert_eq!(
+        statuses(&report),
+        [
+            (2, ImportRowStatus::Created),
+            (4, ImportRowStatus::Created),
+            (6, ImportRowStatus::Created),
+        ]
+    );
+    assert_eq!(report.error, None);
+
+    let ada = harness
+        .users
+        .find_by_email("ada@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(ada.name(), "Lovelace, Ada");
+
+    let grace = harness
+        .users
+        .find_by_email("grace@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(grace.name(), "Grace\nHopper");
+}
+
+#[tokio::test]
+async fn reports_invalid_and_conflicting_rows() {
+    let harness = harness();
+    harness
+        .users
+        .create(&User::new(
+            "taken@example.com".to_string(),
+            "sha256$".to_string(),
+            "Taken".to_string(),
+        ))
+        .await
+        .unwrap();
+
+    let report = import(
+        &harness,
+        "name,email,password\n\
+         Ada,ada@example.com,analytical\n\
+         Bad,not-an-email,analytical\n\
+         Copy,ADA@example.com,analytical\n\
+         Taken,taken@example.com,analytical\n",
+        ImportFormat::Csv,
+        ImportOptions::default(),
+    )
+    .await;
+
+    assert_eq!(
+        statuses(&report),
+        [
+            (3, ImportRowStatus::Failed),
+            (4, ImportRowStatus::Failed),
+            (2, ImportRowStatus::Created),
+            (5, ImportRowStatus::Failed),
+        ]
+    );
+    assert_eq!((report.total, report.succeeded, report.failed), (4, 1, 3));
+    assert_eq!(
+        report.rows[1].errors,
+        ["Duplicate email in import file".to_string()]
+    );
+    assert_eq!(harness.users.len(), 2);
+}
+
+#[tokio::test]
+async fn rejects_an_invalid_csv_header() {
+    let harness = harness();
+
+    let result = harness
+        .use_case
+        .import(
+            &b"name,\xffmail\nAda,ada@example.com,analytical\n"[..],
+            ImportFormat::Csv,
+            ImportOptions::default(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::ValidationError(_))));
+}
+
+#[tokio::test]
+async fn dry_runs_create_nothing() {
+    let harness = harness();
+
+    let report = import(
+        &harness,
+        "{\"name\":\"Ada\",\"email\":\"ada@example.com\",\"password\":\"analytical\"}\n\
+         \n\
+         {\"name\":\"Grace\",\"email\":\"grace@example.com\"}\n",
+        ImportFormat::Jsonl,
+        ImportOptions {
+            dry_run: true,
+            send_welcome_events: true,
+        },
+    )
+    .await;
+
+    assert_eq!(
+        statuses(&report),
+        [(3, ImportRowStatus::Failed), (1, ImportRowStatus::Valid)]
+    );
+    assert!(harness.users.is_empty());
+    assert!(harness.outbox.messages().is_empty());
+}
+
+#[tokio::test]
+async fn enqueues_welcome_events_for_created_users() {
+    let harness = harness();
+
+    import(
+        &harness,
+        "{\"name\":\"Ada\",\"email\":\"ada@example.com\",\"password\":\"analytical\"}\n\
+         {\"name\":\"Grace\",\"email\":\"grace@example.com\",\"password\":\"compilers\"}\n",
+        ImportFormat::Jsonl,
+        ImportOptions {
+            dry_run: false,
+            send_welcome_events: true,
+        },
+    )
+    .await;
+
+    let messages = harness.outbox.messages();
+    assert_eq!(messages.len(), 2);
+    assert!(
+        messages
+            .iter()
+            .all(|message| message.event_type == "UserCreated")
+    );
+}
+
+#[tokio::test]
+async fn returns_a_partial_report_when_the_stream_fails() {
+    let harness = harness();
+    let chunks: Vec<io::Result<&'static [u8]>> = vec![
+        Ok(b"name,email,password\nAda,ada@example.com,analytical\n"),
+        Ok(b"Grace,grace@example.com,comp"),
+        Err(io::Error::other("connection reset")),
+    ];
+
+    let report = harness
+        .use_case
+        .import(
+            StreamReader::new(stream::iter(chunks)),
+            ImportFormat::Csv,
+            ImportOptions::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(statuses(&report), [(2, ImportRowStatus::Created)]);
+    assert_eq!(
+        report.error.as_deref(),
+        Some("Failed to read import: connection reset")
+    );
+    assert_eq!(harness.users.len(), 1);
+}
@@ -0,0 +1,853 @@
+//! Behavior every `UserRepository` and `OutboxRepository` backend must share.
+//!
+//! Each server backend runs against the database described by its usual
+//! environment variables (`MSSQL_*`, `POSTGRES_*`) and is skipped when they are
+//! not set. SQLite always runs against a private in-memory database, and the
+//! in-memory adapter is checked whenever the `testing` feature is on.
+
+use std::sync::Arc;
+
+#[cfg(any(feature = "mssql-sqlx", feature = "mssql-tiberius"))]
+use axum_api::infra::config::MssqlConfig;
+use axum_api::{
+    domain::{
+        entities::{
+            outbox::OutboxMessage,
+            user::{ProfileChanges, Role, User, UserStatus},
+        },
+        repositories::{
+            error::RepositoryError,
+            outbox::OutboxRepository,
+            unit_of_work::UnitOfWorkFactory,
+            user::{SortDirection, UserCursor, UserFilter, UserListQuery, UserRepository},
+        },
+    },
+    infra::migrations::Migrator,
+};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+fn new_user(run: &str, label: &str) -> User {
+    User::new(
+        format!("{label}-{run}@example.com"),
+        "$argon2id$v=19$m=19456,t=2,p=1$c2FsdA$aGFzaA".to_string(),
+        format!("User {label}"),
+    )
+}
+
+async fn creates_and_finds_users(repository: &dyn UserRepository, run: &str) {
+    let user = new_user(run, "Create");
+    let created = repository.create(&user).await.unwrap();
+
+    assert_eq!(created.id(), user.id());
+    assert_eq!(created.email(), format!("Create-{run}@example.com"));
+    assert_eq!(created.role(), Role::User);
+    assert_eq!(created.status(), UserStatus::Active);
+    assert_eq!(created.created_at(), created.updated_at());
+
+    let by_email = repository
+        .find_by_email(&format!("create-{run}@EXAMPLE.com"))
+        .await
+        .unwrap()
+        .expect("email lookup is case-insensitive");
+    assert_eq!(by_email.id(), created.id());
+
+    let by_id = repository
+        .find_by_id(&created.id().to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(by_id.email(), created.email());
+    assert_eq!(by_id.created_at(), created.created_at());
+
+    assert!(
+        repository
+            .find_by_id(&Uuid::new_v4().to_string())
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+async fn rejects_duplicate_emails(repository: &dyn UserRepository, run: &str) {
+    let existing = repository
+        .create(&new_user(run, "duplicate"))
+        .await
+        .unwrap();
+
+    let result = repository.create(&new_user(run, "DUPLICATE")).await;
+    assert!(
+        result
+            .as_ref()
+            .is_err_and(RepositoryError::is_duplicate_email),
+        "{result:?}"
+    );
+
+    // Other unique indexes are violations too, but not duplicate emails.
+    let same_id = User::from_db(
+        *existing.id(),
+        format!("duplicate-id-{run}@example.com"),
+        existing.password().to_string(),
+        existing.name().to_string(),
+        Role::User,
+        UserStatus::Active,
+        None,
+        None,
+        None,
+        Utc::now(),
+        Utc::now(),
+    );
+    let result = repository.create(&same_id).await;
+    assert!(
+        matches!(&result, Err(e @ RepositoryError::UniqueViolation { .. }) if !e.is_duplicate_email()),
+        "{result:?}"
+    );
+}
+
+async fn bulk_create_skips_existing_emails(repository: &dyn UserRepository, run: &str) {
+    let existing = repository
+        .create(&new_user(run, "bulk-existing"))
+        .await
+        .unwrap();
+
+    let users = [
+        new_user(run, "BULK-EXISTING"),
+        new_user(run, "bulk-new-1"),
+        new_user(run, "bulk-new-2"),
+    ];
+    let created = repository.create_many(&users).await.unwrap();
+
+    for user in &created {
+        assert!(users.iter().any(|new| new.id() == user.id()));
+    }
+
+    let mut emails: Vec<&str> = created.iter().map(User::email).collect();
+    emails.sort();
+    assert_eq!(
+        emails,
+        [
+            format!("bulk-new-1-{run}@example.com"),
+            format!("bulk-new-2-{run}@example.com"),
+        ]
+    );
+
+    let found = repository
+        .find_existing_emails(&[
+            existing.email().to_uppercase(),
+            format!("bulk-missing-{run}@example.com"),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(found, [existing.email().to_string()]);
+}
+
+async fn lists_with_filters_and_cursor(repository: &dyn UserRepository, run: &str) {
+    let mut ids = Vec::new();
+    for label in ["list-a", "list-b", "list-c"] {
+        ids.push(*repository.create(&new_user(run, label)).await.unwrap().id());
+    }
+
+    let query = |after| UserListQuery {
+        filter: UserFilter {
+            email_prefix: Some("list-".to_string()),
+            name: Some("user list".to_string()),
+            status: Some(UserStatus::Active),
+            ..Default::default()
+        },
+        direction: SortDirection::Asc,
+        after,
+        limit: 2,
+    };
+
+    let first_page = repository.list(&query(None)).await.unwrap();
+    let first_page: Vec<&User> = first_page
+        .iter()
+        .filter(|user| ids.contains(user.id()))
+        .collect();
+    assert_eq!(first_page.len(), 2);
+
+    let last = first_page[1];
+    let second_page = repository
+        .list(&query(Some(UserCursor {
+            created_at: last.created_at(),
+            id: *last.id(),
+        })))
+        .await
+        .unwrap();
+
+    let mut seen: Vec<Uuid> = first_page.iter().map(|user| *user.id()).collect();
+    seen.extend(
+        second_page
+            .iter()
+            .filter(|user| ids.contains(user.id()))
+            .map(|user| *user.id()),
+    );
+    seen.sort();
+    ids.sort();
+    assert_eq!(seen, ids);
+}
+
+async fn updates_profile_with_optimistic_concurrency(repository: &dyn UserRepository, run: &str) {
+    let created = repository.create(&new_user(run, "profile")).await.unwrap();
+
+    let mut user = created.clone();
+    user.apply_profile_changes(ProfileChanges {
+        name: Some("Renamed".to_string()),
+    });
+
+    let updated = repository
+        .update_profile(&user, created.updated_at())
+        .await
+        .unwrap()
+        .expect("matching version is updated");
+    assert_eq!(updated.name(), "Renamed");
+
+    let stale = repository
+        .update_profile(&user, created.updated_at() - Duration::seconds(1))
+        .await
+        .unwrap();
+    assert!(stale.is_none());
+}
+
+async fn rejects_email_changes_to_taken_addresses(repository: &dyn UserRepository, run: &str) {
+    let first = repository
+        .create(&new_user(run, "email-first"))
+        .await
+        .unwrap();
+    let second = repository
+        .create(&new_user(run, "email-second"))
+        .await
+        .unwrap();
+
+    let mut user = first.clone();
+    user.change_email(second.email().to_uppercase());
+    assert!(!repository.update_email(&user, first.email()).await.unwrap());
+
+    // The previous address is compared like the unique index compares addresses.
+    user.change_email(format!("email-changed-{run}@example.com"));
+    assert!(
+        repository
+            .update_email(&user, &first.email().to_uppercase())
+            .await
+            .unwrap()
+    );
+    assert!(!repository.update_email(&user, first.email()).await.unwrap());
+}
+
+async fn updates_status_with_compare_and_set(repository: &dyn UserRepository, run: &str) {
+    let mut user = repository.create(&new_user(run, "status")).await.unwrap();
+    let until = Utc::now() + Duration::hours(1);
+
+    user.lock("Too many attempts".to_string(), Some(until))
+        .unwrap();
+    assert!(
+        repository
+            .update_status(&user, UserStatus::Active)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !repository
+            .update_status(&user, UserStatus::Active)
+            .await
+            .unwrap()
+    );
+
+    let stored = repository
+        .find_by_id(&user.id().to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.status(), UserStatus::Locked);
+    assert_eq!(stored.status_reason(), Some("Too many attempts"));
+    assert_eq!(
+        stored.status_until().map(|until| until.timestamp_micros()),
+        Some(until.timestamp_micros())
+    );
+}
+
+async fn erases_due_users(repository: &dyn UserRepository, run: &str) {
+    let mut user = repository.create(&new_user(run, "erase")).await.unwrap();
+
+    user.schedule_erasure(Utc::now() - Duration::minutes(1))
+        .unwrap();
+    assert!(
+        repository
+            .update_status(&user, UserStatus::Active)
+            .await
+            .unwrap()
+    );
+
+    let due = repository
+        .list_due_erasures(Utc::now(), 1000)
+        .await
+        .unwrap();
+    assert!(due.iter().any(|due| due.id() == user.id()));
+
+    let mut reactivated = user.clone();
+    reactivated.activate().unwrap();
+    assert!(
+        repository
+            .update_status(&reactivated, UserStatus::PendingDeletion)
+            .await
+            .unwrap()
+    );
+
+    let mut stale = user.clone();
+    stale.anonymize().unwrap();
+    assert!(
+        !repository.erase(&stale).await.unwrap(),
+        "an account reactivated after it was listed is not erased"
+    );
+    let stored = repository
+        .find_by_id(&user.id().to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.status(), UserStatus::Active);
+    assert_eq!(stored.email(), user.email());
+
+    reactivated
+        .schedule_erasure(Utc::now() - Duration::minutes(1))
+        .unwrap();
+    assert!(
+        repository
+            .update_status(&reactivated, UserStatus::Active)
+            .await
+            .unwrap()
+    );
+
+    let mut user = reactivated;
+    user.anonymize().unwrap();
+    assert!(repository.erase(&user).await.unwrap());
+
+    let stored = repository
+        .find_by_id(&user.id().to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.status(), UserStatus::Deleted);
+    assert_eq!(stored.email(), user.email());
+    assert!(stored.status_until().is_none());
+}
+
+fn new_message(aggregate_id: Uuid, payload: &str) -> OutboxMessage {
+    OutboxMessage::new(aggregate_id, "UserCreated", payload.to_string())
+}
+
+/// Messages of the given aggregates ready at `now`, since server databases are shared
+/// between runs.
+async fn pending_for(
+    outbox: &dyn OutboxRepository,
+    now: DateTime<Utc>,
+    max_attempts: u32,
+    aggregates: &[Uuid],
+) -> Vec<OutboxMessage> {
+    outbox
+        .list_pending(now, max_attempts, 10_000)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|message| aggregates.contains(&message.aggregate_id))
+        .collect()
+}
+
+fn payloads(messages: &[OutboxMessage]) -> Vec<&str> {
+    messages
+        .iter()
+        .map(|message| message.payload.as_str())
+        .collect()
+}
+
+async fn relays_outbox_messages_in_order(outbox: &dyn OutboxRepository) {
+    let aggregate = Uuid::new_v4();
+    let first = new_message(aggregate, "first");
+    let second = new_message(aggregate, "second");
+    let third = new_message(aggregate, "third");
+
+    for message in [&first, &second, &third] {
+        outbox.enqueue(message).await.unwrap();
+    }
+
+    let pending = pending_for(outbox, Utc::now(), 5, &[aggregate]).await;
+    assert_eq!(payloads(&pending), ["first", "second", "third"]);
+    assert_eq!(pending[0].id, first.id);
+    assert_eq!(pending[0].event_type, "UserCreated");
+    assert_eq!(pending[0].attempts, 0);
+    assert!(pending[0].next_attempt_at.is_none());
+
+    outbox
+        .record_failure(&first.id, "broker unavailable", Utc::now())
+        .await
+        .unwrap();
+    let retry_at = Utc::now() + Duration::hours(1);
+    outbox
+        .record_failure(&first.id, "broker timeout", retry_at)
+        .await
+        .unwrap();
+
+    assert!(
+        pending_for(outbox, Utc::now(), 5, &[aggregate])
+            .await
+            .is_empty(),
+        "a retry that is not due holds back its aggregate"
+    );
+
+    let due = retry_at + Duration::minutes(1);
+    let pending = pending_for(outbox, due, 5, &[aggregate]).await;
+    assert_eq!(
+        payloads(&pending),
+        ["first", "second", "third"],
+        "failures keep their place in line"
+    );
+    assert_eq!(pending[0].attempts, 2);
+    assert_eq!(pending[0].last_error.as_deref(), Some("broker timeout"));
+    assert!(pending[0].next_attempt_at.is_some());
+
+    assert_eq!(
+        payloads(&pending_for(outbox, due, 2, &[aggregate]).await),
+        ["second", "third"],
+        "dead letters are skipped"
+    );
+
+    let backlog = outbox.backlog(5).await.unwrap();
+    assert!(backlog.pending >= 3);
+    assert!(backlog.failing >= 1);
+    assert!(backlog.oldest_pending_at.is_some());
+    assert!(outbox.backlog(2).await.unwrap().dead_lettered >= 1);
+
+    for message in [&first, &second, &third] {
+        outbox.mark_dispatched(&message.id).await.unwrap();
+    }
+    assert!(pending_for(outbox, due, 5, &[aggregate]).await.is_empty());
+
+    assert!(
+        outbox
+            .delete_dispatched(Utc::now() + Duration::minutes(1), 10_000)
+            .await
+            .unwrap()
+            >= 3
+    );
+    assert!(matches!(
+        outbox.mark_dispatched(&first.id).await,
+        Err(RepositoryError::NoRowFound)
+    ));
+    assert!(matches!(
+        outbox
+            .record_failure(&Uuid::new_v4(), "missing", Utc::now())
+            .await,
+        Err(RepositoryError::NoRowFound)
+    ));
+}
+
+async fn leases_the_relay_to_one_holder(outbox: &dyn OutboxRepository) {
+    let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+    let lease = std::time::Duration::from_secs(60);
+
+    assert!(
+        outbox
+            .acquire_relay_lease(&first, std::time::Duration::ZERO)
+            .await
+            .unwrap(),
+        "no relay may be running against the test database"
+    );
+    assert!(outbox.acquire_relay_lease(&second, lease).await.unwrap());
+    assert!(
+        !outbox.acquire_relay_lease(&first, lease).await.unwrap(),
+        "a live lease is not taken over"
+    );
+    assert!(
+        outbox
+            .acquire_relay_lease(&second, std::time::Duration::ZERO)
+            .await
+            .unwrap(),
+        "the holder renews its own lease"
+    );
+    assert!(
+        outbox.acquire_relay_lease(&first, lease).await.unwrap(),
+        "an expired lease is taken over"
+    );
+    assert!(
+        outbox
+            .acquire_relay_lease(&first, std::time::Duration::ZERO)
+            .await
+            .unwrap()
+    );
+}
+
+async fn units_of_work_commit_or_roll_back(
+    repository: &dyn UserRepository,
+    outbox: &dyn OutboxRepository,
+    unit_of_work: &dyn UnitOfWorkFactory,
+    run: &str,
+) {
+    let dropped = new_user(run, "uow-dropped");
+    {
+        let unit_of_work = unit_of_work.begin().await.unwrap();
+        unit_of_work.users().create(&dropped).await.unwrap();
+        unit_of_work
+            .outbox()
+            .enqueue(&new_message(*dropped.id(), "dropped"))
+            .await
+            .unwrap();
+        assert!(
+            unit_of_work
+                .users()
+                .find_by_id(&dropped.id().to_string())
+                .await
+                .unwrap()
+                .is_some(),
+            "writes are visible inside the unit of work"
+        );
+    }
+
+    let rolled_back = new_user(run, "uow-rolled-back");
+    let transaction = unit_of_work.begin().await.unwrap();
+    transaction.users().create(&rolled_back).await.unwrap();
+    transaction
+        .outbox()
+        .enqueue(&new_message(*rolled_back.id(), "rolled back"))
+        .await
+        .unwrap();
+    transaction.rollback().await.unwrap();
+
+    let committed = new_user(run, "uow-committed");
+    let transaction = unit_of_work.begin().await.unwrap();
+    transaction.users().create(&committed).await.unwrap();
+    transaction
+        .outbox()
+        .enqueue(&new_message(*committed.id(), "committed"))
+        .await
+        .unwrap();
+    transaction.commit().await.unwrap();
+
+    for (user, persisted) in [(&dropped, false), (&rolled_back, false), (&committed, true)] {
+        let found = repository.find_by_id(&user.id().to_string()).await.unwrap();
+        assert_eq!(found.is_some(), persisted, "{}", user.email());
+    }
+
+    let pending = pending_for(
+        outbox,
+        Utc::now(),
+        5,
+        &[*dropped.id(), *rolled_back.id(), *committed.id()],
+    )
+    .await;
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].aggregate_id, *committed.id());
+    outbox.mark_dispatched(&pending[0].id).await.unwrap();
+}
+
+async fn run_suite(
+    repository: Arc<dyn UserRepository>,
+    outbox: Arc<dyn OutboxRepository>,
+    unit_of_work: Arc<dyn UnitOfWorkFactory>,
+) {
+    let run = Uuid::new_v4().simple().to_string();
+    let repository = repository.as_ref();
+    let outbox = outbox.as_ref();
+
+    creates_and_finds_users(repository, &run).await;
+    rejects_duplicate_emails(repository, &run).await;
+    bulk_create_skips_existing_emails(repository, &run).await;
+    lists_with_filters_and_cursor(repository, &run).await;
+    updates_profile_with_optimistic_concurrency(repository, &run).await;
+    rejects_email_changes_to_taken_addresses(repository, &run).await;
+    updates_status_with_compare_and_set(repository, &run).await;
+    erases_due_users(repository, &run).await;
+    relays_outbox_messages_in_order(outbox).await;
+    leases_the_relay_to_one_holder(outbox).await;
+    units_of_work_commit_or_roll_back(repository, outbox, unit_of_work.as_ref(), &run).await;
+}
+
+#[cfg(any(feature = "mssql-sqlx", feature = "mssql-tiberius"))]
+fn mssql_config() -> Option<MssqlConfig> {
+    std::env::var("MSSQL_HOST").ok()?;
+    Some(MssqlConfig::from_env())
+}
+
+#[cfg(feature = "mssql-sqlx")]
+#[tokio::test]
+async fn sqlx_mssql_user_repository() {
+    use axum_api::{
+        adapters::persistence::{
+            sqlx::repositories::{outbox::SqlXOutboxRepository, user::SqlXUserRepository},
+            transaction::SqlxUnitOfWorkFactory,
+        },
+        infra::{
+            migrations::{MSSQL_MIGRATIONS, mssql_sqlx::SqlxMigrationExecutor},
+            mssql_sqlx::init_mssql_db,
+        },
+    };
+
+    let Some(config) = mssql_config() else {
+        return;
+    };
+
+    let pool = init_mssql_db(&config).await.unwrap();
+    Migrator::new(
+        Arc::new(SqlxMigrationExecutor::new(pool.clone())),
+        MSSQL_MIGRATIONS,
+    )
+    .up(None)
+    .await
+    .unwrap();
+
+    run_suite(
+        Arc::new(SqlXUserRepository::new(pool.clone())),
+        Arc::new(SqlXOutboxRepository::new(pool.clone())),
+        Arc::new(SqlxUnitOfWorkFactory::new(
+            pool,
+            |source| Box::new(SqlXUserRepository::from_source(source)),
+            |source| Box::new(SqlXOutboxRepository::from_source(source)),
+        )),
+    )
+    .await;
+}
+
+/// Duplicate keys raised in `language`'s session, and whether their message named the
+/// violated index.
+#[cfg(any(feature = "mssql-sqlx", feature = "mssql-tiberius"))]
+const DUPLICATE_KEY_LANGUAGES: &[(&str, bool)] = &[("us_english", true), ("Deutsch", false)];
+
+#[cfg(any(feature = "mssql-sqlx", feature = "mssql-tiberius"))]
+fn duplicate_key_sql(language: &str) -> String {
+    format!(
+        "SET LANGUAGE {language}; \
+         DECLARE @keys TABLE (id INT PRIMARY KEY); \
+         INSERT INTO @keys (id) VALUES (1), (1);"
+    )
+}
+
+#[cfg(any(feature = "mssql-sqlx", feature = "mssql-tiberius"))]
+fn assert_duplicate_key(language: &str, named: bool, error: RepositoryError) {
+    assert!(
+        matches!(error, RepositoryError::UniqueViolation { .. }),
+        "{language}: {error:?}"
+    );
+    assert_eq!(error.is_unnamed_unique_violation(), !named, "{language}");
+    assert!(!error.is_duplicate_email());
+}
+
+#[cfg(feature = "mssql-sqlx")]
+#[tokio::test]
+async fn sqlx_mssql_recognizes_duplicate_keys_in_any_language() {
+    use axum_api::infra::mssql_sqlx::init_mssql_db;
+
+    let Some(config) = mssql_config() else {
+        return;
+    };
+
+    // A private pool, since the session language outlives the statement.
+    let pool = init_mssql_db(&config).await.unwrap();
+
+    for (language, named) in DUPLICATE_KEY_LANGUAGES {
+        let error = sqlx::query(&duplicate_key_sql(language))
+            .execute(&pool)
+            .await
+            .unwrap_err();
+
+        assert_duplicate_key(language, *named, error.into());
+    }
+}
+
+#[cfg(feature = "mssql-tiberius")]
+#[tokio::test]
+async fn tiberius_mssql_recognizes_duplicate_keys_in_any_language() {
+    use axum_api::infra::mssql_tiberius::init_mssql_tiberius;
+
+    let Some(config) = mssql_config() else {
+        return;
+    };
+
+    let pool = init_mssql_tiberius(&config).await.unwrap();
+    let mut conn = pool.get().await.unwrap();
+
+    for (language, named) in DUPLICATE_KEY_LANGUAGES {
+        let error = match conn.simple_query(duplicate_key_sql(language)).await {
+            Ok(stream) => stream.into_results().await.unwrap_err(),
+            Err(error) => error,
+        };
+
+        assert_duplicate_key(language, *named, error.into());
+    }
+}
+
+#[cfg(feature = "mssql-tiberius")]
+#[tokio::test]
+async fn tiberius_mssql_user_repository() {
+    use axum_api::{
+        adapters::persistence::tiberius::{
+            repositories::{outbox::TiberiusOutboxRepository, user::TiberiusUserRepository},
+            transaction::TiberiusUnitOfWorkFactory,
+        },
+        infra::{
+            migrations::{MSSQL_MIGRATIONS, mssql_tiberius::TiberiusMigrationExecutor},
+            mssql_tiberius::init_mssql_tiberius,
+        },
+    };
+
+    let Some(config) = mssql_config() else {
+        return;
+    };
+
+    let pool = init_mssql_tiberius(&config).await.unwrap();
+    Migrator::new(
+        Arc::new(TiberiusMigrationExecutor::new(pool.clone())),
+        MSSQL_MIGRATIONS,
+    )
+    .up(None)
+    .await
+    .unwrap();
+
+    run_suite(
+        Arc::new(TiberiusUserRepository::new(pool.clone())),
+        Arc::new(TiberiusOutboxRepository::new(pool.clone())),
+        Arc::new(TiberiusUnitOfWorkFactory::new(pool)),
+    )
+    .await;
+}
+
+/// A unit of work dropped uncommitted must not hand its open transaction to the next
+/// caller of the pool.
+#[cfg(feature = "mssql-tiberius")]
+#[tokio::test]
+async fn tiberius_units_of_work_dropped_uncommitted_close_their_connection() {
+    use axum_api::{
+        adapters::persistence::tiberius::transaction::TiberiusUnitOfWorkFactory,
+        infra::mssql_tiberius::init_mssql_tiberius,
+    };
+
+    let Some(mut config) = mssql_config() else {
+        return;
+    };
+    config.pool.max_size = 1;
+    config.pool.min_idle = 0;
+
+    let pool = init_mssql_tiberius(&config).await.unwrap();
+    let unit_of_work = TiberiusUnitOfWorkFactory::new(pool.clone());
+
+    let run = Uuid::new_v4().simple().to_string();
+    {
+        let unit_of_work = unit_of_work.begin().await.unwrap();
+        unit_of_work
+            .users()
+            .create(&new_user(&run, "uow-abandoned"))
+            .await
+            .unwrap();
+    }
+
+    let mut conn = pool.get().await.unwrap();
+    let open_transactions: i32 = conn
+        .simple_query("SELECT @@TRANCOUNT")
+        .await
+        .unwrap()
+        .into_row()
+        .await
+        .unwrap()
+        .and_then(|row| row.get(0))
+        .unwrap();
+
+    assert_eq!(open_transactions, 0);
+    assert_eq!(pool.state().statistics.connections_closed_broken, 1);
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test]
+async fn postgres_user_repository() {
+    use axum_api::{
+        adapters::persistence::{
+            postgres::repositories::{outbox::PgOutboxRepository, user::PgUserRepository},
+            transaction::SqlxUnitOfWorkFactory,
+        },
+        infra::{
+            config::PostgresConfig,
+            migrations::{POSTGRES_MIGRATIONS, postgres::PgMigrationExecutor},
+            postgres::init_postgres_db,
+        },
+    };
+
+    if std::env::var("POSTGRES_HOST").is_err() {
+        return;
+    }
+
+    let pool = init_postgres_db(&PostgresConfig::from_env()).await.unwrap();
+    Migrator::new(
+        Arc::new(PgMigrationExecutor::new(pool.clone())),
+        POSTGRES_MIGRATIONS,
+    )
+    .up(None)
+    .await
+    .unwrap();
+
+    run_suite(
+        Arc::new(PgUserRepository::new(pool.clone())),
+        Arc::new(PgOutboxRepository::new(pool.clone())),
+        Arc::new(SqlxUnitOfWorkFactory::new(
+            pool,
+            |source| Box::new(PgUserRepository::from_source(source)),
+            |source| Box::new(PgOutboxRepository::from_source(source)),
+        )),
+    )
+    .await;
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_user_repository() {
+    use axum_api::{
+        adapters::persistence::{
+            sqlite::repositories::{outbox::SqliteOutboxRepository, user::SqliteUserRepository},
+            transaction::SqlxUnitOfWorkFactory,
+        },
+        infra::{
+            config::{PoolConfig, SqliteConfig},
+            migrations::{SQLITE_MIGRATIONS, sqlite::SqliteMigrationExecutor},
+            sqlite::init_sqlite_db,
+        },
+    };
+
+    let pool = init_sqlite_db(&SqliteConfig {
+        path: SqliteConfig::IN_MEMORY.to_string(),
+        pool: PoolConfig::default(),
+    })
+    .await
+    .unwrap();
+    Migrator::new(
+        Arc::new(SqliteMigrationExecutor::new(pool.clone())),
+        SQLITE_MIGRATIONS,
+    )
+    .up(None)
+    .await
+    .unwrap();
+
+    run_suite(
+        Arc::new(SqliteUserRepository::new(pool.clone())),
+        Arc::new(SqliteOutboxRepository::new(pool.clone())),
+        Arc::new(SqlxUnitOfWorkFactory::new(
+            pool,
+            |source| Box::new(SqliteUserRepository::from_source(source)),
+            |source| Box::new(SqliteOutboxRepository::from_source(source)),
+        )),
+    )
+    .await;
+}
+
+#[cfg(feature = "testing")]
+#[tokio::test]
+async fn in_memory_user_repository() {
+    use axum_api::adapters::memory::{
+        outbox::InMemoryOutboxRepository, unit_of_work::InMemoryUnitOfWorkFactory,
+        user::InMemoryUserRepository,
+    };
+
+    let repository = Arc::new(InMemoryUserRepository::new());
+    let outbox = Arc::new(InMemoryOutboxRepository::new());
+
+    run_suite(
+        repository.clone(),
+        outbox.clone(),
+        Arc::new(InMemoryUnitOfWorkFactory::new(repository, outbox)),
+    )
+    .await;
+}
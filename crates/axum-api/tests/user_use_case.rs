@@ -0,0 +1,231 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+use axum_api::{
+    adapters::memory::events::RecordedEvent,
+    application::{app_error::AppError, use_cases::user::UserUseCase},
+    domain::{
+        entities::user::{ProfileChanges, Role, User, UserStatus},
+        repositories::user::{SortDirection, UserFilter, UserListQuery, UserRepository},
+    },
+};
+use chrono::{Duration, Utc};
+use common::Fakes;
+use uuid::Uuid;
+
+type Harness = common::Harness<UserUseCase>;
+
+fn harness() -> Harness {
+    Fakes::new().harness(Fakes::user_use_case)
+}
+
+async fn create(harness: &Harness, label: &str) -> User {
+    harness
+        .users
+        .create(&User::new(
+            format!("{label}@example.com"),
+            "sha256$".to_string(),
+            format!("User {label}"),
+        ))
+        .await
+        .unwrap()
+}
+
+fn renamed(name: &str) -> ProfileChanges {
+    ProfileChanges {
+        name: Some(name.to_string()),
+    }
+}
+
+#[tokio::test]
+async fn gets_users_by_id() {
+    let harness = harness();
+    let user = create(&harness, "ada").await;
+
+    let found = harness
+        .use_case
+        .get_user_by_id(&user.id().to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.email(), "ada@example.com");
+
+    assert!(
+        harness
+            .use_case
+            .get_user_by_id(&Uuid::new_v4().to_string())
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn lists_users_page_by_page() {
+    let harness = harness();
+    let now = Utc::now();
+
+    let mut expected = Vec::new();
+    for (index, label) in ["a", "b", "c", "d", "e"].iter().enumerate() {
+        let user = User::from_db(
+            Uuid::new_v4(),
+            format!("{label}@example.com"),
+            "sha256$".to_string(),
+            format!("User {label}"),
+            Role::User,
+            UserStatus::Active,
+            None,
+            None,
+            None,
+            now + Duration::seconds(index as i64),
+            now,
+        );
+        expected.push(*user.id());
+        harness.users.insert(user).unwrap();
+    }
+
+    let query = |after| UserListQuery {
+        filter: UserFilter::default(),
+        direction: SortDirection::Asc,
+        after,
+        limit: 2,
+    };
+
+    let mut seen = Vec::new();
+    let mut after = None;
+    let mut pages = 0;
+
+    loop {
+        let (users, next) = harness.use_case.list_users(query(after)).await.unwrap();
+        assert!(users.len() <= 2);
+        seen.extend(users.iter().map(|user| *user.id()));
+        pages += 1;
+
+        match next {
+            Some(cursor) => after = Some(cursor),
+            None => break,
+        }
+    }
+
+    assert_eq!(seen, expected);
+    assert_eq!(pages, 3);
+}
+
+#[tokio::test]
+async fn list_filters_are_applied() {
+    let harness = harness();
+    create(&harness, "ada").await;
+    create(&harness, "grace").await;
+
+    let (users, next) = harness
+        .use_case
+        .list_users(UserListQuery {
+            filter: UserFilter {
+                email_prefix: Some("GRA".to_string()),
+                ..Default::default()
+            },
+            direction: SortDirection::Desc,
+            after: None,
+            limit: 10,
+        })
+        .await
+        .unwrap();
+
+    assert!(next.is_none());
+    assert_eq!(
+        users.iter().map(User::email).collect::<Vec<_>>(),
+        ["grace@example.com"]
+    );
+}
+
+#[tokio::test]
+async fn update_profile_persists_changes_and_publishes_them() {
+    let harness = harness();
+    let user = create(&harness, "ada").await;
+
+    let updated = harness
+        .use_case
+        .update_profile(*user.id(), Some(user.updated_at()), renamed("Countess"))
+        .await
+        .unwrap();
+    assert_eq!(updated.name(), "Countess");
+    assert!(updated.updated_at() > user.updated_at());
+
+    let stored = harness
+        .users
+        .find_by_id(&user.id().to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.name(), "Countess");
+
+    assert!(matches!(
+        harness.events.events().as_slice(),
+        [RecordedEvent::UserUpdated(event)]
+            if event.user_id == *user.id()
+                && event.changes == renamed("Countess")
+                && event.updated_at == updated.updated_at()
+    ));
+}
+
+#[tokio::test]
+async fn update_profile_without_changes_is_a_no_op() {
+    let harness = harness();
+    let user = create(&harness, "ada").await;
+
+    let unchanged = harness
+        .use_case
+        .update_profile(*user.id(), None, renamed("User ada"))
+        .await
+        .unwrap();
+
+    assert_eq!(unchanged.updated_at(), user.updated_at());
+    assert!(harness.events.events().is_empty());
+}
+
+#[tokio::test]
+async fn update_profile_rejects_stale_versions() {
+    let harness = harness();
+    let user = create(&harness, "ada").await;
+
+    let result = harness
+        .use_case
+        .update_profile(
+            *user.id(),
+            Some(user.updated_at() - Duration::seconds(1)),
+            renamed("Countess"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::PreconditionFailed)));
+    assert!(harness.events.events().is_empty());
+}
+
+#[tokio::test]
+async fn update_profile_requires_an_existing_user() {
+    let harness = harness();
+
+    let result = harness
+        .use_case
+        .update_profile(Uuid::new_v4(), None, renamed("Countess"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::UserNotFound)));
+}
+
+#[tokio::test]
+async fn update_profile_succeeds_when_event_publishing_fails() {
+    let harness = harness();
+    let user = create(&harness, "ada").await;
+    harness.events.set_failing(true);
+
+    let updated = harness
+        .use_case
+        .update_profile(*user.id(), None, renamed("Countess"))
+        .await
+        .unwrap();
+
+    assert_eq!(updated.name(), "Countess");
+    assert!(harness.events.events().is_empty());
+}
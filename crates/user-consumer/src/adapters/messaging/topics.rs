@@ -1 +1,4 @@
 pub const USER_CREATED: &str = "user.created";
+pub const USER_DELETED: &str = "user.deleted";
+pub const USER_DATA_EXPORT_READY: &str = "user.data_export.ready";
+pub const USER_EMAIL_CHANGE_REQUESTED: &str = "user.email_change.requested";
@@ -0,0 +1,37 @@
+use crate::adapters::messaging::handler::{EventHandler, KafkaResult};
+use async_trait::async_trait;
+use serde::Deserialize;
+use tracing::info;
+
+#[derive(Deserialize)]
+pub struct UserDataExportReady {
+    pub user_id: String,
+    pub email: String,
+    pub export_id: String,
+    pub download_url: String,
+    pub expires_at: String,
+}
+
+#[derive(Default)]
+pub struct DataExportEmailHandler;
+
+#[async_trait]
+impl EventHandler for DataExportEmailHandler {
+    async fn handle(&self, payload: &str) -> KafkaResult<()> {
+        let event: UserDataExportReady = serde_json::from_str(payload)?;
+
+        info!(
+            "📧 [Data Export] Sending export {} download link to {} ({}), valid until {}",
+            event.export_id, event.user_id, event.email, event.expires_at
+        );
+
+        // Simulate sending email containing event.download_url (e.g., call EmailService)
+
+        info!(
+            "✅ [Data Export] Email sent successfully to {}",
+            event.email
+        );
+
+        Ok(())
+    }
+}
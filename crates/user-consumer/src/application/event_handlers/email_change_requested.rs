@@ -0,0 +1,67 @@
+use crate::adapters::messaging::handler::{EventHandler, KafkaResult};
+use async_trait::async_trait;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use tracing::info;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Deserialize)]
+pub struct EmailChangeRequested {
+    pub user_id: String,
+    pub change_id: String,
+    pub old_email: String,
+    pub new_email: String,
+    pub expires_at: String,
+}
+
+/// The event carries no token; it is rebuilt from the change id with the secret shared
+/// with the API, the same way the API derived it.
+pub struct EmailChangeHandler {
+    secret: Vec<u8>,
+}
+
+impl EmailChangeHandler {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            secret: secret.as_bytes().to_vec(),
+        }
+    }
+
+    fn confirmation_token(&self, change_id: &str) -> String {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any size");
+        mac.update(change_id.as_bytes());
+
+        URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+    }
+}
+
+#[async_trait]
+impl EventHandler for EmailChangeHandler {
+    async fn handle(&self, payload: &str) -> KafkaResult<()> {
+        let event: EmailChangeRequested = serde_json::from_str(payload)?;
+        let _token = self.confirmation_token(&event.change_id);
+
+        info!(
+            "📧 [Email Change] Sending confirmation to {} for user {}, valid until {}",
+            event.new_email, event.user_id, event.expires_at
+        );
+
+        // Simulate sending confirmation email containing the token (e.g., call EmailService)
+
+        info!(
+            "📧 [Email Change] Alerting {} about the requested change",
+            event.old_email
+        );
+
+        info!(
+            "✅ [Email Change] Emails sent successfully for user {}",
+            event.user_id
+        );
+
+        Ok(())
+    }
+}
@@ -1 +1,4 @@
+pub mod data_export_ready;
+pub mod email_change_requested;
+pub mod user_deleted;
 pub mod welcome_email;
@@ -0,0 +1,29 @@
+use crate::adapters::messaging::handler::{EventHandler, KafkaResult};
+use async_trait::async_trait;
+use serde::Deserialize;
+use tracing::info;
+
+#[derive(Deserialize)]
+pub struct UserDeleted {
+    pub user_id: String,
+    pub deleted_at: String,
+}
+
+#[derive(Default)]
+pub struct UserErasureHandler;
+
+#[async_trait]
+impl EventHandler for UserErasureHandler {
+    async fn handle(&self, payload: &str) -> KafkaResult<()> {
+        let event: UserDeleted = serde_json::from_str(payload)?;
+
+        // This service keeps no copies of user data, so there is nothing to erase here
+        // yet. Anything it starts storing per user must be deleted in this handler.
+        info!(
+            "🗑️ [User Erasure] User {} was erased at {}; no local data is held",
+            event.user_id, event.deleted_at
+        );
+
+        Ok(())
+    }
+}
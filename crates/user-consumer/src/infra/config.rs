@@ -3,12 +3,18 @@ use std::env;
 #[derive(Debug, Clone)]
 pub struct AppConfig {
     pub kafka_brokers: String,
+    pub email_change_token_secret: String,
 }
 
 impl AppConfig {
     pub fn from_env() -> Self {
         let kafka_brokers = env::var("KAFKA_BROKERS").unwrap_or_else(|_| "localhost:9092".into());
+        let email_change_token_secret =
+            env::var("EMAIL_CHANGE_TOKEN_SECRET").expect("EMAIL_CHANGE_TOKEN_SECRET must be set");
 
-        Self { kafka_brokers }
+        Self {
+            kafka_brokers,
+            email_change_token_secret,
+        }
     }
 }
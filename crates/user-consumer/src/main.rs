@@ -2,6 +2,9 @@ use dotenvy::dotenv;
 use std::sync::Arc;
 use tracing::info;
 use user_consumer::adapters::messaging::topics;
+use user_consumer::application::event_handlers::data_export_ready::DataExportEmailHandler;
+use user_consumer::application::event_handlers::email_change_requested::EmailChangeHandler;
+use user_consumer::application::event_handlers::user_deleted::UserErasureHandler;
 use user_consumer::application::event_handlers::welcome_email::WelcomeEmailHandler;
 use user_consumer::infra::config::AppConfig;
 use user_consumer::infra::setup::init_tracing;
@@ -22,9 +25,15 @@ async fn main() -> anyhow::Result<()> {
     let consumer = kafka::init_consumer(&config.kafka_brokers)?;
 
     let welcome_email_handler = Arc::new(WelcomeEmailHandler);
-
-    let kafka_consumer =
-        KafkaConsumer::new(consumer).register_handler(topics::USER_CREATED, welcome_email_handler);
+    let user_erasure_handler = Arc::new(UserErasureHandler);
+    let data_export_email_handler = Arc::new(DataExportEmailHandler);
+    let email_change_handler = Arc::new(EmailChangeHandler::new(&config.email_change_token_secret));
+
+    let kafka_consumer = KafkaConsumer::new(consumer)
+        .register_handler(topics::USER_CREATED, welcome_email_handler)
+        .register_handler(topics::USER_DELETED, user_erasure_handler)
+        .register_handler(topics::USER_DATA_EXPORT_READY, data_export_email_handler)
+        .register_handler(topics::USER_EMAIL_CHANGE_REQUESTED, email_change_handler);
 
     kafka_consumer.start().await;
 